@@ -21,6 +21,10 @@ const AVAILABLE_EXTENSIONS: &[&str] = &[
 pub use static_sound::StaticSound;
 pub use streaming::StreamingSound;
 
+#[cfg(feature = "ron")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ron")))]
+pub use settings::{SoundSettings, WithSettings};
+
 mod static_sound {
     use assets_manager::{loader, Asset};
     use kira::sound::static_sound::{StaticSoundData, StaticSoundSettings};
@@ -196,3 +200,186 @@ mod streaming {
         }
     }
 }
+
+#[cfg(feature = "ron")]
+mod settings {
+    use crate::StaticSound;
+    use assets_manager::{loader, AnyCache, Asset, BoxedError, Compound, SharedString};
+    use kira::{
+        sound::{static_sound::StaticSoundSettings, EndPosition, PlaybackPosition, Region},
+        Decibels, Panning, PlaybackRate, Value,
+    };
+
+    /// Settings of a sound, that can be stored in a file next to it.
+    ///
+    /// This is loaded from a RON file with extension `.settings.ron`, in which
+    /// all fields are optional. It is used by [`WithSettings`].
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// (
+    ///     volume: -6.0,
+    ///     loop_region: Some((0.5, None)),
+    /// )
+    /// ```
+    #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+    #[serde(default)]
+    pub struct SoundSettings {
+        /// Where in the sound playback should start, in seconds.
+        pub start_position: f64,
+
+        /// The portion of the sound that should be looped, in seconds. If the
+        /// end is `None`, the region ends with the sound.
+        pub loop_region: Option<(f64, Option<f64>)>,
+
+        /// Whether the sound should be played in reverse.
+        ///
+        /// This is ignored for streaming sounds.
+        pub reverse: bool,
+
+        /// The volume of the sound, in decibels.
+        pub volume: f32,
+
+        /// The playback rate of the sound.
+        pub playback_rate: f64,
+
+        /// The panning of the sound, from -1 (hard left) to 1 (hard right).
+        pub panning: f32,
+    }
+
+    impl Default for SoundSettings {
+        fn default() -> Self {
+            Self {
+                start_position: 0.0,
+                loop_region: None,
+                reverse: false,
+                volume: Decibels::IDENTITY.0,
+                playback_rate: 1.0,
+                panning: Panning::CENTER.0,
+            }
+        }
+    }
+
+    impl Asset for SoundSettings {
+        const EXTENSION: &'static str = "settings.ron";
+        type Loader = loader::RonLoader;
+    }
+
+    impl SoundSettings {
+        fn loop_region(&self) -> Option<Region> {
+            let (start, end) = self.loop_region?;
+            Some(Region {
+                start: PlaybackPosition::Seconds(start),
+                end: match end {
+                    Some(end) => EndPosition::Custom(PlaybackPosition::Seconds(end)),
+                    None => EndPosition::EndOfAudio,
+                },
+            })
+        }
+
+        /// Converts the settings to settings for a static sound.
+        pub fn to_static(&self) -> StaticSoundSettings {
+            StaticSoundSettings {
+                start_position: PlaybackPosition::Seconds(self.start_position),
+                loop_region: self.loop_region(),
+                reverse: self.reverse,
+                volume: Value::Fixed(Decibels(self.volume)),
+                playback_rate: Value::Fixed(PlaybackRate(self.playback_rate)),
+                panning: Value::Fixed(Panning(self.panning)),
+                ..StaticSoundSettings::new()
+            }
+        }
+
+        /// Converts the settings to settings for a streaming sound.
+        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg_attr(docsrs, doc(cfg(not(target_arch = "wasm32"))))]
+        pub fn to_streaming(&self) -> kira::sound::streaming::StreamingSoundSettings {
+            kira::sound::streaming::StreamingSoundSettings {
+                start_position: PlaybackPosition::Seconds(self.start_position),
+                loop_region: self.loop_region(),
+                volume: Value::Fixed(Decibels(self.volume)),
+                playback_rate: Value::Fixed(PlaybackRate(self.playback_rate)),
+                panning: Value::Fixed(Panning(self.panning)),
+                ..Default::default()
+            }
+        }
+    }
+
+    fn is_not_found(err: &assets_manager::Error) -> bool {
+        match err.reason().downcast_ref::<std::io::Error>() {
+            Some(err) => err.kind() == std::io::ErrorKind::NotFound,
+            None => false,
+        }
+    }
+
+    /// Loads the settings of the sound with the given id, if any.
+    fn load_settings(cache: AnyCache, id: &str) -> Result<SoundSettings, BoxedError> {
+        match cache.load::<SoundSettings>(id) {
+            Ok(settings) => Ok(settings.cloned()),
+            Err(err) if is_not_found(&err) => Ok(SoundSettings::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// A sound with settings from an optional sidecar file.
+    ///
+    /// Loading a `WithSettings<StaticSound>` with id `"example.audio.beep"`
+    /// loads the sound `"example.audio.beep"`, and its [`SoundSettings`] with
+    /// the same id (eg from `example/audio/beep.settings.ron`). Default
+    /// settings are used if this file does not exist.
+    ///
+    /// When hot-reloading, the value is reloaded when either file changes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kira::{backend::DefaultBackend, AudioManager, AudioManagerSettings};
+    /// use assets_manager_kira::{StaticSound, WithSettings};
+    ///
+    /// let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+    /// let cache = assets_manager::AssetCache::new("assets")?;
+    ///
+    /// let sound = cache.load::<WithSettings<StaticSound>>("example.audio.beep")?;
+    /// manager.play(sound.cloned())?;
+    ///
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct WithSettings<T>(pub T);
+
+    impl<T> WithSettings<T> {
+        /// Returns the inner sound.
+        #[inline]
+        pub fn into_inner(self) -> T {
+            self.0
+        }
+    }
+
+    impl Compound for WithSettings<StaticSound> {
+        fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+            let sound = cache.load::<StaticSound>(id)?.read();
+            let settings = load_settings(cache, id)?;
+            Ok(WithSettings(sound.with_settings(settings.to_static())))
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    impl Compound for WithSettings<crate::StreamingSound> {
+        fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+            let sound = cache.load::<crate::StreamingSound>(id)?.read();
+            let settings = load_settings(cache, id)?;
+            Ok(WithSettings(sound.with_settings(settings.to_streaming())))
+        }
+    }
+
+    impl<T: kira::sound::SoundData> kira::sound::SoundData for WithSettings<T> {
+        type Error = T::Error;
+        type Handle = T::Handle;
+
+        #[inline]
+        fn into_sound(self) -> Result<(Box<dyn kira::sound::Sound>, Self::Handle), Self::Error> {
+            self.0.into_sound()
+        }
+    }
+}
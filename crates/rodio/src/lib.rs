@@ -7,9 +7,14 @@
 #![warn(missing_docs, missing_debug_implementations)]
 #![forbid(unsafe_code)]
 
-use assets_manager::{loader, Asset, BoxedError, SharedBytes};
+use assets_manager::{
+    loader,
+    source::{DirEntry, FileSystem, Source},
+    Asset, BoxedError, SharedBytes,
+};
 use rodio::decoder::{Decoder, DecoderError};
-use std::{borrow::Cow, io};
+use std::{borrow::Cow, fmt, io, path::Path};
+use sync_file::SyncFile;
 
 #[cfg(test)]
 mod tests;
@@ -137,3 +142,51 @@ sound_assets! {
         AVAILABLE_EXTENSIONS,
     );
 }
+
+/// A sound read from its file when played, instead of being kept in memory.
+///
+/// This is useful for long music tracks, whose content would otherwise be
+/// kept in memory next to the decoded samples. The file is kept open, and
+/// each [`Decoder`] reads it independently.
+///
+/// Unlike other sounds, this is not an asset: it is opened directly from a
+/// file or from a [`FileSystem`] source, and it is not hot-reloaded.
+pub struct StreamingSound {
+    file: SyncFile,
+}
+
+impl StreamingSound {
+    /// Opens the sound file at the given path.
+    ///
+    /// The file is checked to be a sound of an enabled kind.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<StreamingSound, BoxedError> {
+        let file = SyncFile::open(path)?;
+        let _ = Decoder::new(io::BufReader::new(file.clone()))?;
+        Ok(StreamingSound { file })
+    }
+
+    /// Opens the sound with the given id in a file system source.
+    ///
+    /// The file is searched with the extensions of [`Sound`].
+    pub fn from_file_system(fs: &FileSystem, id: &str) -> Result<StreamingSound, BoxedError> {
+        let ext = AVAILABLE_EXTENSIONS
+            .iter()
+            .find(|ext| fs.exists(DirEntry::File(id, ext)))
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        Self::open(fs.path_of(DirEntry::File(id, ext)))
+    }
+
+    /// Creates a [`Decoder`] that can be send to `rodio` to play the sound.
+    ///
+    /// The decoder reads the file as the sound is played.
+    #[inline]
+    pub fn decoder(&self) -> Result<Decoder<io::BufReader<SyncFile>>, DecoderError> {
+        Decoder::new(io::BufReader::new(self.file.clone()))
+    }
+}
+
+impl fmt::Debug for StreamingSound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingSound").finish_non_exhaustive()
+    }
+}
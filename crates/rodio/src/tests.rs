@@ -30,3 +30,16 @@ sound_test! {
     #[cfg(any(feature = "wav", feature = "symphonia-wav"))]
     test_wav => crate::Wav,
 }
+
+#[cfg(any(feature = "wav", feature = "symphonia-wav"))]
+#[test]
+fn streaming() {
+    let fs = assets_manager::source::FileSystem::new("../../assets").unwrap();
+
+    let sound = crate::StreamingSound::from_file_system(&fs, "test.sounds.silence").unwrap();
+    assert!(sound.decoder().is_ok());
+    assert!(sound.decoder().is_ok());
+
+    assert!(crate::StreamingSound::from_file_system(&fs, "test.sounds.missing").is_err());
+    assert!(crate::StreamingSound::open("../../assets/example/hello.txt").is_err());
+}
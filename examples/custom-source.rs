@@ -44,7 +44,7 @@ impl FsWithOverride {
 }
 
 impl Source for FsWithOverride {
-    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
         // Try override path
         if let Some(dir) = &self.override_dir {
             match dir.read(id, ext) {
@@ -82,7 +82,7 @@ impl Source for FsWithOverride {
     fn exists(&self, entry: DirEntry) -> bool {
         self.override_dir
             .as_ref()
-            .map_or(false, |dir| dir.exists(entry))
+            .is_some_and(|dir| dir.exists(entry))
             || self.default_dir.exists(entry)
     }
 
@@ -1,9 +1,11 @@
 use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Format {
+    Bincode,
     Json,
+    Msgpack,
     Ron,
     Toml,
     Txt,
@@ -11,9 +13,31 @@ enum Format {
 }
 
 impl Format {
+    fn parse(name: &syn::LitStr) -> syn::Result<Self> {
+        let format = match name.value().as_str() {
+            "bincode" => Format::Bincode,
+            "json" => Format::Json,
+            "msgpack" => Format::Msgpack,
+            "ron" => Format::Ron,
+            "toml" => Format::Toml,
+            "txt" => Format::Txt,
+            "yml" | "yaml" => Format::Yaml,
+            s => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format_args!("unsupported format: {s:?}"),
+                ))
+            }
+        };
+
+        Ok(format)
+    }
+
     fn path(self) -> TokenStream {
         match self {
+            Format::Bincode => quote::quote!(::assets_manager::loader::BincodeLoader),
             Format::Json => quote::quote!(::assets_manager::loader::JsonLoader),
+            Format::Msgpack => quote::quote!(::assets_manager::loader::MessagePackLoader),
             Format::Ron => quote::quote!(::assets_manager::loader::RonLoader),
             Format::Toml => quote::quote!(::assets_manager::loader::TomlLoader),
             Format::Txt => quote::quote!(::assets_manager::loader::ParseLoader),
@@ -21,23 +45,84 @@ impl Format {
         }
     }
 
-    fn extensions(self) -> TokenStream {
+    fn extensions(self) -> &'static [&'static str] {
         match self {
-            Format::Json => quote::quote!(&["json"]),
-            Format::Ron => quote::quote!(&["ron"]),
-            Format::Toml => quote::quote!(&["toml"]),
-            Format::Txt => quote::quote!(&["txt"]),
-            Format::Yaml => quote::quote!(&["yaml", "yml"]),
+            Format::Bincode => &["bin"],
+            Format::Json => &["json"],
+            Format::Msgpack => &["msgpack"],
+            Format::Ron => &["ron"],
+            Format::Toml => &["toml"],
+            Format::Txt => &["txt"],
+            Format::Yaml => &["yaml", "yml"],
         }
     }
 }
 
+/// How the asset is loaded.
+enum Kind {
+    /// Built-in formats, with `#[asset_format = "..."]` or
+    /// `#[asset(formats(...))]`, and optionally custom extensions.
+    Formats {
+        formats: Vec<Format>,
+        extensions: Vec<syn::LitStr>,
+    },
+
+    /// A custom loader, with `#[asset(loader = "...", extension = "...")]`.
+    Loader {
+        loader: Box<syn::Type>,
+        extensions: Vec<syn::LitStr>,
+    },
+
+    /// A custom loading function, with
+    /// `#[asset(with = "...", extension = "...")]`.
+    With {
+        function: syn::Path,
+        extensions: Vec<syn::LitStr>,
+    },
+
+    /// A `Compound` whose fields are loaded as assets, when no format is
+    /// given.
+    Compound,
+}
+
+/// Flags set with `#[asset(...)]`.
+#[derive(Default)]
+struct Flags {
+    validate: bool,
+    default: Option<syn::Path>,
+}
+
+/// How a field of a `Compound` is loaded.
+enum FieldId {
+    /// The same id as the compound.
+    Same,
+
+    /// A fixed id, with `#[asset(id = "...")]`.
+    Fixed(syn::LitStr),
+
+    /// The id of the compound with a suffix, with `#[asset(id_suffix = "...")]`.
+    Suffix(syn::LitStr),
+
+    /// The field is not loaded but set to its default value, with
+    /// `#[asset(skip)]`.
+    Skip,
+}
+
 pub fn run(input: syn::DeriveInput) -> syn::Result<TokenStream> {
-    let format = get_format(&input.attrs)?;
-    check_fields(&input.data)?;
+    let mut flags = Flags::default();
+    let kind = get_kind(&input.attrs, &mut flags)?;
+
+    if let Kind::Compound = kind {
+        if let Some(default) = flags.default {
+            return Err(syn::Error::new_spanned(
+                default,
+                "`default` cannot be used without an asset format",
+            ));
+        }
+        return run_compound(input, flags.validate);
+    }
 
-    let loader = format.path();
-    let ext = format.extensions();
+    check_fields(&input.data)?;
 
     let asset = input.ident;
 
@@ -46,75 +131,408 @@ pub fn run(input: syn::DeriveInput) -> syn::Result<TokenStream> {
         where_token: Default::default(),
         predicates: Default::default(),
     });
-    add_clauses(&mut where_gen, format);
+    let loader_where = where_gen.clone();
+    where_gen
+        .predicates
+        .push(syn::parse_quote!(Self: ::std::marker::Send + ::std::marker::Sync + 'static));
+
+    let mut derived_loader = None;
+    let (loader, ext) = match &kind {
+        Kind::Formats {
+            formats,
+            extensions,
+        } => {
+            let ext = if extensions.is_empty() {
+                let ext = formats.iter().flat_map(|f| f.extensions());
+                quote::quote!(&[#(#ext),*])
+            } else {
+                quote::quote!(&[#(#extensions),*])
+            };
+
+            if let [format] = formats[..] {
+                (format.path(), ext)
+            } else {
+                let ty = quote::quote!(#asset #ty_gen);
+                derived_loader = Some(derive_loader(formats, &ty, &impl_gen, loader_where));
+                (
+                    quote::quote!(::assets_manager::__private::DeriveLoader),
+                    ext,
+                )
+            }
+        }
+        Kind::Loader { loader, extensions } => {
+            (loader.to_token_stream(), quote::quote!(&[#(#extensions),*]))
+        }
+        Kind::With {
+            function,
+            extensions,
+        } => {
+            let ty = quote::quote!(#asset #ty_gen);
+            derived_loader = Some(quote::quote! {
+                impl #impl_gen ::assets_manager::loader::Loader<#ty> for ::assets_manager::__private::DeriveLoader #loader_where {
+                    #[inline]
+                    fn load(
+                        content: ::std::borrow::Cow<[u8]>,
+                        _: &str,
+                    ) -> ::std::result::Result<#ty, ::assets_manager::BoxedError> {
+                        #function(&content)
+                    }
+                }
+            });
+            (
+                quote::quote!(::assets_manager::__private::DeriveLoader),
+                quote::quote!(&[#(#extensions),*]),
+            )
+        }
+        Kind::Compound => unreachable!(),
+    };
+    where_gen
+        .predicates
+        .push(syn::parse_quote!(#loader: ::assets_manager::loader::Loader<Self>));
+
+    let validate = flags.validate.then(|| {
+        where_gen
+            .predicates
+            .push(syn::parse_quote!(Self: ::assets_manager::asset::Validate));
+        quote::quote! {
+            fn validate(&self) -> ::std::result::Result<(), ::assets_manager::BoxedError> {
+                ::assets_manager::asset::Validate::validate(self)
+            }
+        }
+    });
+
+    let default = flags.default.map(|_| {
+        where_gen
+            .predicates
+            .push(syn::parse_quote!(Self: ::std::default::Default));
+        quote::quote! {
+            fn default_value(
+                id: &::assets_manager::SharedString,
+                error: ::assets_manager::BoxedError,
+            ) -> ::std::result::Result<Self, ::assets_manager::BoxedError> {
+                ::assets_manager::__private::default_value(id, error)
+            }
+        }
+    });
 
     Ok(quote::quote! {
         impl #impl_gen ::assets_manager::Asset for #asset #ty_gen #where_gen {
             const EXTENSIONS: &'static [&'static str] = #ext;
             type Loader = #loader;
+
+            #default
+            #validate
         }
+
+        #derived_loader
     })
 }
 
-fn add_clauses(gen: &mut syn::WhereClause, format: Format) {
-    gen.predicates
-        .push(syn::parse_quote!(Self: ::std::marker::Send + ::std::marker::Sync + 'static));
+/// Implements the loader of an asset that uses several formats.
+///
+/// The format is chosen with the extension of the file. If the extension does
+/// not belong to any format, each format is tried in order.
+fn derive_loader(
+    formats: &[Format],
+    ty: &TokenStream,
+    impl_gen: &syn::ImplGenerics,
+    mut where_gen: syn::WhereClause,
+) -> TokenStream {
+    let loaders: Vec<_> = formats.iter().map(|f| f.path()).collect();
+    for loader in &loaders {
+        where_gen
+            .predicates
+            .push(syn::parse_quote!(#loader: ::assets_manager::loader::Loader<#ty>));
+    }
+
+    let arms = formats.iter().zip(&loaders).map(|(format, loader)| {
+        let ext = format.extensions();
+        quote::quote! {
+            #(#ext)|* => <#loader as ::assets_manager::loader::Loader<#ty>>::load(content, ext),
+        }
+    });
+
+    let first = &loaders[0];
+    let others = &loaders[1..];
 
-    let trait_clause = match format {
-        Format::Json | Format::Ron | Format::Toml | Format::Yaml => {
-            syn::parse_quote!(Self: for<'de> ::serde::Deserialize<'de>)
+    quote::quote! {
+        impl #impl_gen ::assets_manager::loader::Loader<#ty> for ::assets_manager::__private::DeriveLoader #where_gen {
+            fn load(
+                content: ::std::borrow::Cow<[u8]>,
+                ext: &str,
+            ) -> ::std::result::Result<#ty, ::assets_manager::BoxedError> {
+                match ext {
+                    #(#arms)*
+                    _ => {
+                        let content = &*content;
+                        let err = match <#first as ::assets_manager::loader::Loader<#ty>>::load(content.into(), ext) {
+                            ::std::result::Result::Ok(asset) => return ::std::result::Result::Ok(asset),
+                            ::std::result::Result::Err(err) => err,
+                        };
+                        #(
+                            if let ::std::result::Result::Ok(asset) = <#others as ::assets_manager::loader::Loader<#ty>>::load(content.into(), ext) {
+                                return ::std::result::Result::Ok(asset);
+                            }
+                        )*
+                        ::std::result::Result::Err(err)
+                    }
+                }
+            }
         }
-        Format::Txt => syn::parse_quote!(Self: ::std::str::FromStr),
+    }
+}
+
+fn run_compound(input: syn::DeriveInput, validate: bool) -> syn::Result<TokenStream> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(Span::call_site(), "missing asset format"));
     };
-    gen.predicates.push(trait_clause);
+
+    let asset = input.ident;
+
+    let (impl_gen, ty_gen, where_gen) = input.generics.split_for_impl();
+    let mut where_gen = where_gen.cloned().unwrap_or_else(|| syn::WhereClause {
+        where_token: Default::default(),
+        predicates: Default::default(),
+    });
+    where_gen
+        .predicates
+        .push(syn::parse_quote!(Self: ::std::marker::Send + ::std::marker::Sync + 'static));
+
+    let mut fields = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        };
+
+        let id = match get_field_id(&field.attrs)? {
+            FieldId::Skip => {
+                where_gen
+                    .predicates
+                    .push(syn::parse_quote!(#ty: ::std::default::Default));
+                fields.push(quote::quote!(#member: ::std::default::Default::default()));
+                continue;
+            }
+            FieldId::Same => quote::quote!(id),
+            FieldId::Fixed(id) => quote::quote!(#id),
+            FieldId::Suffix(suffix) => quote::quote!(&::std::format!("{}{}", id, #suffix)),
+        };
+
+        where_gen
+            .predicates
+            .push(syn::parse_quote!(#ty: ::assets_manager::Compound));
+        fields.push(quote::quote!(#member: cache.load_owned::<#ty>(#id)?));
+    }
+
+    let validate = validate.then(|| {
+        where_gen
+            .predicates
+            .push(syn::parse_quote!(Self: ::assets_manager::asset::Validate));
+        quote::quote!(::assets_manager::asset::Validate::validate(&asset)?;)
+    });
+
+    Ok(quote::quote! {
+        impl #impl_gen ::assets_manager::Compound for #asset #ty_gen #where_gen {
+            fn load(
+                cache: ::assets_manager::AnyCache,
+                id: &::assets_manager::SharedString,
+            ) -> ::std::result::Result<Self, ::assets_manager::BoxedError> {
+                let asset = Self { #(#fields,)* };
+                #validate
+                ::std::result::Result::Ok(asset)
+            }
+        }
+    })
 }
 
 fn is_format_attribute(meta: &syn::Meta) -> bool {
     meta.path().get_ident().is_some_and(|i| i == "asset_format")
 }
 
-fn get_format(attrs: &[syn::Attribute]) -> syn::Result<Format> {
-    let mut formats = None;
+fn is_asset_attribute(meta: &syn::Meta) -> bool {
+    meta.path().get_ident().is_some_and(|i| i == "asset")
+}
+
+fn get_kind(attrs: &[syn::Attribute], flags: &mut Flags) -> syn::Result<Kind> {
+    let mut formats = Vec::new();
+    let mut loader = None;
+    let mut function = None;
+    let mut extensions = Vec::new();
 
     for attr in attrs {
-        if !is_format_attribute(&attr.meta) {
-            continue;
+        if is_format_attribute(&attr.meta) {
+            if !formats.is_empty() || loader.is_some() || function.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "found multiple asset formats",
+                ));
+            }
+            formats.push(parse_format(attr)?);
+        } else if is_asset_attribute(&attr.meta) {
+            parse_asset_attribute(
+                attr,
+                &mut formats,
+                &mut loader,
+                &mut function,
+                &mut extensions,
+                flags,
+            )?;
+            let count = [!formats.is_empty(), loader.is_some(), function.is_some()]
+                .into_iter()
+                .filter(|&b| b)
+                .count();
+            if count > 1 {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "found multiple asset formats",
+                ));
+            }
         }
+    }
 
-        if formats.is_some() {
+    let kind = match (loader, function) {
+        (Some(loader), _) => Kind::Loader {
+            loader: Box::new(loader),
+            extensions,
+        },
+        (_, Some(function)) => Kind::With {
+            function,
+            extensions,
+        },
+        _ if !formats.is_empty() => Kind::Formats {
+            formats,
+            extensions,
+        },
+        _ if !extensions.is_empty() => {
             return Err(syn::Error::new_spanned(
-                attr,
-                "found multiple asset formats",
-            ));
+                &extensions[0],
+                "missing `loader`, `with` or `formats` for custom extension",
+            ))
         }
+        _ => Kind::Compound,
+    };
 
-        let meta = attr.meta.require_name_value()?;
-        let name = syn::parse2::<syn::LitStr>(meta.value.to_token_stream())?;
+    match &kind {
+        Kind::Loader { loader, extensions } if extensions.is_empty() => Err(
+            syn::Error::new_spanned(loader, "missing `extension` for custom loader"),
+        ),
+        Kind::With {
+            function,
+            extensions,
+        } if extensions.is_empty() => Err(syn::Error::new_spanned(
+            function,
+            "missing `extension` for custom loading function",
+        )),
+        _ => Ok(kind),
+    }
+}
 
-        let format = match name.value().as_str() {
-            "json" => Format::Json,
-            "ron" => Format::Ron,
-            "toml" => Format::Toml,
-            "txt" => Format::Txt,
-            "yml" | "yaml" => Format::Yaml,
-            s => {
-                return Err(syn::Error::new(
-                    name.span(),
-                    format_args!("unsupported format: {s:?}"),
-                ))
+fn parse_format(attr: &syn::Attribute) -> syn::Result<Format> {
+    let meta = attr.meta.require_name_value()?;
+    let name = syn::parse2::<syn::LitStr>(meta.value.to_token_stream())?;
+    Format::parse(&name)
+}
+
+fn parse_asset_attribute(
+    attr: &syn::Attribute,
+    formats: &mut Vec<Format>,
+    loader: &mut Option<syn::Type>,
+    function: &mut Option<syn::Path>,
+    extensions: &mut Vec<syn::LitStr>,
+    flags: &mut Flags,
+) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("loader") {
+            if loader.is_some() {
+                return Err(meta.error("found multiple loaders"));
             }
-        };
+            let path = meta.value()?.parse::<syn::LitStr>()?;
+            *loader = Some(path.parse::<syn::Type>()?);
+            Ok(())
+        } else if meta.path.is_ident("with") {
+            if function.is_some() {
+                return Err(meta.error("found multiple functions"));
+            }
+            let path = meta.value()?.parse::<syn::LitStr>()?;
+            *function = Some(path.parse::<syn::Path>()?);
+            Ok(())
+        } else if meta.path.is_ident("formats") {
+            if !formats.is_empty() {
+                return Err(meta.error("found multiple asset formats"));
+            }
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let names = content.parse_terminated(
+                <syn::LitStr as syn::parse::Parse>::parse,
+                syn::Token![,],
+            )?;
+            for name in &names {
+                let format = Format::parse(name)?;
+                if formats.contains(&format) {
+                    return Err(syn::Error::new(name.span(), "duplicate format"));
+                }
+                formats.push(format);
+            }
+            if formats.is_empty() {
+                return Err(meta.error("expected at least one format"));
+            }
+            Ok(())
+        } else if meta.path.is_ident("extension") {
+            extensions.push(meta.value()?.parse::<syn::LitStr>()?);
+            Ok(())
+        } else if meta.path.is_ident("validate") {
+            flags.validate = true;
+            Ok(())
+        } else if meta.path.is_ident("default") {
+            flags.default = Some(meta.path);
+            Ok(())
+        } else {
+            Err(meta.error(
+                "unsupported attribute, expected `loader`, `with`, `formats`, `extension`, `validate` or `default`",
+            ))
+        }
+    })
+}
+
+fn get_field_id(attrs: &[syn::Attribute]) -> syn::Result<FieldId> {
+    let mut id = FieldId::Same;
 
-        formats = Some(format);
+    for attr in attrs {
+        if is_format_attribute(&attr.meta) {
+            return Err(syn::Error::new_spanned(attr, "unexpected attribute"));
+        }
+        if !is_asset_attribute(&attr.meta) {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if !matches!(id, FieldId::Same) {
+                return Err(meta.error("found multiple ids"));
+            }
+
+            if meta.path.is_ident("id") {
+                id = FieldId::Fixed(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("id_suffix") {
+                id = FieldId::Suffix(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                id = FieldId::Skip;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `id`, `id_suffix` or `skip`"))
+            }
+        })?;
     }
 
-    formats.ok_or_else(|| syn::Error::new(Span::call_site(), "missing asset format"))
+    Ok(id)
 }
 
 fn check_fields(data: &syn::Data) -> syn::Result<()> {
     let check_attrs = |attrs: &[syn::Attribute]| {
         for attr in attrs {
-            if is_format_attribute(&attr.meta) {
+            if is_format_attribute(&attr.meta) || is_asset_attribute(&attr.meta) {
                 return Err(syn::Error::new_spanned(attr, "unexpected attribute"));
             }
         }
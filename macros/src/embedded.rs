@@ -6,16 +6,136 @@ use std::{
 };
 use syn::parse::{Parse, ParseStream};
 
-pub struct Input(PathBuf);
+pub struct Input {
+    path: PathBuf,
+    nfc_ids: bool,
+    filter: Filter,
+
+    /// Environment variables read to resolve the path.
+    env_vars: Vec<String>,
+}
+
+/// Selects the files to embed.
+#[derive(Default)]
+struct Filter {
+    /// Glob patterns of files to embed. All files are embedded if empty.
+    include: Vec<String>,
+
+    /// Glob patterns of files and directories not to embed.
+    exclude: Vec<String>,
+
+    /// Extensions of files to embed. All extensions are embedded if `None`.
+    extensions: Option<Vec<String>>,
+}
+
+impl Filter {
+    fn accepts_dir(&self, path: &str) -> bool {
+        !self.exclude.iter().any(|p| glob_match(p, path))
+    }
+
+    fn accepts_file(&self, path: &str, ext: &str) -> bool {
+        if let Some(extensions) = &self.extensions {
+            if !extensions.iter().any(|e| e == ext) {
+                return false;
+            }
+        }
+
+        (self.include.is_empty() || self.include.iter().any(|p| glob_match(p, path)))
+            && !self.exclude.iter().any(|p| glob_match(p, path))
+    }
+}
+
+fn parse_list(input: ParseStream) -> Result<Vec<String>, syn::Error> {
+    input.parse::<syn::Token![=]>()?;
+    let content;
+    syn::bracketed!(content in input);
+    let list = content.parse_terminated(<syn::LitStr as Parse>::parse, syn::Token![,])?;
+    Ok(list.iter().map(|s| s.value()).collect())
+}
 
 impl Parse for Input {
     fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let manifest_dir = if input.peek(syn::Ident) && input.peek2(syn::Token![:]) {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "manifest_dir" {
+                return Err(syn::Error::new(ident.span(), "expected `manifest_dir`"));
+            }
+            input.parse::<syn::Token![:]>()?;
+            true
+        } else {
+            false
+        };
         let lit_path = input.parse::<syn::LitStr>()?;
 
-        match Path::new(&lit_path.value()).canonicalize() {
-            Ok(path) => Ok(Input(path)),
-            Err(e) => Err(syn::Error::new(lit_path.span(), e)),
+        let mut nfc_ids = false;
+        let mut expand = false;
+        let mut env_var = None;
+        let mut filter = Filter::default();
+        while input.parse::<Option<syn::Token![,]>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+            let option = input.parse::<syn::Ident>()?;
+            if option == "nfc_ids" {
+                nfc_ids = true;
+            } else if option == "expand_vars" {
+                expand = true;
+            } else if option == "include" {
+                filter.include = parse_list(input)?;
+            } else if option == "exclude" {
+                filter.exclude = parse_list(input)?;
+            } else if option == "extensions" {
+                filter.extensions = Some(parse_list(input)?);
+            } else if option == "env" {
+                input.parse::<syn::Token![=]>()?;
+                env_var = Some(input.parse::<syn::LitStr>()?);
+            } else {
+                return Err(syn::Error::new(option.span(), "unknown option"));
+            }
         }
+
+        let mut env_vars = Vec::new();
+
+        // The environment variable overrides the given path if it is set
+        let env_path = env_var.and_then(|var| {
+            env_vars.push(var.value());
+            std::env::var_os(var.value())
+        });
+        let path = match env_path {
+            Some(path) => PathBuf::from(path),
+            None if expand => expand_vars(&lit_path.value(), &mut env_vars)
+                .map_err(|e| syn::Error::new(lit_path.span(), e))?
+                .into(),
+            None => PathBuf::from(lit_path.value()),
+        };
+
+        let path = if manifest_dir && path.is_relative() {
+            env_vars.push("CARGO_MANIFEST_DIR".to_owned());
+            match std::env::var_os("CARGO_MANIFEST_DIR") {
+                Some(dir) => Path::new(&dir).join(path),
+                None => {
+                    let msg = "environment variable `CARGO_MANIFEST_DIR` is not set";
+                    return Err(syn::Error::new(lit_path.span(), msg));
+                }
+            }
+        } else {
+            path
+        };
+
+        let path = match path.canonicalize() {
+            Ok(path) => path,
+            Err(e) => {
+                let msg = format!("{}: {}", path.display(), e);
+                return Err(syn::Error::new(lit_path.span(), msg));
+            }
+        };
+
+        Ok(Input {
+            path,
+            nfc_ids,
+            filter,
+            env_vars,
+        })
     }
 }
 
@@ -25,17 +145,75 @@ impl Input {
         let mut content = Content::new();
         content.push_dir(None, Id::new());
 
-        read_dir(&self.0, &mut content, Id::new(), &mut errors);
+        let mut dir = Dir {
+            content: &mut content,
+            filter: &self.filter,
+            errors: &mut errors,
+        };
+        dir.read(&self.path, "", Id::new());
+        if self.nfc_ids {
+            content.normalize_ids();
+        }
 
         if errors.is_empty() {
             content.sort();
-            Ok(content.to_token_stream())
+            let content = content.to_token_stream();
+
+            // Make the compiler rebuild the crate when these variables change
+            let env_vars = &self.env_vars;
+            Ok(quote! {{
+                #(const _: Option<&str> = option_env!(#env_vars);)*
+                #content
+            }})
         } else {
             Err(errors)
         }
     }
 }
 
+/// Expands environment variables in a path, written `$VAR` or `${VAR}`.
+///
+/// `$$` is replaced by a single `$`. The names of read variables are pushed
+/// to `vars`.
+fn expand_vars(path: &str, vars: &mut Vec<String>) -> Result<String, String> {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(pos) = rest.find('$') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+
+        let (name, after) = if let Some(braced) = rest.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => return Err(format!("unclosed `${{` in \"{path}\"")),
+            }
+        } else if let Some(after) = rest.strip_prefix('$') {
+            result.push('$');
+            rest = after;
+            continue;
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+
+        if name.is_empty() {
+            return Err(format!("missing variable name after `$` in \"{path}\""));
+        }
+        vars.push(name.to_owned());
+        match std::env::var(name) {
+            Ok(value) => result.push_str(&value),
+            Err(_) => return Err(format!("environment variable `{name}` is not set")),
+        }
+        rest = after;
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
 fn extension_of(path: &Path) -> Option<&str> {
     match path.extension() {
         Some(ext) => ext.to_str(),
@@ -47,39 +225,120 @@ fn push_error<T: std::fmt::Display>(errors: &mut Vec<syn::Error>, err: T) {
     errors.push(syn::Error::new(Span::call_site(), err));
 }
 
-fn read_dir(path: &Path, content: &mut Content, id: Id, errors: &mut Vec<syn::Error>) {
-    let dir = match path.read_dir() {
-        Ok(dir) => dir,
-        Err(e) => {
-            push_error(errors, format!("{}: {}", path.display(), e));
-            return;
+/// Returns `true` if `path` matches the glob pattern `pattern`.
+///
+/// Both are `/`-separated paths. `**` matches any number of path segments,
+/// `*` matches any sequence of characters in a segment and `?` matches a
+/// single character.
+fn glob_match(pattern: &str, path: &str) -> bool {
+    fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|i| match_segments(rest, &path[i..])),
+            Some((segment, rest)) => match path.split_first() {
+                Some((name, path)) => wildcard_match(segment, name) && match_segments(rest, path),
+                None => false,
+            },
+        }
+    }
+
+    let pattern: Vec<_> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match_segments(&pattern, &path)
+}
+
+/// Returns `true` if `name` matches the wildcard pattern `pattern`.
+///
+/// `*` matches any sequence of characters and `?` matches a single character.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and of the matching name
+    // character, to backtrack to.
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
         }
-    };
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+struct Dir<'a> {
+    content: &'a mut Content,
+    filter: &'a Filter,
+    errors: &'a mut Vec<syn::Error>,
+}
 
-    for elem in dir {
-        let path = match elem {
-            Ok(e) => e.path(),
+impl Dir<'_> {
+    /// Reads a directory, `rel_path` being its path relative to the embedded
+    /// directory.
+    fn read(&mut self, path: &Path, rel_path: &str, id: Id) {
+        let dir = match path.read_dir() {
+            Ok(dir) => dir,
             Err(e) => {
-                push_error(errors, format!("{}: {}", path.display(), e));
-                continue;
+                push_error(self.errors, format!("{}: {}", path.display(), e));
+                return;
             }
         };
 
-        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+        for elem in dir {
+            let path = match elem {
+                Ok(e) => e.path(),
+                Err(e) => {
+                    push_error(self.errors, format!("{}: {}", path.display(), e));
+                    continue;
+                }
+            };
+
+            let (Some(stem), Some(name)) = (
+                path.file_stem().and_then(|s| s.to_str()),
+                path.file_name().and_then(|s| s.to_str()),
+            ) else {
+                continue;
+            };
             let this_id = id.clone().push(stem);
+            let this_path = if rel_path.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{rel_path}/{name}")
+            };
 
             let Ok(meta) = path.metadata() else {
                 continue;
             };
 
             if meta.is_dir() {
-                content.push_dir(Some(&id), this_id.clone());
-                read_dir(&path, content, this_id, errors);
+                if self.filter.accepts_dir(&this_path) {
+                    self.content.push_dir(Some(&id), this_id.clone());
+                    self.read(&path, &this_path, this_id);
+                }
             } else if meta.is_file() {
                 if let Some(ext) = extension_of(&path) {
-                    let ext = ext.to_owned();
-                    let desc = FileDesc(this_id, ext, path);
-                    content.push_file(desc, &id);
+                    if self.filter.accepts_file(&this_path, ext) {
+                        let ext = ext.to_owned();
+                        let desc = FileDesc(this_id, ext, path);
+                        self.content.push_file(desc, &id);
+                    }
                 }
             }
         }
@@ -101,6 +360,10 @@ impl Id {
         self.0.push_str(id);
         self
     }
+
+    fn normalize(&mut self) {
+        self.0 = crate::nfc::nfc(&self.0).into_owned();
+    }
 }
 
 struct FileDesc(Id, String, PathBuf);
@@ -144,6 +407,26 @@ impl Content {
         self.dirs.insert(id, Vec::new());
     }
 
+    /// Normalizes ids to Unicode Normalization Form C.
+    fn normalize_ids(&mut self) {
+        for FileDesc(id, _, _) in &mut self.files {
+            id.normalize();
+        }
+
+        let dirs = std::mem::take(&mut self.dirs);
+        self.dirs = dirs
+            .into_iter()
+            .map(|(mut id, mut entries)| {
+                id.normalize();
+                for entry in &mut entries {
+                    let (DirEntry::File(id, _) | DirEntry::Dir(id)) = entry;
+                    id.normalize();
+                }
+                (id, entries)
+            })
+            .collect();
+    }
+
     /// Sorts directory content to ensure reproducible builds.
     fn sort(&mut self) {
         // We can't use `sort_unstable_by_key` for some lifetime reason.
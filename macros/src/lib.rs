@@ -4,6 +4,8 @@ use proc_macro::TokenStream;
 
 mod derive;
 mod embedded;
+#[path = "../../src/utils/nfc/mod.rs"]
+mod nfc;
 
 #[proc_macro]
 pub fn embed(input: TokenStream) -> TokenStream {
@@ -11,7 +13,7 @@ pub fn embed(input: TokenStream) -> TokenStream {
     input.expand_dir().unwrap_or_else(to_compile_errors).into()
 }
 
-#[proc_macro_derive(Asset, attributes(asset_format))]
+#[proc_macro_derive(Asset, attributes(asset_format, asset))]
 pub fn derive_asset(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     derive::run(input)
@@ -11,14 +11,20 @@
 //! - The `CacheExt` adds generics on top of `Cache` to ease the use of
 //!   `Cache`'s methods.
 
-use std::{any::TypeId, fmt, io};
+use std::{any::TypeId, borrow::Cow, fmt, io};
 
 use crate::{
-    asset::DirLoadable,
+    asset::{AssetParams, DirLoadable, ParamAsset},
     entry::{CacheEntry, UntypedHandle},
+    error::LoadError,
+    failures::Failures,
     key::Type,
-    source::{DirEntry, Source},
-    Compound, Error, Handle, SharedString, Storable,
+    listing::Listing,
+    mount::Mounts,
+    source::{DirEntry, OwnedDirEntry, ReadSeek, Source},
+    utils::HashSet,
+    AggregateError, BoxedError, Compound, DirLoadReport, DirValues, Error, Find, Handle, Locale,
+    SealPolicy, SharedString, Storable, ValidationReport,
 };
 
 #[cfg(feature = "hot-reloading")]
@@ -46,10 +52,15 @@ struct AnySource<'a> {
 
 impl Source for AnySource<'_> {
     #[inline]
-    fn read(&self, id: &str, ext: &str) -> io::Result<crate::source::FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<crate::source::FileContent<'_>> {
         self.cache.read(id, ext)
     }
 
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        self.cache.open(id, ext)
+    }
+
     #[inline]
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
         self.cache.read_dir(id, f)
@@ -59,6 +70,11 @@ impl Source for AnySource<'_> {
     fn exists(&self, entry: DirEntry) -> bool {
         self.cache.exists(entry)
     }
+
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.cache.file_size(id, ext)
+    }
 }
 
 impl<'a> AnyCache<'a> {
@@ -68,12 +84,38 @@ impl<'a> AnyCache<'a> {
         AnySource { cache: self.cache }
     }
 
+    /// Adds an entry loaded outside of the cache.
+    #[inline]
+    pub(crate) fn insert_entry(self, entry: CacheEntry) -> &'a UntypedHandle {
+        self.cache.insert(entry)
+    }
+
+    /// Returns `true` if the source has a file or a directory with the given
+    /// id.
+    ///
+    /// The parent directory is read, so that an entry that is added later
+    /// triggers a reload during [`Compound::load`].
+    pub(crate) fn has_entry(self, id: &str) -> bool {
+        let parent = DirEntry::Directory(id).parent_id().unwrap_or("");
+        let mut found = false;
+        let _ = self.raw_source().read_dir(parent, &mut |entry| {
+            found |= entry.id() == id;
+        });
+        found
+    }
+
     #[cfg(feature = "hot-reloading")]
     #[inline]
     pub(crate) fn reloader(self) -> Option<&'a HotReloader> {
         self.cache.reloader()
     }
 
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub(crate) fn stats(self) -> Option<&'a crate::stats::Recorder> {
+        self.cache.stats()
+    }
+
     /// Loads an asset.
     ///
     /// If the asset is not found in the cache, it is loaded from the source.
@@ -89,6 +131,28 @@ impl<'a> AnyCache<'a> {
         self.cache._load(id)
     }
 
+    /// Loads an asset with parameters.
+    ///
+    /// The parameters are given to [`ParamAsset::load`] and are part of the
+    /// key of the asset in the cache: assets with the same id but different
+    /// parameters are different values. If the asset with the same parameters
+    /// is not found in the cache, it is loaded from the source.
+    ///
+    /// The `id` of the returned handle is not the given id, as it also
+    /// identifies the parameters.
+    ///
+    /// # Errors
+    ///
+    /// Errors can occur in the same cases as for [`load`](Self::load).
+    #[inline]
+    pub fn load_with<T: ParamAsset>(
+        self,
+        id: &str,
+        params: T::Params,
+    ) -> Result<&'a Handle<T>, Error> {
+        self.cache._load_with(id, params)
+    }
+
     /// Loads an asset and panic if an error happens.
     ///
     /// # Panics
@@ -110,6 +174,24 @@ impl<'a> AnyCache<'a> {
         self.cache._get_cached(id)
     }
 
+    /// Gets a copy of a value from the cache.
+    ///
+    /// This is equivalent to `get_cached` followed by [`Handle::copied`], but
+    /// no guard outlives the call.
+    #[inline]
+    pub fn get_copied<T: Storable + Copy>(self, id: &str) -> Option<T> {
+        self.cache._get_copied(id)
+    }
+
+    /// Gets a clone of a value from the cache.
+    ///
+    /// This is equivalent to `get_cached` followed by [`Handle::cloned`], but
+    /// no guard outlives the call.
+    #[inline]
+    pub fn get_cloned<T: Storable + Clone>(self, id: &str) -> Option<T> {
+        self.cache._get_cloned(id)
+    }
+
     /// Gets a value with the given type from the cache.
     ///
     /// This is an equivalent of `get_cached` but with a dynamic type.
@@ -142,6 +224,9 @@ impl<'a> AnyCache<'a> {
     /// Note that this function only gets the ids of assets, and that are not
     /// actually loaded. The returned handle can be use to iterate over them.
     ///
+    /// Ids are sorted lexicographically, so their order does not depend on the
+    /// source or on the platform, and is kept when the directory is reloaded.
+    ///
     /// # Errors
     ///
     /// An error is returned if the given id does not match a valid readable
@@ -154,6 +239,55 @@ impl<'a> AnyCache<'a> {
         self.load::<crate::Directory<T>>(id)
     }
 
+    /// Loads a directory and all the assets it contains.
+    ///
+    /// Unlike [`load_dir`](Self::load_dir), the assets are loaded eagerly,
+    /// so they can be accessed without the cache, or looked up by name with
+    /// [`DirValues::get`]. Subdirectories are not loaded.
+    ///
+    /// The returned value is not cached and is not updated when files are
+    /// added or removed, but the assets themselves are hot-reloaded.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the directory cannot be read or if one of the
+    /// assets fails to load.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+    /// use assets_manager::{Asset, AssetCache, loader};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Monster {
+    ///     name: String,
+    ///     description: String,
+    ///     health: u32,
+    /// }
+    ///
+    /// impl Asset for Monster {
+    ///     const EXTENSION: &'static str = "ron";
+    ///     type Loader = loader::RonLoader;
+    /// }
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// let monsters = cache.load_dir_values::<Monster>("example.monsters")?;
+    ///
+    /// let goblin = monsters.get("goblin").unwrap();
+    /// assert_eq!(goblin.read().name, "Goblin");
+    /// # }}
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[inline]
+    pub fn load_dir_values<T: DirLoadable + Compound>(
+        self,
+        id: &str,
+    ) -> Result<DirValues<'a, T>, Error> {
+        self.cache._load_dir_values(id)
+    }
+
     /// Loads a directory and its subdirectories.
     ///
     /// The directory's id is constructed the same way as assets. To specify
@@ -162,6 +296,8 @@ impl<'a> AnyCache<'a> {
     /// Note that this function only gets the ids of assets, and that are not
     /// actually loaded. The returned handle can be use to iterate over them.
     ///
+    /// Ids are sorted lexicographically, like with [`load_dir`](Self::load_dir).
+    ///
     /// # Errors
     ///
     /// An error is returned if the given id does not match a valid readable
@@ -177,6 +313,157 @@ impl<'a> AnyCache<'a> {
         self.load::<crate::RecursiveDirectory<T>>(id)
     }
 
+    /// Loads all files of a directory with a custom loader.
+    ///
+    /// This is useful for types that cannot implement [`Asset`], for example
+    /// types provided at runtime by plugins. The files with one of the given
+    /// extensions are loaded with the `load` closure and stored in the cache
+    /// as values of type `T`. If several files have the same id, the first
+    /// extension in `extensions` wins. Values already in the cache are not
+    /// loaded again.
+    ///
+    /// If `recursive` is `true`, subdirectories are loaded too, and
+    /// subdirectories that can't be read are ignored.
+    ///
+    /// The returned handles are sorted by id. As the closure is not stored,
+    /// the values loaded this way are not hot-reloaded.
+    ///
+    /// [`Asset`]: crate::Asset
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the given id does not match a valid readable
+    /// directory, or if a file fails to load.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    /// use std::str;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    ///
+    /// let handles = cache.load_dir_with("example.monsters", false, &["ron"], |bytes, _| {
+    ///     Ok(str::from_utf8(&bytes)?.len())
+    /// })?;
+    ///
+    /// assert_eq!(handles.len(), 2);
+    /// assert_eq!(handles[0].id(), "example.monsters.giant_bat");
+    /// assert!(handles[0].downcast_ref::<usize>().is_some());
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[inline]
+    pub fn load_dir_with<T: Storable>(
+        self,
+        id: &str,
+        recursive: bool,
+        extensions: &[&str],
+        mut load: impl FnMut(Cow<[u8]>, &str) -> Result<T, BoxedError>,
+    ) -> Result<Vec<&'a UntypedHandle>, Error> {
+        self.cache
+            ._load_dir_with(id, recursive, extensions, &mut load)
+    }
+
+    /// Returns the ids of all assets of type `T` that the source contains in
+    /// the given directory and its subdirectories.
+    ///
+    /// Unlike [`load_rec_dir`](Self::load_rec_dir), this function directly
+    /// reads the source every time: it does not store directories in the
+    /// cache, and directories read are not recorded as dependencies during
+    /// [`Compound::load`]. This is useful for tools that need to know which
+    /// assets could be loaded.
+    ///
+    /// The returned ids are sorted and deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the given id does not match a valid readable
+    /// directory. Subdirectories that can't be read are ignored.
+    #[inline]
+    pub fn available_ids<T: DirLoadable>(self, id: &str) -> Result<Vec<SharedString>, Error> {
+        self.cache._available_ids::<T>(id)
+    }
+
+    /// Returns the ids of all files of the source that match a pattern.
+    ///
+    /// The pattern is made of segments separated by `.`, like ids. In a
+    /// segment, `*` matches any sequence of characters and `?` matches a
+    /// single character. A segment `**` matches any number of segments,
+    /// including none.
+    ///
+    /// Files of all extensions are searched, and only directories that can
+    /// match are read. As with [`available_ids`](Self::available_ids), the
+    /// source is read directly and directories are not recorded as
+    /// dependencies.
+    ///
+    /// The returned ids are sorted and deduplicated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    ///
+    /// let ids = cache.find_ids("example.monsters.g*");
+    /// assert_eq!(ids, ["example.monsters.giant_bat", "example.monsters.goblin"]);
+    ///
+    /// let ids = cache.find_ids("**.goblin");
+    /// assert_eq!(ids, ["example.monsters.goblin"]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn find_ids(self, pattern: &str) -> Vec<SharedString> {
+        self.cache._find_ids(pattern)
+    }
+
+    /// Returns an iterator over the entries of the source that match a
+    /// pattern.
+    ///
+    /// Patterns are the same as in [`find_ids`](Self::find_ids), but both
+    /// files and directories are yielded, and directories are read lazily as
+    /// the iterator advances. Entries are yielded in no particular order, and
+    /// files can be filtered by extension with [`Find::with_extensions`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    ///
+    /// let mut ids: Vec<_> = cache
+    ///     .find("example.**.g*")
+    ///     .with_extensions(&["ron"])
+    ///     .map(|entry| entry.as_dir_entry().id().to_owned())
+    ///     .collect();
+    /// ids.sort();
+    /// assert_eq!(ids, ["example.monsters.giant_bat", "example.monsters.goblin"]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn find(self, pattern: &str) -> Find<'a> {
+        Find::new(self, pattern)
+    }
+
+    /// Returns the locale used to load [`Localized`](crate::Localized)
+    /// assets.
+    #[inline]
+    pub fn locale(self) -> Locale {
+        crate::locale::get(self)
+    }
+
+    /// Sets the locale used to load [`Localized`](crate::Localized) assets.
+    ///
+    /// When hot-reloading is enabled, `Localized` assets that are already
+    /// loaded are reloaded for the new locale during the next call to
+    /// [`AssetCache::hot_reload`]. Otherwise, only assets loaded afterwards
+    /// use the new locale.
+    #[inline]
+    pub fn set_locale(self, locale: Locale) {
+        crate::locale::set(self, locale)
+    }
+
     /// Loads an owned version of an asset.
     ///
     /// Note that the asset will not be fetched from the cache nor will it be
@@ -190,6 +477,73 @@ impl<'a> AnyCache<'a> {
         self.cache._load_owned(id)
     }
 
+    /// Loads an asset and returns a clone of it.
+    ///
+    /// Unlike [`load_owned`](Self::load_owned), the asset is fetched from the
+    /// cache or added to it, so it can be hot-reloaded. The returned value is
+    /// not updated when the asset is reloaded though.
+    #[inline]
+    pub fn load_cloned<T: Compound + Clone>(self, id: &str) -> Result<T, Error> {
+        self.cache._load_cloned(id)
+    }
+
+    /// Loads all the given assets, collecting all errors.
+    ///
+    /// Unlike loading each asset with [`load`](Self::load) and `?`, this
+    /// function does not stop at the first error: all assets are loaded, and
+    /// if any fails, all the errors are returned. This is useful to implement
+    /// [`Compound`]s that load many children.
+    ///
+    /// Successfully loaded assets are stored in the cache.
+    #[inline]
+    pub fn load_all<'i, T: Compound>(
+        self,
+        ids: impl IntoIterator<Item = &'i str>,
+    ) -> Result<Vec<&'a Handle<T>>, AggregateError> {
+        self.cache._load_all(&mut ids.into_iter())
+    }
+
+    /// Loads all the given assets and reports errors.
+    ///
+    /// Unlike [`load`](Self::load), this function does not stop at the first
+    /// error: all assets are loaded, and all errors are collected in the
+    /// returned report. This is useful to check that assets are valid, eg in
+    /// CI.
+    ///
+    /// Successfully loaded assets are stored in the cache.
+    #[inline]
+    pub fn validate<'i, T: Compound>(
+        self,
+        ids: impl IntoIterator<Item = &'i str>,
+    ) -> ValidationReport {
+        self.cache._validate::<T>(&mut ids.into_iter())
+    }
+
+    /// Loads all assets of a directory and its subdirectories and reports
+    /// errors.
+    ///
+    /// See [`validate`](Self::validate) and [`load_rec_dir`](Self::load_rec_dir)
+    /// for more details. An error reading the directory itself is included in
+    /// the report.
+    #[inline]
+    pub fn validate_dir<T: DirLoadable + Compound>(self, id: &str) -> ValidationReport {
+        self.cache._validate_dir::<T>(id)
+    }
+
+    /// Loads all assets of a directory and its subdirectories, and collects
+    /// both the loaded assets and the errors.
+    ///
+    /// Unlike [`load_rec_dir`](Self::load_rec_dir), directories that can't be
+    /// read are not ignored but reported as errors, and all assets are
+    /// actually loaded. Like [`available_ids`](Self::available_ids), the
+    /// source is read directly and directories are not stored in the cache.
+    ///
+    /// This is useful for tools that need to show everything that is broken.
+    #[inline]
+    pub fn load_dir_report<T: DirLoadable + Compound>(self, id: &str) -> DirLoadReport<'a, T> {
+        self.cache._load_dir_report::<T>(id)
+    }
+
     /// Temporarily prevent `Compound` dependencies to be recorded.
     ///
     /// This function disables dependencies recording in [`Compound::load`].
@@ -213,40 +567,137 @@ impl<'a> AnyCache<'a> {
         }
     }
 
+    /// Records a file or a directory as a dependency of the asset being
+    /// loaded.
+    ///
+    /// Files and directories read through the cache are recorded
+    /// automatically, so this is only needed when a file is read by other
+    /// means during [`Compound::load`], for example by a library that opens
+    /// the path itself. When the entry changes, the asset is reloaded.
+    ///
+    /// When hot-reloading is disabled, when the cache's [`Source`] does not
+    /// support hot-reloading, or outside of [`Compound::load`], this function
+    /// does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use assets_manager::{AnyCache, BoxedError, Compound, SharedString, source::DirEntry};
+    ///
+    /// struct Font(Vec<u8>);
+    ///
+    /// impl Compound for Font {
+    ///     fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+    ///         // Read by an external library
+    ///         let path = format!("assets/{}.ttf", id.replace('.', "/"));
+    ///         let font = std::fs::read(path)?;
+    ///
+    ///         cache.record_dependency(DirEntry::File(id, "ttf"));
+    ///         Ok(Font(font))
+    ///     }
+    /// }
+    /// ```
+    #[allow(unused_variables)]
+    pub fn record_dependency(self, entry: DirEntry) {
+        #[cfg(feature = "hot-reloading")]
+        if let Some(reloader) = self.reloader() {
+            match entry {
+                DirEntry::File(id, ext) => records::add_file_record(reloader, id, ext),
+                DirEntry::Directory(id) => records::add_dir_record(reloader, id),
+            }
+        }
+    }
+
+    /// Records an asset as a dependency of the asset being loaded, without
+    /// loading it.
+    ///
+    /// When the asset is reloaded, the asset being loaded is reloaded too.
+    /// Assets loaded through the cache are recorded automatically.
+    ///
+    /// When hot-reloading is disabled, when the cache's [`Source`] does not
+    /// support hot-reloading, or outside of [`Compound::load`], this function
+    /// does nothing.
+    #[allow(unused_variables)]
+    pub fn record_asset_dependency<T: Storable>(self, id: &str) {
+        #[cfg(feature = "hot-reloading")]
+        if let Some(reloader) = self.reloader() {
+            records::add_record(reloader, id.into(), TypeId::of::<T>());
+        }
+    }
+
     /// Returns `true` if values stored in this cache may be hot-reloaded.
     #[inline]
     pub fn is_hot_reloaded(self) -> bool {
         self.cache._has_reloader()
     }
 
+    /// Loads a new value for an asset in the cache, without writing it.
+    ///
+    /// Returns `None` if the asset is not in the cache, and `Some(Err(()))` if
+    /// reloading failed.
     #[cfg(feature = "hot-reloading")]
-    pub(crate) fn reload_untyped(self, id: SharedString, typ: Type) -> Option<Dependencies> {
+    pub(crate) fn reload_untyped(
+        self,
+        id: SharedString,
+        typ: Type,
+    ) -> Option<Result<(&'a UntypedHandle, CacheEntry, Dependencies), ()>> {
         let handle = self.get_cached_untyped(&id, typ.type_id)?;
 
         let load_asset = || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| (typ.inner.load)(self, id)))
+            #[cfg(feature = "stats")]
+            let start = std::time::Instant::now();
+
+            let ((entry, ids), size) = crate::entry::measure_reads(|| {
+                crate::entry::record_dependency_ids(|| {
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        (typ.inner.load)(self, id.clone())
+                    }))
+                })
+            });
+
+            #[cfg(feature = "stats")]
+            if let Some(stats) = self.stats() {
+                let result = match &entry {
+                    Ok(Ok(_)) => Ok(size),
+                    _ => Err(()),
+                };
+                stats.record_load(typ.type_id, typ.type_name(), true, result, start.elapsed());
+            }
+
+            entry.map(|entry| {
+                entry.map(|mut entry| {
+                    entry.set_loaded_size(size);
+                    entry.set_dependency_ids(ids);
+                    entry
+                })
+            })
         };
+        // The hashes of reloaded files were updated when their changes were
+        // received
         let (entry, deps) = if let Some(reloader) = self.reloader() {
-            records::record(reloader, load_asset)
+            let (entry, deps, _) = records::record(reloader, load_asset);
+            (entry, deps)
         } else {
             log::warn!("No reloader in hot-reloading context");
             (load_asset(), Dependencies::new())
         };
-        match entry {
-            Ok(Ok(e)) => {
-                handle.write(e);
-                log::info!("Reloading \"{}\"", handle.id());
-                Some(deps)
-            }
+        let err = match entry {
+            Ok(Ok(e)) => return Some(Ok((handle, e, deps))),
             Ok(Err(err)) => {
                 log::warn!("Error reloading \"{}\": {}", err.id(), err.reason());
-                None
+                err
             }
-            Err(_) => {
-                log::warn!("Panic while reloading asset");
-                None
+            Err(payload) => {
+                let err = crate::PanicError::new(&*payload);
+                log::warn!("Panic while reloading \"{id}\": {err}");
+                Error::with_type(id, typ.type_name(), Box::new(err))
             }
+        };
+        handle.record_reload_error(&err);
+        if let Some(reloader) = self.reloader() {
+            reloader.report_error(handle.id(), &err);
         }
+        Some(Err(()))
     }
 }
 
@@ -268,12 +719,19 @@ pub(crate) trait Cache {
     #[cfg(feature = "hot-reloading")]
     fn reloader(&self) -> Option<&HotReloader>;
 
-    fn read(&self, id: &str, ext: &str) -> io::Result<crate::source::FileContent>;
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> Option<&crate::stats::Recorder>;
+
+    fn read(&self, id: &str, ext: &str) -> io::Result<crate::source::FileContent<'_>>;
+
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>>;
 
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()>;
 
     fn exists(&self, entry: DirEntry) -> bool;
 
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64>;
+
     fn get_cached_entry(&self, id: &str, type_id: TypeId) -> Option<&UntypedHandle>;
 
     fn contains(&self, id: &str, type_id: TypeId) -> bool;
@@ -283,6 +741,9 @@ pub(crate) trait Cache {
     fn load_owned_entry(&self, id: &str, typ: Type) -> Result<CacheEntry, Error>;
 
     fn insert(&self, entry: CacheEntry) -> &UntypedHandle;
+
+    /// Returns `true` if a new entry can be added to the cache.
+    fn check_seal(&self, id: &str) -> bool;
 }
 
 pub(crate) trait RawCache: Sized {
@@ -296,15 +757,98 @@ pub(crate) trait RawCache: Sized {
     #[cfg(feature = "hot-reloading")]
     fn reloader(&self) -> Option<&HotReloader>;
 
+    #[inline]
+    fn seal_policy(&self) -> Option<SealPolicy> {
+        None
+    }
+
+    /// Failed loads remembered by the cache, if any.
+    #[inline]
+    fn failures(&self) -> Option<&Failures> {
+        None
+    }
+
+    /// Statistics about the cache, if they are recorded.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn stats(&self) -> Option<&crate::stats::Recorder> {
+        None
+    }
+
+    /// Called when an entry is accessed.
+    #[inline]
+    fn touch(&self, _entry: &UntypedHandle) {}
+
+    /// Sources mounted in the cache, if any.
+    #[inline]
+    fn mounts(&self) -> Option<&Mounts> {
+        None
+    }
+
+    /// Returns the mounted source that serves `id`, and the id relative to it.
+    #[inline]
+    fn mounted_source<'a>(&self, id: &'a str) -> Option<(&dyn Source, &'a str)> {
+        self.mounts()?.route(id)
+    }
+
+    /// The directory structure of the source, if it was scanned.
+    #[inline]
+    fn listing(&self) -> Option<&Listing> {
+        None
+    }
+
+    /// Reads a directory of the source, using its listing if possible.
+    fn source_read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        match self.listing().and_then(|l| l.read_dir(id, f)) {
+            Some(result) => result,
+            None => self.get_source().read_dir(id, f),
+        }
+    }
+
+    /// Checks if an entry exists in the source, using its listing if possible.
+    fn source_exists(&self, entry: DirEntry) -> bool {
+        match self.listing().and_then(|l| l.exists(entry)) {
+            Some(exists) => exists,
+            None => self.get_source().exists(entry),
+        }
+    }
+
+    /// Returns the id to which `id` redirects, if it is an alias.
+    #[inline]
+    fn resolve_alias(&self, _id: &str) -> Option<SharedString> {
+        None
+    }
+
     #[cold]
     fn add_asset(&self, id: &str, typ: Type) -> Result<&UntypedHandle, Error> {
         log::trace!("Loading \"{}\"", id);
 
+        if !Cache::check_seal(self, id) {
+            let err = LoadError::Sealed.into();
+            return Err(Error::with_type(id.into(), typ.type_name(), err));
+        }
+
+        let failures = self.failures();
+        if let Some(err) = failures.and_then(|f| f.check(id, typ.type_id)) {
+            return Err(err);
+        }
+
         let id = SharedString::from(id);
         let cache = AnyCache { cache: self };
-        let entry = crate::asset::load_and_record(cache, id, typ)?;
+        let result = crate::asset::load_and_record(cache, id.clone(), typ);
+
+        if let Some(failures) = failures {
+            match &result {
+                Ok(_) => failures.remove(&id, typ.type_id),
+                Err(err) => failures.record(&id, typ.type_id, typ.type_name(), err),
+            }
+        }
+
+        let entry = result?;
 
-        Ok(self.assets().insert(entry))
+        let entry = self.assets().insert(entry);
+        self.touch(entry);
+        Ok(entry)
     }
 }
 
@@ -315,12 +859,39 @@ impl<T: RawCache> Cache for T {
         self.reloader()
     }
 
-    fn read(&self, id: &str, ext: &str) -> io::Result<crate::source::FileContent> {
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn stats(&self) -> Option<&crate::stats::Recorder> {
+        self.stats()
+    }
+
+    fn read(&self, id: &str, ext: &str) -> io::Result<crate::source::FileContent<'_>> {
         #[cfg(feature = "hot-reloading")]
         if let Some(reloader) = self.reloader() {
             records::add_file_record(reloader, id, ext);
         }
-        self.get_source().read(id, ext)
+        let content = match self.mounted_source(id) {
+            Some((source, id)) => source.read(id, ext)?,
+            None => self.get_source().read(id, ext)?,
+        };
+        #[cfg(feature = "hot-reloading")]
+        if let Some(reloader) = self.reloader().filter(|r| r.hashes_content()) {
+            let hash = records::content_hash(content.as_ref());
+            records::add_file_hash(reloader, id, ext, hash);
+        }
+        crate::entry::add_read_bytes(content.as_ref().len());
+        Ok(content)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        #[cfg(feature = "hot-reloading")]
+        if let Some(reloader) = self.reloader() {
+            records::add_file_record(reloader, id, ext);
+        }
+        match self.mounted_source(id) {
+            Some((source, id)) => source.open(id, ext),
+            None => self.get_source().open(id, ext),
+        }
     }
 
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
@@ -328,41 +899,107 @@ impl<T: RawCache> Cache for T {
         if let Some(reloader) = self.reloader() {
             records::add_dir_record(reloader, id);
         }
-        self.get_source().read_dir(id, f)
+
+        let Some(mounts) = self.mounts() else {
+            return self.source_read_dir(id, f);
+        };
+        if let Some((source, rel)) = mounts.route(id) {
+            return Mounts::read_dir(source, id, rel, f);
+        }
+
+        let mut seen = HashSet::new();
+        let result = self.source_read_dir(id, &mut |entry| {
+            if let DirEntry::Directory(dir) = entry {
+                seen.insert(dir.to_owned());
+            }
+            f(entry);
+        });
+
+        match mounts.mount_points(id, &seen, f) {
+            true => Ok(()),
+            false => result,
+        }
     }
 
     fn exists(&self, entry: DirEntry) -> bool {
-        self.get_source().exists(entry)
+        let id = entry.id();
+        match self.mounted_source(id) {
+            Some((source, rel)) => match entry {
+                DirEntry::File(_, ext) => source.exists(DirEntry::File(rel, ext)),
+                DirEntry::Directory(_) => source.exists(DirEntry::Directory(rel)),
+            },
+            None => {
+                self.source_exists(entry)
+                    || (entry.is_dir() && self.mounts().is_some_and(|m| m.is_mount_point(id)))
+            }
+        }
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        match self.mounted_source(id) {
+            Some((source, id)) => source.file_size(id, ext),
+            None => self.get_source().file_size(id, ext),
+        }
     }
 
     fn get_cached_entry(&self, id: &str, type_id: TypeId) -> Option<&UntypedHandle> {
+        let alias = self.resolve_alias(id);
+        let id = alias.as_deref().unwrap_or(id);
+
+        let entry = self.assets().get(id, type_id);
+        if let Some(entry) = entry {
+            self.touch(entry);
+        }
+
         #[cfg(feature = "hot-reloading")]
         if let Some(reloader) = self.reloader() {
-            let (id, entry) = match self.assets().get(id, type_id) {
-                Some(entry) => (entry.id().clone(), Some(entry)),
-                None => (id.into(), None),
+            let id = match entry {
+                Some(entry) => {
+                    crate::entry::add_dependency_id(entry);
+                    entry.id().clone()
+                }
+                None => id.into(),
             };
             records::add_record(reloader, id, type_id);
-            return entry;
         }
 
-        self.assets().get(id, type_id)
+        entry
     }
 
     #[inline]
     fn contains(&self, id: &str, type_id: TypeId) -> bool {
+        let alias = self.resolve_alias(id);
+        let id = alias.as_deref().unwrap_or(id);
+
         self.assets().contains_key(id, type_id)
     }
 
     fn load_entry(&self, id: &str, typ: Type) -> Result<&UntypedHandle, Error> {
-        match self.get_cached_entry(id, typ.type_id) {
+        let alias = self.resolve_alias(id);
+        let id = alias.as_deref().unwrap_or(id);
+
+        let entry = self.get_cached_entry(id, typ.type_id);
+
+        #[cfg(feature = "stats")]
+        if let Some(stats) = self.stats() {
+            stats.record_lookup(typ.type_id, typ.type_name(), entry.is_some());
+        }
+
+        match entry {
             Some(entry) => Ok(entry),
-            None => self.add_asset(id, typ),
+            None => {
+                let entry = self.add_asset(id, typ)?;
+                #[cfg(feature = "hot-reloading")]
+                if self.reloader().is_some() {
+                    crate::entry::add_dependency_id(entry);
+                }
+                Ok(entry)
+            }
         }
     }
 
     fn load_owned_entry(&self, id: &str, typ: Type) -> Result<CacheEntry, Error> {
-        let id = SharedString::from(id);
+        let id = self.resolve_alias(id).unwrap_or_else(|| id.into());
 
         #[cfg(feature = "hot-reloading")]
         if typ.is_hot_reloaded() {
@@ -378,10 +1015,24 @@ impl<T: RawCache> Cache for T {
     fn insert(&self, entry: CacheEntry) -> &UntypedHandle {
         self.assets().insert(entry)
     }
+
+    fn check_seal(&self, id: &str) -> bool {
+        match self.seal_policy() {
+            None => true,
+            Some(SealPolicy::LogAndAllow) => {
+                log::warn!("Adding \"{id}\" to a sealed cache");
+                true
+            }
+            Some(SealPolicy::Error) => false,
+        }
+    }
 }
 
+/// A closure that loads a value from raw bytes and an extension.
+type DynLoader<'a, T> = dyn FnMut(Cow<[u8]>, &str) -> Result<T, BoxedError> + 'a;
+
 pub(crate) trait CacheExt: Cache {
-    fn _as_any_cache(&self) -> AnyCache;
+    fn _as_any_cache(&self) -> AnyCache<'_>;
 
     #[inline]
     fn _has_reloader(&self) -> bool {
@@ -398,10 +1049,20 @@ pub(crate) trait CacheExt: Cache {
         Some(entry.downcast_ref_ok())
     }
 
+    #[inline]
+    fn _get_copied<T: Storable + Copy>(&self, id: &str) -> Option<T> {
+        self._get_cached(id).map(Handle::copied)
+    }
+
+    #[inline]
+    fn _get_cloned<T: Storable + Clone>(&self, id: &str) -> Option<T> {
+        self._get_cached(id).map(Handle::cloned)
+    }
+
     #[cold]
     fn add_any<T: Storable>(&self, id: &str, asset: T) -> &UntypedHandle {
         let id = SharedString::from(id);
-        let entry = CacheEntry::new_any(asset, id, false);
+        let entry = CacheEntry::new_any(asset, id, false, self._as_any_cache());
 
         self.insert(entry)
     }
@@ -409,7 +1070,12 @@ pub(crate) trait CacheExt: Cache {
     fn _get_or_insert<T: Storable>(&self, id: &str, default: T) -> &Handle<T> {
         let entry = match self.get_cached_entry(id, TypeId::of::<T>()) {
             Some(entry) => entry,
-            None => self.add_any(id, default),
+            None => {
+                if !self.check_seal(id) {
+                    panic!("cannot add \"{id}\" to a sealed cache");
+                }
+                self.add_any(id, default)
+            }
         };
 
         entry.downcast_ref_ok()
@@ -425,6 +1091,35 @@ pub(crate) trait CacheExt: Cache {
         Ok(entry.downcast_ref_ok())
     }
 
+    fn _load_with<T: ParamAsset>(&self, id: &str, params: T::Params) -> Result<&Handle<T>, Error> {
+        let cache = self._as_any_cache();
+        let hash = crate::asset::hash_params(&params);
+
+        // Look for the parameters among the ids with the same hash
+        let mut n = 0;
+        loop {
+            let param_id = crate::asset::param_id(id, hash, n);
+
+            let stored = match cache.no_record(|| self._get_cached::<AssetParams<T>>(&param_id)) {
+                Some(stored) => stored,
+                None if self.check_seal(&param_id) => self
+                    .add_any(&param_id, AssetParams::<T>(params.clone()))
+                    .downcast_ref_ok(),
+                None => {
+                    let err = LoadError::Sealed.into();
+                    return Err(Error::with_type(id.into(), std::any::type_name::<T>(), err));
+                }
+            };
+
+            if stored.read().0 == params {
+                let entry = self.load_entry(&param_id, Type::of_params::<T>())?;
+                return Ok(entry.downcast_ref_ok());
+            }
+
+            n += 1;
+        }
+    }
+
     #[inline]
     #[track_caller]
     fn _load_expect<T: Compound>(&self, id: &str) -> &Handle<T> {
@@ -446,22 +1141,215 @@ pub(crate) trait CacheExt: Cache {
     }
 
     #[inline]
+    fn _load_dir_values<T: DirLoadable + Compound>(
+        &self,
+        id: &str,
+    ) -> Result<DirValues<'_, T>, Error> {
+        let dir = self._load::<crate::Directory<T>>(id)?;
+        let handles = dir
+            .read()
+            .ids()
+            .map(|id| self._load(id))
+            .collect::<Result<_, _>>()?;
+        Ok(DirValues::new(handles))
+    }
+
+    #[inline]
+    fn _load_cloned<T: Compound + Clone>(&self, id: &str) -> Result<T, Error> {
+        Ok(self._load::<T>(id)?.cloned())
+    }
+
     fn _load_owned<T: Compound>(&self, id: &str) -> Result<T, Error> {
         let entry = self.load_owned_entry(id, Type::of_asset::<T>())?;
         Ok(entry.into_inner().0)
     }
+
+    fn _load_dir_with<T: Storable>(
+        &self,
+        id: &str,
+        recursive: bool,
+        extensions: &[&str],
+        load: &mut DynLoader<T>,
+    ) -> Result<Vec<&UntypedHandle>, Error> {
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
+
+        let mut read_dir = |dir: &str, dirs: &mut Vec<String>| {
+            self.read_dir(dir, &mut |entry| match entry {
+                DirEntry::File(id, ext) => {
+                    if let Some(pos) = extensions.iter().position(|e| *e == ext) {
+                        files.push((SharedString::from(id), pos));
+                    }
+                }
+                DirEntry::Directory(id) => {
+                    if recursive {
+                        dirs.push(id.to_owned());
+                    }
+                }
+            })
+        };
+
+        read_dir(id, &mut dirs).map_err(|err| Error::new(id.into(), err.into()))?;
+        while let Some(dir) = dirs.pop() {
+            let _ = read_dir(&dir, &mut dirs);
+        }
+
+        // Keep the first extension for each id
+        files.sort_unstable();
+        files.dedup_by(|a, b| a.0 == b.0);
+
+        let mut handles = Vec::with_capacity(files.len());
+        for (id, pos) in files {
+            let handle = match self.get_cached_entry(&id, TypeId::of::<T>()) {
+                Some(handle) => handle,
+                None => {
+                    if !self.check_seal(&id) {
+                        let err = LoadError::Sealed.into();
+                        return Err(Error::with_type(id, std::any::type_name::<T>(), err));
+                    }
+
+                    let ext = extensions[pos];
+                    let value = self
+                        .read(&id, ext)
+                        .map_err(LoadError::from)
+                        .and_then(|content| Ok(content.with_cow(|c| load(c, ext))?));
+                    match value {
+                        Ok(value) => self.add_any(&id, value),
+                        Err(err) => {
+                            let type_name = std::any::type_name::<T>();
+                            return Err(Error::with_type(id, type_name, err.into()));
+                        }
+                    }
+                }
+            };
+            handles.push(handle);
+        }
+
+        Ok(handles)
+    }
+
+    fn _available_ids<T: DirLoadable>(&self, id: &str) -> Result<Vec<SharedString>, Error> {
+        let cache = self._as_any_cache();
+        let id = SharedString::from(id);
+
+        let mut ids = cache
+            .no_record(|| T::select_ids(cache, &id))
+            .map_err(|err| Error::new(id.clone(), err.into()))?;
+
+        let mut dirs = Vec::new();
+        let add_dirs = |id: &SharedString, dirs: &mut Vec<SharedString>| {
+            let _ = cache.no_record(|| T::sub_directories(cache, id, |id| dirs.push(id.into())));
+        };
+        add_dirs(&id, &mut dirs);
+
+        while let Some(dir) = dirs.pop() {
+            if let Ok(child) = cache.no_record(|| T::select_ids(cache, &dir)) {
+                ids.extend(child);
+                add_dirs(&dir, &mut dirs);
+            }
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    #[inline]
+    fn _find(&self, pattern: &str) -> Find<'_> {
+        Find::new(self._as_any_cache(), pattern)
+    }
+
+    #[inline]
+    fn _locale(&self) -> Locale {
+        crate::locale::get(self._as_any_cache())
+    }
+
+    #[inline]
+    fn _set_locale(&self, locale: Locale) {
+        crate::locale::set(self._as_any_cache(), locale)
+    }
+
+    fn _find_ids(&self, pattern: &str) -> Vec<SharedString> {
+        let mut ids: Vec<SharedString> = self
+            ._find(pattern)
+            .filter_map(|entry| match entry {
+                OwnedDirEntry::File(id, _) => Some(id),
+                OwnedDirEntry::Directory(_) => None,
+            })
+            .collect();
+
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    fn _load_all<T: Compound>(
+        &self,
+        ids: &mut dyn Iterator<Item = &str>,
+    ) -> Result<Vec<&Handle<T>>, AggregateError> {
+        AggregateError::collect(ids.map(|id| self._load(id)))
+    }
+
+    fn _validate<T: Compound>(&self, ids: &mut dyn Iterator<Item = &str>) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for id in ids {
+            report.push(self._load::<T>(id));
+        }
+        report
+    }
+
+    fn _load_dir_report<T: DirLoadable + Compound>(&self, id: &str) -> DirLoadReport<'_, T> {
+        let cache = self._as_any_cache();
+        let mut ids = Vec::new();
+        let mut dirs = vec![SharedString::from(id)];
+        let mut errors = Vec::new();
+
+        while let Some(dir) = dirs.pop() {
+            let result = cache.no_record(|| {
+                ids.extend(T::select_ids(cache, &dir)?);
+                T::sub_directories(cache, &dir, |id| dirs.push(id.into()))
+            });
+            if let Err(err) = result {
+                errors.push(Error::new(dir, err.into()));
+            }
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+
+        let mut report = DirLoadReport::new();
+        for err in errors {
+            report.push(Err(err));
+        }
+        for id in ids {
+            report.push(self._load::<T>(&id));
+        }
+        report.sort();
+        report
+    }
+
+    fn _validate_dir<T: DirLoadable + Compound>(&self, id: &str) -> ValidationReport {
+        match self._load::<crate::RecursiveDirectory<T>>(id) {
+            Ok(dir) => self._validate::<T>(&mut dir.read().ids().map(|id| &**id)),
+            Err(err) => {
+                let mut report = ValidationReport::new();
+                report.push::<()>(Err(err));
+                report
+            }
+        }
+    }
 }
 
 impl<T: Cache> CacheExt for T {
     #[inline]
-    fn _as_any_cache(&self) -> AnyCache {
+    fn _as_any_cache(&self) -> AnyCache<'_> {
         AnyCache { cache: self }
     }
 }
 
 impl CacheExt for dyn Cache + '_ {
     #[inline]
-    fn _as_any_cache(&self) -> AnyCache {
+    fn _as_any_cache(&self) -> AnyCache<'_> {
         AnyCache { cache: self }
     }
 }
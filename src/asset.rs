@@ -32,11 +32,28 @@
 //! observe a state where `B` is reloaded but `A` is not reloaded yet.
 //! Additionally, if `A` fails to reload, the inconsistent state is kept as is.
 
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+mod atlas;
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "dds")]
+mod dds;
 #[cfg(feature = "ab_glyph")]
 mod fonts;
 #[cfg(feature = "gltf")]
 #[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
 mod gltf;
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+mod keyed;
+#[cfg(feature = "ktx2")]
+mod ktx2;
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+mod layered;
+#[cfg(feature = "bincode")]
+mod string_table;
+mod validated;
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+mod versioned;
 
 #[cfg(test)]
 mod tests;
@@ -51,17 +68,98 @@ use crate::{
     utils::{Private, SharedBytes, SharedString},
     AnyCache, AssetCache, BoxedError, Error,
 };
-use crate::{error::ErrorKind, key::Type, loader::Loader};
+use crate::{error::LoadError, key::Type, loader::Loader, TooLargeError};
 
 #[allow(unused)]
-use std::{borrow::Cow, io, sync::Arc};
+use std::{borrow::Cow, hash, io, sync::Arc};
+
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml")))
+)]
+pub use self::atlas::{Atlas, AtlasFrame, AtlasNaming, DefaultAtlasNaming, TexturePackerNaming};
+
+#[cfg(feature = "csv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+pub use self::csv::Csv;
+
+#[cfg(feature = "dds")]
+pub use self::dds::{Dds, DdsFormat};
 
 #[cfg(feature = "gltf")]
-pub use self::gltf::Gltf;
+pub use self::gltf::{AnimationChannel, Gltf, GltfLazy, Keyframe, Keyframes};
+
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml")))
+)]
+pub use self::keyed::Keyed;
+
+#[cfg(feature = "ktx2")]
+pub use self::ktx2::Ktx2;
+
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml")))
+)]
+pub use self::layered::{ConfigLayers, DefaultLayers, LayeredConfig};
+
+#[cfg(feature = "bincode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+pub use self::string_table::StringTable;
+
+pub use self::validated::{Validate, Validated};
+
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml")))
+)]
+pub use self::versioned::{Migrations, Versioned, VersionedAsset, VersionedLoader};
 
 #[cfg(doc)]
 use crate::Handle;
 
+/// The names of the asset traits in the next major version of this crate.
+///
+/// In the next major version, [`Asset`] is renamed [`FileAsset`] and
+/// [`Compound`] is renamed `Asset`. The items of this module are aliases of
+/// the current traits under their new names, so that code can be migrated
+/// before upgrading:
+///
+/// ```
+/// use assets_manager::{asset::next::{Asset, FileAsset}, AnyCache, BoxedError, SharedString};
+/// use std::borrow::Cow;
+///
+/// struct Text(String);
+///
+/// impl FileAsset for Text {
+///     const EXTENSION: &'static str = "txt";
+///
+///     fn from_bytes(bytes: Cow<[u8]>) -> Result<Self, BoxedError> {
+///         Ok(Text(String::from_utf8(bytes.into_owned())?))
+///     }
+/// }
+///
+/// struct Greeting(String);
+///
+/// impl Asset for Greeting {
+///     fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+///         let text = cache.load::<Text>(id)?.read();
+///         Ok(Greeting(format!("Hello, {}!", text.0)))
+///     }
+/// }
+/// ```
+pub mod next {
+    #[doc(no_inline)]
+    pub use super::Compound as Asset;
+    #[doc(no_inline)]
+    pub use super::FileAsset;
+}
+
 /// An asset is a type loadable from raw bytes.
 ///
 /// `Asset`s can be loaded and retrieved by an [`AssetCache`].
@@ -141,6 +239,10 @@ pub trait Asset: Sized + Send + Sync + 'static {
     ///
     /// By default, this method always returns an error.
     ///
+    /// When hot-reloading is enabled, the files that were tried and missing
+    /// are still registered as dependencies of the asset, so creating one of
+    /// them later replaces the default value.
+    ///
     /// # Example
     ///
     /// On error, log it and return a default value:
@@ -173,33 +275,372 @@ pub trait Asset: Sized + Send + Sync + 'static {
         Err(error)
     }
 
+    /// Checks that a loaded value is valid.
+    ///
+    /// This is called after each successful conversion from raw bytes, when
+    /// the asset is loaded and when it is reloaded. Returning an error makes
+    /// loading fail as if the conversion failed, so invariants that the
+    /// format cannot express (eg ranges or relations between fields) are
+    /// reported with the id of the asset instead of breaking code later.
+    ///
+    /// By default, all values are valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+    /// use assets_manager::{Asset, AssetCache, BoxedError, loader, source::InMemory};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Range {
+    ///     min: u32,
+    ///     max: u32,
+    /// }
+    ///
+    /// impl Asset for Range {
+    ///     const EXTENSION: &'static str = "ron";
+    ///     type Loader = loader::RonLoader;
+    ///
+    ///     fn validate(&self) -> Result<(), BoxedError> {
+    ///         if self.min > self.max {
+    ///             return Err(format!("min ({}) is greater than max ({})", self.min, self.max).into());
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let source = InMemory::new();
+    /// source.insert("range", "ron", b"(min: 2, max: 1)".to_vec());
+    ///
+    /// let cache = AssetCache::with_source(source);
+    /// assert!(cache.load::<Range>("range").is_err());
+    /// # }}
+    /// ```
+    #[inline]
+    fn validate(&self) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
     /// If `false`, disable hot-reloading for assets of this type (`true` by
     /// default). This avoids having to lock the asset to read it (ie it makes
     /// [`Handle::read`] a noop)
     const HOT_RELOADED: bool = true;
+
+    /// The maximum size of a file of this type, in bytes (no limit by
+    /// default).
+    ///
+    /// Loading a bigger file fails with a [`TooLargeError`]. When the source
+    /// knows the size of the file, it is not read at all.
+    ///
+    /// [`TooLargeError`]: crate::TooLargeError
+    const MAX_SIZE: Option<u64> = None;
 }
 
-pub(crate) fn load_from_source<T: Asset>(
+/// An asset type that can be created from raw bytes.
+///
+/// This is an alternative to [`Asset`] that does not require a separate
+/// [`Loader`] type: all types that implement this trait implement `Asset`
+/// too, using [`loader::FileAssetLoader`].
+///
+/// This mirrors the trait of the same name in the next major version of this
+/// crate, where `Asset` is renamed `FileAsset` and `Compound` is renamed
+/// `Asset`. Implementing this trait instead of `Asset` eases the migration.
+///
+/// Existing [`Loader`]s can be used to implement `from_bytes`.
+///
+/// # Example
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{loader::{Loader, RonLoader}, BoxedError, FileAsset};
+/// use serde::Deserialize;
+/// use std::borrow::Cow;
+///
+/// #[derive(Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl FileAsset for Point {
+///     const EXTENSION: &'static str = "ron";
+///
+///     fn from_bytes(bytes: Cow<[u8]>) -> Result<Self, BoxedError> {
+///         RonLoader::load(bytes, "ron")
+///     }
+/// }
+/// # }}
+/// ```
+pub trait FileAsset: Storable {
+    /// Use this field if your asset only uses one extension.
+    ///
+    /// This value is ignored if you set `EXTENSIONS` too.
+    const EXTENSION: &'static str = "";
+
+    /// This field enables you to specify multiple extension for an asset.
+    ///
+    /// See [`Asset::EXTENSIONS`] for more details.
+    const EXTENSIONS: &'static [&'static str] = &[Self::EXTENSION];
+
+    /// Creates a value of this type from raw bytes.
+    fn from_bytes(bytes: Cow<[u8]>) -> Result<Self, BoxedError>;
+
+    /// Specifies a eventual default value to use if an asset fails to load.
+    ///
+    /// See [`Asset::default_value`] for more details.
+    #[inline]
+    #[allow(unused_variables)]
+    fn default_value(id: &SharedString, error: BoxedError) -> Result<Self, BoxedError> {
+        Err(error)
+    }
+
+    /// Checks that a loaded value is valid.
+    ///
+    /// See [`Asset::validate`] for more details.
+    #[inline]
+    fn validate(&self) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    /// If `false`, disable hot-reloading for assets of this type (`true` by
+    /// default).
+    const HOT_RELOADED: bool = true;
+
+    /// The maximum size of a file of this type, in bytes.
+    ///
+    /// See [`Asset::MAX_SIZE`] for more details.
+    const MAX_SIZE: Option<u64> = None;
+}
+
+impl<T> Asset for T
+where
+    T: FileAsset,
+{
+    const EXTENSIONS: &'static [&'static str] = T::EXTENSIONS;
+    type Loader = loader::FileAssetLoader;
+
+    #[inline]
+    fn default_value(id: &SharedString, error: BoxedError) -> Result<Self, BoxedError> {
+        T::default_value(id, error)
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<(), BoxedError> {
+        <T as FileAsset>::validate(self)
+    }
+
+    const HOT_RELOADED: bool = <T as FileAsset>::HOT_RELOADED;
+    const MAX_SIZE: Option<u64> = <T as FileAsset>::MAX_SIZE;
+}
+
+/// Tries to load a file with each extension in order.
+fn load_file<T>(
     source: impl Source,
-    id: &SharedString,
-) -> Result<T, BoxedError> {
-    let load_with_ext = |ext| -> Result<T, ErrorKind> {
-        let asset = source
-            .read(id, ext)?
-            .with_cow(|content| T::Loader::load(content, ext))?;
+    id: &str,
+    extensions: &[&str],
+    max_size: Option<u64>,
+    mut load: impl FnMut(Cow<[u8]>, &str) -> Result<T, BoxedError>,
+) -> Result<T, LoadError> {
+    let mut load_with_ext = |ext| -> Result<T, LoadError> {
+        if let (Some(limit), Some(size)) = (max_size, source.file_size(id, ext)) {
+            TooLargeError::check(size, limit)?;
+        }
+
+        let content = source.read(id, ext)?;
+        if let Some(limit) = max_size {
+            TooLargeError::check(content.as_ref().len() as u64, limit)?;
+        }
+
+        let asset = content.with_cow(|content| load(content, ext))?;
         Ok(asset)
     };
 
-    let mut error = ErrorKind::NoDefaultValue;
+    let mut error = LoadError::NoDefaultValue;
 
-    for ext in T::EXTENSIONS {
+    for ext in extensions {
         match load_with_ext(ext) {
             Err(err) => error = err.or(error),
             Ok(asset) => return Ok(asset),
         }
     }
 
-    T::default_value(id, error.into())
+    Err(error)
+}
+
+pub(crate) fn load_from_source<T: Asset>(
+    source: impl Source,
+    id: &SharedString,
+) -> Result<T, BoxedError> {
+    let load = |content: Cow<[u8]>, ext: &str| {
+        let asset = T::Loader::load(content, ext)?;
+        asset.validate()?;
+        Ok(asset)
+    };
+
+    match load_file(source, id, T::EXTENSIONS, T::MAX_SIZE, load) {
+        Ok(asset) => Ok(asset),
+        Err(error) => T::default_value(id, error.into()),
+    }
+}
+
+/// An asset that can be converted back to raw bytes.
+///
+/// This is the inverse of [`Asset`]'s loading: it enables to save an asset
+/// with [`AssetCache::save`], for example in an editor.
+///
+/// # Example
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{loader, Asset, BoxedError, SaveAsset};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Level {
+///     name: String,
+///     size: (u32, u32),
+/// }
+///
+/// impl Asset for Level {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = loader::RonLoader;
+/// }
+///
+/// impl SaveAsset for Level {
+///     fn to_bytes(&self, _ext: &str) -> Result<Vec<u8>, BoxedError> {
+///         Ok(ron::to_string(self)?.into_bytes())
+///     }
+/// }
+/// # }}
+/// ```
+pub trait SaveAsset: Asset {
+    /// Converts the asset to the content of a file with the given extension.
+    ///
+    /// The extension is one of [`Asset::EXTENSIONS`].
+    fn to_bytes(&self, ext: &str) -> Result<Vec<u8>, BoxedError>;
+}
+
+/// An asset type whose loading depends on parameters.
+///
+/// This is like an [`Asset`], but the conversion from raw bytes receives a
+/// value of type [`Self::Params`]. Such assets are loaded with
+/// [`AssetCache::load_with`], and the parameters are part of the key of the
+/// asset in the cache: loading the same file with different parameters gives
+/// different values, and loading it twice with the same parameters gives the
+/// same value.
+///
+/// When hot-reloading is enabled, all variants of an asset are reloaded when
+/// its file is modified.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, BoxedError, ParamAsset};
+/// use std::borrow::Cow;
+///
+/// struct Shader(String);
+///
+/// #[derive(Clone, Hash, PartialEq, Eq)]
+/// struct ShaderFeatures {
+///     shadows: bool,
+/// }
+///
+/// impl ParamAsset for Shader {
+///     const EXTENSION: &'static str = "txt";
+///     type Params = ShaderFeatures;
+///
+///     fn load(content: Cow<[u8]>, _: &str, features: &ShaderFeatures) -> Result<Self, BoxedError> {
+///         let mut source = String::from_utf8(content.into_owned())?;
+///         if features.shadows {
+///             source.insert_str(0, "#define SHADOWS\n");
+///         }
+///         Ok(Shader(source))
+///     }
+/// }
+///
+/// let cache = AssetCache::new("assets")?;
+///
+/// let plain = cache.load_with::<Shader>("example.hello", ShaderFeatures { shadows: false })?;
+/// let shadows = cache.load_with::<Shader>("example.hello", ShaderFeatures { shadows: true })?;
+///
+/// assert_eq!(plain.read().0, "Hello !\n");
+/// assert_eq!(shadows.read().0, "#define SHADOWS\nHello !\n");
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub trait ParamAsset: Storable {
+    /// Use this field if your asset only uses one extension.
+    ///
+    /// This value is ignored if you set `EXTENSIONS` too.
+    const EXTENSION: &'static str = "";
+
+    /// This field enables you to specify multiple extension for an asset.
+    ///
+    /// See [`Asset::EXTENSIONS`] for more details.
+    const EXTENSIONS: &'static [&'static str] = &[Self::EXTENSION];
+
+    /// The parameters given to [`load`](Self::load).
+    type Params: Clone + hash::Hash + Eq + Send + Sync + 'static;
+
+    /// Creates a value of this type from raw bytes and parameters.
+    fn load(content: Cow<[u8]>, ext: &str, params: &Self::Params) -> Result<Self, BoxedError>;
+
+    /// If `false`, disable hot-reloading for assets of this type (`true` by
+    /// default).
+    const HOT_RELOADED: bool = true;
+
+    /// The maximum size of a file of this type, in bytes.
+    ///
+    /// See [`Asset::MAX_SIZE`] for more details.
+    const MAX_SIZE: Option<u64> = None;
+}
+
+/// The parameters of a [`ParamAsset`], stored next to it in the cache.
+pub(crate) struct AssetParams<T: ParamAsset>(pub T::Params);
+
+/// Returns the id under which the asset `id` with parameters of hash `hash`
+/// is stored.
+///
+/// `n` is used to get a different id if the hashes of two parameters collide.
+pub(crate) fn param_id(id: &str, hash: u64, n: usize) -> String {
+    match n {
+        0 => format!("{id}#{hash:016x}"),
+        _ => format!("{id}#{hash:016x}-{n}"),
+    }
+}
+
+/// Hashes parameters in a way that does not depend on the cache.
+pub(crate) fn hash_params<P: hash::Hash>(params: &P) -> u64 {
+    use std::hash::Hasher;
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    params.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Loads a `ParamAsset` from the id given by [`param_id`], using the
+/// parameters stored in the cache.
+pub(crate) fn load_with_params<T: ParamAsset>(
+    cache: AnyCache,
+    id: &SharedString,
+) -> Result<T, Error> {
+    let base = id.rsplit_once('#').map_or(&**id, |(base, _)| base);
+    let error =
+        |err: LoadError| Error::with_type(base.into(), std::any::type_name::<T>(), err.into());
+
+    let params = cache
+        .no_record(|| cache.get_cached::<AssetParams<T>>(id))
+        .ok_or_else(|| error(LoadError::InvalidId))?;
+    let params = &params.read().0;
+
+    load_file(
+        cache.raw_source(),
+        base,
+        T::EXTENSIONS,
+        T::MAX_SIZE,
+        |content, ext| T::load(content, ext, params),
+    )
+    .map_err(error)
 }
 
 /// An asset type that can load other kinds of assets.
@@ -220,6 +661,10 @@ pub(crate) fn load_from_source<T: Asset>(
 /// Compound. When the former is reloaded, the latter will be reloaded too. An
 /// asset cannot depend on itself, or it may cause deadlocks to happen.
 ///
+/// Dependencies are only recorded for the given cache: assets loaded from
+/// another hot-reloaded cache are not tracked, and a warning is logged when
+/// that happens.
+///
 /// To opt out of dependencies recording, use [`AssetCache::no_record`].
 pub trait Compound: Sized + Send + Sync + 'static {
     /// Loads an asset from the cache.
@@ -234,7 +679,7 @@ pub trait Compound: Sized + Send + Sync + 'static {
     const HOT_RELOADED: bool = true;
 }
 
-fn is_invalid_id(id: &str) -> bool {
+pub(crate) fn is_invalid_id(id: &str) -> bool {
     id.starts_with('.')
         || id.ends_with('.')
         || id.contains("..")
@@ -249,23 +694,46 @@ pub(crate) fn load_and_record(
     typ: Type,
 ) -> Result<CacheEntry, Error> {
     if is_invalid_id(&id) {
-        return Err(Error::new(id, ErrorKind::InvalidId.into()));
+        return Err(Error::with_type(
+            id,
+            typ.type_name(),
+            LoadError::InvalidId.into(),
+        ));
     }
 
-    #[cfg(feature = "hot-reloading")]
-    if typ.is_hot_reloaded() {
-        if let Some(reloader) = cache.reloader() {
-            let (entry, deps) = crate::hot_reloading::records::record(reloader, || {
-                (typ.inner.load)(cache, id.clone())
-            });
-            if entry.is_ok() {
-                reloader.add_asset(id, deps, typ);
+    #[cfg(feature = "stats")]
+    let start = std::time::Instant::now();
+
+    let (entry, size) = crate::entry::measure_reads(|| {
+        #[cfg(feature = "hot-reloading")]
+        if typ.is_hot_reloaded() {
+            if let Some(reloader) = cache.reloader() {
+                let ((entry, ids), deps, hashes) =
+                    crate::hot_reloading::records::record(reloader, || {
+                        crate::entry::record_dependency_ids(|| (typ.inner.load)(cache, id.clone()))
+                    });
+                if entry.is_ok() {
+                    reloader.add_asset(id, deps, hashes, typ);
+                }
+                return entry.map(|mut entry| {
+                    entry.set_dependency_ids(ids);
+                    entry
+                });
             }
-            return entry;
         }
+
+        (typ.inner.load)(cache, id)
+    });
+
+    #[cfg(feature = "stats")]
+    if let Some(stats) = cache.stats() {
+        let result = entry.as_ref().map(|_| size).map_err(|_| ());
+        stats.record_load(typ.type_id, typ.type_name(), false, result, start.elapsed());
     }
 
-    (typ.inner.load)(cache, id)
+    let mut entry = entry?;
+    entry.set_loaded_size(size);
+    Ok(entry)
 }
 
 impl<T> Compound for T
@@ -411,6 +879,17 @@ macro_rules! serde_assets {
                 type Loader = loader::LoadFrom<T, $loader>;
             }
 
+            #[cfg(feature = $feature)]
+            impl<T> Validate for $name<T>
+            where
+                T: Validate,
+            {
+                #[inline]
+                fn validate(&self) -> Result<(), BoxedError> {
+                    self.0.validate()
+                }
+            }
+
             #[cfg(feature = $feature)]
             impl<T> AsRef<T> for $name<T> {
                 #[inline]
@@ -463,6 +942,40 @@ serde_assets! {
     );
 }
 
+/// Extensions of the enabled serde formats, which [`load_serde`] can read.
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+const SERDE_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "json")]
+    "json",
+    #[cfg(feature = "ron")]
+    "ron",
+    #[cfg(feature = "toml")]
+    "toml",
+    #[cfg(feature = "yaml")]
+    "yaml",
+    #[cfg(feature = "yaml")]
+    "yml",
+];
+
+/// Deserializes a value in the format given by the extension.
+#[cfg(any(feature = "json", feature = "ron", feature = "toml", feature = "yaml"))]
+fn load_serde<T>(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    match ext {
+        #[cfg(feature = "json")]
+        "json" => loader::JsonLoader::load(content, ext),
+        #[cfg(feature = "ron")]
+        "ron" => loader::RonLoader::load(content, ext),
+        #[cfg(feature = "toml")]
+        "toml" => loader::TomlLoader::load(content, ext),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => loader::YamlLoader::load(content, ext),
+        _ => Err(format!("unsupported extension: \"{ext}\"").into()),
+    }
+}
+
 macro_rules! image_assets {
     (
         $(
@@ -476,6 +989,11 @@ macro_rules! image_assets {
     ) => {
         $(
             #[doc = $doc]
+            ///
+            /// The image is decoded all at once from the bytes given by the
+            /// source, so both the encoded and the decoded images are in
+            /// memory during loading. For very large images, consider using
+            /// [`StreamedImage`] instead.
             #[cfg(feature = $feature)]
             #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
             #[derive(Clone, Debug)]
@@ -531,3 +1049,58 @@ image_assets! {
         ["webp"],
     );
 }
+
+/// Image formats that [`StreamedImage`] can decode, with their extensions.
+#[cfg(any(feature = "bmp", feature = "jpeg", feature = "png", feature = "webp"))]
+const STREAMED_FORMATS: &[(&str, image::ImageFormat)] = &[
+    #[cfg(feature = "png")]
+    ("png", image::ImageFormat::Png),
+    #[cfg(feature = "jpeg")]
+    ("jpg", image::ImageFormat::Jpeg),
+    #[cfg(feature = "jpeg")]
+    ("jpeg", image::ImageFormat::Jpeg),
+    #[cfg(feature = "webp")]
+    ("webp", image::ImageFormat::WebP),
+    #[cfg(feature = "bmp")]
+    ("bmp", image::ImageFormat::Bmp),
+];
+
+/// An image decoded while it is read from the source.
+///
+/// Unlike image assets such as `Png`, the image is not read in memory before
+/// being decoded: it is decoded from the reader given by [`Source::open`], so
+/// only the decoded image is fully in memory. This is useful for very large
+/// images, if the source supports streaming reads (e.g.
+/// [`FileSystem`](crate::source::FileSystem)).
+///
+/// Files are looked for with the extensions of all enabled image formats, in
+/// the order PNG, JPEG, WebP and BMP.
+#[cfg(any(feature = "bmp", feature = "jpeg", feature = "png", feature = "webp"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "bmp", feature = "jpeg", feature = "png", feature = "webp")))
+)]
+#[derive(Clone, Debug)]
+#[repr(transparent)]
+pub struct StreamedImage(pub image::DynamicImage);
+
+#[cfg(any(feature = "bmp", feature = "jpeg", feature = "png", feature = "webp"))]
+impl Compound for StreamedImage {
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let source = cache.raw_source();
+        let mut error = LoadError::NoDefaultValue;
+
+        for &(ext, format) in STREAMED_FORMATS {
+            match source.open(id, ext) {
+                Ok(reader) => {
+                    let reader = io::BufReader::new(reader);
+                    let image = image::ImageReader::with_format(reader, format).decode()?;
+                    return Ok(StreamedImage(image));
+                }
+                Err(err) => error = LoadError::from(err).or(error),
+            }
+        }
+
+        Err(error.into())
+    }
+}
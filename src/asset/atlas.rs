@@ -0,0 +1,306 @@
+use super::layered::Value;
+use crate::{
+    loader::Loader, AnyCache, AsAnyCache, Asset, BoxedError, Compound, Handle, SharedString,
+};
+use std::{borrow::Cow, collections::BTreeMap, fmt, marker::PhantomData};
+
+/// A sub-rectangle of the image of an [`Atlas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AtlasFrame {
+    /// The horizontal position of the frame in the image, in pixels.
+    pub x: u32,
+    /// The vertical position of the frame in the image, in pixels.
+    pub y: u32,
+    /// The width of the frame, in pixels.
+    pub width: u32,
+    /// The height of the frame, in pixels.
+    pub height: u32,
+    /// Whether the frame is rotated by 90° clockwise in the image.
+    pub rotated: bool,
+}
+
+fn field<'a>(map: &'a [(Value, Value)], key: &str) -> Option<&'a Value> {
+    map.iter().find_map(|(k, v)| match k {
+        Value::String(k) if k == key => Some(v),
+        _ => None,
+    })
+}
+
+fn parse_u32(map: &[(Value, Value)], keys: &[&str]) -> Result<u32, BoxedError> {
+    let value = keys.iter().find_map(|key| field(map, key));
+    match value {
+        Some(&Value::U64(n)) => Ok(u32::try_from(n)?),
+        Some(&Value::I64(n)) => Ok(u32::try_from(n)?),
+        Some(_) => Err(format!("`{}` is not an integer", keys[0]).into()),
+        None => Err(format!("missing `{}`", keys[0]).into()),
+    }
+}
+
+/// Parses a frame, either as a rectangle or as a TexturePacker frame, whose
+/// rectangle is in a `frame` field.
+fn parse_frame(value: &Value) -> Result<AtlasFrame, BoxedError> {
+    let Value::Map(map) = value else {
+        return Err("expected a map".into());
+    };
+
+    let (rect, rotated) = match field(map, "frame") {
+        Some(Value::Map(rect)) => {
+            let rotated = match field(map, "rotated") {
+                Some(&Value::Bool(rotated)) => rotated,
+                None | Some(Value::Null) => false,
+                Some(_) => return Err("`rotated` is not a boolean".into()),
+            };
+            (rect, rotated)
+        }
+        Some(_) => return Err("`frame` is not a map".into()),
+        None => (map, false),
+    };
+
+    Ok(AtlasFrame {
+        x: parse_u32(rect, &["x"])?,
+        y: parse_u32(rect, &["y"])?,
+        width: parse_u32(rect, &["w", "width"])?,
+        height: parse_u32(rect, &["h", "height"])?,
+        rotated,
+    })
+}
+
+/// The frames of an atlas, in any enabled format.
+struct Frames(BTreeMap<String, AtlasFrame>);
+
+struct FramesLoader;
+
+impl Loader<Frames> for FramesLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Frames, BoxedError> {
+        let value: Value = super::load_serde(content, ext)?;
+        let frames = match &value {
+            Value::Map(root) => field(root, "frames"),
+            _ => None,
+        };
+
+        let mut result = BTreeMap::new();
+        let mut add = |name: String, frame: &Value| -> Result<(), BoxedError> {
+            let parsed = parse_frame(frame).map_err(|err| format!("frame \"{name}\": {err}"))?;
+            if result.contains_key(&name) {
+                return Err(format!("duplicate frame \"{name}\"").into());
+            }
+            result.insert(name, parsed);
+            Ok(())
+        };
+
+        match frames {
+            Some(Value::Map(frames)) => {
+                for (name, frame) in frames {
+                    let Value::String(name) = name else {
+                        return Err("frame names must be strings".into());
+                    };
+                    add(name.clone(), frame)?;
+                }
+            }
+            Some(Value::Seq(frames)) => {
+                for frame in frames {
+                    let name = match frame {
+                        Value::Map(map) => match field(map, "filename") {
+                            Some(Value::String(name)) => name.clone(),
+                            _ => return Err("missing frame `filename`".into()),
+                        },
+                        _ => return Err("expected a frame".into()),
+                    };
+                    add(name, frame)?;
+                }
+            }
+            _ => return Err("missing `frames`".into()),
+        }
+
+        Ok(Frames(result))
+    }
+}
+
+impl Asset for Frames {
+    const EXTENSIONS: &'static [&'static str] = super::SERDE_EXTENSIONS;
+    type Loader = FramesLoader;
+}
+
+/// Gives the ids of the files of an [`Atlas`], and the names of its frames.
+///
+/// This is implemented on marker types, so that atlases with different
+/// conventions can be stored in the cache.
+pub trait AtlasNaming: Send + Sync + 'static {
+    /// Returns the id of the image of the atlas with the given id.
+    ///
+    /// The default is the id of the atlas.
+    fn image_id(id: &str) -> String {
+        id.to_owned()
+    }
+
+    /// Returns the id of the frames of the atlas with the given id.
+    ///
+    /// The default is the id of the atlas.
+    fn frames_id(id: &str) -> String {
+        id.to_owned()
+    }
+
+    /// Returns the name given to a frame in the atlas, from its name in the
+    /// frames file.
+    ///
+    /// The default keeps the name as is.
+    fn frame_name(name: &str) -> Cow<'_, str> {
+        Cow::Borrowed(name)
+    }
+}
+
+/// The default naming of an [`Atlas`].
+///
+/// The image and the frames have the same id as the atlas, and frames keep
+/// their name.
+#[derive(Debug)]
+pub struct DefaultAtlasNaming(());
+
+impl AtlasNaming for DefaultAtlasNaming {}
+
+/// The naming of an [`Atlas`] exported by TexturePacker.
+///
+/// The image and the frames have the same id as the atlas, and the extension
+/// of the original files is removed from frame names: `"hero/walk_01.png"` is
+/// named `"hero/walk_01"`.
+#[derive(Debug)]
+pub struct TexturePackerNaming(());
+
+impl AtlasNaming for TexturePackerNaming {
+    fn frame_name(name: &str) -> Cow<'_, str> {
+        match name.rsplit_once('.') {
+            Some((stem, ext)) if !ext.contains('/') => Cow::Borrowed(stem),
+            _ => Cow::Borrowed(name),
+        }
+    }
+}
+
+/// A texture atlas: an image with named sub-rectangles.
+///
+/// The image is loaded as an `I` (eg `Png`), and the frames from a file in
+/// any enabled serde format (JSON, RON, TOML or YAML). Ids of these files and
+/// names of frames are given by `N`.
+///
+/// The frames file is a map with a `frames` key that contains either:
+/// - A map from names to frames.
+/// - A list of frames with a `filename` key for their name.
+///
+/// A frame is either a rectangle with `x`, `y`, `w` and `h` keys (or `width`
+/// and `height`), or a map with such a rectangle in `frame` key, and an
+/// optional `rotated` boolean. This matches the "JSON (Hash)" and "JSON
+/// (Array)" exports of TexturePacker, and other keys are ignored.
+///
+/// As an `Atlas` is stored in the cache, it only stores the id of its image,
+/// and the image is given by the cache it was loaded from.
+///
+/// When hot-reloading, the atlas is reloaded when the image or the frames
+/// change.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(all(feature = "json", feature = "png"))] {
+/// use assets_manager::{
+///     asset::{Atlas, Png, TexturePackerNaming},
+///     AssetCache,
+/// };
+///
+/// let cache = AssetCache::new("assets")?;
+///
+/// // Loads "example/atlas.png" and "example/atlas.json"
+/// let atlas = cache.load::<Atlas<Png, TexturePackerNaming>>("example.atlas")?.read();
+///
+/// let blue = atlas.frame("blue").unwrap();
+/// assert_eq!((blue.x, blue.y, blue.width, blue.height), (2, 0, 2, 2));
+///
+/// let image = atlas.image(&cache).unwrap().read();
+/// assert_eq!(image.0.width(), 4);
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct Atlas<I, N = DefaultAtlasNaming> {
+    image: SharedString,
+    frames: BTreeMap<SharedString, AtlasFrame>,
+    _marker: PhantomData<(I, N)>,
+}
+
+impl<I, N> Compound for Atlas<I, N>
+where
+    I: Compound,
+    N: AtlasNaming,
+{
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let image = cache.load::<I>(&N::image_id(id))?.id().clone();
+
+        let frames = cache.load::<Frames>(&N::frames_id(id))?.read();
+        let frames = frames
+            .0
+            .iter()
+            .map(|(name, frame)| (N::frame_name(name).into(), *frame))
+            .collect();
+
+        Ok(Atlas {
+            image,
+            frames,
+            _marker: PhantomData,
+        })
+    }
+
+    const HOT_RELOADED: bool = true;
+}
+
+impl<I, N> Atlas<I, N> {
+    /// Returns the id of the image of the atlas.
+    #[inline]
+    pub fn image_id(&self) -> &SharedString {
+        &self.image
+    }
+
+    /// Returns the frame with the given name.
+    #[inline]
+    pub fn frame(&self, name: &str) -> Option<&AtlasFrame> {
+        self.frames.get(name)
+    }
+
+    /// Returns an iterator over the names and frames of the atlas, sorted by
+    /// name.
+    #[inline]
+    pub fn frames(&self) -> impl ExactSizeIterator<Item = (&SharedString, &AtlasFrame)> {
+        self.frames.iter()
+    }
+
+    /// The number of frames in the atlas.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if the atlas has no frame.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl<I, N> Atlas<I, N>
+where
+    I: Compound,
+{
+    /// Returns the image of the atlas.
+    ///
+    /// This function does not do any I/O: `cache` must be the cache the
+    /// atlas was loaded from.
+    #[inline]
+    pub fn image<'a>(&self, cache: impl AsAnyCache<'a>) -> Option<&'a Handle<I>> {
+        cache.as_any_cache().get_cached(&self.image)
+    }
+}
+
+impl<I, N> fmt::Debug for Atlas<I, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Atlas")
+            .field("image", &self.image)
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
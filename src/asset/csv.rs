@@ -0,0 +1,102 @@
+use crate::{
+    loader::{load_csv, CsvLoader, CsvOptions, LoadFrom},
+    Asset, BoxedError, ParamAsset,
+};
+use serde::Deserialize;
+use std::borrow::Cow;
+
+/// The rows of a CSV or TSV file.
+///
+/// Each row is deserialized into a `T` with `serde`. With default options,
+/// the first row contains the headers, so structs fields are matched with
+/// columns by name.
+///
+/// Files with extension `csv` are separated with commas, and files with
+/// extension `tsv` with tabs. Other options can be given with
+/// [`AssetCache::load_with`](crate::AssetCache::load_with), in which case
+/// files with both extensions use them.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{asset::Csv, loader::CsvOptions, source::InMemory, AssetCache};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Item {
+///     name: String,
+///     price: u32,
+/// }
+///
+/// let source = InMemory::new();
+/// source.insert("items", "csv", b"name,price\nsword,100\nshield,80\n".to_vec());
+/// source.insert("raw_items", "csv", b"sword;100\nshield;80\n".to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+///
+/// let items = cache.load::<Csv<Item>>("items")?.read();
+/// assert_eq!(items.0[1].name, "shield");
+///
+/// let options = CsvOptions::new().with_delimiter(b';').with_headers(false);
+/// let raw_items = cache.load_with::<Csv<(String, u32)>>("raw_items", options)?.read();
+/// assert_eq!(raw_items.0[0], ("sword".to_owned(), 100));
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Csv<T>(pub Vec<T>);
+
+impl<T> Csv<T> {
+    /// Unwraps the rows.
+    #[inline]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Default for Csv<T> {
+    #[inline]
+    fn default() -> Self {
+        Csv(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for Csv<T> {
+    #[inline]
+    fn from(rows: Vec<T>) -> Self {
+        Csv(rows)
+    }
+}
+
+impl<T> From<Csv<T>> for Vec<T> {
+    #[inline]
+    fn from(csv: Csv<T>) -> Self {
+        csv.0
+    }
+}
+
+impl<T> AsRef<[T]> for Csv<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> Asset for Csv<T>
+where
+    T: for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+    const EXTENSIONS: &'static [&'static str] = &["csv", "tsv"];
+    type Loader = LoadFrom<Vec<T>, CsvLoader>;
+}
+
+impl<T> ParamAsset for Csv<T>
+where
+    T: for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+    const EXTENSIONS: &'static [&'static str] = &["csv", "tsv"];
+    type Params = CsvOptions;
+
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str, options: &CsvOptions) -> Result<Self, BoxedError> {
+        load_csv(&content, *options).map(Csv)
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{loader, Asset, BoxedError};
+use std::{borrow::Cow, fmt, ops::Range};
+
+const MAGIC: &[u8; 4] = b"DDS ";
+
+/// Size of the magic and the header, after which the DX10 header or the data
+/// starts.
+const HEADER_END: usize = 128;
+const DX10_HEADER_END: usize = HEADER_END + 20;
+
+const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_VOLUME: u32 = 0x20_0000;
+const RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, BoxedError> {
+    match data.get(offset..offset + 4) {
+        Some(bytes) => Ok(u32::from_le_bytes(bytes.try_into().unwrap())),
+        None => Err("unexpected end of DDS file".into()),
+    }
+}
+
+/// The pixel format of a [`Dds`] texture.
+#[cfg_attr(docsrs, doc(cfg(feature = "dds")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DdsFormat {
+    /// A format given by a four-character code, eg `*b"DXT1"`.
+    FourCC([u8; 4]),
+
+    /// A `DXGI_FORMAT` value, given by the DX10 header.
+    Dxgi(u32),
+
+    /// An uncompressed format, described by its size and its channels.
+    Masks {
+        /// The number of bits per pixel.
+        bit_count: u32,
+        /// The mask of the red (or luminance) channel.
+        r: u32,
+        /// The mask of the green channel.
+        g: u32,
+        /// The mask of the blue channel.
+        b: u32,
+        /// The mask of the alpha channel.
+        a: u32,
+    },
+}
+
+/// The size of a format, used to find the surfaces in the data.
+#[derive(Clone, Copy)]
+enum Layout {
+    /// Bytes per 4x4 block.
+    Blocks(usize),
+
+    /// Bits per pixel.
+    Pixels(usize),
+}
+
+impl DdsFormat {
+    fn layout(self) -> Option<Layout> {
+        Some(match self {
+            DdsFormat::FourCC(code) => match &code {
+                b"DXT1" | b"ATI1" | b"BC4U" | b"BC4S" => Layout::Blocks(8),
+                b"DXT2" | b"DXT3" | b"DXT4" | b"DXT5" | b"ATI2" | b"BC5U" | b"BC5S" => {
+                    Layout::Blocks(16)
+                }
+                _ => return None,
+            },
+            DdsFormat::Dxgi(format) => match format {
+                70..=72 | 79..=81 => Layout::Blocks(8),
+                73..=78 | 82..=84 | 94..=99 => Layout::Blocks(16),
+                1..=4 => Layout::Pixels(128),
+                5..=8 => Layout::Pixels(96),
+                9..=22 => Layout::Pixels(64),
+                23..=47 | 67 | 87..=93 => Layout::Pixels(32),
+                48..=60 | 85 | 86 | 115 => Layout::Pixels(16),
+                61..=65 => Layout::Pixels(8),
+                _ => return None,
+            },
+            DdsFormat::Masks { bit_count, .. } => match bit_count {
+                8 | 16 | 24 | 32 | 64 | 128 => Layout::Pixels(bit_count as usize),
+                _ => return None,
+            },
+        })
+    }
+}
+
+/// Returns the size of a surface of the given dimensions.
+fn surface_len(layout: Layout, width: u32, height: u32, depth: u32) -> Option<usize> {
+    let (width, height, depth) = (width as usize, height as usize, depth as usize);
+    let slice = match layout {
+        Layout::Blocks(size) => ((width + 3) / 4)
+            .checked_mul((height + 3) / 4)?
+            .checked_mul(size)?,
+        Layout::Pixels(bits) => ((width.checked_mul(bits)? + 7) / 8).checked_mul(height)?,
+    };
+    slice.checked_mul(depth)
+}
+
+/// A texture in the [DDS] format.
+///
+/// The texture is not decoded: this gives access to its header and to the raw
+/// bytes of each surface, that can be uploaded as is to the GPU if the format
+/// is supported.
+///
+/// A DDS file contains several images when it is an array or a cube map
+/// (6 faces per array element), each of which has the same number of mip
+/// levels. Surfaces can only be located for block-compressed formats (BC1 to
+/// BC7) and formats with a whole number of bytes per pixel; for other formats,
+/// only the whole [`payload`](Self::payload) is available.
+///
+/// [DDS]: https://learn.microsoft.com/windows/win32/direct3ddds/dx-graphics-dds
+#[cfg_attr(docsrs, doc(cfg(feature = "dds")))]
+#[derive(Clone)]
+pub struct Dds {
+    data: Box<[u8]>,
+    payload_start: usize,
+    format: DdsFormat,
+    width: u32,
+    height: u32,
+    depth: u32,
+    level_count: u32,
+    array_size: u32,
+    is_cubemap: bool,
+    surfaces: Option<Vec<Range<usize>>>,
+}
+
+impl Dds {
+    fn parse(data: Vec<u8>) -> Result<Self, BoxedError> {
+        if !data.starts_with(MAGIC) || read_u32(&data, 4)? != 124 {
+            return Err("invalid DDS header".into());
+        }
+
+        let flags = read_u32(&data, 8)?;
+        let height = read_u32(&data, 12)?;
+        let width = read_u32(&data, 16)?;
+        let caps2 = read_u32(&data, 112)?;
+        let depth = match caps2 & DDSCAPS2_VOLUME {
+            0 => 1,
+            _ => read_u32(&data, 24)?.max(1),
+        };
+        let level_count = match flags & DDSD_MIPMAPCOUNT {
+            0 => 1,
+            _ => read_u32(&data, 28)?.max(1),
+        };
+
+        let pf_flags = read_u32(&data, 80)?;
+        let four_cc = read_u32(&data, 84)?.to_le_bytes();
+        let (format, payload_start, array_size, is_cubemap) = if pf_flags & DDPF_FOURCC == 0 {
+            let format = DdsFormat::Masks {
+                bit_count: read_u32(&data, 88)?,
+                r: read_u32(&data, 92)?,
+                g: read_u32(&data, 96)?,
+                b: read_u32(&data, 100)?,
+                a: read_u32(&data, 104)?,
+            };
+            (format, HEADER_END, 1, caps2 & DDSCAPS2_CUBEMAP != 0)
+        } else if &four_cc == b"DX10" {
+            let format = DdsFormat::Dxgi(read_u32(&data, HEADER_END)?);
+            let is_cubemap = read_u32(&data, HEADER_END + 8)? & RESOURCE_MISC_TEXTURECUBE != 0;
+            let array_size = read_u32(&data, HEADER_END + 12)?.max(1);
+            (format, DX10_HEADER_END, array_size, is_cubemap)
+        } else {
+            let format = DdsFormat::FourCC(four_cc);
+            (format, HEADER_END, 1, caps2 & DDSCAPS2_CUBEMAP != 0)
+        };
+        if payload_start > data.len() {
+            return Err("unexpected end of DDS file".into());
+        }
+
+        let mut dds = Dds {
+            data: data.into_boxed_slice(),
+            payload_start,
+            format,
+            width,
+            height,
+            depth,
+            level_count,
+            array_size,
+            is_cubemap,
+            surfaces: None,
+        };
+        dds.surfaces = dds.locate_surfaces()?;
+        Ok(dds)
+    }
+
+    fn locate_surfaces(&self) -> Result<Option<Vec<Range<usize>>>, BoxedError> {
+        let Some(layout) = self.format.layout() else {
+            return Ok(None);
+        };
+
+        let mut surfaces = Vec::new();
+        let mut offset = self.payload_start;
+        for _ in 0..self.image_count() {
+            for level in 0..self.level_count {
+                let dim = |x: u32| x.checked_shr(level).unwrap_or(0).max(1);
+                let len = surface_len(layout, dim(self.width), dim(self.height), dim(self.depth))
+                    .ok_or("DDS texture too large")?;
+                let end = offset
+                    .checked_add(len)
+                    .filter(|&end| end <= self.data.len())
+                    .ok_or("unexpected end of DDS file")?;
+                surfaces.push(offset..end);
+                offset = end;
+            }
+        }
+
+        Ok(Some(surfaces))
+    }
+
+    /// The pixel format of the texture.
+    #[inline]
+    pub fn format(&self) -> DdsFormat {
+        self.format
+    }
+
+    /// The width of the base level, in pixels.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the base level, in pixels.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The depth of the base level, in pixels, or `1` if the texture is not a
+    /// volume texture.
+    #[inline]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// The number of mip levels.
+    #[inline]
+    pub fn level_count(&self) -> u32 {
+        self.level_count
+    }
+
+    /// The number of elements of the texture array, or `1` if the texture is
+    /// not an array.
+    #[inline]
+    pub fn array_size(&self) -> u32 {
+        self.array_size
+    }
+
+    /// Returns `true` if the texture is a cube map.
+    #[inline]
+    pub fn is_cubemap(&self) -> bool {
+        self.is_cubemap
+    }
+
+    /// The number of images in the file: the size of the array, times 6 for
+    /// cube maps.
+    #[inline]
+    pub fn image_count(&self) -> u32 {
+        self.array_size
+            .saturating_mul(if self.is_cubemap { 6 } else { 1 })
+    }
+
+    /// Returns the bytes of a mip level of an image, `0` being the base level.
+    ///
+    /// Returns `None` if the indices are out of bounds, or if surfaces cannot
+    /// be located for the format of the texture.
+    pub fn surface(&self, image: u32, level: u32) -> Option<&[u8]> {
+        let surfaces = self.surfaces.as_ref()?;
+        if image >= self.image_count() || level >= self.level_count {
+            return None;
+        }
+        let index = image as usize * self.level_count as usize + level as usize;
+        let range = surfaces.get(index)?;
+        Some(&self.data[range.clone()])
+    }
+
+    /// Returns an iterator over the mip levels of an image, starting with the
+    /// base level.
+    ///
+    /// The iterator is empty if the index is out of bounds, or if surfaces
+    /// cannot be located for the format of the texture.
+    pub fn levels(&self, image: u32) -> impl ExactSizeIterator<Item = &[u8]> {
+        let ranges = match &self.surfaces {
+            Some(surfaces) if image < self.image_count() => {
+                let start = image as usize * self.level_count as usize;
+                &surfaces[start..start + self.level_count as usize]
+            }
+            _ => &[],
+        };
+        ranges.iter().map(|range| &self.data[range.clone()])
+    }
+
+    /// Returns the bytes of all surfaces, after the headers.
+    #[inline]
+    pub fn payload(&self) -> &[u8] {
+        &self.data[self.payload_start..]
+    }
+
+    /// Returns the bytes of the whole file.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "dds")))]
+impl loader::Loader<Dds> for loader::TextureLoader {
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Dds, BoxedError> {
+        Dds::parse(content.into_owned())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "dds")))]
+impl Asset for Dds {
+    const EXTENSION: &'static str = "dds";
+    type Loader = loader::TextureLoader;
+}
+
+impl fmt::Debug for Dds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dds")
+            .field("format", &self.format)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("depth", &self.depth)
+            .field("level_count", &self.level_count)
+            .field("array_size", &self.array_size)
+            .field("is_cubemap", &self.is_cubemap)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,8 +1,12 @@
 use std::borrow::Cow;
 
-use crate::{loader, Asset, BoxedError};
+use crate::{loader, Asset, BoxedError, ParamAsset};
 use ab_glyph::{FontArc, FontVec};
 
+/// Fonts files, including font collections, of which the first font is loaded
+/// by default.
+const EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
 #[cfg_attr(docsrs, doc(cfg(feature = "ab_glyph")))]
 impl loader::Loader<FontVec> for loader::FontLoader {
     fn load(content: Cow<[u8]>, _: &str) -> Result<FontVec, BoxedError> {
@@ -20,11 +24,41 @@ impl loader::Loader<FontArc> for loader::FontLoader {
 #[cfg_attr(docsrs, doc(cfg(feature = "ab_glyph")))]
 impl Asset for FontVec {
     type Loader = loader::FontLoader;
-    const EXTENSIONS: &'static [&'static str] = &["ttf", "otf"];
+    const EXTENSIONS: &'static [&'static str] = EXTENSIONS;
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "ab_glyph")))]
 impl Asset for FontArc {
     type Loader = loader::FontLoader;
-    const EXTENSIONS: &'static [&'static str] = &["ttf", "otf"];
+    const EXTENSIONS: &'static [&'static str] = EXTENSIONS;
+}
+
+/// Loads the font with the given index in a font collection (`.ttc`).
+///
+/// Index `0` is also the only font of a single font file.
+#[cfg_attr(docsrs, doc(cfg(feature = "ab_glyph")))]
+impl ParamAsset for FontVec {
+    const EXTENSIONS: &'static [&'static str] = EXTENSIONS;
+    type Params = u32;
+
+    fn load(content: Cow<[u8]>, _: &str, &index: &u32) -> Result<Self, BoxedError> {
+        Ok(FontVec::try_from_vec_and_index(
+            content.into_owned(),
+            index,
+        )?)
+    }
+}
+
+/// Loads the font with the given index in a font collection (`.ttc`).
+///
+/// Index `0` is also the only font of a single font file.
+#[cfg_attr(docsrs, doc(cfg(feature = "ab_glyph")))]
+impl ParamAsset for FontArc {
+    const EXTENSIONS: &'static [&'static str] = EXTENSIONS;
+    type Params = u32;
+
+    fn load(content: Cow<[u8]>, _: &str, &index: &u32) -> Result<Self, BoxedError> {
+        let font = FontVec::try_from_vec_and_index(content.into_owned(), index)?;
+        Ok(FontArc::new(font))
+    }
 }
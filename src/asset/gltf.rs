@@ -1,5 +1,5 @@
-use crate::{loader, utils, AnyCache, Asset, BoxedError, Compound, SharedString};
-use std::path;
+use crate::{loader, utils, AnyCache, AsAnyCache, Asset, BoxedError, Compound, SharedString};
+use std::{path, sync::OnceLock};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
 impl Asset for gltf::Gltf {
@@ -49,6 +49,178 @@ impl Gltf {
     pub fn get_image_by_index(&self, index: usize) -> &image::DynamicImage {
         &self.images[index]
     }
+
+    /// Decodes the channels of an animation.
+    ///
+    /// Returns an error if the accessors of the animation are invalid.
+    pub fn animation_channels(
+        &self,
+        animation: &gltf::Animation,
+    ) -> Result<Vec<AnimationChannel>, BoxedError> {
+        use gltf::animation::{util::ReadOutputs, Interpolation};
+
+        let get_buffer = |buffer: gltf::Buffer| Some(self.get_buffer(&buffer));
+
+        animation
+            .channels()
+            .map(|channel| {
+                let reader = channel.reader(get_buffer);
+                let interpolation = channel.sampler().interpolation();
+                let cubic = interpolation == Interpolation::CubicSpline;
+
+                let times: Vec<f32> = reader
+                    .read_inputs()
+                    .ok_or("missing animation inputs")?
+                    .collect();
+                let outputs = reader.read_outputs().ok_or("missing animation outputs")?;
+
+                let keyframes = match outputs {
+                    ReadOutputs::Translations(values) => {
+                        Keyframes::Translation(to_keyframes(&times, values, cubic)?)
+                    }
+                    ReadOutputs::Rotations(values) => {
+                        Keyframes::Rotation(to_keyframes(&times, values.into_f32(), cubic)?)
+                    }
+                    ReadOutputs::Scales(values) => {
+                        Keyframes::Scale(to_keyframes(&times, values, cubic)?)
+                    }
+                    ReadOutputs::MorphTargetWeights(values) => {
+                        let values: Vec<f32> = values.into_f32().collect();
+                        let per_keyframe = times.len() * if cubic { 3 } else { 1 };
+                        if per_keyframe == 0 || values.len() % per_keyframe != 0 {
+                            return Err("invalid number of morph target weights".into());
+                        }
+                        let weights = values.chunks(values.len() / per_keyframe);
+                        let weights = weights.map(<[f32]>::to_vec);
+                        Keyframes::MorphTargetWeights(to_keyframes(&times, weights, cubic)?)
+                    }
+                };
+
+                Ok(AnimationChannel {
+                    node: channel.target().node().index(),
+                    interpolation,
+                    keyframes,
+                })
+            })
+            .collect()
+    }
+
+    /// Decodes the inverse bind matrices of a skin, one per joint, as
+    /// column-major matrices.
+    ///
+    /// If the skin does not define them, they are identity matrices.
+    pub fn inverse_bind_matrices(
+        &self,
+        skin: &gltf::Skin,
+    ) -> Result<Vec<[[f32; 4]; 4]>, BoxedError> {
+        let joint_count = skin.joints().len();
+
+        let matrices: Vec<_> = if skin.inverse_bind_matrices().is_some() {
+            let reader = skin.reader(|buffer| Some(self.get_buffer(&buffer)));
+            reader
+                .read_inverse_bind_matrices()
+                .ok_or("missing inverse bind matrices")?
+                .collect()
+        } else {
+            const IDENTITY: [[f32; 4]; 4] = [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ];
+            vec![IDENTITY; joint_count]
+        };
+
+        if matrices.len() < joint_count {
+            return Err("missing inverse bind matrices".into());
+        }
+        Ok(matrices)
+    }
+}
+
+/// A keyframe of an animation channel.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+pub struct Keyframe<T> {
+    /// The time of the keyframe, in seconds.
+    pub time: f32,
+
+    /// The value of the animated property at this time.
+    pub value: T,
+
+    /// The in-tangent and out-tangent of the keyframe, for cubic spline
+    /// interpolation.
+    pub tangents: Option<(T, T)>,
+}
+
+/// The keyframes of an animation channel, by animated property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+pub enum Keyframes {
+    /// Translations of the node.
+    Translation(Vec<Keyframe<[f32; 3]>>),
+
+    /// Rotations of the node, as unit quaternions (`[x, y, z, w]`).
+    Rotation(Vec<Keyframe<[f32; 4]>>),
+
+    /// Scales of the node.
+    Scale(Vec<Keyframe<[f32; 3]>>),
+
+    /// Weights of the morph targets of the mesh of the node.
+    MorphTargetWeights(Vec<Keyframe<Vec<f32>>>),
+}
+
+/// A decoded animation channel of a glTF document.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+pub struct AnimationChannel {
+    /// The index of the animated node.
+    pub node: usize,
+
+    /// How values are interpolated between keyframes.
+    pub interpolation: gltf::animation::Interpolation,
+
+    /// The keyframes of the channel.
+    pub keyframes: Keyframes,
+}
+
+/// Groups values in keyframes, with tangents if the interpolation is a cubic
+/// spline.
+fn to_keyframes<T>(
+    times: &[f32],
+    values: impl Iterator<Item = T>,
+    cubic_spline: bool,
+) -> Result<Vec<Keyframe<T>>, BoxedError> {
+    let mut values = values.fuse();
+    let mut keyframes = Vec::with_capacity(times.len());
+
+    for &time in times {
+        let keyframe = if cubic_spline {
+            match (values.next(), values.next(), values.next()) {
+                (Some(in_tangent), Some(value), Some(out_tangent)) => Keyframe {
+                    time,
+                    value,
+                    tangents: Some((in_tangent, out_tangent)),
+                },
+                _ => return Err("missing animation output".into()),
+            }
+        } else {
+            match values.next() {
+                Some(value) => Keyframe {
+                    time,
+                    value,
+                    tangents: None,
+                },
+                None => return Err("missing animation output".into()),
+            }
+        };
+        keyframes.push(keyframe);
+    }
+
+    if values.next().is_some() {
+        return Err("too many animation outputs".into());
+    }
+    Ok(keyframes)
 }
 
 #[derive(Clone)]
@@ -129,21 +301,27 @@ impl<'a> UriContent<'a> {
     }
 }
 
+fn load_buffer_uri(cache: AnyCache, base_id: &str, uri: &str) -> Result<Vec<u8>, BoxedError> {
+    Ok(match UriContent::parse_uri(base_id, uri, None)? {
+        UriContent::Bin { content: data, .. } => data,
+        UriContent::File { id, .. } => cache.load::<Bin>(&id)?.cloned().0,
+    })
+}
+
 fn load_buffer(
     cache: AnyCache,
     base_id: &str,
     buffer: gltf::Buffer,
     blob: &mut Option<Vec<u8>>,
 ) -> Result<Vec<u8>, BoxedError> {
-    Ok(match buffer.source() {
-        gltf::buffer::Source::Bin => blob.take().ok_or("missing binary portion of binary glTF")?,
-        gltf::buffer::Source::Uri(uri) => match UriContent::parse_uri(base_id, uri, None)? {
-            UriContent::Bin { content: data, .. } => data,
-            UriContent::File { id, .. } => cache.load::<Bin>(&id)?.cloned().0,
-        },
-    })
+    match buffer.source() {
+        gltf::buffer::Source::Bin => Ok(blob.take().ok_or(MISSING_BLOB)?),
+        gltf::buffer::Source::Uri(uri) => load_buffer_uri(cache, base_id, uri),
+    }
 }
 
+const MISSING_BLOB: &str = "missing binary portion of binary glTF";
+
 fn load_image_from_buffer(
     buffer: &[u8],
     mime_type: Option<&str>,
@@ -162,11 +340,11 @@ fn load_image_from_buffer(
     Ok(image::load_from_memory_with_format(buffer, format)?)
 }
 
-fn load_image(
+fn load_image<'a>(
     cache: AnyCache,
     base_id: &str,
-    buffers: &[Vec<u8>],
     image: gltf::Image,
+    get_buffer: impl FnOnce(usize) -> Result<&'a [u8], BoxedError>,
 ) -> Result<image::DynamicImage, BoxedError> {
     match image.source() {
         gltf::image::Source::Uri { uri, mime_type } => {
@@ -182,7 +360,7 @@ fn load_image(
             }
         }
         gltf::image::Source::View { view, mime_type } => {
-            let buffer = &buffers[view.buffer().index()];
+            let buffer = get_buffer(view.buffer().index())?;
             let offset = view.offset();
             let buffer = &buffer[offset..offset + view.length()];
 
@@ -191,15 +369,19 @@ fn load_image(
     }
 }
 
+fn base_id(id: &str) -> &str {
+    match id.rfind('.') {
+        Some(index) => &id[..index],
+        None => "",
+    }
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
 impl Compound for Gltf {
     fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
         let gltf::Gltf { document, mut blob } = cache.load::<gltf::Gltf>(id)?.cloned();
 
-        let base_id = match id.rfind('.') {
-            Some(index) => &id[..index],
-            None => "",
-        };
+        let base_id = base_id(id);
 
         let buffers: Vec<_> = document
             .buffers()
@@ -207,7 +389,7 @@ impl Compound for Gltf {
             .collect::<Result<_, _>>()?;
         let images = document
             .images()
-            .map(|i| load_image(cache, base_id, &buffers, i))
+            .map(|i| load_image(cache, base_id, i, |b| Ok(&buffers[b])))
             .collect::<Result<_, _>>()?;
 
         Ok(Gltf {
@@ -224,3 +406,150 @@ impl super::DirLoadable for Gltf {
         gltf::Gltf::select_ids(cache, id)
     }
 }
+
+/// Loads glTF 3D assets, with buffers and images loaded on first access.
+///
+/// This is like [`Gltf`], but only the glTF document is parsed when the asset
+/// is loaded. Buffers and images are loaded and decoded the first time they
+/// are accessed, then kept in the value. This makes the loading of large
+/// scenes much faster when only a part of them is needed.
+///
+/// As an asset is stored in the cache, methods that access buffers and images
+/// take the cache the asset was loaded from, to load external files.
+///
+/// When hot-reloading, the asset is reloaded when the glTF file changes, but
+/// not when the files of its buffers and images change.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{asset::GltfLazy, AssetCache};
+///
+/// let cache = AssetCache::new("assets")?;
+///
+/// let gltf = cache.load::<GltfLazy>("test.gltf.box")?.read();
+///
+/// // Only the image is loaded, not the buffer
+/// let image = gltf.get_image_by_index(&cache, 0)?;
+/// assert_eq!(image.width(), 256);
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+pub struct GltfLazy {
+    /// The glTF document.
+    pub document: gltf::Document,
+
+    base_id: String,
+    blob: Option<Vec<u8>>,
+    images: Vec<OnceLock<image::DynamicImage>>,
+    buffers: Vec<OnceLock<Vec<u8>>>,
+}
+
+impl GltfLazy {
+    /// Retreives the content of a buffer, loading it if needed.
+    ///
+    /// `cache` must be the cache the asset was loaded from.
+    pub fn get_buffer<'a>(
+        &self,
+        cache: impl AsAnyCache<'a>,
+        buffer: &gltf::Buffer,
+    ) -> Result<&[u8], BoxedError> {
+        self.get_buffer_by_index(cache, buffer.index())
+    }
+
+    /// Retreives the content of a buffer by its index, loading it if needed.
+    ///
+    /// `cache` must be the cache the asset was loaded from.
+    pub fn get_buffer_by_index<'a>(
+        &self,
+        cache: impl AsAnyCache<'a>,
+        index: usize,
+    ) -> Result<&[u8], BoxedError> {
+        let cell = &self.buffers[index];
+        if let Some(buffer) = cell.get() {
+            return Ok(buffer);
+        }
+
+        let buffer = self.document.buffers().nth(index).unwrap();
+        match buffer.source() {
+            // The binary portion is kept as is
+            gltf::buffer::Source::Bin => Ok(self.blob.as_deref().ok_or(MISSING_BLOB)?),
+            gltf::buffer::Source::Uri(uri) => {
+                let content = load_buffer_uri(cache.as_any_cache(), &self.base_id, uri)?;
+                Ok(cell.get_or_init(|| content))
+            }
+        }
+    }
+
+    /// Retreives the content of a buffer view, loading its buffer if needed.
+    ///
+    /// `cache` must be the cache the asset was loaded from.
+    pub fn get_buffer_view<'a>(
+        &self,
+        cache: impl AsAnyCache<'a>,
+        view: &gltf::buffer::View,
+    ) -> Result<&[u8], BoxedError> {
+        let buffer = self.get_buffer(cache, &view.buffer())?;
+        let start = view.offset();
+        let end = start + view.length();
+        Ok(&buffer[start..end])
+    }
+
+    /// Retreives the content of an image, loading it if needed.
+    ///
+    /// `cache` must be the cache the asset was loaded from.
+    pub fn get_image<'a>(
+        &self,
+        cache: impl AsAnyCache<'a>,
+        image: &gltf::Image,
+    ) -> Result<&image::DynamicImage, BoxedError> {
+        self.get_image_by_index(cache, image.index())
+    }
+
+    /// Retreives the content of an image by its index, loading it if needed.
+    ///
+    /// `cache` must be the cache the asset was loaded from.
+    pub fn get_image_by_index<'a>(
+        &self,
+        cache: impl AsAnyCache<'a>,
+        index: usize,
+    ) -> Result<&image::DynamicImage, BoxedError> {
+        let cell = &self.images[index];
+        if let Some(image) = cell.get() {
+            return Ok(image);
+        }
+
+        let cache = cache.as_any_cache();
+        let image = self.document.images().nth(index).unwrap();
+        let image = load_image(cache, &self.base_id, image, |b| {
+            self.get_buffer_by_index(cache, b)
+        })?;
+        Ok(cell.get_or_init(|| image))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+impl Compound for GltfLazy {
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let gltf::Gltf { document, blob } = cache.load::<gltf::Gltf>(id)?.cloned();
+
+        let buffers = document.buffers().map(|_| OnceLock::new()).collect();
+        let images = document.images().map(|_| OnceLock::new()).collect();
+
+        Ok(GltfLazy {
+            document,
+            base_id: base_id(id).to_owned(),
+            blob,
+            images,
+            buffers,
+        })
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+impl super::DirLoadable for GltfLazy {
+    fn select_ids(cache: AnyCache, id: &SharedString) -> std::io::Result<Vec<SharedString>> {
+        gltf::Gltf::select_ids(cache, id)
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    loader::Loader, AnyCache, AsAnyCache, Asset, BoxedError, Compound, Handle, SharedString,
+};
+use std::{borrow::Cow, collections::BTreeMap, fmt, marker::PhantomData};
+
+/// A manifest mapping keys to ids, in any enabled format.
+struct Manifest(BTreeMap<SharedString, SharedString>);
+
+struct ManifestLoader;
+
+impl Loader<Manifest> for ManifestLoader {
+    #[inline]
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Manifest, BoxedError> {
+        super::load_serde(content, ext).map(Manifest)
+    }
+}
+
+impl Asset for Manifest {
+    const EXTENSIONS: &'static [&'static str] = super::SERDE_EXTENSIONS;
+    type Loader = ManifestLoader;
+}
+
+/// A registry of assets, loaded from a manifest that maps keys to ids.
+///
+/// The manifest is a map from strings to strings, in JSON, RON, TOML or YAML
+/// format depending on the enabled features. All assets it refers to are loaded with
+/// the registry, which fails to load if one of them does.
+///
+/// When hot-reloading, the registry is reloaded when the manifest changes, and
+/// when one of the assets is reloaded.
+///
+/// As a `Keyed` is stored in the cache, it only stores ids: handles are given
+/// by the cache they were loaded from.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "json")] {
+/// use assets_manager::{asset::Keyed, source::InMemory, AssetCache};
+///
+/// let source = InMemory::new();
+/// source.insert("greetings", "json", br#"{ "en": "hello.en", "fr": "hello.fr" }"#.to_vec());
+/// source.insert("hello.en", "txt", b"Hello".to_vec());
+/// source.insert("hello.fr", "txt", b"Bonjour".to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+/// let greetings = cache.load::<Keyed<String>>("greetings")?.read();
+///
+/// let fr = greetings.get(&cache, "fr").unwrap();
+/// assert_eq!(*fr.read(), "Bonjour");
+///
+/// let keys: Vec<_> = greetings.keys().collect();
+/// assert_eq!(keys, ["en", "fr"]);
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct Keyed<T> {
+    ids: BTreeMap<SharedString, SharedString>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Compound for Keyed<T>
+where
+    T: Compound,
+{
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let ids = cache.load::<Manifest>(id)?.read().0.clone();
+
+        for id in ids.values() {
+            cache.load::<T>(id)?;
+        }
+
+        Ok(Keyed {
+            ids,
+            _marker: PhantomData,
+        })
+    }
+
+    const HOT_RELOADED: bool = true;
+}
+
+impl<T> Keyed<T> {
+    /// Returns the id of the asset with the given key.
+    #[inline]
+    pub fn id(&self, key: &str) -> Option<&SharedString> {
+        self.ids.get(key)
+    }
+
+    /// Returns `true` if the registry contains the given key.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.ids.contains_key(key)
+    }
+
+    /// Returns an iterator over the keys of the registry, sorted.
+    #[inline]
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &SharedString> {
+        self.ids.keys()
+    }
+
+    /// Returns an iterator over the keys and ids of the registry, sorted by
+    /// key.
+    #[inline]
+    pub fn ids(&self) -> impl ExactSizeIterator<Item = (&SharedString, &SharedString)> {
+        self.ids.iter()
+    }
+
+    /// The number of entries in the registry.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if the registry has no entry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl<T> Keyed<T>
+where
+    T: Compound,
+{
+    /// Returns the asset with the given key.
+    ///
+    /// This function does not do any I/O: `cache` must be the cache the
+    /// registry was loaded from.
+    #[inline]
+    pub fn get<'a>(&self, cache: impl AsAnyCache<'a>, key: &str) -> Option<&'a Handle<T>> {
+        cache.as_any_cache().get_cached(self.ids.get(key)?)
+    }
+
+    /// Returns an iterator over the keys and assets of the registry, sorted by
+    /// key.
+    ///
+    /// This function does not do any I/O: `cache` must be the cache the
+    /// registry was loaded from.
+    #[inline]
+    pub fn iter<'h, 'a: 'h>(
+        &'h self,
+        cache: impl AsAnyCache<'a>,
+    ) -> impl Iterator<Item = (&'h SharedString, &'a Handle<T>)> + 'h {
+        let cache = cache.as_any_cache();
+        self.ids
+            .iter()
+            .filter_map(move |(key, id)| Some((key, cache.get_cached(id)?)))
+    }
+
+    /// Collects the assets of the registry in a map.
+    ///
+    /// This function does not do any I/O: `cache` must be the cache the
+    /// registry was loaded from.
+    pub fn to_map<'a>(
+        &self,
+        cache: impl AsAnyCache<'a>,
+    ) -> std::collections::HashMap<SharedString, &'a Handle<T>> {
+        self.iter(cache.as_any_cache())
+            .map(|(key, handle)| (key.clone(), handle))
+            .collect()
+    }
+}
+
+impl<T> fmt::Debug for Keyed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyed").field("ids", &self.ids).finish()
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{loader, Asset, BoxedError};
+use std::{borrow::Cow, fmt, ops::Range};
+
+const IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// Size of the identifier, header and index, after which the level index
+/// starts.
+const LEVEL_INDEX_START: usize = 80;
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, BoxedError> {
+    match data.get(offset..offset + 4) {
+        Some(bytes) => Ok(u32::from_le_bytes(bytes.try_into().unwrap())),
+        None => Err("unexpected end of KTX2 file".into()),
+    }
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, BoxedError> {
+    match data.get(offset..offset + 8) {
+        Some(bytes) => Ok(u64::from_le_bytes(bytes.try_into().unwrap())),
+        None => Err("unexpected end of KTX2 file".into()),
+    }
+}
+
+/// Returns `offset..offset+len` if it fits in `data`.
+fn range(data: &[u8], offset: u64, len: u64) -> Result<Range<usize>, BoxedError> {
+    let start = usize::try_from(offset)?;
+    let end = start
+        .checked_add(usize::try_from(len)?)
+        .filter(|&end| end <= data.len())
+        .ok_or("KTX2 data out of bounds")?;
+    Ok(start..end)
+}
+
+/// A texture in the [KTX2] format.
+///
+/// The texture is not decoded: this gives access to its header and to the raw
+/// bytes of each mip level, that can be uploaded as is to the GPU if the
+/// format is supported. If the texture is supercompressed, levels must be
+/// decompressed first with the scheme given by
+/// [`supercompression_scheme`](Self::supercompression_scheme).
+///
+/// [KTX2]: https://registry.khronos.org/KTX/specs/2.0/ktxspec.v2.html
+#[cfg_attr(docsrs, doc(cfg(feature = "ktx2")))]
+#[derive(Clone)]
+pub struct Ktx2 {
+    data: Box<[u8]>,
+    vk_format: u32,
+    type_size: u32,
+    width: u32,
+    height: u32,
+    depth: u32,
+    layer_count: u32,
+    face_count: u32,
+    supercompression_scheme: u32,
+    levels: Vec<(Range<usize>, u64)>,
+    dfd: Range<usize>,
+    kvd: Range<usize>,
+}
+
+impl Ktx2 {
+    fn parse(data: Vec<u8>) -> Result<Self, BoxedError> {
+        if !data.starts_with(&IDENTIFIER) {
+            return Err("invalid KTX2 identifier".into());
+        }
+
+        let level_count = read_u32(&data, 40)?.max(1);
+        let mut levels = Vec::with_capacity(level_count.min(32) as usize);
+        for i in 0..level_count as usize {
+            let offset = LEVEL_INDEX_START + 24 * i;
+            let level = range(
+                &data,
+                read_u64(&data, offset)?,
+                read_u64(&data, offset + 8)?,
+            )?;
+            levels.push((level, read_u64(&data, offset + 16)?));
+        }
+
+        let dfd = range(
+            &data,
+            read_u32(&data, 48)?.into(),
+            read_u32(&data, 52)?.into(),
+        )?;
+        let kvd = range(
+            &data,
+            read_u32(&data, 56)?.into(),
+            read_u32(&data, 60)?.into(),
+        )?;
+
+        Ok(Ktx2 {
+            vk_format: read_u32(&data, 12)?,
+            type_size: read_u32(&data, 16)?,
+            width: read_u32(&data, 20)?,
+            height: read_u32(&data, 24)?,
+            depth: read_u32(&data, 28)?,
+            layer_count: read_u32(&data, 32)?,
+            face_count: read_u32(&data, 36)?,
+            supercompression_scheme: read_u32(&data, 44)?,
+            levels,
+            dfd,
+            kvd,
+            data: data.into_boxed_slice(),
+        })
+    }
+
+    /// The format of the texture, as a `VkFormat` value.
+    ///
+    /// This is `0` (`VK_FORMAT_UNDEFINED`) for Basis Universal textures, whose
+    /// format is given by the data format descriptor.
+    #[inline]
+    pub fn vk_format(&self) -> u32 {
+        self.vk_format
+    }
+
+    /// The size of the data type of the format, in bytes, used for endianness
+    /// conversion.
+    #[inline]
+    pub fn type_size(&self) -> u32 {
+        self.type_size
+    }
+
+    /// The width of the base level, in pixels.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the base level, in pixels, or `0` for 1D textures.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The depth of the base level, in pixels, or `0` for 1D and 2D textures.
+    #[inline]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// The number of array layers, or `0` if the texture is not an array.
+    #[inline]
+    pub fn layer_count(&self) -> u32 {
+        self.layer_count
+    }
+
+    /// The number of faces: `6` for cube maps, and `1` otherwise.
+    #[inline]
+    pub fn face_count(&self) -> u32 {
+        self.face_count
+    }
+
+    /// The supercompression scheme of the levels: `0` for none, `1` for Basis
+    /// Universal, `2` for Zstandard and `3` for Zlib.
+    #[inline]
+    pub fn supercompression_scheme(&self) -> u32 {
+        self.supercompression_scheme
+    }
+
+    /// The number of mip levels.
+    #[inline]
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the bytes of a mip level, `0` being the base level.
+    ///
+    /// A level contains all layers and faces of the texture.
+    #[inline]
+    pub fn level(&self, level: usize) -> Option<&[u8]> {
+        let (range, _) = self.levels.get(level)?;
+        Some(&self.data[range.clone()])
+    }
+
+    /// Returns an iterator over the bytes of the mip levels, starting with the
+    /// base level.
+    #[inline]
+    pub fn levels(&self) -> impl ExactSizeIterator<Item = &[u8]> {
+        self.levels
+            .iter()
+            .map(|(range, _)| &self.data[range.clone()])
+    }
+
+    /// Returns the size of a mip level once decompressed, in bytes.
+    #[inline]
+    pub fn uncompressed_level_len(&self, level: usize) -> Option<u64> {
+        Some(self.levels.get(level)?.1)
+    }
+
+    /// Returns the data format descriptor of the texture.
+    #[inline]
+    pub fn data_format_descriptor(&self) -> &[u8] {
+        &self.data[self.dfd.clone()]
+    }
+
+    /// Returns the value of a key in the key/value data of the texture,
+    /// without its trailing NUL byte if any.
+    pub fn key_value(&self, key: &str) -> Option<&[u8]> {
+        let mut kvd = &self.data[self.kvd.clone()];
+        while kvd.len() >= 4 {
+            let len = u32::from_le_bytes(kvd[..4].try_into().unwrap()) as usize;
+            let entry = kvd.get(4..4 + len)?;
+            let nul = entry.iter().position(|&b| b == 0)?;
+            if &entry[..nul] == key.as_bytes() {
+                let value = &entry[nul + 1..];
+                return Some(value.strip_suffix(&[0]).unwrap_or(value));
+            }
+            kvd = kvd.get((4 + len + 3) & !3..)?;
+        }
+        None
+    }
+
+    /// Returns the bytes of the whole file.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ktx2")))]
+impl loader::Loader<Ktx2> for loader::TextureLoader {
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Ktx2, BoxedError> {
+        Ktx2::parse(content.into_owned())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ktx2")))]
+impl Asset for Ktx2 {
+    const EXTENSION: &'static str = "ktx2";
+    type Loader = loader::TextureLoader;
+}
+
+impl fmt::Debug for Ktx2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ktx2")
+            .field("vk_format", &self.vk_format)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("depth", &self.depth)
+            .field("layer_count", &self.layer_count)
+            .field("face_count", &self.face_count)
+            .field("level_count", &self.levels.len())
+            .field("supercompression_scheme", &self.supercompression_scheme)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{loader::Loader, AnyCache, Asset, BoxedError, Compound, SharedString};
+use serde::de::{
+    self,
+    value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+    Deserialize, Deserializer, IntoDeserializer, Visitor,
+};
+use std::{borrow::Cow, fmt, io, marker::PhantomData};
+
+/// A value of any serde format, used to merge layers and to read atlases.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Value {
+    Null,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+impl Value {
+    /// Merges `overlay` on top of `self`.
+    ///
+    /// Maps are merged recursively, and other values are replaced.
+    fn merge(&mut self, overlay: Value) {
+        match (self, overlay) {
+            (Value::Map(base), Value::Map(overlay)) => {
+                for (key, value) in overlay {
+                    match base.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, v)) => v.merge(value),
+                        None => base.push((key, value)),
+                    }
+                }
+            }
+            (this, overlay) => *this = overlay,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::I64(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::U64(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::F64(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::Bytes(v.to_owned()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::Bytes(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::Seq(values))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Value::Map(entries))
+    }
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Bytes(v) => visitor.visit_byte_buf(v),
+            Value::Seq(v) => visitor.visit_seq(SeqDeserializer::new(v.into_iter())),
+            Value::Map(v) => visitor.visit_map(MapDeserializer::new(v.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Map(map) if map.len() == 1 => {
+                let map = MapDeserializer::new(map.into_iter());
+                visitor.visit_enum(MapAccessDeserializer::new(map))
+            }
+            _ => Err(de::Error::custom("expected an enum")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for Value {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// A layer of a config, in any enabled format.
+struct Layer(Value);
+
+struct LayerLoader;
+
+impl Loader<Layer> for LayerLoader {
+    #[inline]
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Layer, BoxedError> {
+        super::load_serde(content, ext).map(Layer)
+    }
+}
+
+impl Asset for Layer {
+    const EXTENSIONS: &'static [&'static str] = super::SERDE_EXTENSIONS;
+    type Loader = LayerLoader;
+}
+
+/// Gives the layers of a [`LayeredConfig`].
+///
+/// This is implemented on marker types, so that configs with different layers
+/// can be stored in the cache.
+pub trait ConfigLayers: Send + Sync + 'static {
+    /// Returns the ids of the layers of the config with the given id, from
+    /// the lowest priority to the highest.
+    fn layers(id: &str) -> Vec<String>;
+}
+
+/// The default layers of a [`LayeredConfig`].
+///
+/// The layers of the config `"settings"` are:
+/// - `"settings"`, with default values,
+/// - `"settings@{os}"`, where `os` is the current platform as given by
+///   [`std::env::consts::OS`] (eg `"settings@linux"`),
+/// - `"settings@user"`, with user overrides.
+#[derive(Debug)]
+pub struct DefaultLayers(());
+
+impl ConfigLayers for DefaultLayers {
+    fn layers(id: &str) -> Vec<String> {
+        vec![
+            id.to_owned(),
+            format!("{id}@{}", std::env::consts::OS),
+            format!("{id}@user"),
+        ]
+    }
+}
+
+/// A config merged from several files.
+///
+/// The layers given by `L` are loaded in order, each one overriding the
+/// previous ones, and the result is deserialized into a `T`. Layers can use
+/// any enabled serde format (JSON, RON, TOML or YAML), and can be mixed.
+/// Layers that do not exist are skipped, but at least one of them must exist.
+///
+/// Maps (and structs) are merged deeply: a layer only needs to contain the
+/// keys it overrides. Other values, including arrays, are replaced as a
+/// whole by the value of the last layer that contains them.
+///
+/// When hot-reloading, the config is reloaded when a layer changes, or when a
+/// layer is added or removed.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(all(feature = "json", feature = "toml"))] {
+/// use assets_manager::{asset::LayeredConfig, source::InMemory, AssetCache};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     volume: u32,
+///     window: Window,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Window {
+///     width: u32,
+///     height: u32,
+/// }
+///
+/// let source = InMemory::new();
+/// source.insert(
+///     "settings",
+///     "json",
+///     br#"{ "volume": 50, "window": { "width": 800, "height": 600 } }"#.to_vec(),
+/// );
+/// source.insert("settings@user", "toml", b"[window]\nwidth = 1920".to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+/// let settings = cache.load::<LayeredConfig<Settings>>("settings")?.read();
+///
+/// assert_eq!(settings.get().volume, 50);
+/// assert_eq!(settings.get().window.width, 1920);
+/// assert_eq!(settings.get().window.height, 600);
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct LayeredConfig<T, L = DefaultLayers> {
+    value: T,
+    layers: Vec<SharedString>,
+    _marker: PhantomData<L>,
+}
+
+impl<T, L> Compound for LayeredConfig<T, L>
+where
+    T: for<'de> Deserialize<'de> + Send + Sync + 'static,
+    L: ConfigLayers,
+{
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let mut value = None;
+        let mut layers = Vec::new();
+
+        for layer in L::layers(id) {
+            if !cache.has_entry(&layer) {
+                continue;
+            }
+
+            let handle = cache.load::<Layer>(&layer)?;
+            let layer_value = handle.read().0.clone();
+            match &mut value {
+                Some(value) => Value::merge(value, layer_value),
+                None => value = Some(layer_value),
+            }
+            layers.push(handle.id().clone());
+        }
+
+        let value = value.ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+        Ok(LayeredConfig {
+            value: T::deserialize(value)?,
+            layers,
+            _marker: PhantomData,
+        })
+    }
+
+    const HOT_RELOADED: bool = true;
+}
+
+impl<T, L> LayeredConfig<T, L> {
+    /// Returns the merged value.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwraps the merged value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns an iterator over the ids of the layers that were found, from
+    /// the lowest priority to the highest.
+    #[inline]
+    pub fn layers(&self) -> impl ExactSizeIterator<Item = &SharedString> {
+        self.layers.iter()
+    }
+}
+
+impl<T, L> AsRef<T> for LayeredConfig<T, L> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Debug, L> fmt::Debug for LayeredConfig<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayeredConfig")
+            .field("value", &self.value)
+            .field("layers", &self.layers)
+            .finish()
+    }
+}
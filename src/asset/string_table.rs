@@ -0,0 +1,143 @@
+use crate::{
+    loader::{BincodeLoader, LoadFrom},
+    AnyCache, AsAnyCache, Asset, BoxedError, Error,
+};
+use std::collections::BTreeMap;
+
+/// A table of strings loaded from a single packed file.
+///
+/// Loading thousands of small text files one by one can be slow. This type
+/// loads a single Bincode-encoded map from keys to strings instead, and gives
+/// access to each string by key.
+///
+/// The pack can be built from a directory of loose text files with
+/// [`StringTable::from_dir`] and written with [`StringTable::to_bytes`].
+/// Entries are sorted by key, so the same strings always produce the same
+/// bytes.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{asset::StringTable, AssetCache};
+///
+/// let cache = AssetCache::new("assets")?;
+///
+/// // Build a table from loose ".txt" files
+/// let table = StringTable::from_dir(&cache, "test.read_dir")?;
+/// assert!(table.get("c").is_some());
+///
+/// // Write it and load it back
+/// let bytes = table.to_bytes()?;
+/// let loaded = StringTable::from_bytes(&bytes)?;
+/// assert_eq!(loaded, table);
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringTable {
+    strings: BTreeMap<String, String>,
+}
+
+impl StringTable {
+    /// Creates an empty `StringTable`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a table from the text files of a directory and its
+    /// subdirectories.
+    ///
+    /// The key of each string is the id of its file relative to the
+    /// directory. For example, `"menu.title"` is the key of the file with id
+    /// `"lang.en.menu.title"` when building from directory `"lang.en"`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the directory cannot be read or if a file
+    /// fails to load.
+    pub fn from_dir<'a>(cache: impl AsAnyCache<'a>, id: &str) -> Result<Self, Error> {
+        let cache: AnyCache = cache.as_any_cache();
+        let mut strings = BTreeMap::new();
+
+        for file_id in cache.available_ids::<String>(id)? {
+            let key = match id {
+                "" => &file_id,
+                _ => &file_id[id.len() + 1..],
+            };
+            let value = cache.load_owned::<String>(&file_id)?;
+            strings.insert(key.to_owned(), value);
+        }
+
+        Ok(Self { strings })
+    }
+
+    /// Decodes a table from the bytes of a packed file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BoxedError> {
+        Ok(Self {
+            strings: bincode::deserialize(bytes)?,
+        })
+    }
+
+    /// Encodes the table to the bytes of a packed file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BoxedError> {
+        Ok(bincode::serialize(&self.strings)?)
+    }
+
+    /// Returns the string with the given key.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+
+    /// Returns `true` if the table contains a string with the given key.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.strings.contains_key(key)
+    }
+
+    /// Adds a string to the table, returning the previous string with the
+    /// same key.
+    #[inline]
+    pub fn insert(&mut self, key: String, value: String) -> Option<String> {
+        self.strings.insert(key, value)
+    }
+
+    /// The number of strings in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if the table contains no string.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns an iterator over the keys and strings of the table, sorted by
+    /// key.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.strings.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl From<BTreeMap<String, String>> for StringTable {
+    #[inline]
+    fn from(strings: BTreeMap<String, String>) -> Self {
+        Self { strings }
+    }
+}
+
+impl FromIterator<(String, String)> for StringTable {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            strings: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Asset for StringTable {
+    const EXTENSION: &'static str = "bin";
+    type Loader = LoadFrom<BTreeMap<String, String>, BincodeLoader>;
+}
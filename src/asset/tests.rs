@@ -40,3 +40,825 @@ pub fn gltf_dir() {
         ]
     )
 }
+
+#[cfg(feature = "gltf")]
+#[test]
+pub fn gltf_lazy() {
+    let cache = AssetCache::new("assets").unwrap();
+
+    for id in [
+        "test.gltf.box",
+        "test.gltf.box-bin",
+        "test.gltf.box-embedded",
+    ] {
+        let eager = cache.load::<asset::Gltf>(id).unwrap().read();
+        let lazy = cache.load::<asset::GltfLazy>(id).unwrap().read();
+
+        for image in lazy.document.images() {
+            let loaded = lazy.get_image(&cache, &image).unwrap();
+            assert_eq!(loaded, eager.get_image(&image));
+        }
+        for buffer in lazy.document.buffers() {
+            let loaded = lazy.get_buffer(&cache, &buffer).unwrap();
+            assert_eq!(loaded, eager.get_buffer(&buffer));
+        }
+        for view in lazy.document.views() {
+            let loaded = lazy.get_buffer_view(&cache, &view).unwrap();
+            assert_eq!(loaded, eager.get_buffer_view(&view));
+        }
+    }
+}
+
+#[cfg(feature = "gltf")]
+#[test]
+pub fn gltf_animation() {
+    use crate::{
+        asset::{Keyframe, Keyframes},
+        source::InMemory,
+    };
+    use gltf::animation::Interpolation;
+
+    let source = InMemory::new();
+    source.insert(
+        "animated",
+        "gltf",
+        br#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"name": "root", "children": [1]}, {"name": "joint"}],
+            "skins": [{"joints": [1], "inverseBindMatrices": 4}, {"joints": [0, 1]}],
+            "animations": [{
+                "samplers": [
+                    {"input": 0, "output": 1},
+                    {"input": 0, "output": 2, "interpolation": "CUBICSPLINE"},
+                    {"input": 0, "output": 3, "interpolation": "STEP"}
+                ],
+                "channels": [
+                    {"sampler": 0, "target": {"node": 1, "path": "translation"}},
+                    {"sampler": 1, "target": {"node": 1, "path": "rotation"}},
+                    {"sampler": 2, "target": {"node": 0, "path": "weights"}}
+                ]
+            }],
+            "buffers": [{
+                "byteLength": 208,
+                "uri": "data:application/octet-stream;base64,AAAAAAAAgD8AAAAAAAAAAAAAAAAAAIA/AAAAQAAAQEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAIA/AACAPwAAAAAAAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAAAAAAAAAAAAgD8AAAAAAACgQAAAwEAAAOBAAACAPw=="
+            }],
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 32, "byteLength": 96},
+                {"buffer": 0, "byteOffset": 128, "byteLength": 16},
+                {"buffer": 0, "byteOffset": 144, "byteLength": 64}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR", "min": [0], "max": [1]},
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5126, "count": 6, "type": "VEC4"},
+                {"bufferView": 3, "componentType": 5126, "count": 4, "type": "SCALAR"},
+                {"bufferView": 4, "componentType": 5126, "count": 1, "type": "MAT4"}
+            ]
+        }"#
+        .to_vec(),
+    );
+
+    let cache = AssetCache::with_source(source);
+    let gltf = cache.load::<asset::Gltf>("animated").unwrap().read();
+
+    let animation = gltf.document.animations().next().unwrap();
+    let channels = gltf.animation_channels(&animation).unwrap();
+    assert_eq!(channels.len(), 3);
+
+    assert_eq!(channels[0].node, 1);
+    assert_eq!(channels[0].interpolation, Interpolation::Linear);
+    assert_eq!(
+        channels[0].keyframes,
+        Keyframes::Translation(vec![
+            Keyframe {
+                time: 0.0,
+                value: [0.0, 0.0, 0.0],
+                tangents: None,
+            },
+            Keyframe {
+                time: 1.0,
+                value: [1.0, 2.0, 3.0],
+                tangents: None,
+            },
+        ])
+    );
+
+    assert_eq!(channels[1].interpolation, Interpolation::CubicSpline);
+    let Keyframes::Rotation(rotations) = &channels[1].keyframes else {
+        panic!("expected rotations");
+    };
+    assert_eq!(rotations.len(), 2);
+    assert_eq!(rotations[1].value, [0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(rotations[1].tangents, Some(([0.0; 4], [0.0; 4])));
+
+    assert_eq!(channels[2].node, 0);
+    let Keyframes::MorphTargetWeights(weights) = &channels[2].keyframes else {
+        panic!("expected weights");
+    };
+    assert_eq!(weights[0].value, [1.0, 0.0]);
+    assert_eq!(weights[1].value, [0.0, 1.0]);
+
+    let mut skins = gltf.document.skins();
+    let matrices = gltf.inverse_bind_matrices(&skins.next().unwrap()).unwrap();
+    assert_eq!(matrices.len(), 1);
+    assert_eq!(matrices[0][3], [5.0, 6.0, 7.0, 1.0]);
+    let matrices = gltf.inverse_bind_matrices(&skins.next().unwrap()).unwrap();
+    assert_eq!(matrices.len(), 2);
+    assert_eq!(matrices[1][1], [0.0, 1.0, 0.0, 0.0]);
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Bytes(usize);
+
+impl FileAsset for Bytes {
+    const EXTENSION: &'static str = "x";
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Result<Self, BoxedError> {
+        Ok(Bytes(bytes.len()))
+    }
+}
+
+#[test]
+fn file_asset() {
+    let cache = AssetCache::new("assets").unwrap();
+    assert_eq!(*cache.load::<Bytes>("test.cache").unwrap().read(), Bytes(2));
+    assert!(cache.load::<Bytes>("test.missing").is_err());
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn streamed_image() {
+    use crate::asset::{Png, StreamedImage};
+
+    let cache = AssetCache::new("assets").unwrap();
+    let streamed = cache
+        .load::<StreamedImage>("test.gltf.CesiumLogoFlat")
+        .unwrap();
+    let png = cache.load::<Png>("test.gltf.CesiumLogoFlat").unwrap();
+    assert_eq!(streamed.read().0, png.read().0);
+
+    let err = cache.load::<StreamedImage>("test.gltf.box").unwrap_err();
+    assert!(err.reason().downcast_ref::<std::io::Error>().is_some());
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn string_table() {
+    let dir = std::env::temp_dir().join("assets_manager_string_table");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("lang/en/menu")).unwrap();
+    std::fs::write(dir.join("lang/en/menu/title.txt"), "My game").unwrap();
+    std::fs::write(dir.join("lang/en/quit.txt"), "Quit").unwrap();
+
+    let cache = AssetCache::new(&dir).unwrap();
+    let table = asset::StringTable::from_dir(&cache, "lang.en").unwrap();
+    assert_eq!(
+        table.iter().collect::<Vec<_>>(),
+        [("menu.title", "My game"), ("quit", "Quit")]
+    );
+    assert!(asset::StringTable::from_dir(&cache, "lang.fr").is_err());
+
+    std::fs::write(dir.join("en.bin"), table.to_bytes().unwrap()).unwrap();
+    let loaded = cache.load::<asset::StringTable>("en").unwrap().read();
+    assert_eq!(*loaded, table);
+    assert_eq!(loaded.get("quit"), Some("Quit"));
+    assert_eq!(loaded.get("missing"), None);
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv() {
+    use crate::{asset::Csv, loader::CsvOptions, source::InMemory};
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Item {
+        name: String,
+        price: u32,
+        kind: Kind,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    enum Kind {
+        Weapon,
+        Armor,
+    }
+
+    let source = InMemory::new();
+    source.insert(
+        "items",
+        "csv",
+        b"price,name,kind\n10,sword,Weapon\n".to_vec(),
+    );
+    source.insert(
+        "table",
+        "tsv",
+        b"name\tprice\tkind\nhelm\t5\tArmor\n".to_vec(),
+    );
+    source.insert("raw", "csv", b"axe|7|Weapon\n".to_vec());
+
+    let cache = AssetCache::with_source(source);
+
+    let items = cache.load::<Csv<Item>>("items").unwrap().read();
+    assert_eq!(
+        items.0,
+        [Item {
+            name: "sword".into(),
+            price: 10,
+            kind: Kind::Weapon,
+        }]
+    );
+
+    let table = cache.load::<Csv<Item>>("table").unwrap().read();
+    assert_eq!(table.0[0].name, "helm");
+    assert_eq!(table.0[0].kind, Kind::Armor);
+
+    // With headers, the only line is the header row
+    assert!(cache.load::<Csv<Item>>("raw").unwrap().read().0.is_empty());
+    let options = CsvOptions::new().with_delimiter(b'|').with_headers(false);
+    let raw = cache.load_with::<Csv<Item>>("raw", options).unwrap().read();
+    assert_eq!(raw.0[0].price, 7);
+}
+
+#[cfg(any(feature = "dds", feature = "ktx2"))]
+fn load_texture<T>(bytes: std::borrow::Cow<[u8]>) -> Result<T, BoxedError>
+where
+    loader::TextureLoader: loader::Loader<T>,
+{
+    <loader::TextureLoader as loader::Loader<T>>::load(bytes, "")
+}
+
+#[cfg(feature = "ktx2")]
+#[test]
+fn ktx2() {
+    use crate::asset::Ktx2;
+
+    fn u32s(data: &mut Vec<u8>, values: &[u32]) {
+        values.iter().for_each(|v| data.extend(v.to_le_bytes()));
+    }
+    fn u64s(data: &mut Vec<u8>, values: &[u64]) {
+        values.iter().for_each(|v| data.extend(v.to_le_bytes()));
+    }
+
+    let mut data = vec![
+        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+    ];
+    // vkFormat, typeSize, width, height, depth, layers, faces, levels, supercompression
+    u32s(&mut data, &[131, 1, 8, 4, 0, 0, 1, 2, 0]);
+    // Data format descriptor and key/value data
+    u32s(&mut data, &[128, 4, 132, 24]);
+    u64s(&mut data, &[0, 0]);
+    // Level index
+    u64s(&mut data, &[160, 16, 16, 176, 8, 8]);
+    data.extend([1, 2, 3, 4]);
+    u32s(&mut data, &[18]);
+    data.extend(b"KTXorientation\0rd\0\0\0");
+    data.extend([0; 4]);
+    data.extend([0xAA; 16]);
+    data.extend([0xBB; 8]);
+
+    let texture = load_texture::<Ktx2>(data.clone().into()).unwrap();
+    assert_eq!(texture.vk_format(), 131);
+    assert_eq!((texture.width(), texture.height()), (8, 4));
+    assert_eq!(texture.level_count(), 2);
+    assert_eq!(texture.level(0).unwrap(), [0xAA; 16]);
+    assert_eq!(texture.levels().nth(1).unwrap(), [0xBB; 8]);
+    assert!(texture.level(2).is_none());
+    assert_eq!(texture.data_format_descriptor(), [1, 2, 3, 4]);
+    assert_eq!(texture.key_value("KTXorientation").unwrap(), b"rd");
+    assert!(texture.key_value("KTXwriter").is_none());
+
+    // Truncated file
+    assert!(load_texture::<Ktx2>(data[..170].into()).is_err());
+    assert!(load_texture::<Ktx2>(b"not a texture"[..].into()).is_err());
+}
+
+#[cfg(feature = "dds")]
+#[test]
+fn dds() {
+    use crate::asset::{Dds, DdsFormat};
+
+    fn header(four_cc: &[u8; 4], width: u32, height: u32, levels: u32, caps2: u32) -> Vec<u8> {
+        let mut data = b"DDS ".to_vec();
+        let mut header = [0u32; 31];
+        header[0] = 124;
+        header[1] = 0x2_0000;
+        (header[2], header[3]) = (height, width);
+        header[6] = levels;
+        header[18] = 32;
+        header[19] = 0x4;
+        header[20] = u32::from_le_bytes(*four_cc);
+        header[27] = caps2;
+        header.iter().for_each(|v| data.extend(v.to_le_bytes()));
+        data
+    }
+
+    // BC1 with mip levels 8x8, 4x4, 2x2 and 1x1
+    let mut data = header(b"DXT1", 8, 8, 4, 0);
+    data.extend([1; 32]);
+    data.extend([2; 8]);
+    data.extend([3; 8]);
+    data.extend([4; 8]);
+
+    let texture = load_texture::<Dds>(data.clone().into()).unwrap();
+    assert_eq!(texture.format(), DdsFormat::FourCC(*b"DXT1"));
+    assert_eq!(texture.level_count(), 4);
+    assert_eq!(texture.image_count(), 1);
+    assert_eq!(texture.surface(0, 0).unwrap(), [1; 32]);
+    assert_eq!(texture.surface(0, 3).unwrap(), [4; 8]);
+    assert!(texture.surface(1, 0).is_none());
+    assert_eq!(texture.levels(0).len(), 4);
+    assert_eq!(texture.payload().len(), 56);
+    assert!(load_texture::<Dds>(data[..170].into()).is_err());
+
+    // DX10 cube map of R8G8B8A8 pixels
+    let mut data = header(b"DX10", 2, 2, 1, 0);
+    [28, 3, 0x4, 1, 0]
+        .iter()
+        .for_each(|v: &u32| data.extend(v.to_le_bytes()));
+    for face in 0..6 {
+        data.extend([face; 16]);
+    }
+
+    let texture = load_texture::<Dds>(data.into()).unwrap();
+    assert_eq!(texture.format(), DdsFormat::Dxgi(28));
+    assert!(texture.is_cubemap());
+    assert_eq!(texture.image_count(), 6);
+    assert_eq!(texture.surface(5, 0).unwrap(), [5; 16]);
+
+    // Unknown formats only give the payload
+    let mut data = header(b"YUY2", 2, 2, 1, 0);
+    data.extend([0; 8]);
+    let texture = load_texture::<Dds>(data.into()).unwrap();
+    assert!(texture.surface(0, 0).is_none());
+    assert_eq!(texture.levels(0).len(), 0);
+    assert_eq!(texture.payload(), [0; 8]);
+
+    // Truncated headers
+    let data = header(b"YUY2", 2, 2, 1, 0);
+    assert!(load_texture::<Dds>(data[..120].into()).is_err());
+    let mut data = header(b"DX10", 2, 2, 1, 0);
+    [28, 3, 0, 1]
+        .iter()
+        .for_each(|v: &u32| data.extend(v.to_le_bytes()));
+    assert!(load_texture::<Dds>(data.into()).is_err());
+
+    // Huge dimensions
+    let data = header(b"DXT5", u32::MAX, u32::MAX, 1, 0);
+    assert!(load_texture::<Dds>(data.into()).is_err());
+}
+
+#[cfg(all(feature = "json", feature = "ron"))]
+#[test]
+fn atlas() {
+    use crate::{
+        asset::{Atlas, AtlasFrame, AtlasNaming, TexturePackerNaming},
+        source::InMemory,
+    };
+
+    struct Sheets;
+
+    impl AtlasNaming for Sheets {
+        fn image_id(id: &str) -> String {
+            format!("{id}.image")
+        }
+
+        fn frames_id(id: &str) -> String {
+            format!("{id}.frames")
+        }
+    }
+
+    let source = InMemory::new();
+    source.insert("packed", "txt", b"image".to_vec());
+    source.insert(
+        "packed",
+        "json",
+        br#"{"frames": [
+            {"filename": "walk/01.png", "frame": {"x": 0, "y": 0, "w": 16, "h": 8}, "rotated": true},
+            {"filename": "walk/02.png", "frame": {"x": 16, "y": 0, "w": 16, "h": 8}}
+        ], "meta": {"size": {"w": 32, "h": 8}}}"#
+            .to_vec(),
+    );
+    source.insert("sheet.image", "txt", b"other image".to_vec());
+    source.insert(
+        "sheet.frames",
+        "ron",
+        br#"{"frames": {"a": {"x": 1, "y": 2, "width": 3, "height": 4}}}"#.to_vec(),
+    );
+    source.insert("invalid", "txt", b"image".to_vec());
+    source.insert(
+        "invalid",
+        "json",
+        br#"{"frames": {"a": {"x": -1}}}"#.to_vec(),
+    );
+
+    let cache = AssetCache::with_source(source);
+
+    let packed = cache
+        .load::<Atlas<String, TexturePackerNaming>>("packed")
+        .unwrap()
+        .read();
+    assert_eq!(packed.len(), 2);
+    assert_eq!(
+        packed.frame("walk/01"),
+        Some(&AtlasFrame {
+            x: 0,
+            y: 0,
+            width: 16,
+            height: 8,
+            rotated: true,
+        })
+    );
+    assert!(!packed.frame("walk/02").unwrap().rotated);
+    assert_eq!(*packed.image(&cache).unwrap().read(), "image");
+
+    let sheet = cache.load::<Atlas<String, Sheets>>("sheet").unwrap().read();
+    assert_eq!(sheet.image_id(), "sheet.image");
+    let names: Vec<_> = sheet.frames().map(|(name, _)| name).collect();
+    assert_eq!(names, ["a"]);
+    assert_eq!(sheet.frame("a").unwrap().height, 4);
+
+    assert!(cache.load::<Atlas<String>>("invalid").is_err());
+    assert!(cache.load::<Atlas<String>>("sheet").is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn keyed() {
+    use crate::{asset::Keyed, source::InMemory};
+
+    let source = InMemory::new();
+    source.insert(
+        "items",
+        "json",
+        br#"{"b": "text.b", "a": "text.a"}"#.to_vec(),
+    );
+    source.insert("text.a", "txt", b"A".to_vec());
+    source.insert("text.b", "txt", b"B".to_vec());
+    source.insert("broken", "json", br#"{"a": "text.missing"}"#.to_vec());
+
+    let cache = AssetCache::with_source(source);
+    let items = cache.load::<Keyed<String>>("items").unwrap().read();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items.id("a").unwrap(), "text.a");
+    assert_eq!(*items.get(&cache, "b").unwrap().read(), "B");
+    assert!(items.get(&cache, "c").is_none());
+
+    let values: Vec<_> = items
+        .iter(&cache)
+        .map(|(key, handle)| (key.to_string(), handle.read().clone()))
+        .collect();
+    assert_eq!(values, [("a".into(), "A".into()), ("b".into(), "B".into())]);
+    assert_eq!(items.to_map(&cache).len(), 2);
+
+    assert!(cache.load::<Keyed<String>>("broken").is_err());
+}
+
+#[cfg(all(feature = "json", feature = "ron", feature = "toml"))]
+#[test]
+fn layered_config() {
+    use crate::{
+        asset::{ConfigLayers, LayeredConfig},
+        source::InMemory,
+    };
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    enum Mode {
+        Windowed,
+        Fullscreen { monitor: u32 },
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Settings {
+        name: Option<String>,
+        volume: f32,
+        mode: Mode,
+        keys: Vec<String>,
+    }
+
+    struct Layers;
+    impl ConfigLayers for Layers {
+        fn layers(id: &str) -> Vec<String> {
+            vec![
+                format!("{id}.base"),
+                format!("{id}.missing"),
+                format!("{id}.user"),
+            ]
+        }
+    }
+
+    let source = InMemory::new();
+    source.insert(
+        "settings.base",
+        "json",
+        br#"{"name": null, "volume": 0.5, "mode": "Windowed", "keys": ["a", "b"]}"#.to_vec(),
+    );
+    source.insert(
+        "settings.user",
+        "ron",
+        br#"{"mode": {"Fullscreen": {"monitor": 1}}, "keys": ["c"]}"#.to_vec(),
+    );
+    source.insert("other", "toml", b"volume = 1.0".to_vec());
+
+    let cache = AssetCache::with_source(source);
+    let settings = cache
+        .load::<LayeredConfig<Settings, Layers>>("settings")
+        .unwrap()
+        .read();
+
+    assert_eq!(
+        *settings.get(),
+        Settings {
+            name: None,
+            volume: 0.5,
+            mode: Mode::Fullscreen { monitor: 1 },
+            keys: vec!["c".into()],
+        }
+    );
+    assert_eq!(
+        settings.layers().collect::<Vec<_>>(),
+        ["settings.base", "settings.user"]
+    );
+
+    // The default layers start with the id itself
+    assert!(cache.load::<LayeredConfig<Settings>>("other").is_err());
+    assert!(cache.load::<LayeredConfig<Settings>>("missing").is_err());
+}
+
+#[cfg(all(feature = "json", feature = "ron"))]
+#[test]
+fn versioned() {
+    use crate::{
+        asset::{Migrations, Versioned, VersionedAsset},
+        source::InMemory,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct V1 {
+        name: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct V2 {
+        names: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Item {
+        names: Vec<String>,
+        price: u32,
+    }
+
+    impl VersionedAsset for Item {
+        const VERSION: u64 = 3;
+        const VERSION_FIELD: &'static str = "format";
+
+        fn migrations(migrations: &mut Migrations<Self>) {
+            migrations
+                .add(1, |old: V1| Item {
+                    names: vec![old.name],
+                    price: 0,
+                })
+                .add(2, |old: V2| Item {
+                    names: old.names,
+                    price: 0,
+                });
+        }
+    }
+
+    let source = InMemory::new();
+    source.insert("v1", "json", br#"{"format": 1, "name": "a"}"#.to_vec());
+    source.insert("v2", "ron", br#"(format: 2, names: ["a", "b"])"#.to_vec());
+    source.insert(
+        "v3",
+        "json",
+        br#"{"format": 3, "names": [], "price": 2}"#.to_vec(),
+    );
+    source.insert("v4", "json", br#"{"format": 4}"#.to_vec());
+    source.insert("none", "json", br#"{"names": [], "price": 2}"#.to_vec());
+
+    let cache = AssetCache::with_source(source);
+    let load = |id| {
+        cache
+            .load::<Versioned<Item>>(id)
+            .map(|h| h.read().0.names.clone())
+    };
+
+    assert_eq!(load("v1").unwrap(), ["a"]);
+    assert_eq!(load("v2").unwrap(), ["a", "b"]);
+    assert!(load("v3").unwrap().is_empty());
+    assert!(load("v4").is_err());
+    assert!(load("none").is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn validate() {
+    use crate::{
+        asset::{Json, Validate, Validated},
+        source::InMemory,
+    };
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Percent(u32);
+
+    impl Validate for Percent {
+        fn validate(&self) -> Result<(), BoxedError> {
+            if self.0 > 100 {
+                return Err(format!("{} is greater than 100", self.0).into());
+            }
+            Ok(())
+        }
+    }
+
+    impl Asset for Percent {
+        const EXTENSION: &'static str = "json";
+        type Loader = loader::JsonLoader;
+
+        fn validate(&self) -> Result<(), BoxedError> {
+            Validate::validate(self)
+        }
+    }
+
+    let source = InMemory::new();
+    source.insert("ok", "json", b"[10, 100]".to_vec());
+    source.insert("invalid", "json", b"[10, 101]".to_vec());
+    source.insert("small", "json", b"50".to_vec());
+    source.insert("big", "json", b"150".to_vec());
+
+    let cache = AssetCache::with_source(source);
+    assert!(cache.load::<Percent>("small").is_ok());
+    let err = cache.load::<Percent>("big").unwrap_err();
+    assert_eq!(err.reason().to_string(), "150 is greater than 100");
+
+    assert!(cache.load::<Validated<Json<Vec<Percent>>>>("ok").is_ok());
+    assert!(cache
+        .load::<Validated<Json<Vec<Percent>>>>("invalid")
+        .is_err());
+    assert!(cache.load::<Json<Vec<Percent>>>("invalid").is_ok());
+}
+
+#[cfg(all(feature = "macros", feature = "json"))]
+#[test]
+fn derive_validate() {
+    use crate::{asset::Validate, source::InMemory};
+
+    #[derive(Asset, serde::Deserialize)]
+    #[asset_format = "json"]
+    #[asset(validate)]
+    struct Name(String);
+
+    impl Validate for Name {
+        fn validate(&self) -> Result<(), BoxedError> {
+            if self.0.is_empty() {
+                return Err("empty name".into());
+            }
+            Ok(())
+        }
+    }
+
+    let source = InMemory::new();
+    source.insert("a", "json", br#""a""#.to_vec());
+    source.insert("empty", "json", br#""""#.to_vec());
+
+    let cache = AssetCache::with_source(source);
+    assert!(cache.load::<Name>("a").is_ok());
+    assert!(cache.load::<Name>("empty").is_err());
+}
+
+#[cfg(all(feature = "macros", feature = "json"))]
+#[test]
+fn derive_compound() {
+    use crate::source::InMemory;
+
+    #[derive(Asset, serde::Deserialize)]
+    #[asset_format = "json"]
+    struct Stats {
+        health: u32,
+    }
+
+    #[derive(Asset)]
+    struct Monster {
+        name: String,
+        #[asset(id_suffix = ".stats")]
+        stats: Stats,
+        #[asset(id = "common.sprite")]
+        sprite: String,
+        #[asset(skip)]
+        kills: u32,
+    }
+
+    #[derive(Asset)]
+    struct Pair(String, #[asset(id_suffix = ".stats")] Stats);
+
+    let source = InMemory::new();
+    source.insert("goblin", "txt", b"Goblin".to_vec());
+    source.insert("goblin.stats", "json", br#"{ "health": 10 }"#.to_vec());
+    source.insert("common.sprite", "txt", b"sprite".to_vec());
+
+    let cache = AssetCache::with_source(source);
+    let monster = cache.load::<Monster>("goblin").unwrap().read();
+    assert_eq!(monster.name, "Goblin");
+    assert_eq!(monster.stats.health, 10);
+    assert_eq!(monster.sprite, "sprite");
+    assert_eq!(monster.kills, 0);
+
+    let pair = cache.load::<Pair>("goblin").unwrap().read();
+    assert_eq!(pair.0, "Goblin");
+    assert_eq!(pair.1.health, 10);
+
+    assert!(cache.load::<Monster>("orc").is_err());
+}
+
+#[cfg(all(feature = "macros", feature = "json", feature = "ron"))]
+#[test]
+fn derive_formats() {
+    use crate::source::InMemory;
+
+    #[derive(Asset, serde::Deserialize)]
+    #[asset(formats("ron", "json"))]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Asset, Default, serde::Deserialize)]
+    #[asset_format = "json"]
+    #[asset(extension = "pt", default)]
+    struct OtherPoint {
+        x: i32,
+    }
+
+    assert_eq!(Point::EXTENSIONS, ["ron", "json"]);
+    assert_eq!(OtherPoint::EXTENSIONS, ["pt"]);
+
+    let source = InMemory::new();
+    source.insert("a", "ron", b"(x: 1, y: 2)".to_vec());
+    source.insert("b", "json", br#"{ "x": 3, "y": 4 }"#.to_vec());
+    source.insert("c", "pt", br#"{ "x": 5 }"#.to_vec());
+    source.insert("d", "json", b"(x: 1, y: 2)".to_vec());
+
+    let cache = AssetCache::with_source(source);
+    let a = cache.load::<Point>("a").unwrap().read();
+    assert_eq!((a.x, a.y), (1, 2));
+    let b = cache.load::<Point>("b").unwrap().read();
+    assert_eq!((b.x, b.y), (3, 4));
+    assert!(cache.load::<Point>("d").is_err());
+
+    assert_eq!(cache.load::<OtherPoint>("c").unwrap().read().x, 5);
+    assert_eq!(cache.load::<OtherPoint>("missing").unwrap().read().x, 0);
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn derive_with() {
+    use crate::source::InMemory;
+
+    #[derive(Asset)]
+    #[asset(with = "parse_len", extension = "len", extension = "bytes")]
+    struct Len(usize);
+
+    fn parse_len(bytes: &[u8]) -> Result<Len, BoxedError> {
+        if bytes.is_empty() {
+            return Err("empty file".into());
+        }
+        Ok(Len(bytes.len()))
+    }
+
+    assert_eq!(Len::EXTENSIONS, ["len", "bytes"]);
+
+    let source = InMemory::new();
+    source.insert("a", "len", b"abc".to_vec());
+    source.insert("b", "bytes", b"abcde".to_vec());
+    source.insert("empty", "len", Vec::new());
+
+    let cache = AssetCache::with_source(source);
+    assert_eq!(cache.load::<Len>("a").unwrap().read().0, 3);
+    assert_eq!(cache.load::<Len>("b").unwrap().read().0, 5);
+    assert!(cache.load::<Len>("empty").is_err());
+}
+
+#[cfg(all(feature = "macros", feature = "msgpack"))]
+#[test]
+fn derive_msgpack() {
+    use crate::source::InMemory;
+
+    #[derive(Asset, serde::Deserialize)]
+    #[asset_format = "msgpack"]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    assert_eq!(Point::EXTENSIONS, ["msgpack"]);
+
+    let source = InMemory::new();
+    // [1, 2]
+    source.insert("point", "msgpack", vec![0x92, 0x01, 0x02]);
+
+    let cache = AssetCache::with_source(source);
+    let point = cache.load::<Point>("point").unwrap().read();
+    assert_eq!((point.x, point.y), (1, 2));
+}
@@ -0,0 +1,122 @@
+use crate::{loader::LoadFrom, Asset, BoxedError, SharedString};
+
+/// A check of the invariants of a value.
+///
+/// This trait enables to validate values of types that do not implement
+/// [`Asset`] themselves, such as the content of a [`Json`](super::Json), with
+/// the [`Validated`] asset. It is also used by `#[asset(validate)]` with
+/// `#[derive(Asset)]`.
+///
+/// See [`Asset::validate`] for more details.
+pub trait Validate {
+    /// Returns an error if the value is not valid.
+    fn validate(&self) -> Result<(), BoxedError>;
+}
+
+impl<T: Validate> Validate for [T] {
+    fn validate(&self) -> Result<(), BoxedError> {
+        self.iter().try_for_each(T::validate)
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    #[inline]
+    fn validate(&self) -> Result<(), BoxedError> {
+        self[..].validate()
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    #[inline]
+    fn validate(&self) -> Result<(), BoxedError> {
+        self.as_ref().map_or(Ok(()), T::validate)
+    }
+}
+
+/// Loads an asset and checks it with its [`Validate`] implementation.
+///
+/// If the check fails, loading the asset fails, with the error returned by
+/// [`Validate::validate`]. This also happens when the asset is reloaded: the
+/// previous value is then kept.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "json")] {
+/// use assets_manager::{
+///     asset::{Json, Validate, Validated},
+///     source::InMemory,
+///     AssetCache, BoxedError,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Weapon {
+///     damage: u32,
+///     critical_chance: f32,
+/// }
+///
+/// impl Validate for Weapon {
+///     fn validate(&self) -> Result<(), BoxedError> {
+///         if !(0.0..=1.0).contains(&self.critical_chance) {
+///             return Err("critical_chance must be between 0 and 1".into());
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let source = InMemory::new();
+/// source.insert("sword", "json", br#"{ "damage": 10, "critical_chance": 0.1 }"#.to_vec());
+/// source.insert("axe", "json", br#"{ "damage": 15, "critical_chance": 10 }"#.to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+/// assert!(cache.load::<Validated<Json<Weapon>>>("sword").is_ok());
+/// assert!(cache.load::<Validated<Json<Weapon>>>("axe").is_err());
+/// # }}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Validated<A>(pub A);
+
+impl<A> Validated<A> {
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> A {
+        self.0
+    }
+}
+
+impl<A> From<A> for Validated<A> {
+    #[inline]
+    fn from(asset: A) -> Self {
+        Self(asset)
+    }
+}
+
+impl<A> AsRef<A> for Validated<A> {
+    #[inline]
+    fn as_ref(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A> Asset for Validated<A>
+where
+    A: Asset + Validate,
+{
+    const EXTENSIONS: &'static [&'static str] = A::EXTENSIONS;
+    type Loader = LoadFrom<A, A::Loader>;
+
+    #[inline]
+    fn default_value(id: &SharedString, error: BoxedError) -> Result<Self, BoxedError> {
+        A::default_value(id, error).map(Validated)
+    }
+
+    fn validate(&self) -> Result<(), BoxedError> {
+        Asset::validate(&self.0)?;
+        Validate::validate(&self.0)
+    }
+
+    const HOT_RELOADED: bool = A::HOT_RELOADED;
+    const MAX_SIZE: Option<u64> = A::MAX_SIZE;
+}
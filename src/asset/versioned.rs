@@ -0,0 +1,198 @@
+use super::layered::Value;
+use crate::{loader::Loader, Asset, BoxedError};
+use serde::Deserialize;
+use std::{borrow::Cow, fmt};
+
+/// A type that is loaded from data files carrying a version.
+///
+/// Files of such types have a version field (`"version"` by default). When a
+/// file has an older version than [`VERSION`](Self::VERSION), the migration
+/// registered for that version is used to load it, so that old files keep
+/// working after the type evolved.
+///
+/// These types are loaded with the [`Versioned`] asset.
+pub trait VersionedAsset: for<'de> Deserialize<'de> + Send + Sync + 'static {
+    /// The current version of the type.
+    const VERSION: u64;
+
+    /// The name of the field that contains the version.
+    ///
+    /// The field is left in the data, so the type can also contain it.
+    const VERSION_FIELD: &'static str = "version";
+
+    /// Registers the migrations from older versions.
+    ///
+    /// By default, no migration is registered, so only files of the current
+    /// version can be loaded.
+    #[allow(unused_variables)]
+    fn migrations(migrations: &mut Migrations<Self>) {}
+}
+
+type Migration<T> = Box<dyn Fn(Value) -> Result<T, BoxedError>>;
+
+/// The migrations of a [`VersionedAsset`] from its older versions.
+///
+/// See [`VersionedAsset::migrations`].
+pub struct Migrations<T> {
+    migrations: Vec<(u64, Migration<T>)>,
+}
+
+impl<T> Migrations<T> {
+    /// Registers how to load files of an older version.
+    ///
+    /// Files of version `version` are deserialized as an `Old`, which is then
+    /// converted to the current type with `migrate`. To migrate through
+    /// several versions, `migrate` can chain conversions.
+    ///
+    /// Registering the same version twice replaces the previous migration.
+    pub fn add<Old, F>(&mut self, version: u64, migrate: F) -> &mut Self
+    where
+        Old: for<'de> Deserialize<'de>,
+        F: Fn(Old) -> T + 'static,
+    {
+        let migration = move |value| Ok(migrate(Old::deserialize(value)?));
+        self.migrations.retain(|(v, _)| *v != version);
+        self.migrations.push((version, Box::new(migration)));
+        self
+    }
+
+    fn get(&self, version: u64) -> Option<&Migration<T>> {
+        self.migrations
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, m)| m)
+    }
+}
+
+impl<T> fmt::Debug for Migrations<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let versions = self.migrations.iter().map(|(v, _)| v);
+        f.debug_set().entries(versions).finish()
+    }
+}
+
+/// Loads a [`VersionedAsset`], running migrations from older versions.
+///
+/// The file can use any enabled serde format (JSON, RON, TOML or YAML). Files
+/// without a version, or with a version that is neither the current one nor
+/// an older one with a registered migration, fail to load.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "json")] {
+/// use assets_manager::{
+///     asset::{Migrations, Versioned, VersionedAsset},
+///     source::InMemory,
+///     AssetCache,
+/// };
+/// use serde::Deserialize;
+///
+/// // Version 1 only had a volume
+/// #[derive(Deserialize)]
+/// struct SettingsV1 {
+///     volume: u32,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     volume: u32,
+///     muted: bool,
+/// }
+///
+/// impl VersionedAsset for Settings {
+///     const VERSION: u64 = 2;
+///
+///     fn migrations(migrations: &mut Migrations<Self>) {
+///         migrations.add(1, |old: SettingsV1| Settings {
+///             volume: old.volume,
+///             muted: false,
+///         });
+///     }
+/// }
+///
+/// let source = InMemory::new();
+/// source.insert("old", "json", br#"{ "version": 1, "volume": 50 }"#.to_vec());
+/// source.insert("new", "json", br#"{ "version": 2, "volume": 0, "muted": true }"#.to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+/// let old = cache.load::<Versioned<Settings>>("old")?.read();
+/// assert_eq!(old.0.volume, 50);
+/// assert!(!old.0.muted);
+///
+/// let new = cache.load::<Versioned<Settings>>("new")?.read();
+/// assert!(new.0.muted);
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Versioned<T>(pub T);
+
+impl<T> Versioned<T> {
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> AsRef<T> for Versioned<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: VersionedAsset> Asset for Versioned<T> {
+    const EXTENSIONS: &'static [&'static str] = super::SERDE_EXTENSIONS;
+    type Loader = VersionedLoader;
+}
+
+/// Loads [`Versioned`] assets.
+#[derive(Debug)]
+pub struct VersionedLoader(());
+
+impl<T: VersionedAsset> Loader<Versioned<T>> for VersionedLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Versioned<T>, BoxedError> {
+        let value: Value = super::load_serde(content, ext)?;
+        let version = version_of(&value, T::VERSION_FIELD)?;
+
+        if version == T::VERSION {
+            return Ok(Versioned(T::deserialize(value)?));
+        }
+        if version > T::VERSION {
+            let current = T::VERSION;
+            return Err(format!("version {version} is newer than the supported {current}").into());
+        }
+
+        let mut migrations = Migrations {
+            migrations: Vec::new(),
+        };
+        T::migrations(&mut migrations);
+        match migrations.get(version) {
+            Some(migrate) => {
+                log::debug!("Migrating data from version {version} to {}", T::VERSION);
+                migrate(value).map(Versioned)
+            }
+            None => Err(format!("no migration from version {version}").into()),
+        }
+    }
+}
+
+fn version_of(value: &Value, field: &str) -> Result<u64, BoxedError> {
+    let Value::Map(entries) = value else {
+        return Err("expected a map with a version".into());
+    };
+    let version = entries.iter().find_map(|(k, v)| match k {
+        Value::String(k) if k == field => Some(v),
+        _ => None,
+    });
+
+    match version {
+        Some(&Value::U64(v)) => Ok(v),
+        Some(&Value::I64(v)) if v >= 0 => Ok(v as u64),
+        Some(_) => Err(format!("invalid \"{field}\" field").into()),
+        None => Err(format!("missing \"{field}\" field").into()),
+    }
+}
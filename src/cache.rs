@@ -2,17 +2,33 @@
 
 use crate::{
     anycache::{AssetMap as _, Cache, CacheExt},
-    asset::{DirLoadable, Storable},
+    asset::{DirLoadable, ParamAsset, SaveAsset, Storable},
+    deferred::WorkerPool,
     entry::{CacheEntry, UntypedHandle},
-    source::{FileSystem, Source},
-    utils::{RandomState, RwLock},
-    AnyCache, Compound, Error, Handle,
+    error::LoadError,
+    failures::Failures,
+    listing::Listing,
+    mount::Mounts,
+    source::{DirEntry, FileSystem, Source, WritableSource},
+    utils::{HashMap, RandomState, RwLock},
+    AggregateError, AnyCache, Asset, BoxedError, Compound, DirLoadReport, DirValues, Error, Find,
+    Handle, LoadFailure, Locale, Manifest, ManifestEntry, PendingHandle, RetryPolicy, SharedString,
+    ValidationReport,
 };
 
 #[cfg(doc)]
 use crate::AssetReadGuard;
 
-use std::{any::TypeId, fmt, io, path::Path};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    fmt, io,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+};
 
 #[cfg(feature = "hot-reloading")]
 use crate::hot_reloading::{records, HotReloader};
@@ -82,6 +98,75 @@ impl AssetMap {
             shard.0.get_mut().clear();
         }
     }
+
+    fn retain(&mut self, f: &mut dyn FnMut(&UntypedHandle) -> bool) {
+        for shard in &mut *self.shards {
+            shard.0.get_mut().retain(f);
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &UntypedHandle> + '_ {
+        // Entries are boxed and cannot be removed while the map is borrowed,
+        // so handles outlive the guard of their shard.
+        self.shards.iter().flat_map(|shard| {
+            let shard = shard.0.read();
+            let handles: Vec<_> = shard
+                .iter()
+                .map(|entry| unsafe { entry.inner().extend_lifetime() })
+                .collect();
+            handles
+        })
+    }
+
+    fn memory_usage(&self) -> usize {
+        let mut usage = 0;
+        for shard in &*self.shards {
+            for entry in shard.0.read().iter() {
+                if let Some((size, _)) = entry.inner().eviction_infos() {
+                    usage += size;
+                }
+            }
+        }
+        usage
+    }
+
+    /// Removes the least recently used entries until the memory usage is
+    /// below `budget`, and returns the number of removed entries.
+    fn evict(&mut self, budget: usize) -> usize {
+        let mut usage = 0;
+        let mut candidates = Vec::new();
+
+        for shard in &mut *self.shards {
+            for entry in shard.0.get_mut().iter() {
+                if let Some((size, last_access)) = entry.inner().eviction_infos() {
+                    usage += size;
+                    if size != 0 {
+                        let (type_id, id) = entry.as_key();
+                        candidates.push((last_access, size, SharedString::from(id), type_id));
+                    }
+                }
+            }
+        }
+
+        if usage <= budget {
+            return 0;
+        }
+
+        candidates.sort_unstable_by_key(|&(last_access, ..)| last_access);
+
+        let mut count = 0;
+        for (_, size, id, type_id) in candidates {
+            if usage <= budget {
+                break;
+            }
+            if self.remove(&id, type_id) {
+                log::trace!("Evicting \"{id}\" from the cache");
+                usage -= size;
+                count += 1;
+            }
+        }
+        count
+    }
 }
 
 impl crate::anycache::AssetMap for AssetMap {
@@ -181,9 +266,54 @@ pub struct AssetCache<S = FileSystem> {
     pub(crate) reloader: Option<HotReloader>,
 
     pub(crate) assets: AssetMap,
+    seal: AtomicU8,
+    budget: AtomicUsize,
+    epoch: AtomicUsize,
+    workers: OnceLock<WorkerPool>,
+    aliases: Aliases,
+    mounts: Mounts,
+    listing: Arc<Listing>,
+    failures: Arc<Failures>,
+    #[cfg(feature = "stats")]
+    stats: crate::stats::Recorder,
     source: S,
 }
 
+/// What happens when a sealed cache is asked for an asset it does not
+/// contain.
+///
+/// See [`AssetCache::seal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SealPolicy {
+    /// Loading the asset fails with a [`SealedError`].
+    ///
+    /// [`SealedError`]: crate::SealedError
+    Error,
+
+    /// A warning is logged and the asset is loaded normally.
+    LogAndAllow,
+}
+
+impl SealPolicy {
+    const NONE: u8 = 0;
+
+    fn to_u8(policy: Option<Self>) -> u8 {
+        match policy {
+            None => Self::NONE,
+            Some(SealPolicy::Error) => 1,
+            Some(SealPolicy::LogAndAllow) => 2,
+        }
+    }
+
+    fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            1 => Some(SealPolicy::Error),
+            2 => Some(SealPolicy::LogAndAllow),
+            _ => None,
+        }
+    }
+}
+
 impl<S: Source> crate::anycache::RawCache for AssetCache<S> {
     type AssetMap = AssetMap;
     type Source = S;
@@ -203,8 +333,106 @@ impl<S: Source> crate::anycache::RawCache for AssetCache<S> {
     fn reloader(&self) -> Option<&HotReloader> {
         self.reloader.as_ref()
     }
+
+    #[inline]
+    fn seal_policy(&self) -> Option<SealPolicy> {
+        SealPolicy::from_u8(self.seal.load(Ordering::Relaxed))
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn stats(&self) -> Option<&crate::stats::Recorder> {
+        Some(&self.stats)
+    }
+
+    #[inline]
+    fn touch(&self, entry: &UntypedHandle) {
+        if self.budget.load(Ordering::Relaxed) != NO_BUDGET {
+            entry.touch(self.epoch.load(Ordering::Relaxed));
+        }
+    }
+
+    #[inline]
+    fn resolve_alias(&self, id: &str) -> Option<SharedString> {
+        self.aliases.resolve(id)
+    }
+
+    #[inline]
+    fn mounts(&self) -> Option<&Mounts> {
+        (!self.mounts.is_empty()).then_some(&self.mounts)
+    }
+
+    #[inline]
+    fn listing(&self) -> Option<&Listing> {
+        Some(&self.listing)
+    }
+
+    #[inline]
+    fn failures(&self) -> Option<&Failures> {
+        Some(&self.failures)
+    }
 }
 
+/// Ids that redirect to other ids.
+struct Aliases {
+    /// `true` if `map` is not empty, to avoid taking the lock in the common
+    /// case.
+    any: AtomicBool,
+    map: RwLock<HashMap<SharedString, SharedString>>,
+}
+
+impl Aliases {
+    fn new() -> Self {
+        Self {
+            any: AtomicBool::new(false),
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn resolve(&self, id: &str) -> Option<SharedString> {
+        if !self.any.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let map = self.map.read();
+        let mut target = map.get(id)?;
+        while let Some(next) = map.get(&**target) {
+            target = next;
+        }
+        Some(target.clone())
+    }
+
+    fn insert(&self, from: &str, to: &str) -> bool {
+        let mut map = self.map.write();
+
+        // Reject cycles
+        let mut target = to;
+        loop {
+            if target == from {
+                return false;
+            }
+            match map.get(target) {
+                Some(next) => target = next,
+                None => break,
+            }
+        }
+
+        map.insert(from.into(), to.into());
+        self.any.store(true, Ordering::Release);
+        true
+    }
+
+    fn remove(&self, from: &str) -> Option<SharedString> {
+        let mut map = self.map.write();
+        let to = map.remove(from);
+        self.any.store(!map.is_empty(), Ordering::Release);
+        to
+    }
+}
+
+/// The value of `AssetCache::budget` when there is no memory budget.
+const NO_BUDGET: usize = usize::MAX;
+
 impl AssetCache<FileSystem> {
     /// Creates a cache that loads assets from the given directory.
     ///
@@ -224,11 +452,24 @@ impl<S: Source> AssetCache<S> {
     ///
     /// If hot-reloading fails to start, an error is logged.
     pub fn with_source(source: S) -> AssetCache<S> {
+        let listing = Arc::new(Listing::new());
+        let failures = Arc::new(Failures::new());
+
         Self {
             #[cfg(feature = "hot-reloading")]
-            reloader: HotReloader::make(&source),
+            reloader: HotReloader::make(&source, listing.clone(), failures.clone()),
 
             assets: AssetMap::new(),
+            seal: AtomicU8::new(SealPolicy::NONE),
+            budget: AtomicUsize::new(NO_BUDGET),
+            epoch: AtomicUsize::new(0),
+            workers: OnceLock::new(),
+            aliases: Aliases::new(),
+            mounts: Mounts::new(),
+            listing,
+            failures,
+            #[cfg(feature = "stats")]
+            stats: crate::stats::Recorder::new(),
             source,
         }
     }
@@ -240,6 +481,16 @@ impl<S: Source> AssetCache<S> {
             reloader: None,
 
             assets: AssetMap::new(),
+            seal: AtomicU8::new(SealPolicy::NONE),
+            budget: AtomicUsize::new(NO_BUDGET),
+            epoch: AtomicUsize::new(0),
+            workers: OnceLock::new(),
+            aliases: Aliases::new(),
+            mounts: Mounts::new(),
+            listing: Arc::new(Listing::new()),
+            failures: Arc::new(Failures::new()),
+            #[cfg(feature = "stats")]
+            stats: crate::stats::Recorder::new(),
             source,
         }
     }
@@ -250,6 +501,354 @@ impl<S: Source> AssetCache<S> {
         &self.source
     }
 
+    /// Walks the source once and keeps its directory structure in memory.
+    ///
+    /// Afterwards, reading directories (eg with [`load_dir`](Self::load_dir))
+    /// and checking if files exist do not access the source anymore. This is
+    /// useful for sources that are slow to list, like a large directory on
+    /// the filesystem. Calling this function again scans the source again.
+    ///
+    /// With hot-reloading, the listing is updated when the source reports
+    /// changes. Otherwise, files added to the source afterwards are not seen.
+    ///
+    /// Mounted sources are not scanned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.scan()?;
+    ///
+    /// let dir = cache.load_dir::<String>("example")?;
+    /// assert!(dir.read().ids().any(|id| id == "example.hello"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn scan(&self) -> io::Result<()> {
+        self.listing.scan(&self.source)
+    }
+
+    /// Seals the cache.
+    ///
+    /// A sealed cache does not accept new assets: loading an asset that is
+    /// not already in the cache follows the given policy. This is useful to
+    /// check that all assets are loaded at startup.
+    ///
+    /// Assets that were loaded before are still available, and
+    /// [`load_owned`](Self::load_owned) is not affected except for the assets
+    /// it loads from the cache. With [`SealPolicy::Error`],
+    /// [`get_or_insert`](Self::get_or_insert) panics if the asset is not in
+    /// the cache.
+    ///
+    /// Note that reloading a `Compound` that loads new assets fails in a
+    /// sealed cache with [`SealPolicy::Error`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::{AssetCache, SealPolicy, SealedError};
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// cache.seal(SealPolicy::Error);
+    /// assert!(cache.load::<String>("example.hello").is_ok());
+    ///
+    /// let err = cache.load::<String>("common.name").unwrap_err();
+    /// assert!(err.reason().is::<SealedError>());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn seal(&self, policy: SealPolicy) {
+        self.seal
+            .store(SealPolicy::to_u8(Some(policy)), Ordering::Relaxed);
+    }
+
+    /// Unseals the cache, so that new assets can be loaded again.
+    #[inline]
+    pub fn unseal(&self) {
+        self.seal.store(SealPolicy::to_u8(None), Ordering::Relaxed);
+    }
+
+    /// Returns the current policy if the cache is sealed.
+    #[inline]
+    pub fn seal_policy(&self) -> Option<SealPolicy> {
+        crate::anycache::RawCache::seal_policy(self)
+    }
+
+    /// Makes `from` an alias of `to`.
+    ///
+    /// Loading or getting an asset with id `from` then transparently uses the
+    /// asset with id `to` instead, so the returned handle has id `to`. This is
+    /// useful to keep old ids working when assets are moved, for example if
+    /// they are stored in saved games. Aliases can be chained: if `to` is
+    /// itself an alias, the redirect is followed. For the purpose of
+    /// hot-reloading, assets loaded through an alias depend on the target
+    /// asset.
+    ///
+    /// Aliases can be read from a manifest file by loading it as any other
+    /// asset and calling this function for each entry.
+    ///
+    /// Adding an alias does not change assets that are already cached with id
+    /// `from`.
+    ///
+    /// Returns `false` and does nothing if the alias would create a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// assert!(cache.add_alias("greeting", "example.hello"));
+    ///
+    /// let hello = cache.load::<String>("greeting")?;
+    /// assert_eq!(hello.id(), "example.hello");
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn add_alias(&self, from: &str, to: &str) -> bool {
+        self.aliases.insert(from, to)
+    }
+
+    /// Removes an alias added with [`add_alias`](Self::add_alias), and
+    /// returns the id it redirected to.
+    pub fn remove_alias(&self, from: &str) -> Option<SharedString> {
+        self.aliases.remove(from)
+    }
+
+    /// Returns the id to which `id` redirects, following chains of aliases,
+    /// or `None` if `id` is not an alias.
+    pub fn resolve_alias(&self, id: &str) -> Option<SharedString> {
+        self.aliases.resolve(id)
+    }
+
+    /// Sets the approximate memory budget of the cache, in bytes.
+    ///
+    /// When a budget is set, the cache tracks when assets are accessed, and
+    /// [`evict`](Self::evict) removes the least recently used assets until
+    /// the memory usage of the cache fits in the budget. There is no budget
+    /// by default.
+    ///
+    /// The size of an asset is approximated by the number of bytes read from
+    /// the source to load it, without the bytes read by the assets it
+    /// depends on. Only assets loaded from the source can be evicted, as they
+    /// can be loaded again later: values added with
+    /// [`get_or_insert`](Self::get_or_insert) are kept.
+    #[inline]
+    pub fn set_memory_budget(&self, budget: Option<usize>) {
+        let budget = budget.map_or(NO_BUDGET, |b| b.min(NO_BUDGET - 1));
+        self.budget.store(budget, Ordering::Relaxed);
+    }
+
+    /// Returns the memory budget of the cache, if any.
+    ///
+    /// See [`set_memory_budget`](Self::set_memory_budget) for more details.
+    #[inline]
+    pub fn memory_budget(&self) -> Option<usize> {
+        match self.budget.load(Ordering::Relaxed) {
+            NO_BUDGET => None,
+            budget => Some(budget),
+        }
+    }
+
+    /// Returns the approximate memory usage of assets that can be evicted,
+    /// in bytes.
+    ///
+    /// See [`set_memory_budget`](Self::set_memory_budget) for more details.
+    pub fn memory_usage(&self) -> usize {
+        self.assets.memory_usage()
+    }
+
+    /// Returns statistics about the assets loaded by this cache.
+    ///
+    /// Statistics are grouped by type, and include the number of lookups that
+    /// found an asset in the cache or not, and the number of loads from the
+    /// source with the bytes they read and the time they took.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "stats")] {
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// let stats = cache.statistics();
+    /// let strings = stats.of::<String>().unwrap();
+    /// assert_eq!(strings.loads(), 1);
+    /// assert_eq!(strings.hits(), 1);
+    /// assert_eq!(strings.misses(), 1);
+    /// # }}
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn statistics(&self) -> crate::stats::Statistics {
+        self.stats.snapshot()
+    }
+
+    /// Resets the statistics returned by [`statistics`](Self::statistics).
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn reset_statistics(&self) {
+        self.stats.clear();
+    }
+
+    /// Returns an iterator over the handles of all values in the cache.
+    ///
+    /// This includes assets, directories and values inserted with
+    /// [`get_or_insert`](Self::get_or_insert), in an unspecified order. Values
+    /// added to the cache while iterating may or may not be yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// for handle in cache.iter_handles() {
+    ///     println!("{} ({}): {:?}", handle.id(), handle.type_name(), handle.last_reload_id());
+    /// }
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[inline]
+    pub fn iter_handles(&self) -> impl Iterator<Item = &UntypedHandle> + '_ {
+        self.assets.iter()
+    }
+
+    /// Returns a summary of the content of the cache.
+    ///
+    /// The returned [`Manifest`] lists the id, type and last reload of each
+    /// value in the cache, and can be compared with another one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// println!("{}", cache.dump_manifest());
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn dump_manifest(&self) -> Manifest {
+        let entries = self
+            .iter_handles()
+            .map(|handle| ManifestEntry {
+                id: handle.id().clone(),
+                type_name: handle.type_name(),
+                reload_id: handle.last_reload_id(),
+                mount: self.mounts.prefix_of(handle.id()).cloned(),
+            })
+            .collect();
+        Manifest::new(entries, self.failures.list())
+    }
+
+    /// Sets when loading an asset that failed to load is tried again.
+    ///
+    /// By default, failed assets are tried again each time they are loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::{AssetCache, RetryPolicy};
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.set_retry_policy(RetryPolicy::Never);
+    ///
+    /// assert!(cache.load::<String>("example.missing").is_err());
+    /// let failure = cache.last_error::<String>("example.missing").unwrap();
+    /// assert_eq!(failure.attempts(), 1);
+    ///
+    /// // The source is not read again
+    /// assert!(cache.load::<String>("example.missing").is_err());
+    /// assert_eq!(cache.last_error::<String>("example.missing").unwrap().attempts(), 1);
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        self.failures.set_policy(policy);
+    }
+
+    /// Returns the last failure to load an asset, if it failed to load since
+    /// it last loaded successfully.
+    ///
+    /// With hot-reloading, the failure is forgotten when a file with the same
+    /// id changes, so that the asset is tried again the next time it is
+    /// loaded.
+    pub fn last_error<T: Storable>(&self, id: &str) -> Option<LoadFailure> {
+        self.failures.get(id, TypeId::of::<T>())
+    }
+
+    /// Returns all failures to load assets that were not followed by a
+    /// successful load, sorted by id.
+    pub fn load_failures(&self) -> Vec<LoadFailure> {
+        self.failures.list()
+    }
+
+    /// Forgets all failures to load assets, so that they are tried again the
+    /// next time they are loaded.
+    pub fn clear_load_failures(&self) {
+        self.failures.clear();
+    }
+
+    /// Returns an iterator over the handles of all values of the given type in
+    /// the cache.
+    ///
+    /// See [`iter_handles`](Self::iter_handles) for more details.
+    #[inline]
+    pub fn iter_handles_of(&self, type_id: TypeId) -> impl Iterator<Item = &UntypedHandle> + '_ {
+        self.iter_handles()
+            .filter(move |handle| handle.type_id() == type_id)
+    }
+
+    /// Evicts assets until the memory usage of the cache fits in its budget,
+    /// and returns the number of evicted assets.
+    ///
+    /// Assets that were not accessed for the longest time are evicted first.
+    /// An asset is accessed when it is loaded or retrieved from the cache, so
+    /// assets used through a handle kept across calls to this function are
+    /// not considered accessed. As this function takes a mutable reference
+    /// to the cache, such handles cannot exist anyway: assets in use cannot
+    /// be evicted.
+    ///
+    /// This function does nothing if there is no budget. It is typically
+    /// called between frames.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let mut cache = AssetCache::new("assets")?;
+    /// cache.set_memory_budget(Some(10));
+    ///
+    /// cache.load::<String>("example.hello")?;
+    /// cache.evict();
+    ///
+    /// cache.load::<String>("common.name")?;
+    /// assert!(cache.memory_usage() > 10);
+    ///
+    /// // "example.hello" is the least recently used asset
+    /// assert_eq!(cache.evict(), 1);
+    /// assert!(!cache.contains::<String>("example.hello"));
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn evict(&mut self) -> usize {
+        let epoch = self.epoch.get_mut();
+        *epoch = epoch.wrapping_add(1);
+
+        match self.memory_budget() {
+            Some(budget) => self.assets.evict(budget),
+            None => 0,
+        }
+    }
+
     /// Temporarily prevent `Compound` dependencies to be recorded.
     ///
     /// See [`AnyCache::no_record`] for more details.
@@ -274,6 +873,70 @@ impl<S: Source> AssetCache<S> {
         self._load(id)
     }
 
+    /// Loads an asset with parameters.
+    ///
+    /// See [`AnyCache::load_with`] for more details.
+    #[inline]
+    pub fn load_with<T: ParamAsset>(
+        &self,
+        id: &str,
+        params: T::Params,
+    ) -> Result<&Handle<T>, Error> {
+        self._load_with(id, params)
+    }
+
+    /// Starts loading an asset on a background thread.
+    ///
+    /// The file is read and decoded by a small pool of threads owned by the
+    /// cache, so that loading large assets does not block the current thread.
+    /// The returned [`PendingHandle`] can be polled with
+    /// [`is_ready`](PendingHandle::is_ready), and the asset is added to the
+    /// cache when calling [`wait`](PendingHandle::wait).
+    ///
+    /// If the asset is already in the cache, it is returned directly. The
+    /// asset is loaded on the current thread if the source cannot be used
+    /// from another thread, ie if [`Source::make_source`] returns `None`.
+    ///
+    /// Only [`Asset`]s can be loaded this way, as a `Compound` needs to
+    /// access the cache while loading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    ///
+    /// let pending = cache.load_deferred::<String>("example.hello");
+    ///
+    /// // Do something else meanwhile
+    ///
+    /// let hello = pending.wait()?;
+    /// assert_eq!(&*hello.read(), "Hello !\n");
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn load_deferred<T: Asset>(&self, id: &str) -> PendingHandle<'_, T> {
+        let cache = self.as_any_cache();
+        let alias = self.aliases.resolve(id);
+        let id = alias.as_deref().unwrap_or(id);
+
+        if let Some(handle) = self._get_cached(id) {
+            return PendingHandle::ready(cache, id, Ok(handle));
+        }
+
+        if self.mounts.route(id).is_some() {
+            return PendingHandle::ready(cache, id, self._load(id));
+        }
+
+        match self.source.make_source() {
+            Some(source) if !crate::asset::is_invalid_id(id) && self.check_seal(id) => {
+                let pool = self.workers.get_or_init(WorkerPool::new);
+                PendingHandle::spawn(cache, pool, source, id)
+            }
+            _ => PendingHandle::ready(cache, id, self._load(id)),
+        }
+    }
+
     /// Loads an asset and panic if an error happens.
     ///
     /// See [`AnyCache::load_expect`] for more details.
@@ -291,6 +954,22 @@ impl<S: Source> AssetCache<S> {
         self._get_cached(id)
     }
 
+    /// Gets a copy of a value from the cache.
+    ///
+    /// See [`AnyCache::get_copied`] for more details.
+    #[inline]
+    pub fn get_copied<T: Storable + Copy>(&self, id: &str) -> Option<T> {
+        self._get_copied(id)
+    }
+
+    /// Gets a clone of a value from the cache.
+    ///
+    /// See [`AnyCache::get_cloned`] for more details.
+    #[inline]
+    pub fn get_cloned<T: Storable + Clone>(&self, id: &str) -> Option<T> {
+        self._get_cloned(id)
+    }
+
     /// Gets a value with the given type from the cache.
     ///
     /// This is an equivalent of `get_cached` but with a dynamic type.
@@ -312,7 +991,7 @@ impl<S: Source> AssetCache<S> {
     /// See [`AnyCache::contains`] for more details.
     #[inline]
     pub fn contains<T: Storable>(&self, id: &str) -> bool {
-        self.assets.contains_key(id, TypeId::of::<T>())
+        self._contains::<T>(id)
     }
 
     /// Loads a directory.
@@ -337,6 +1016,74 @@ impl<S: Source> AssetCache<S> {
         self.load::<crate::RecursiveDirectory<T>>(id)
     }
 
+    /// Loads a directory and all the assets it contains.
+    ///
+    /// See [`AnyCache::load_dir_values`] for more details.
+    #[inline]
+    pub fn load_dir_values<T: DirLoadable + Compound>(
+        &self,
+        id: &str,
+    ) -> Result<DirValues<'_, T>, Error> {
+        self._load_dir_values(id)
+    }
+
+    /// Loads all files of a directory with a custom loader.
+    ///
+    /// See [`AnyCache::load_dir_with`] for more details.
+    #[inline]
+    pub fn load_dir_with<T: Storable>(
+        &self,
+        id: &str,
+        recursive: bool,
+        extensions: &[&str],
+        mut load: impl FnMut(Cow<[u8]>, &str) -> Result<T, BoxedError>,
+    ) -> Result<Vec<&UntypedHandle>, Error> {
+        self._load_dir_with(id, recursive, extensions, &mut load)
+    }
+
+    /// Returns the ids of all assets of type `T` that the source contains in
+    /// the given directory and its subdirectories.
+    ///
+    /// See [`AnyCache::available_ids`] for more details.
+    #[inline]
+    pub fn available_ids<T: DirLoadable>(&self, id: &str) -> Result<Vec<SharedString>, Error> {
+        self._available_ids::<T>(id)
+    }
+
+    /// Returns the ids of all files of the source that match a pattern.
+    ///
+    /// See [`AnyCache::find_ids`] for more details.
+    #[inline]
+    pub fn find_ids(&self, pattern: &str) -> Vec<SharedString> {
+        self._find_ids(pattern)
+    }
+
+    /// Returns an iterator over the entries of the source that match a
+    /// pattern.
+    ///
+    /// See [`AnyCache::find`] for more details.
+    #[inline]
+    pub fn find(&self, pattern: &str) -> Find<'_> {
+        self._find(pattern)
+    }
+
+    /// Returns the locale used to load [`Localized`](crate::Localized)
+    /// assets.
+    ///
+    /// See [`AnyCache::locale`] for more details.
+    #[inline]
+    pub fn locale(&self) -> Locale {
+        self._locale()
+    }
+
+    /// Sets the locale used to load [`Localized`](crate::Localized) assets.
+    ///
+    /// See [`AnyCache::set_locale`] for more details.
+    #[inline]
+    pub fn set_locale(&self, locale: Locale) {
+        self._set_locale(locale)
+    }
+
     /// Loads an owned version of an asset.
     ///
     /// See [`AnyCache::load_owned`] for more details.
@@ -345,9 +1092,57 @@ impl<S: Source> AssetCache<S> {
         self._load_owned(id)
     }
 
+    /// Loads an asset and returns a clone of it.
+    ///
+    /// See [`AnyCache::load_cloned`] for more details.
+    #[inline]
+    pub fn load_cloned<T: Compound + Clone>(&self, id: &str) -> Result<T, Error> {
+        self._load_cloned(id)
+    }
+
+    /// Loads all the given assets, collecting all errors.
+    ///
+    /// See [`AnyCache::load_all`] for more details.
+    #[inline]
+    pub fn load_all<'i, T: Compound>(
+        &self,
+        ids: impl IntoIterator<Item = &'i str>,
+    ) -> Result<Vec<&Handle<T>>, AggregateError> {
+        self._load_all(&mut ids.into_iter())
+    }
+
+    /// Loads all the given assets and reports errors.
+    ///
+    /// See [`AnyCache::validate`] for more details.
+    #[inline]
+    pub fn validate<'i, T: Compound>(
+        &self,
+        ids: impl IntoIterator<Item = &'i str>,
+    ) -> ValidationReport {
+        self._validate::<T>(&mut ids.into_iter())
+    }
+
+    /// Loads all assets of a directory and its subdirectories and reports
+    /// errors.
+    ///
+    /// See [`AnyCache::validate_dir`] for more details.
+    #[inline]
+    pub fn validate_dir<T: DirLoadable + Compound>(&self, id: &str) -> ValidationReport {
+        self._validate_dir::<T>(id)
+    }
+
+    /// Loads all assets of a directory and its subdirectories, and collects
+    /// both the loaded assets and the errors.
+    ///
+    /// See [`AnyCache::load_dir_report`] for more details.
+    #[inline]
+    pub fn load_dir_report<T: DirLoadable + Compound>(&self, id: &str) -> DirLoadReport<'_, T> {
+        self._load_dir_report::<T>(id)
+    }
+
     /// Converts to an `AnyCache`.
     #[inline]
-    pub fn as_any_cache(&self) -> AnyCache {
+    pub fn as_any_cache(&self) -> AnyCache<'_> {
         self._as_any_cache()
     }
 }
@@ -363,6 +1158,92 @@ impl<S: Source> AssetCache<S> {
         self.assets.remove(id, TypeId::of::<T>())
     }
 
+    /// Mounts a source at a prefix of ids.
+    ///
+    /// Assets whose id is `prefix` or starts with `prefix.` are then loaded
+    /// from `source`, with the id relative to the prefix. For example, if
+    /// `source` is mounted at `"mods.cool_mod"`, the asset with id
+    /// `"mods.cool_mod.textures.hero"` is loaded from file `textures.hero` of
+    /// `source`. Other assets are loaded from the source of the cache. When
+    /// mounts are nested, the longest prefix is used. Mounting a source at a
+    /// prefix that is already mounted replaces the previous source.
+    ///
+    /// Assets with an id under `prefix` that are already in the cache are
+    /// removed, so that they are loaded from the new source the next time
+    /// they are needed. As this function takes a mutable reference to the
+    /// cache, no handle to them can still exist.
+    ///
+    /// Note that mounted sources are not watched for hot-reloading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::{AssetCache, source::FileSystem};
+    ///
+    /// let mut cache = AssetCache::new("assets")?;
+    /// cache.mount("mods.cool_mod", FileSystem::new("assets/example")?);
+    ///
+    /// let hello = cache.load::<String>("mods.cool_mod.hello")?;
+    /// assert_eq!(*hello.read(), *cache.load::<String>("example.hello")?.read());
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn mount(&mut self, prefix: &str, source: impl Source + Send + Sync + 'static) {
+        self.mounts.insert(prefix.into(), Box::new(source));
+        self.invalidate_prefix(prefix);
+    }
+
+    /// Unmounts the source mounted at `prefix`, and returns whether there was
+    /// one.
+    ///
+    /// Assets with an id under `prefix` are removed from the cache, as with
+    /// [`mount`](Self::mount).
+    pub fn unmount(&mut self, prefix: &str) -> bool {
+        let removed = self.mounts.remove(prefix);
+        if removed {
+            self.invalidate_prefix(prefix);
+        }
+        removed
+    }
+
+    fn invalidate_prefix(&mut self, prefix: &str) {
+        self.assets
+            .retain(&mut |handle| !crate::mount::is_under(handle.id(), prefix));
+    }
+
+    /// Removes an asset and the assets that are only used by it from the
+    /// cache, and returns the number of removed assets.
+    ///
+    /// The assets loaded by `Compound::load` are known from the dependency
+    /// graph built for hot-reloading. An asset is removed if all the assets
+    /// that depend on it are removed. Assets that are used by other assets
+    /// are kept.
+    ///
+    /// Note that only the dependencies of hot-reloaded assets are known, so if
+    /// hot-reloading is disabled, only the given asset is removed. Assets that
+    /// are loaded directly are not known either, so they may be removed if
+    /// they were also used by a removed asset. They will be loaded again next
+    /// time they are needed.
+    ///
+    /// As this function takes a mutable reference to the cache, no handle to
+    /// a removed asset can still exist.
+    pub fn unload_cascade(&mut self, id: &str, type_id: TypeId) -> usize {
+        let removed = self.assets.remove(id, type_id) as usize;
+
+        #[cfg(feature = "hot-reloading")]
+        if let Some(reloader) = &self.reloader {
+            let key = crate::hot_reloading::AssetKey::new_with(id.into(), type_id);
+            let keys = reloader.remove_cascade(key);
+            let dependencies = keys
+                .into_iter()
+                .filter(|key| key.id != id || key.type_id != type_id)
+                .filter(|key| self.assets.remove(&key.id, key.type_id))
+                .count();
+            return removed + dependencies;
+        }
+
+        removed
+    }
+
     /// Takes ownership on a cached asset.
     ///
     /// The corresponding asset is removed from the cache.
@@ -372,12 +1253,39 @@ impl<S: Source> AssetCache<S> {
         Some(asset)
     }
 
+    /// Removes from the cache all the assets for which `f` returns `false`.
+    ///
+    /// As this function takes a mutable reference to the cache, no handle to
+    /// a removed asset can still exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let mut cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    /// cache.get_or_insert::<u32>("cutscene.intro", 1);
+    ///
+    /// // Unload assets that are not needed anymore
+    /// cache.retain(|handle| !handle.id().starts_with("cutscene."));
+    ///
+    /// assert!(cache.contains::<String>("example.hello"));
+    /// assert!(!cache.contains::<u32>("cutscene.intro"));
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[inline]
+    pub fn retain(&mut self, mut f: impl FnMut(&UntypedHandle) -> bool) {
+        self.assets.retain(&mut f);
+    }
+
     /// Clears the cache.
     ///
     /// Removes all cached assets and directories.
     #[inline]
     pub fn clear(&mut self) {
         self.assets.clear();
+        self.failures.clear();
 
         #[cfg(feature = "hot-reloading")]
         if let Some(reloader) = &self.reloader {
@@ -386,6 +1294,101 @@ impl<S: Source> AssetCache<S> {
     }
 }
 
+impl<S: WritableSource> AssetCache<S> {
+    /// Saves an asset of the cache back to the source.
+    ///
+    /// This is the same as [`save_value`](Self::save_value) with the value
+    /// currently in the cache.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the asset is not in the cache, or in the same
+    /// cases as `save_value`.
+    pub fn save<T: SaveAsset>(&self, id: &str) -> Result<(), Error> {
+        match self.assets.get(id, TypeId::of::<T>()) {
+            Some(handle) => self.save_value(id, &*handle.downcast_ref_ok::<T>().read()),
+            None => {
+                let err = LoadError::from(io::Error::from(io::ErrorKind::NotFound));
+                Err(Error::with_type(
+                    id.into(),
+                    std::any::type_name::<T>(),
+                    err.into(),
+                ))
+            }
+        }
+    }
+
+    /// Saves a value to the source, as the asset with the given id.
+    ///
+    /// The value is converted to bytes with [`SaveAsset::to_bytes`] and
+    /// written to the file the asset is loaded from: the first extension in
+    /// [`Asset::EXTENSIONS`] for which a file exists is used, or the first
+    /// extension if there is no such file.
+    ///
+    /// The cache itself is not modified, but when hot-reloading is enabled,
+    /// the asset is reloaded from the written file, and so are the assets
+    /// that depend on it.
+    ///
+    /// [`Asset::EXTENSIONS`]: crate::Asset::EXTENSIONS
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the id is invalid, if the asset type has no
+    /// extension, if the value cannot be converted or if the file cannot be
+    /// written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+    /// use assets_manager::{loader, Asset, AssetCache, BoxedError, SaveAsset};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Level {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Asset for Level {
+    ///     const EXTENSION: &'static str = "ron";
+    ///     type Loader = loader::RonLoader;
+    /// }
+    ///
+    /// impl SaveAsset for Level {
+    ///     fn to_bytes(&self, _ext: &str) -> Result<Vec<u8>, BoxedError> {
+    ///         Ok(ron::to_string(self)?.into_bytes())
+    ///     }
+    /// }
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    ///
+    /// let mut level = cache.load_owned::<Level>("levels.first")?;
+    /// level.name = "The first level".into();
+    /// cache.save_value("levels.first", &level)?;
+    /// # }}
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn save_value<T: SaveAsset>(&self, id: &str, value: &T) -> Result<(), Error> {
+        let error =
+            |err: LoadError| Error::with_type(id.into(), std::any::type_name::<T>(), err.into());
+
+        if crate::asset::is_invalid_id(id) {
+            return Err(error(LoadError::InvalidId));
+        }
+
+        let ext = T::EXTENSIONS
+            .iter()
+            .find(|ext| self.source.exists(DirEntry::File(id, ext)))
+            .or_else(|| T::EXTENSIONS.first())
+            .ok_or_else(|| error(LoadError::NoDefaultValue))?;
+
+        let content = value.to_bytes(ext).map_err(|err| error(err.into()))?;
+        self.source
+            .write(id, ext, &content)
+            .map_err(|err| error(err.into()))
+    }
+}
+
 impl<S> AssetCache<S>
 where
     S: Source + Sync,
@@ -435,6 +1438,347 @@ where
             reloader.send_static(&self.assets);
         }
     }
+
+    /// Skips reloading files whose content did not change.
+    ///
+    /// Some tools rewrite files with identical content, which triggers
+    /// reloads that are useless. When this is enabled, the content of files
+    /// is hashed when they are loaded and when they are modified, and assets
+    /// are only reloaded if the hash changed. This is disabled by default, as
+    /// it requires reading files one more time.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn skip_unchanged_reloads(&self, skip: bool) {
+        if let Some(reloader) = &self.reloader {
+            reloader.skip_unchanged(skip);
+        }
+    }
+
+    /// Sets how assets that repeatedly fail to reload are quarantined.
+    ///
+    /// Assets that fail to reload many times in a row (eg because a file is
+    /// being edited) are reloaded less and less often, until a reload
+    /// succeeds. Quarantine is disabled by default, and can be disabled again
+    /// with `None`.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn set_quarantine_policy(&self, policy: Option<crate::hot_reloading::QuarantinePolicy>) {
+        if let Some(reloader) = &self.reloader {
+            reloader.set_quarantine_policy(policy);
+        }
+    }
+
+    /// Returns the assets currently quarantined.
+    ///
+    /// See [`set_quarantine_policy`](Self::set_quarantine_policy) for more
+    /// details.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    pub fn quarantined_assets(&self) -> Vec<crate::hot_reloading::QuarantinedAsset> {
+        match &self.reloader {
+            Some(reloader) => reloader.quarantined_assets(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns a snapshot of the dependency graph used for hot-reloading.
+    ///
+    /// It tells which files, directories and assets each hot-reloaded asset
+    /// was loaded from, which is useful for tools that need to know what to
+    /// rebuild when a file changes.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, the returned graph is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "hot-reloading")] {
+    /// use assets_manager::{AssetCache, hot_reloading::{AssetKey, Dependency}};
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// let graph = cache.dependency_graph();
+    /// let node = graph.get(&AssetKey::new::<String>("example.hello".into())).unwrap();
+    /// assert_eq!(node.dependencies(), [Dependency::file("example.hello".into(), "txt".into())]);
+    /// # }}
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    pub fn dependency_graph(&self) -> crate::hot_reloading::DependencyGraph {
+        match &self.reloader {
+            Some(reloader) => reloader.dependency_graph(),
+            None => Default::default(),
+        }
+    }
+
+    /// Returns the assets that are reloaded when the file, directory or asset
+    /// with the given id changes.
+    ///
+    /// This includes the assets with this id and, transitively, the assets
+    /// that depend on them, in the order they are reloaded. This tells for
+    /// example which assets use a texture.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this returns an empty list.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "hot-reloading")] {
+    /// use assets_manager::{AnyCache, AssetCache, BoxedError, Compound, SharedString};
+    ///
+    /// struct Greeting(String);
+    ///
+    /// impl Compound for Greeting {
+    ///     fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+    ///         Ok(Greeting(cache.load::<String>(id)?.read().clone()))
+    ///     }
+    /// }
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<Greeting>("example.hello")?;
+    ///
+    /// let dependents = cache.dependents_of("example.hello");
+    /// assert_eq!(dependents.len(), 2);
+    /// assert_eq!(dependents[1].type_id(), std::any::TypeId::of::<Greeting>());
+    /// # }}
+    /// # Ok::<(), BoxedError>(())
+    /// ```
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    pub fn dependents_of(&self, id: &str) -> Vec<crate::hot_reloading::AssetKey> {
+        match &self.reloader {
+            Some(reloader) => reloader.dependents_of(id.into()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Sets a function called each time an asset fails to reload.
+    ///
+    /// The function is given the id of the asset and the error. It is called
+    /// from the thread that reloads assets, which is the one calling
+    /// [`hot_reload`](Self::hot_reload) unless
+    /// [`enhance_hot_reloading`](Self::enhance_hot_reloading) was called.
+    /// Calling this function again replaces the previous hook.
+    ///
+    /// Panics of the loader are caught and reported the same way, with a
+    /// [`PanicError`](crate::PanicError) as reason.
+    ///
+    /// The error is also kept by the handle of the asset until it is reloaded
+    /// successfully, see [`Handle::last_reload_error`].
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "hot-reloading")] {
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.on_reload_error(|id, err| {
+    ///     eprintln!("Failed to reload \"{id}\": {}", err.reason());
+    /// });
+    /// # }}
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    pub fn on_reload_error<F>(&self, hook: F)
+    where
+        F: Fn(&SharedString, &Error) + Send + Sync + 'static,
+    {
+        if let Some(reloader) = &self.reloader {
+            reloader.set_error_hook(Some(Box::new(hook)));
+        }
+    }
+
+    /// Periodically reloads assets of type `T`, even if the source reports no
+    /// change.
+    ///
+    /// This is useful for sources that cannot notify changes, such as
+    /// network-backed sources. Such sources must still support
+    /// hot-reloading, but they do not have to send events. Reloads go
+    /// through the usual hot-reloading process, so compounds that depend on
+    /// refreshed assets are reloaded too. If an asset matches several rules,
+    /// the shortest interval is used. `None` removes the rule.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn set_refresh_interval<T: Compound>(&self, interval: Option<std::time::Duration>) {
+        if let Some(reloader) = &self.reloader {
+            let filter = crate::hot_reloading::RefreshFilter::Type(TypeId::of::<T>());
+            reloader.set_refresh(filter, interval);
+        }
+    }
+
+    /// Periodically reloads assets whose id is `prefix` or starts with
+    /// `prefix` followed by a `.`, even if the source reports no change.
+    ///
+    /// See [`set_refresh_interval`](Self::set_refresh_interval) for more
+    /// details.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn set_prefix_refresh_interval(&self, prefix: &str, interval: Option<std::time::Duration>) {
+        if let Some(reloader) = &self.reloader {
+            let filter = crate::hot_reloading::RefreshFilter::Prefix(prefix.into());
+            reloader.set_refresh(filter, interval);
+        }
+    }
+
+    /// Reloads the asset of type `T` with the given id from the source, even
+    /// if the source reports no change.
+    ///
+    /// The reload goes through the usual hot-reloading process, so compounds
+    /// that depend on this asset are reloaded too. It happens during the next
+    /// call to [`hot_reload`](Self::hot_reload), or in the background after
+    /// a call to [`enhance_hot_reloading`](Self::enhance_hot_reloading).
+    ///
+    /// This is useful for sources that cannot notify changes. Such sources
+    /// must still support hot-reloading, but they do not have to send events.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn invalidate<T: Compound>(&self, id: &str) {
+        if let Some(reloader) = &self.reloader {
+            let id = self.aliases.resolve(id).unwrap_or_else(|| id.into());
+            reloader.invalidate(id, Some(TypeId::of::<T>()));
+        }
+    }
+
+    /// Reloads all assets with the given id from the source, whatever their
+    /// type, even if the source reports no change.
+    ///
+    /// See [`invalidate`](Self::invalidate) for more details.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn invalidate_id(&self, id: &str) {
+        if let Some(reloader) = &self.reloader {
+            let id = self.aliases.resolve(id).unwrap_or_else(|| id.into());
+            reloader.invalidate(id, None);
+        }
+    }
+
+    /// Waits for changes to settle before reloading assets.
+    ///
+    /// Editors often save files in several steps (eg truncating a file then
+    /// writing it), and tools may write many files at once. When a delay is
+    /// set, changes are collected until no event was received for this
+    /// duration, and are then handled together, so each asset is reloaded
+    /// once. `None` removes the delay, which is the default.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn set_reload_debounce(&self, delay: Option<std::time::Duration>) {
+        if let Some(reloader) = &self.reloader {
+            reloader.set_debounce(None, delay);
+        }
+    }
+
+    /// Waits for changes to settle before reloading assets of type `T`.
+    ///
+    /// This is useful for assets that are expensive to reload. Like the one
+    /// set with [`set_reload_debounce`](Self::set_reload_debounce), the delay
+    /// starts from the last event, so it only has an effect if it is longer.
+    /// `None` removes the delay for this type.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn set_type_reload_debounce<T: Compound>(&self, delay: Option<std::time::Duration>) {
+        if let Some(reloader) = &self.reloader {
+            reloader.set_debounce(Some(TypeId::of::<T>()), delay);
+        }
+    }
+
+    /// Sets whether changes handled together are applied atomically.
+    ///
+    /// By default, each asset is written as soon as it is reloaded, so
+    /// another thread may see the new value of an asset while its dependents
+    /// still have their old one (eg a shader and a material that uses it).
+    /// When this is enabled, new values of all assets affected by a batch of
+    /// changes are loaded first, and written to the cache at once. Compounds
+    /// reloaded in the batch see the new values of their dependencies.
+    ///
+    /// This works well with [`set_reload_debounce`](Self::set_reload_debounce),
+    /// which groups related changes in the same batch.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn set_atomic_reloads(&self, atomic: bool) {
+        if let Some(reloader) = &self.reloader {
+            reloader.set_atomic(atomic);
+        }
+    }
+
+    /// Freezes the cache until the returned guard is dropped.
+    ///
+    /// While the cache is frozen, no asset is reloaded: changes are kept and
+    /// reloads are applied once the last guard is dropped, or for the next
+    /// call to [`hot_reload`](Self::hot_reload) after that. This guarantees
+    /// that all reads done during a frame see the same version of assets,
+    /// even with [`enhance_hot_reloading`](Self::enhance_hot_reloading).
+    ///
+    /// If a reload is in progress, this function waits until it is done.
+    /// Several guards can be alive at the same time.
+    ///
+    /// If `self.source()` was created without hot-reloading or if it failed to
+    /// start, this function is a no-op.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache: &'static AssetCache = Box::leak(Box::new(AssetCache::new("assets")?));
+    /// cache.enhance_hot_reloading();
+    ///
+    /// loop {
+    ///     let _frame = cache.freeze();
+    ///
+    ///     // All reads during the frame see the same value
+    ///     let hello = cache.load::<String>("example.hello")?;
+    ///     println!("{}", hello.read());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    #[inline]
+    pub fn freeze(&self) -> crate::hot_reloading::FreezeGuard<'_> {
+        match &self.reloader {
+            Some(reloader) => reloader.freeze(),
+            None => crate::hot_reloading::FreezeGuard::none(),
+        }
+    }
 }
 
 impl<S> Default for AssetCache<S>
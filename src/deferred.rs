@@ -0,0 +1,295 @@
+//! Loading assets on background threads
+
+use crate::{
+    asset::load_from_source,
+    entry::CacheEntry,
+    source::{DirEntry, FileContent, Source},
+    utils::{Condvar, Mutex},
+    AnyCache, Asset, BoxedError, Error, Handle, SharedString,
+};
+use std::{
+    cell::Cell,
+    fmt, io,
+    panic::AssertUnwindSafe,
+    sync::{mpsc, Arc},
+};
+
+#[cfg(feature = "hot-reloading")]
+use std::cell::RefCell;
+
+#[cfg(doc)]
+use crate::AssetCache;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The maximum number of threads of a pool.
+const MAX_WORKERS: usize = 4;
+
+/// A pool of threads used to load assets in the background.
+///
+/// Threads stop once the pool is dropped and all queued jobs are done.
+pub(crate) struct WorkerPool {
+    sender: mpsc::Sender<Job>,
+}
+
+impl WorkerPool {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let count = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_WORKERS);
+
+        for i in 0..count {
+            let receiver = receiver.clone();
+            let result = std::thread::Builder::new()
+                .name(format!("assets_manager-worker-{i}"))
+                .spawn(move || loop {
+                    let job = receiver.lock().recv();
+                    match job {
+                        // Panics are reported through the `Completer`
+                        Ok(job) => drop(std::panic::catch_unwind(AssertUnwindSafe(job))),
+                        Err(_) => break,
+                    }
+                });
+
+            if let Err(err) = result {
+                log::error!("Failed to start loading thread: {err}");
+            }
+        }
+
+        Self { sender }
+    }
+
+    fn spawn(&self, job: Job) -> Result<(), Job> {
+        self.sender.send(job).map_err(|err| err.0)
+    }
+}
+
+/// A source that remembers what was read from it.
+struct Recorder {
+    source: Box<dyn Source + Send>,
+    bytes: Cell<usize>,
+    /// The extensions of the files read, with the hash of their content if
+    /// `hash_content` is set.
+    #[cfg(feature = "hot-reloading")]
+    extensions: RefCell<Vec<(SharedString, Option<u64>)>>,
+    #[cfg(feature = "hot-reloading")]
+    hash_content: bool,
+}
+
+impl Source for Recorder {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let result = self.source.read(id, ext);
+
+        #[cfg(feature = "hot-reloading")]
+        {
+            let hash = match &result {
+                Ok(content) if self.hash_content => Some(
+                    crate::hot_reloading::records::content_hash(content.as_ref()),
+                ),
+                _ => None,
+            };
+            self.extensions.borrow_mut().push((ext.into(), hash));
+        }
+
+        let content = result?;
+        self.bytes.set(self.bytes.get() + content.as_ref().len());
+        Ok(content)
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        self.source.read_dir(id, f)
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        self.source.exists(entry)
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.source.file_size(id, ext)
+    }
+}
+
+/// An asset loaded by a worker thread.
+struct Loaded<T> {
+    value: T,
+    size: usize,
+    #[cfg(feature = "hot-reloading")]
+    extensions: Vec<(SharedString, Option<u64>)>,
+}
+
+struct Slot<T> {
+    result: Mutex<Option<Result<Loaded<T>, BoxedError>>>,
+    cond: Condvar,
+}
+
+/// Fills a `Slot`, even if the loading thread panics.
+struct Completer<T>(Arc<Slot<T>>);
+
+impl<T> Completer<T> {
+    fn complete(&self, result: Result<Loaded<T>, BoxedError>) {
+        *self.0.result.lock() = Some(result);
+        self.0.cond.notify_all();
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        let mut result = self.0.result.lock();
+        if result.is_none() {
+            *result = Some(Err("the loading thread panicked".into()));
+            drop(result);
+            self.0.cond.notify_all();
+        }
+    }
+}
+
+enum State<'a, T> {
+    Done(Result<&'a Handle<T>, Error>),
+    Loading(Arc<Slot<T>>),
+}
+
+/// An asset being loaded in the background.
+///
+/// This is returned by [`AssetCache::load_deferred`]. The asset is added to
+/// the cache when [`wait`](Self::wait) is called.
+pub struct PendingHandle<'a, T> {
+    cache: AnyCache<'a>,
+    id: SharedString,
+    state: State<'a, T>,
+}
+
+impl<'a, T: Asset> PendingHandle<'a, T> {
+    pub(crate) fn ready(
+        cache: AnyCache<'a>,
+        id: &str,
+        result: Result<&'a Handle<T>, Error>,
+    ) -> Self {
+        Self {
+            cache,
+            id: id.into(),
+            state: State::Done(result),
+        }
+    }
+
+    /// Loads the asset with a new worker source, or on the current thread if
+    /// the pool cannot take it.
+    pub(crate) fn spawn(
+        cache: AnyCache<'a>,
+        pool: &WorkerPool,
+        source: Box<dyn Source + Send>,
+        id: &str,
+    ) -> Self {
+        let id = SharedString::from(id);
+        let slot = Arc::new(Slot {
+            result: Mutex::new(None),
+            cond: Condvar::new(),
+        });
+
+        #[cfg(feature = "hot-reloading")]
+        let hash_content = cache.reloader().is_some_and(|r| r.hashes_content());
+
+        let completer = Completer(slot.clone());
+        let job_id = id.clone();
+        let job = Box::new(move || {
+            let recorder = Recorder {
+                source,
+                bytes: Cell::new(0),
+                #[cfg(feature = "hot-reloading")]
+                extensions: RefCell::new(Vec::new()),
+                #[cfg(feature = "hot-reloading")]
+                hash_content,
+            };
+            let result = load_from_source::<T>(&recorder, &job_id).map(|value| Loaded {
+                value,
+                size: recorder.bytes.get(),
+                #[cfg(feature = "hot-reloading")]
+                extensions: recorder.extensions.into_inner(),
+            });
+            completer.complete(result);
+        });
+
+        let state = match pool.spawn(job) {
+            Ok(()) => State::Loading(slot),
+            Err(_) => State::Done(cache.load(&id)),
+        };
+
+        Self { cache, id, state }
+    }
+
+    /// Returns the id of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// Returns `true` if the asset finished loading, so [`wait`](Self::wait)
+    /// will not block.
+    pub fn is_ready(&self) -> bool {
+        match &self.state {
+            State::Done(_) => true,
+            State::Loading(slot) => slot.result.lock().is_some(),
+        }
+    }
+
+    /// Blocks until the asset is loaded, and returns a handle to it.
+    ///
+    /// If the asset was added to the cache in the meantime, that asset is
+    /// returned instead.
+    pub fn wait(self) -> Result<&'a Handle<T>, Error> {
+        let slot = match self.state {
+            State::Done(result) => return result,
+            State::Loading(slot) => slot,
+        };
+
+        let result = {
+            let guard = slot.result.lock();
+            slot.cond.wait_while(guard, |r| r.is_none()).take()
+        };
+
+        if let Some(handle) = self.cache.get_cached::<T>(&self.id) {
+            return Ok(handle);
+        }
+
+        match result {
+            Some(Ok(loaded)) => Ok(insert(self.cache, self.id, loaded)),
+            Some(Err(err)) => Err(Error::with_type(self.id, std::any::type_name::<T>(), err)),
+            None => unreachable!(),
+        }
+    }
+}
+
+/// Adds an asset loaded by a worker thread to the cache.
+fn insert<'a, T: Asset>(cache: AnyCache<'a>, id: SharedString, loaded: Loaded<T>) -> &'a Handle<T> {
+    #[cfg(feature = "hot-reloading")]
+    if T::HOT_RELOADED {
+        if let Some(reloader) = cache.reloader() {
+            use crate::hot_reloading::records;
+
+            let ((), deps, hashes) = records::record(reloader, || {
+                for (ext, hash) in &loaded.extensions {
+                    records::add_file_record(reloader, &id, ext);
+                    if let Some(hash) = *hash {
+                        records::add_file_hash(reloader, &id, ext, hash);
+                    }
+                }
+            });
+            let typ = crate::key::Type::of_asset::<T>();
+            reloader.add_asset(id.clone(), deps, hashes, typ);
+        }
+    }
+
+    let mut entry = CacheEntry::new(loaded.value, id, cache);
+    entry.set_loaded_size(loaded.size);
+    cache.insert_entry(entry).downcast_ref_ok()
+}
+
+impl<T> fmt::Debug for PendingHandle<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingHandle")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
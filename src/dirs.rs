@@ -147,6 +147,8 @@ where
 }
 
 /// Stores ids in a directory containing assets of type `T`
+///
+/// Ids are sorted lexicographically and deduplicated.
 pub struct Directory<T> {
     ids: Vec<SharedString>,
     _marker: PhantomData<T>,
@@ -222,6 +224,9 @@ impl<T> fmt::Debug for Directory<T> {
 }
 
 /// Stores ids in a recursive directory containing assets of type `T`
+///
+/// Ids are sorted lexicographically, whatever the order in which the source
+/// yields directories.
 pub struct RecursiveDirectory<T> {
     ids: Vec<SharedString>,
     _marker: PhantomData<T>,
@@ -243,6 +248,9 @@ where
             }
         })?;
 
+        // Subdirectories are yielded in an order that depends on the source
+        ids.sort_unstable();
+
         Ok(RecursiveDirectory {
             ids,
             _marker: PhantomData,
@@ -302,3 +310,256 @@ impl<T> fmt::Debug for RecursiveDirectory<T> {
             .finish()
     }
 }
+
+/// Selects the assets included in a [`FilteredDir`].
+///
+/// This is implemented on marker types, so that filtered directories can be
+/// stored in the cache like other assets.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{Asset, AssetCache, DirFilter, FilteredDir, loader};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Monster {
+///     name: String,
+///     description: String,
+///     health: u32,
+/// }
+///
+/// impl Asset for Monster {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = loader::RonLoader;
+/// }
+///
+/// /// Only keep goblins.
+/// struct Goblins;
+///
+/// impl DirFilter for Goblins {
+///     fn includes(id: &str) -> bool {
+///         id.ends_with(".goblin")
+///     }
+/// }
+///
+/// let cache = AssetCache::new("assets")?;
+/// let dir = cache.load::<FilteredDir<Monster, Goblins>>("example.monsters")?;
+///
+/// let ids: Vec<_> = dir.read().ids().map(|id| id.to_string()).collect();
+/// assert_eq!(ids, ["example.monsters.goblin"]);
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub trait DirFilter: Send + Sync + 'static {
+    /// Extensions of the files to select, instead of the ones given by
+    /// [`DirLoadable::select_ids`].
+    ///
+    /// By default, the ids of the directory are selected like with
+    /// [`Directory`].
+    const EXTENSIONS: Option<&'static [&'static str]> = None;
+
+    /// Returns `true` if the asset with the given id is included.
+    fn includes(id: &str) -> bool;
+
+    /// Compares two ids to order the directory.
+    ///
+    /// Ids are sorted lexicographically by default. The sort is stable, so
+    /// ids that compare equal keep this order.
+    #[inline]
+    fn compare(a: &str, b: &str) -> std::cmp::Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Stores the ids of a directory containing assets of type `T`, filtered by
+/// `F`.
+///
+/// This is like a [`Directory`], but only ids accepted by [`DirFilter`] are
+/// kept, in the order given by [`DirFilter::compare`]. When hot-reloading, the
+/// directory is read again, filtered and sorted when files are added or
+/// removed.
+pub struct FilteredDir<T, F> {
+    ids: Vec<SharedString>,
+    _marker: PhantomData<(T, F)>,
+}
+
+impl<T, F> Compound for FilteredDir<T, F>
+where
+    T: DirLoadable,
+    F: DirFilter,
+{
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let mut ids = match F::EXTENSIONS {
+            Some(extensions) => {
+                let mut ids = Vec::new();
+                cache.raw_source().read_dir(id, &mut |entry| {
+                    if let DirEntry::File(id, ext) = entry {
+                        if extensions.contains(&ext) {
+                            ids.push(id.into());
+                        }
+                    }
+                })?;
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            }
+            None => cache.load::<Directory<T>>(id)?.read().ids.clone(),
+        };
+
+        ids.retain(|id| F::includes(id));
+        ids.sort_by(|a, b| F::compare(a, b));
+
+        Ok(FilteredDir {
+            ids,
+            _marker: PhantomData,
+        })
+    }
+
+    const HOT_RELOADED: bool = true;
+}
+
+impl<T, F> FilteredDir<T, F> {
+    /// Returns an iterator over the ids of the assets in the directory.
+    pub fn ids(&self) -> impl ExactSizeIterator<Item = &SharedString> {
+        self.ids.iter()
+    }
+}
+
+impl<T, F> FilteredDir<T, F>
+where
+    T: Storable,
+{
+    /// Returns an iterator over the assets in the directory.
+    ///
+    /// This fonction does not do any I/O and assets that previously failed to
+    /// load are ignored.
+    #[inline]
+    pub fn iter_cached<'h, 'a: 'h>(
+        &'h self,
+        cache: impl crate::AsAnyCache<'a>,
+    ) -> impl Iterator<Item = &'a Handle<T>> + 'h {
+        let cache = cache.as_any_cache();
+        self.ids().filter_map(move |id| cache.get_cached(id))
+    }
+}
+
+impl<T, F> FilteredDir<T, F>
+where
+    T: Compound,
+{
+    /// Returns an iterator over the assets in the directory.
+    ///
+    /// This function will happily try to load all assets, even if an error
+    /// occured the last time it was tried.
+    #[inline]
+    pub fn iter<'h, 'a: 'h>(
+        &'h self,
+        cache: impl crate::AsAnyCache<'a>,
+    ) -> impl ExactSizeIterator<Item = Result<&'a Handle<T>, Error>> + 'h {
+        let cache = cache.as_any_cache();
+        self.ids().map(move |id| cache.load(id))
+    }
+}
+
+impl<T, F> fmt::Debug for FilteredDir<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilteredDir")
+            .field("ids", &self.ids)
+            .finish()
+    }
+}
+
+/// The assets of a directory, loaded eagerly.
+///
+/// This is returned by [`AnyCache::load_dir_values`]. Unlike [`Directory`],
+/// the cache is not needed to access the assets, and they can be looked up by
+/// name, which is the last segment of their id.
+pub struct DirValues<'a, T> {
+    handles: Vec<&'a Handle<T>>,
+}
+
+impl<'a, T> DirValues<'a, T> {
+    /// `handles` must be sorted by id and come from the same directory.
+    pub(crate) fn new(handles: Vec<&'a Handle<T>>) -> Self {
+        Self { handles }
+    }
+
+    /// Returns an iterator over the assets, sorted by id.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a Handle<T>> + '_ {
+        self.handles.iter().copied()
+    }
+
+    /// Returns an iterator over the names of the assets and the assets,
+    /// sorted by name.
+    #[inline]
+    pub fn iter_named(&self) -> impl ExactSizeIterator<Item = (&'a str, &'a Handle<T>)> + '_ {
+        self.handles
+            .iter()
+            .map(|&handle| (name_of(handle.id()), handle))
+    }
+
+    /// Gets an asset by its name, the last segment of its id.
+    ///
+    /// For example, `"goblin"` is the name of `"example.monsters.goblin"`.
+    pub fn get(&self, name: &str) -> Option<&'a Handle<T>> {
+        // All ids have the same prefix, so they are also sorted by name.
+        let index = self
+            .handles
+            .binary_search_by(|handle| name_of(handle.id()).cmp(name))
+            .ok()?;
+        Some(self.handles[index])
+    }
+
+    /// Returns `true` if the directory contains an asset with the given name.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// The number of assets.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if the directory contains no asset.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+impl<'s, 'a, T> IntoIterator for &'s DirValues<'a, T> {
+    type Item = &'a Handle<T>;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'s, &'a Handle<T>>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.handles.iter().copied()
+    }
+}
+
+impl<T> Clone for DirValues<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            handles: self.handles.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for DirValues<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids: Vec<_> = self.handles.iter().map(|h| h.id()).collect();
+        f.debug_struct("DirValues").field("ids", &ids).finish()
+    }
+}
+
+fn name_of(id: &str) -> &str {
+    match id.rfind('.') {
+        Some(n) => &id[n + 1..],
+        None => id,
+    }
+}
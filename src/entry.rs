@@ -1,17 +1,64 @@
 //! Definitions of cache entries
 
-use crate::{asset::Storable, utils::RwLock, Compound, SharedString};
+use crate::{
+    asset::Storable,
+    utils::{Mutex, RwLock},
+    AnyCache, Compound, LoadFailure, SharedString,
+};
 use std::{
     any::{Any, TypeId},
-    cell::UnsafeCell,
+    cell::{Cell, UnsafeCell},
     fmt,
     marker::PhantomData,
     ops::Deref,
-    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 #[cfg(feature = "hot-reloading")]
-use crate::utils::RwLockReadGuard;
+use crate::{
+    hot_reloading::AssetKey,
+    utils::{Condvar, RwLockReadGuard},
+    Error,
+};
+#[cfg(feature = "hot-reloading")]
+use std::cell::RefCell;
+
+/// Used to wake up threads waiting for an asset to be reloaded.
+///
+/// This is shared by all entries of a cache, as reloads are rare enough that
+/// spurious wake ups are cheaper than a condvar per entry.
+#[cfg(feature = "hot-reloading")]
+pub(crate) struct Reloads {
+    lock: Mutex<()>,
+    cond: Condvar,
+}
+
+#[cfg(feature = "hot-reloading")]
+impl Reloads {
+    pub(crate) const fn new() -> Self {
+        Self {
+            lock: Mutex::new(()),
+            cond: Condvar::new(),
+        }
+    }
+
+    fn notify(&self) {
+        let _guard = self.lock.lock();
+        self.cond.notify_all();
+    }
+
+    fn wait(&self, reload_id: &AtomicReloadId, last: ReloadId, timeout: Duration) -> bool {
+        let guard = self.lock.lock();
+        let (_guard, timed_out) = self
+            .cond
+            .wait_timeout_while(guard, timeout, |_| reload_id.load() <= last);
+        !timed_out
+    }
+}
 
 #[cfg(feature = "hot-reloading")]
 unsafe fn swap_any(a: &mut dyn Any, b: &mut dyn Any) {
@@ -31,16 +78,134 @@ unsafe fn swap_any(a: &mut dyn Any, b: &mut dyn Any) {
     }
 }
 
+thread_local! {
+    /// The number of bytes read from the source by the asset being loaded.
+    static READ_BYTES: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Runs `f` and returns the number of bytes read from the source meanwhile.
+///
+/// Bytes read by nested loads are not counted.
+pub(crate) fn measure_reads<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    struct Restore(Option<usize>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            READ_BYTES.with(|b| b.set(self.0));
+        }
+    }
+
+    let _restore = Restore(READ_BYTES.with(|b| b.replace(Some(0))));
+    let result = f();
+    let size = READ_BYTES.with(|b| b.get()).unwrap_or(0);
+    (result, size)
+}
+
+/// Records that bytes were read from the source.
+#[inline]
+pub(crate) fn add_read_bytes(len: usize) {
+    READ_BYTES.with(|b| {
+        if let Some(n) = b.get() {
+            b.set(Some(n + len));
+        }
+    });
+}
+
+#[cfg(feature = "hot-reloading")]
+thread_local! {
+    /// The versions of the assets used by the asset being loaded.
+    static DEPENDENCY_IDS: RefCell<Option<Vec<(AssetKey, ReloadId)>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns the `ReloadId`s of the assets it used.
+///
+/// Assets used by nested loads are not recorded.
+#[cfg(feature = "hot-reloading")]
+pub(crate) fn record_dependency_ids<T>(f: impl FnOnce() -> T) -> (T, Vec<(AssetKey, ReloadId)>) {
+    struct Restore(Option<Vec<(AssetKey, ReloadId)>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DEPENDENCY_IDS.with(|ids| *ids.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(DEPENDENCY_IDS.with(|ids| ids.replace(Some(Vec::new()))));
+    let result = f();
+    let ids = DEPENDENCY_IDS
+        .with(|ids| ids.borrow_mut().take())
+        .unwrap_or_default();
+    (result, ids)
+}
+
+/// Records that an asset was used by the asset being loaded.
+#[cfg(feature = "hot-reloading")]
+pub(crate) fn add_dependency_id(handle: &UntypedHandle) {
+    DEPENDENCY_IDS.with(|ids| {
+        if let Some(ids) = &mut *ids.borrow_mut() {
+            let reload_id = handle.last_reload_id();
+            let inner = &handle.inner;
+            match ids
+                .iter_mut()
+                .find(|(key, _)| key.type_id == inner.type_id && key.id == inner.id)
+            {
+                Some((_, id)) => *id = reload_id,
+                None => ids.push((
+                    AssetKey::new_with(inner.id.clone(), inner.type_id),
+                    reload_id,
+                )),
+            }
+        }
+    });
+}
+
+/// Informations used to evict entries from the cache.
+struct Usage {
+    /// `true` if the entry was loaded from the source, and can thus be loaded
+    /// again.
+    loaded: bool,
+    size: AtomicUsize,
+    last_access: AtomicUsize,
+}
+
+impl Usage {
+    const fn new() -> Self {
+        Self {
+            loaded: false,
+            size: AtomicUsize::new(0),
+            last_access: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Returns a new unique identifier for an entry.
+fn next_uid() -> u64 {
+    static NEXT_UID: AtomicU64 = AtomicU64::new(0);
+    NEXT_UID.fetch_add(1, Ordering::Relaxed)
+}
+
 #[allow(dead_code)]
 pub(crate) struct Dynamic {
     lock: RwLock<()>,
     reload_global: AtomicBool,
     reload: AtomicReloadId,
+    #[cfg(feature = "hot-reloading")]
+    dependency_ids: Mutex<Vec<(AssetKey, ReloadId)>>,
+    reload_error: Mutex<Option<LoadFailure>>,
+    #[cfg(feature = "hot-reloading")]
+    reloads: Arc<Reloads>,
 }
 
+/// A shared copy of the value of an entry, and the version it was taken from.
+type Snapshot = Option<(ReloadId, Arc<dyn Any + Send + Sync>)>;
+
 struct EntryStorage<T: ?Sized> {
     id: SharedString,
     type_id: TypeId,
+    type_name: &'static str,
+    uid: u64,
+    usage: Usage,
+    snapshot: Mutex<Snapshot>,
     #[cfg(feature = "hot-reloading")]
     dynamic: Option<Dynamic>,
     value: UnsafeCell<T>,
@@ -56,6 +221,10 @@ impl<T: Storable> Entry<T> {
         Self {
             id,
             type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            uid: next_uid(),
+            usage: Usage::new(),
+            snapshot: Mutex::new(None),
             #[cfg(feature = "hot-reloading")]
             dynamic: None,
             value: UnsafeCell::new(value),
@@ -63,14 +232,21 @@ impl<T: Storable> Entry<T> {
     }
 
     #[cfg(feature = "hot-reloading")]
-    fn new_dynamic(id: SharedString, value: T) -> Self {
+    fn new_dynamic(id: SharedString, value: T, reloads: Arc<Reloads>) -> Self {
         Self {
             id,
             type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            uid: next_uid(),
+            usage: Usage::new(),
+            snapshot: Mutex::new(None),
             dynamic: Some(Dynamic {
                 lock: RwLock::new(()),
                 reload_global: AtomicBool::new(false),
                 reload: AtomicReloadId::new(),
+                dependency_ids: Mutex::new(Vec::new()),
+                reload_error: Mutex::new(None),
+                reloads,
             }),
             value: UnsafeCell::new(value),
         }
@@ -98,18 +274,90 @@ impl<T: ?Sized> EntryStorage<T> {
     }
 }
 
+/// Writes new values to several entries at once.
+///
+/// Locks of all entries are held while values are swapped, so readers either
+/// see all old values or all new ones.
+#[cfg(feature = "hot-reloading")]
+pub(crate) fn write_all(writes: Vec<(&UntypedHandle, CacheEntry)>) {
+    let mut writes: Vec<_> = writes
+        .into_iter()
+        .map(|(handle, value)| {
+            let entry = &handle.inner;
+            assert!(entry.type_id == value.0.type_id);
+            match &entry.dynamic {
+                Some(d) => (entry, d, value),
+                None => wrong_handle_type(),
+            }
+        })
+        .collect();
+
+    // Locks are always taken in the same order, so that concurrent
+    // transactions cannot wait for each other.
+    writes.sort_by_key(|(entry, _, _)| entry.uid);
+    let guards: Vec<_> = writes.iter().map(|(_, d, _)| d.lock.write()).collect();
+
+    for (entry, d, value) in &mut writes {
+        let size = value.0.usage.size.load(Ordering::Relaxed);
+        entry.usage.size.store(size, Ordering::Relaxed);
+        unsafe {
+            swap_any(&mut *entry.value.get(), value.0.value.get_mut());
+        }
+        *d.reload_error.lock() = None;
+        d.reload.increment();
+        d.reload_global.store(true, Ordering::Release);
+    }
+
+    // Values were loaded with the new values of entries written together, so
+    // they depend on their new `ReloadId`s.
+    let all_ids: Vec<_> = writes
+        .iter_mut()
+        .map(|(_, _, value)| match &mut value.0.dynamic {
+            Some(new) => std::mem::take(new.dependency_ids.get_mut()),
+            None => Vec::new(),
+        })
+        .collect();
+    for ((_, d, _), mut ids) in writes.iter().zip(all_ids) {
+        for (key, reload_id) in &mut ids {
+            let written = writes
+                .iter()
+                .find(|(e, _, _)| e.type_id == key.type_id && e.id == key.id);
+            if let Some((_, dep, _)) = written {
+                *reload_id = dep.reload.load();
+            }
+        }
+        *d.dependency_ids.lock() = ids;
+    }
+
+    drop(guards);
+    // All entries belong to the same cache, so they share their `Reloads`
+    if let Some((_, d, _)) = writes.first() {
+        d.reloads.notify();
+    }
+}
+
 impl UntypedEntry {
     #[cfg(feature = "hot-reloading")]
     pub fn write(&self, mut value: CacheEntry) {
         assert!(self.type_id == value.0.type_id);
 
         if let Some(d) = &self.dynamic {
+            let size = value.0.usage.size.load(Ordering::Relaxed);
+            self.usage.size.store(size, Ordering::Relaxed);
+            let dependency_ids = match &mut value.0.dynamic {
+                Some(new) => std::mem::take(new.dependency_ids.get_mut()),
+                None => Vec::new(),
+            };
+
             unsafe {
                 let _g = d.lock.write();
                 swap_any(&mut *self.value.get(), value.0.value.get_mut());
+                *d.dependency_ids.lock() = dependency_ids;
+                *d.reload_error.lock() = None;
                 d.reload.increment();
                 d.reload_global.store(true, Ordering::Release);
             }
+            d.reloads.notify();
             return;
         }
 
@@ -148,29 +396,52 @@ impl CacheEntry {
     ///
     /// The returned structure can safely use its methods with type parameter `T`.
     #[inline]
-    pub fn new<T: Compound>(asset: T, id: SharedString, mutable: impl FnOnce() -> bool) -> Self {
-        Self::new_any(asset, id, T::HOT_RELOADED && mutable())
+    pub fn new<T: Compound>(asset: T, id: SharedString, cache: AnyCache) -> Self {
+        Self::new_any(asset, id, T::HOT_RELOADED, cache)
     }
 
     /// Creates a new `CacheEntry` containing a value of type `T`.
     ///
     /// The returned structure can safely use its methods with type parameter `T`.
     #[inline]
-    pub fn new_any<T: Storable>(value: T, id: SharedString, _mutable: bool) -> Self {
+    pub fn new_any<T: Storable>(
+        value: T,
+        id: SharedString,
+        _hot_reloaded: bool,
+        _cache: AnyCache,
+    ) -> Self {
         #[cfg(not(feature = "hot-reloading"))]
         let inner = EntryStorage::new_static(id, value);
 
         // Even if hot-reloading is enabled, we can avoid the lock in some cases.
         #[cfg(feature = "hot-reloading")]
-        let inner = if _mutable {
-            EntryStorage::new_dynamic(id, value)
-        } else {
-            EntryStorage::new_static(id, value)
+        let inner = match _cache.reloader() {
+            Some(reloader) if _hot_reloaded => {
+                EntryStorage::new_dynamic(id, value, reloader.reloads().clone())
+            }
+            _ => EntryStorage::new_static(id, value),
         };
 
         CacheEntry(Box::new(inner))
     }
 
+    /// Marks the entry as loaded from the source, with the given
+    /// approximate size in bytes.
+    #[inline]
+    pub(crate) fn set_loaded_size(&mut self, size: usize) {
+        self.0.usage.loaded = true;
+        *self.0.usage.size.get_mut() = size;
+    }
+
+    /// Sets the versions of the assets used to load the entry.
+    #[cfg(feature = "hot-reloading")]
+    #[inline]
+    pub(crate) fn set_dependency_ids(&mut self, ids: Vec<(AssetKey, ReloadId)>) {
+        if let Some(d) = &mut self.0.dynamic {
+            *d.dependency_ids.get_mut() = ids;
+        }
+    }
+
     #[inline]
     pub(crate) fn as_key(&self) -> (TypeId, &str) {
         (self.0.type_id, &self.0.id)
@@ -235,6 +506,21 @@ impl UntypedHandle {
         self.inner.is::<T>()
     }
 
+    /// Returns the `TypeId` of the inner type.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.inner.type_id
+    }
+
+    /// Returns the name of the inner type.
+    ///
+    /// As with [`std::any::type_name`], the returned string is meant for
+    /// diagnostics only and its exact content is not specified.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.inner.type_name
+    }
+
     /// Returns a handle to the asset if it is of type `T`.
     #[inline]
     pub fn downcast_ref<T: Storable>(&self) -> Option<&Handle<T>> {
@@ -255,6 +541,41 @@ impl UntypedHandle {
     pub(crate) fn write(&self, asset: CacheEntry) {
         self.inner.write(asset);
     }
+
+    /// Records a failure to reload the asset, until it is written again.
+    #[cfg(feature = "hot-reloading")]
+    pub(crate) fn record_reload_error(&self, error: &Error) {
+        if let Some(d) = &self.inner.dynamic {
+            let mut last = d.reload_error.lock();
+            match &mut *last {
+                Some(failure) => failure.update(error),
+                None => {
+                    let (id, type_id, type_name) =
+                        (&self.inner.id, self.inner.type_id, self.inner.type_name);
+                    *last = Some(LoadFailure::new(id.clone(), type_id, type_name, error));
+                }
+            }
+        }
+    }
+
+    /// Records an access to the entry at the given epoch.
+    #[inline]
+    pub(crate) fn touch(&self, epoch: usize) {
+        self.inner.usage.last_access.store(epoch, Ordering::Relaxed);
+    }
+
+    /// Returns the size and the epoch of the last access of the entry, or
+    /// `None` if it cannot be evicted.
+    #[inline]
+    pub(crate) fn eviction_infos(&self) -> Option<(usize, usize)> {
+        let usage = &self.inner.usage;
+        usage.loaded.then(|| {
+            (
+                usage.size.load(Ordering::Relaxed),
+                usage.last_access.load(Ordering::Relaxed),
+            )
+        })
+    }
 }
 
 /// A handle on an asset.
@@ -317,6 +638,22 @@ impl<T: ?Sized> Handle<T> {
         self.inner.untyped_handle()
     }
 
+    /// Creates a [`WeakHandle`] to the asset.
+    ///
+    /// The returned value does not borrow the cache, and can be upgraded back
+    /// to a handle as long as the asset is not removed from the cache.
+    #[inline]
+    pub fn downgrade(&self) -> WeakHandle<T>
+    where
+        T: Storable,
+    {
+        WeakHandle {
+            id: self.inner.id.clone(),
+            uid: self.inner.uid,
+            _marker: PhantomData,
+        }
+    }
+
     /// Returns a `ReloadWatcher` that can be used to check whether this asset
     /// was reloaded.
     ///
@@ -350,7 +687,7 @@ impl<T: ?Sized> Handle<T> {
     /// ```
     #[inline]
     pub fn reload_watcher(&self) -> ReloadWatcher<'_> {
-        ReloadWatcher::new(self.either(|| None, |d| Some(&d.reload)))
+        ReloadWatcher::new(self.either(|| None, Some))
     }
 
     /// Returns the last `ReloadId` associated with this asset.
@@ -362,6 +699,86 @@ impl<T: ?Sized> Handle<T> {
         self.either(|| ReloadId::NEVER, |this| this.reload.load())
     }
 
+    /// Returns the assets that were used to load this asset, with their
+    /// [`ReloadId`] at that time.
+    ///
+    /// Assets are reloaded one after the other, so a compound may be reloaded
+    /// before or after the assets it depends on. Comparing these `ReloadId`s
+    /// with the current [`last_reload_id`](Self::last_reload_id) of each
+    /// dependency tells whether the asset is up to date with its inputs.
+    ///
+    /// The returned list matches the value seen through a guard returned by
+    /// [`read`](Self::read) taken before calling this function. It is empty
+    /// if hot-reloading is disabled for this asset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "hot-reloading")] {
+    /// use assets_manager::{AnyCache, AssetCache, BoxedError, Compound, SharedString};
+    ///
+    /// struct Names(String);
+    ///
+    /// impl Compound for Names {
+    ///     fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+    ///         Ok(Names(cache.load::<String>(id)?.read().clone()))
+    ///     }
+    /// }
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// let names = cache.load::<Names>("common.name")?;
+    /// let name = cache.load::<String>("common.name")?;
+    ///
+    /// let _guard = names.read();
+    /// let up_to_date = names.dependency_ids().iter().all(|(key, reload_id)| {
+    ///     key.id() != name.id() || *reload_id == name.last_reload_id()
+    /// });
+    /// assert!(up_to_date);
+    /// # }}
+    /// # Ok::<(), BoxedError>(())
+    /// ```
+    #[cfg(feature = "hot-reloading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+    pub fn dependency_ids(&self) -> Vec<(AssetKey, ReloadId)> {
+        self.either(Vec::new, |d| d.dependency_ids.lock().clone())
+    }
+
+    /// Returns the error of the last failed reload of this asset, if it was
+    /// not successfully reloaded since.
+    ///
+    /// When reloading fails, the previous value of the asset is kept, so this
+    /// can be used to tell that the value is stale, eg to show the error in
+    /// an in-game console. [`LoadFailure::attempts`] counts the consecutive
+    /// failed reloads.
+    ///
+    /// See also [`AssetCache::on_reload_error`] to be notified of these
+    /// errors.
+    ///
+    /// [`AssetCache::on_reload_error`]: crate::AssetCache::on_reload_error
+    pub fn last_reload_error(&self) -> Option<LoadFailure> {
+        self.either(|| None, |d| d.reload_error.lock().clone())
+    }
+
+    /// Blocks the current thread until the asset is reloaded, or until the
+    /// timeout elapses.
+    ///
+    /// Returns `true` if the asset was reloaded after this function was
+    /// called. If hot-reloading is disabled for this asset, this function
+    /// returns `false` immediately.
+    ///
+    /// Note that unless [`AssetCache::enhance_hot_reloading`] was called,
+    /// assets are only reloaded during calls to [`AssetCache::hot_reload`],
+    /// so this must be called from another thread.
+    ///
+    /// To avoid missing reloads that happen before this function is called,
+    /// use [`ReloadWatcher::wait_for_reload`].
+    ///
+    /// [`AssetCache::enhance_hot_reloading`]: crate::AssetCache::enhance_hot_reloading
+    /// [`AssetCache::hot_reload`]: crate::AssetCache::hot_reload
+    pub fn wait_for_reload(&self, timeout: Duration) -> bool {
+        self.reload_watcher().wait_for_reload(timeout)
+    }
+
     /// Returns `true` if the asset has been reloaded since last call to this
     /// method with **any** handle on this asset.
     ///
@@ -379,6 +796,87 @@ impl<T: ?Sized> Handle<T> {
     }
 }
 
+impl<T: ?Sized> Handle<T> {
+    /// Returns a handle to a part of the asset, such as a field or an element
+    /// of a collection.
+    ///
+    /// The projection is applied each time the returned handle is read, so it
+    /// always sees the last version of the asset, even after it is reloaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// let handle = cache.load::<String>("example.hello")?;
+    ///
+    /// let first_word = handle.map_ref(|s| s.split_whitespace().next().unwrap());
+    /// assert_eq!(&*first_word.read(), "Hello");
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    #[inline]
+    pub fn map_ref<U: ?Sized, F>(&self, project: F) -> ProjectedHandle<'_, T, U, F>
+    where
+        F: Fn(&T) -> &U,
+    {
+        ProjectedHandle {
+            handle: self,
+            project,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Handle<T>
+where
+    T: Storable + Clone,
+{
+    /// Returns a shared copy of the current value of the asset.
+    ///
+    /// Unlike a guard returned by [`read`](Self::read), the returned value
+    /// does not prevent the asset from being reloaded, so it can be kept for
+    /// as long as needed, for example during a whole frame. It is not updated
+    /// when the asset is reloaded.
+    ///
+    /// The copy is made on the first call after each reload, and shared by
+    /// following calls, so this costs an allocation per reload only for assets
+    /// that are actually snapshotted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    /// use std::sync::Arc;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// let handle = cache.load::<String>("example.hello")?;
+    ///
+    /// let snapshot = handle.snapshot();
+    /// assert_eq!(snapshot.trim(), "Hello !");
+    /// assert!(Arc::ptr_eq(&snapshot, &handle.snapshot()));
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn snapshot(&self) -> Arc<T> {
+        // The reload id is read with the guard held, so it matches the value.
+        let guard = self.read();
+        let reload_id = self.last_reload_id();
+
+        let mut snapshot = self.inner.snapshot.lock();
+        if let Some((id, value)) = &*snapshot {
+            if *id == reload_id {
+                if let Ok(value) = value.clone().downcast() {
+                    return value;
+                }
+            }
+        }
+
+        let value = Arc::new(T::clone(&guard));
+        *snapshot = Some((reload_id, value.clone()));
+        value
+    }
+}
+
 impl<T> Handle<T>
 where
     T: Copy,
@@ -428,6 +926,204 @@ where
     }
 }
 
+/// Displays the id of the asset.
+impl<T: ?Sized> fmt::Display for Handle<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.id(), f)
+    }
+}
+
+impl<T> Handle<T>
+where
+    T: fmt::Debug + ?Sized,
+{
+    /// Returns a value that pretty-prints the id and the value of the asset.
+    ///
+    /// The asset is locked only while it is being formatted, so the returned
+    /// value can be kept around, eg in a logging macro.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// let handle = cache.load::<String>("example.hello")?;
+    ///
+    /// assert_eq!(handle.to_string(), "example.hello");
+    /// assert_eq!(handle.debug_value().to_string(), "\"example.hello\": \"Hello !\\n\"");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn debug_value(&self) -> impl fmt::Display + fmt::Debug + '_ {
+        DebugValue(self)
+    }
+}
+
+struct DebugValue<'a, T: ?Sized>(&'a Handle<T>);
+
+impl<T: fmt::Debug + ?Sized> fmt::Display for DebugValue<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\": {:#?}", self.0.id(), &*self.0.read())
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for DebugValue<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// A handle to a part of an asset.
+///
+/// This is obtained with [`Handle::map_ref`]. This type borrows the handle of
+/// the whole asset, and applies the projection each time it is read, so it
+/// follows reloads of the asset.
+///
+/// The projection is expected to be cheap, as it is applied on each call to
+/// [`read`](Self::read).
+pub struct ProjectedHandle<'a, T: ?Sized, U: ?Sized, F> {
+    handle: &'a Handle<T>,
+    project: F,
+    _marker: PhantomData<fn(&T) -> &U>,
+}
+
+impl<'a, T: ?Sized, U: ?Sized, F> ProjectedHandle<'a, T, U, F>
+where
+    F: Fn(&T) -> &U,
+{
+    /// Locks the pointed asset for reading, and returns a guard to the
+    /// projected value.
+    ///
+    /// See [`Handle::read`] for more details.
+    #[inline]
+    pub fn read(&self) -> AssetReadGuard<'a, U> {
+        AssetReadGuard::map(self.handle.read(), &self.project)
+    }
+
+    /// Returns the handle of the whole asset.
+    #[inline]
+    pub fn handle(&self) -> &'a Handle<T> {
+        self.handle
+    }
+
+    /// Returns the id of the asset.
+    #[inline]
+    pub fn id(&self) -> &'a SharedString {
+        self.handle.id()
+    }
+
+    /// Returns a `ReloadWatcher` that can be used to check whether the asset
+    /// was reloaded.
+    ///
+    /// See [`Handle::reload_watcher`] for more details.
+    #[inline]
+    pub fn reload_watcher(&self) -> ReloadWatcher<'a> {
+        self.handle.reload_watcher()
+    }
+
+    /// Returns the last `ReloadId` associated with the asset.
+    ///
+    /// See [`Handle::last_reload_id`] for more details.
+    #[inline]
+    pub fn last_reload_id(&self) -> ReloadId {
+        self.handle.last_reload_id()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, F: Clone> Clone for ProjectedHandle<'_, T, U, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle,
+            project: self.project.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, F: Copy> Copy for ProjectedHandle<'_, T, U, F> {}
+
+impl<T: ?Sized, U, F> fmt::Debug for ProjectedHandle<'_, T, U, F>
+where
+    U: fmt::Debug + ?Sized,
+    F: Fn(&T) -> &U,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProjectedHandle")
+            .field("id", self.id())
+            .field("value", &&*self.read())
+            .finish()
+    }
+}
+
+/// A non-owning reference to an asset in a cache.
+///
+/// This is obtained with [`Handle::downgrade`]. Unlike a [`Handle`], it does
+/// not borrow the cache, so it can be stored anywhere. It can be upgraded
+/// back to a `Handle` with the cache it comes from, as long as the asset was
+/// not removed from it (for example with `AssetCache::remove` or
+/// `AssetCache::evict`). Reloading an asset does not invalidate weak handles
+/// to it, but removing it and loading it again does.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::AssetCache;
+///
+/// let mut cache = AssetCache::new("assets")?;
+///
+/// let weak = cache.load::<String>("example.hello")?.downgrade();
+/// assert!(weak.upgrade(&cache).is_some());
+///
+/// cache.remove::<String>("example.hello");
+/// assert!(weak.upgrade(&cache).is_none());
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct WeakHandle<T> {
+    id: SharedString,
+    uid: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Storable> WeakHandle<T> {
+    /// Returns a handle to the asset if it is still in the cache.
+    ///
+    /// `cache` must be the cache from which the asset was obtained.
+    #[inline]
+    pub fn upgrade<'a>(&self, cache: impl crate::AsAnyCache<'a>) -> Option<&'a Handle<T>> {
+        let handle = cache.as_any_cache().get_cached::<T>(&self.id)?;
+        (handle.inner.uid == self.uid).then_some(handle)
+    }
+
+    /// Returns the id of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+}
+
+impl<T> Clone for WeakHandle<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            uid: self.uid,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakHandle")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
 /// RAII guard used to keep a read lock on an asset and release it when dropped.
 ///
 /// This type is a smart pointer to type `T`.
@@ -531,23 +1227,35 @@ where
 }
 
 #[cfg(feature = "hot-reloading")]
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone, Copy)]
 struct ReloadWatcherInner<'a> {
     reload_id: &'a AtomicReloadId,
+    reloads: &'a Reloads,
     last_reload_id: ReloadId,
 }
 
 #[cfg(feature = "hot-reloading")]
 impl<'a> ReloadWatcherInner<'a> {
     #[inline]
-    fn new(reload_id: &'a AtomicReloadId) -> Self {
+    fn new(dynamic: &'a Dynamic) -> Self {
         Self {
-            reload_id,
-            last_reload_id: reload_id.load(),
+            reload_id: &dynamic.reload,
+            reloads: &dynamic.reloads,
+            last_reload_id: dynamic.reload.load(),
         }
     }
 }
 
+#[cfg(feature = "hot-reloading")]
+impl fmt::Debug for ReloadWatcherInner<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadWatcherInner")
+            .field("reload_id", &self.reload_id)
+            .field("last_reload_id", &self.last_reload_id)
+            .finish()
+    }
+}
+
 /// A watcher that can tell when an asset is reloaded.
 ///
 /// Each `ReloadWatcher` is associated to a single asset in a cache.
@@ -562,9 +1270,9 @@ pub struct ReloadWatcher<'a> {
 
 impl<'a> ReloadWatcher<'a> {
     #[inline]
-    fn new(_reload_id: Option<&'a AtomicReloadId>) -> Self {
+    fn new(_dynamic: Option<&'a Dynamic>) -> Self {
         #[cfg(feature = "hot-reloading")]
-        let inner = _reload_id.map(ReloadWatcherInner::new);
+        let inner = _dynamic.map(ReloadWatcherInner::new);
         Self {
             #[cfg(feature = "hot-reloading")]
             inner,
@@ -585,6 +1293,27 @@ impl<'a> ReloadWatcher<'a> {
         false
     }
 
+    /// Blocks the current thread until the watched asset is reloaded, or
+    /// until the timeout elapses.
+    ///
+    /// Returns `true` if the asset was reloaded since the last call to
+    /// [`reloaded`](Self::reloaded) or to this function, without blocking if
+    /// it already was. If the asset cannot be reloaded, this function returns
+    /// `false` immediately.
+    ///
+    /// See [`Handle::wait_for_reload`] for more details.
+    pub fn wait_for_reload(&mut self, _timeout: Duration) -> bool {
+        #[cfg(feature = "hot-reloading")]
+        if let Some(inner) = &mut self.inner {
+            inner
+                .reloads
+                .wait(inner.reload_id, inner.last_reload_id, _timeout);
+            return self.reloaded();
+        }
+
+        false
+    }
+
     /// Returns the last `ReloadId` associated with this asset.
     #[inline]
     pub fn last_reload_id(&self) -> ReloadId {
@@ -617,7 +1346,7 @@ impl Default for ReloadWatcher<'_> {
 /// [`ReloadWatcher`]. In this case, you may be interested in using an
 /// [`AtomicReloadId`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ReloadId(usize);
+pub struct ReloadId(pub(crate) usize);
 
 impl ReloadId {
     /// A `ReloadId` for values that were never updated.
@@ -710,6 +1439,85 @@ impl Default for AtomicReloadId {
     }
 }
 
+/// Data derived from an asset, recomputed when the asset is reloaded.
+///
+/// This stores a value computed from an asset with the [`ReloadId`] of the
+/// asset at that time, and only recomputes it when the asset is reloaded.
+///
+/// A `ReloadCache` should always be used with the same handle, as `ReloadId`s
+/// of different handles cannot be compared.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, ReloadCache};
+///
+/// # let cache = AssetCache::new("assets")?;
+/// let handle = cache.load::<String>("example.hello")?;
+/// let mut word_count = ReloadCache::new();
+///
+/// // Called each frame, only counts words when the asset changes
+/// let count = *word_count.get_or_recompute(handle, |s| s.split_whitespace().count());
+/// assert_eq!(count, 2);
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct ReloadCache<T: ?Sized, D> {
+    value: Option<(ReloadId, D)>,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized, D> ReloadCache<T, D> {
+    /// Creates a new empty `ReloadCache`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            value: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the derived value, computing it with `f` if the asset was
+    /// reloaded since last computation or if there is no value yet.
+    pub fn get_or_recompute(&mut self, handle: &Handle<T>, f: impl FnOnce(&T) -> D) -> &D {
+        // Load the id before reading the asset: if a reload happens between
+        // both, the value will be recomputed once more next time.
+        let reload_id = handle.last_reload_id();
+
+        let value = match self.value.take() {
+            Some((id, value)) if id == reload_id => value,
+            _ => f(&handle.read()),
+        };
+        &self.value.insert((reload_id, value)).1
+    }
+
+    /// Returns the last computed value, if any, even if it is outdated.
+    #[inline]
+    pub fn get(&self) -> Option<&D> {
+        self.value.as_ref().map(|(_, value)| value)
+    }
+
+    /// Removes the stored value, so that it is recomputed on next access.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.value = None;
+    }
+}
+
+impl<T: ?Sized, D> Default for ReloadCache<T, D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized, D: fmt::Debug> fmt::Debug for ReloadCache<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadCache")
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
 #[cold]
 #[track_caller]
 fn wrong_handle_type() -> ! {
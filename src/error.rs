@@ -6,7 +6,7 @@ use crate::SharedString;
 pub type BoxedError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 #[derive(Debug)]
-pub(crate) enum ErrorKind {
+pub(crate) enum LoadError {
     /// An asset without extension was loaded.
     NoDefaultValue,
 
@@ -18,38 +18,52 @@ pub(crate) enum ErrorKind {
 
     /// The provided ID was invalid.
     InvalidId,
+
+    /// The file is bigger than the maximum allowed size.
+    TooLarge { size: u64, limit: u64 },
+
+    /// The asset was not loaded before the cache was sealed.
+    Sealed,
 }
 
-impl From<io::Error> for ErrorKind {
+impl From<io::Error> for LoadError {
     fn from(err: io::Error) -> Self {
-        Self::Io(err)
+        match err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<TooLargeError>())
+        {
+            Some(&TooLargeError { size, limit }) => Self::TooLarge { size, limit },
+            None => Self::Io(err),
+        }
     }
 }
 
-impl From<BoxedError> for ErrorKind {
+impl From<BoxedError> for LoadError {
     fn from(err: BoxedError) -> Self {
         Self::Conversion(err)
     }
 }
 
-impl From<ErrorKind> for BoxedError {
-    fn from(err: ErrorKind) -> Self {
+impl From<LoadError> for BoxedError {
+    fn from(err: LoadError) -> Self {
         match err {
-            ErrorKind::NoDefaultValue => Box::new(NoDefaultValueError),
-            ErrorKind::Io(err) => Box::new(err),
-            ErrorKind::Conversion(err) => err,
-            ErrorKind::InvalidId => Box::new(InvalidIdError),
+            LoadError::NoDefaultValue => Box::new(NoDefaultValueError),
+            LoadError::Io(err) => Box::new(err),
+            LoadError::Conversion(err) => err,
+            LoadError::InvalidId => Box::new(InvalidIdError),
+            LoadError::TooLarge { size, limit } => Box::new(TooLargeError { size, limit }),
+            LoadError::Sealed => Box::new(SealedError),
         }
     }
 }
 
-impl ErrorKind {
+impl LoadError {
     pub fn or(self, other: Self) -> Self {
-        use ErrorKind::*;
+        use LoadError::*;
 
         match (self, other) {
             (NoDefaultValue, other) => other,
-            (Io(_), other @ Conversion(_)) => other,
+            (Io(_), other @ (Conversion(_) | TooLarge { .. })) => other,
             (Io(err), other @ Io(_)) if err.kind() == io::ErrorKind::NotFound => other,
             (this, _) => this,
         }
@@ -78,8 +92,283 @@ impl fmt::Display for InvalidIdError {
 
 impl std::error::Error for InvalidIdError {}
 
+/// The error returned when a file is bigger than the maximum allowed size.
+///
+/// This error is returned without reading the file when its size is known in
+/// advance. It can be obtained from an [`Error`] with [`Error::downcast`].
+///
+/// See [`Asset::MAX_SIZE`] and [`SizeLimit`].
+///
+/// [`Asset::MAX_SIZE`]: crate::Asset::MAX_SIZE
+/// [`SizeLimit`]: crate::source::SizeLimit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooLargeError {
+    size: u64,
+    limit: u64,
+}
+
+impl TooLargeError {
+    /// Creates a new `TooLargeError`.
+    #[inline]
+    pub fn new(size: u64, limit: u64) -> Self {
+        Self { size, limit }
+    }
+
+    /// The size of the file, in bytes.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The maximum allowed size, in bytes.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns `Err` if `size` is greater than `limit`.
+    pub(crate) fn check(size: u64, limit: u64) -> Result<(), Self> {
+        if size > limit {
+            Err(Self { size, limit })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for TooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "file is too large ({} bytes, the limit is {} bytes)",
+            self.size, self.limit
+        )
+    }
+}
+
+impl std::error::Error for TooLargeError {}
+
+impl From<TooLargeError> for LoadError {
+    fn from(TooLargeError { size, limit }: TooLargeError) -> Self {
+        Self::TooLarge { size, limit }
+    }
+}
+
+impl From<TooLargeError> for io::Error {
+    fn from(err: TooLargeError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// The error returned when loading an asset that is not in a sealed cache.
+///
+/// See [`AssetCache::seal`].
+///
+/// [`AssetCache::seal`]: crate::AssetCache::seal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SealedError;
+
+impl fmt::Display for SealedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the cache is sealed and the asset was not loaded before")
+    }
+}
+
+impl std::error::Error for SealedError {}
+
+/// The error reported when loading an asset panicked during hot-reloading.
+///
+/// When reloading an asset panics, the panic is caught and the previous value
+/// of the asset is kept. The panic is then reported like other reload errors,
+/// with this error as reason.
+///
+/// See [`Handle::last_reload_error`] and [`AssetCache::on_reload_error`].
+///
+/// [`Handle::last_reload_error`]: crate::Handle::last_reload_error
+/// [`AssetCache::on_reload_error`]: crate::AssetCache::on_reload_error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicError {
+    message: Option<String>,
+}
+
+impl PanicError {
+    #[cfg(feature = "hot-reloading")]
+    pub(crate) fn new(payload: &(dyn std::any::Any + Send)) -> Self {
+        let message = if let Some(msg) = payload.downcast_ref::<&'static str>() {
+            Some((*msg).to_owned())
+        } else {
+            payload.downcast_ref::<String>().cloned()
+        };
+        Self { message }
+    }
+
+    /// The message of the panic, if it was a string.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(msg) => write!(f, "the loader panicked: {msg}"),
+            None => f.write_str("the loader panicked"),
+        }
+    }
+}
+
+impl std::error::Error for PanicError {}
+
+/// The kind of an [`Error`].
+///
+/// This is obtained with [`Error::kind`], and enables to know why an asset
+/// failed to load without downcasting its inner error, for example to tell
+/// apart missing and corrupt files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The file of the asset was not found.
+    NotFound,
+
+    /// An I/O error occured while reading the file.
+    Io(io::ErrorKind),
+
+    /// The file was expected to be UTF-8 but it is not.
+    InvalidUtf8,
+
+    /// A value could not be parsed from text, eg with [`ParseLoader`].
+    ///
+    /// [`ParseLoader`]: crate::loader::ParseLoader
+    Parse,
+
+    /// The file could not be deserialized.
+    Deserialize {
+        /// The name of the format, eg `"json"` or `"ron"`.
+        format: &'static str,
+    },
+
+    /// The id of the asset is invalid.
+    InvalidId,
+
+    /// The file is bigger than the maximum allowed size.
+    ///
+    /// See [`TooLargeError`].
+    TooLarge,
+
+    /// The cache is sealed and the asset was not loaded before.
+    ///
+    /// See [`SealedError`].
+    Sealed,
+
+    /// The loader panicked while reloading the asset.
+    ///
+    /// See [`PanicError`].
+    Panic,
+
+    /// Several assets failed to load.
+    ///
+    /// See [`AggregateError`].
+    Multiple,
+
+    /// Another error, returned by a custom loader for example.
+    Other,
+}
+
+impl ErrorKind {
+    /// Returns `true` if the kind is `NotFound`.
+    #[inline]
+    pub fn is_not_found(self) -> bool {
+        self == Self::NotFound
+    }
+
+    fn of_io(err: &io::Error) -> Self {
+        if let Some(inner) = err.get_ref() {
+            let kind = Self::of(inner);
+            if kind != Self::Other {
+                return kind;
+            }
+        }
+
+        match err.kind() {
+            io::ErrorKind::NotFound => Self::NotFound,
+            kind => Self::Io(kind),
+        }
+    }
+
+    /// Returns the name of the format of a deserialization error.
+    #[allow(unused_variables)]
+    fn format_of(err: &(dyn std::error::Error + 'static)) -> Option<&'static str> {
+        macro_rules! formats {
+            ($($feature:literal => $ty:ty => $name:literal,)*) => {$(
+                #[cfg(feature = $feature)]
+                if err.is::<$ty>() {
+                    return Some($name);
+                }
+            )*};
+        }
+
+        formats! {
+            "bincode" => bincode::Error => "bincode",
+            "json" => serde_json::Error => "json",
+            "msgpack" => rmp_serde::decode::Error => "msgpack",
+            "ron" => ron::error::SpannedError => "ron",
+            "ron" => ron::Error => "ron",
+            "toml" => basic_toml::Error => "toml",
+            "yaml" => serde_yaml::Error => "yaml",
+            "image" => image::ImageError => "image",
+            "gltf" => gltf::Error => "gltf",
+        }
+
+        None
+    }
+
+    /// Finds the kind of an error, looking at its sources if it is unknown.
+    fn of(mut err: &(dyn std::error::Error + 'static)) -> Self {
+        loop {
+            let kind = if let Some(err) = err.downcast_ref::<Error>() {
+                Self::of(err.reason())
+            } else if let Some(err) = err.downcast_ref::<io::Error>() {
+                Self::of_io(err)
+            } else if let Some(err) = err.downcast_ref::<crate::failures::PreviousFailure>() {
+                err.kind
+            } else if err.is::<AggregateError>() {
+                Self::Multiple
+            } else if err.is::<std::str::Utf8Error>() || err.is::<std::string::FromUtf8Error>() {
+                Self::InvalidUtf8
+            } else if err.is::<std::num::ParseIntError>()
+                || err.is::<std::num::ParseFloatError>()
+                || err.is::<std::str::ParseBoolError>()
+                || err.is::<std::char::ParseCharError>()
+            {
+                Self::Parse
+            } else if err.is::<TooLargeError>() {
+                Self::TooLarge
+            } else if err.is::<SealedError>() {
+                Self::Sealed
+            } else if err.is::<PanicError>() {
+                Self::Panic
+            } else if err.is::<InvalidIdError>() {
+                Self::InvalidId
+            } else if err.is::<NoDefaultValueError>() {
+                Self::NotFound
+            } else if let Some(format) = Self::format_of(err) {
+                Self::Deserialize { format }
+            } else {
+                Self::Other
+            };
+
+            match err.source() {
+                Some(source) if kind == Self::Other => err = source,
+                _ => return kind,
+            }
+        }
+    }
+}
+
 struct ErrorRepr {
     id: SharedString,
+    type_name: Option<&'static str>,
     error: BoxedError,
 }
 
@@ -89,7 +378,20 @@ pub struct Error(Box<ErrorRepr>);
 impl Error {
     #[cold]
     pub(crate) fn new(id: SharedString, error: BoxedError) -> Self {
-        Self(Box::new(ErrorRepr { id, error }))
+        Self(Box::new(ErrorRepr {
+            id,
+            type_name: None,
+            error,
+        }))
+    }
+
+    #[cold]
+    pub(crate) fn with_type(id: SharedString, type_name: &'static str, error: BoxedError) -> Self {
+        Self(Box::new(ErrorRepr {
+            id,
+            type_name: Some(type_name),
+            error,
+        }))
     }
 
     /// The id of the asset that was being loaded when the error happened.
@@ -98,6 +400,39 @@ impl Error {
         &self.0.id
     }
 
+    /// The name of the type of the asset that was being loaded, if known.
+    ///
+    /// The name is given by [`std::any::type_name`], so it should only be
+    /// used for diagnostics.
+    #[inline]
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.0.type_name
+    }
+
+    /// Returns the kind of the error.
+    ///
+    /// If the asset failed to load because another asset failed, this is the
+    /// kind of the error of the other asset, so this gives the root cause of
+    /// the error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::{AssetCache, ErrorKind};
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    ///
+    /// let err = cache.load::<String>("example.missing").unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::NotFound);
+    ///
+    /// let err = cache.load::<String>("example..hello").unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidId);
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::of(self.reason())
+    }
+
     /// Like `source`, but never fails.
     #[inline]
     pub fn reason(&self) -> &(dyn std::error::Error + 'static) {
@@ -127,6 +462,7 @@ impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Error")
             .field("id", &self.0.id)
+            .field("type_name", &self.0.type_name)
             .field("error", &self.0.error)
             .finish()
     }
@@ -134,7 +470,11 @@ impl fmt::Debug for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_fmt(format_args!("failed to load \"{}\"", self.id()))
+        f.write_fmt(format_args!("failed to load \"{}\"", self.id()))?;
+        if let Some(type_name) = self.0.type_name {
+            f.write_fmt(format_args!(" as `{type_name}`"))?;
+        }
+        Ok(())
     }
 }
 
@@ -144,3 +484,179 @@ impl std::error::Error for Error {
         Some(self.reason())
     }
 }
+
+/// An error that gathers the errors of several assets.
+///
+/// By default, a [`Compound`] that loads many children stops at the first
+/// error. This type can be used instead to report all failures at once: it
+/// can be returned from [`Compound::load`], and its `Display` implementation
+/// prints a summary tree of all the errors, including nested ones.
+///
+/// [`Compound`]: crate::Compound
+/// [`Compound::load`]: crate::Compound::load
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AggregateError, AnyCache, BoxedError, Compound, SharedString};
+///
+/// #[derive(Debug)]
+/// struct Numbers(Vec<i32>);
+///
+/// impl Compound for Numbers {
+///     fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+///         let ids = ["a", "b", "c"].map(|name| format!("{id}.{name}"));
+///         let handles = cache.load_all::<String>(ids.iter().map(|id| &**id))?;
+///
+///         let numbers = handles.iter().map(|h| h.read().len() as i32).collect();
+///         Ok(Numbers(numbers))
+///     }
+/// }
+///
+/// let cache = assets_manager::AssetCache::new("assets")?;
+/// let err = cache.load::<Numbers>("example").unwrap_err();
+///
+/// let err = err.downcast::<AggregateError>().unwrap();
+/// assert_eq!(err.len(), 3);
+/// println!("{err}");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct AggregateError {
+    errors: Vec<Error>,
+}
+
+impl AggregateError {
+    /// Creates an empty `AggregateError`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the results of loading several assets.
+    ///
+    /// Returns all the values if no error happened, and all the errors
+    /// otherwise.
+    pub fn collect<T>(results: impl IntoIterator<Item = Result<T, Error>>) -> Result<Vec<T>, Self> {
+        let mut values = Vec::new();
+        let mut errors = Self::new();
+
+        for result in results {
+            match result {
+                Ok(value) => values.push(value),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        errors.into_result()?;
+        Ok(values)
+    }
+
+    /// Adds an error.
+    #[inline]
+    pub fn push(&mut self, error: Error) {
+        self.errors.push(error);
+    }
+
+    /// Returns `Ok` if there is no error, and `Err(self)` otherwise.
+    #[inline]
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// The number of errors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if there is no error.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the errors, in the order they were added.
+    #[inline]
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Consumes the `AggregateError`, returning the errors.
+    #[inline]
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let n = self.errors.len();
+        write!(
+            f,
+            "failed to load {n} asset{}",
+            if n == 1 { "" } else { "s" }
+        )?;
+
+        for err in &self.errors {
+            write!(
+                f,
+                "\n{:indent$}- \"{}\": ",
+                "",
+                err.id(),
+                indent = depth * 2
+            )?;
+
+            let mut reason = Some(err.reason());
+            let mut first = true;
+            while let Some(err) = reason {
+                if !first {
+                    f.write_str(": ")?;
+                }
+                first = false;
+
+                if let Some(nested) = err.downcast_ref::<AggregateError>() {
+                    nested.fmt_tree(f, depth + 1)?;
+                    break;
+                }
+                fmt::Display::fmt(err, f)?;
+                reason = err.source();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Vec<Error>> for AggregateError {
+    #[inline]
+    fn from(errors: Vec<Error>) -> Self {
+        Self { errors }
+    }
+}
+
+impl FromIterator<Error> for AggregateError {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Error>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Error> for AggregateError {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Error>>(&mut self, iter: I) {
+        self.errors.extend(iter);
+    }
+}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_tree(f, 0)
+    }
+}
+
+impl std::error::Error for AggregateError {}
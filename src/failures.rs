@@ -0,0 +1,286 @@
+//! Failed loads remembered by the cache
+
+use crate::{
+    utils::{HashMap, Mutex},
+    validation::ErrorChain,
+    BoxedError, Error, ErrorKind, SharedString,
+};
+use std::{
+    any::TypeId,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
+
+#[cfg(doc)]
+use crate::{AssetCache, Handle};
+
+/// Configures when loading an asset that failed to load is tried again.
+///
+/// While an asset is not retried, loading it fails immediately with the
+/// previous error, without reading the source.
+///
+/// See [`AssetCache::set_retry_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryPolicy {
+    /// Failed assets are tried again each time they are loaded.
+    ///
+    /// This is the default.
+    #[default]
+    Always,
+
+    /// Failed assets are tried again once the delay has elapsed since the
+    /// last attempt.
+    After(Duration),
+
+    /// Failed assets are never tried again, until their failure is cleared.
+    Never,
+}
+
+/// A failure to load an asset.
+///
+/// See [`AssetCache::last_error`] and [`Handle::last_reload_error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadFailure {
+    id: SharedString,
+    type_id: TypeId,
+    type_name: &'static str,
+    message: String,
+    kind: ErrorKind,
+    since: SystemTime,
+    attempts: u32,
+    last_attempt: Instant,
+}
+
+impl LoadFailure {
+    pub(crate) fn new(
+        id: SharedString,
+        type_id: TypeId,
+        type_name: &'static str,
+        error: &Error,
+    ) -> Self {
+        Self {
+            id,
+            type_id,
+            type_name,
+            message: ErrorChain(error.reason()).to_string(),
+            kind: error.kind(),
+            since: SystemTime::now(),
+            attempts: 1,
+            last_attempt: Instant::now(),
+        }
+    }
+
+    /// Records a new failed attempt.
+    pub(crate) fn update(&mut self, error: &Error) {
+        self.message = ErrorChain(error.reason()).to_string();
+        self.kind = error.kind();
+        self.attempts = self.attempts.saturating_add(1);
+        self.last_attempt = Instant::now();
+    }
+
+    /// The id of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// The `TypeId` of the asset.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type of the asset.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The message of the last error, including its sources.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The kind of the last error.
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The time of the first failure since the asset last loaded
+    /// successfully.
+    #[inline]
+    pub fn since(&self) -> SystemTime {
+        self.since
+    }
+
+    /// The number of failed attempts to load the asset.
+    #[inline]
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+impl fmt::Display for LoadFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" ({}): {}", self.id, self.type_name, self.message)
+    }
+}
+
+/// The error returned when an asset is not tried again after a failure.
+#[derive(Debug)]
+pub(crate) struct PreviousFailure {
+    message: String,
+    pub(crate) kind: ErrorKind,
+}
+
+impl fmt::Display for PreviousFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the asset failed to load previously: {}", self.message)
+    }
+}
+
+impl std::error::Error for PreviousFailure {}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for LoadFailure {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let since = self
+            .since
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let mut failure = s.serialize_struct("LoadFailure", 5)?;
+        failure.serialize_field("id", &self.id)?;
+        failure.serialize_field("type", self.type_name)?;
+        failure.serialize_field("error", &self.message)?;
+        failure.serialize_field("since", &since)?;
+        failure.serialize_field("attempts", &self.attempts)?;
+        failure.end()
+    }
+}
+
+/// The failed loads of a cache.
+pub(crate) struct Failures {
+    /// `true` if `map` is not empty, to avoid taking the lock in the common
+    /// case.
+    any: AtomicBool,
+    policy: Mutex<RetryPolicy>,
+    map: Mutex<HashMap<SharedString, Vec<LoadFailure>>>,
+}
+
+impl Failures {
+    pub fn new() -> Self {
+        Self {
+            any: AtomicBool::new(false),
+            policy: Mutex::new(RetryPolicy::Always),
+            map: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_policy(&self, policy: RetryPolicy) {
+        *self.policy.lock() = policy;
+    }
+
+    /// Returns an error if the asset should not be tried again.
+    pub fn check(&self, id: &str, type_id: TypeId) -> Option<Error> {
+        if !self.any.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let policy = *self.policy.lock();
+        let map = self.map.lock();
+        let failure = map.get(id)?.iter().find(|f| f.type_id == type_id)?;
+
+        let retry = match policy {
+            RetryPolicy::Always => true,
+            RetryPolicy::After(delay) => failure.last_attempt.elapsed() >= delay,
+            RetryPolicy::Never => false,
+        };
+        if retry {
+            return None;
+        }
+
+        let err = PreviousFailure {
+            message: failure.message.clone(),
+            kind: failure.kind,
+        };
+        Some(Error::with_type(
+            failure.id.clone(),
+            failure.type_name,
+            Box::new(err) as BoxedError,
+        ))
+    }
+
+    pub fn record(
+        &self,
+        id: &SharedString,
+        type_id: TypeId,
+        type_name: &'static str,
+        error: &Error,
+    ) {
+        let mut map = self.map.lock();
+        let failures = map.entry(id.clone()).or_default();
+        match failures.iter_mut().find(|f| f.type_id == type_id) {
+            Some(f) => f.update(error),
+            None => failures.push(LoadFailure::new(id.clone(), type_id, type_name, error)),
+        }
+        self.any.store(true, Ordering::Release);
+    }
+
+    pub fn remove(&self, id: &str, type_id: TypeId) {
+        if !self.any.load(Ordering::Acquire) {
+            return;
+        }
+
+        let mut map = self.map.lock();
+        if let Some(failures) = map.get_mut(id) {
+            failures.retain(|f| f.type_id != type_id);
+            if failures.is_empty() {
+                map.remove(id);
+            }
+        }
+        self.any.store(!map.is_empty(), Ordering::Release);
+    }
+
+    /// Forgets the failures of all types with the given id.
+    #[cfg(feature = "hot-reloading")]
+    pub fn remove_id(&self, id: &str) {
+        if !self.any.load(Ordering::Acquire) {
+            return;
+        }
+
+        let mut map = self.map.lock();
+        if map.remove(id).is_some() {
+            log::trace!("Forgetting failures of \"{id}\"");
+        }
+        self.any.store(!map.is_empty(), Ordering::Release);
+    }
+
+    pub fn clear(&self) {
+        let mut map = self.map.lock();
+        map.clear();
+        self.any.store(false, Ordering::Release);
+    }
+
+    pub fn get(&self, id: &str, type_id: TypeId) -> Option<LoadFailure> {
+        if !self.any.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let map = self.map.lock();
+        map.get(id)?.iter().find(|f| f.type_id == type_id).cloned()
+    }
+
+    /// Returns all failures, sorted by id and type name.
+    pub fn list(&self) -> Vec<LoadFailure> {
+        let mut list: Vec<_> = self.map.lock().values().flatten().cloned().collect();
+        list.sort_unstable_by(|a, b| (&*a.id, a.type_name).cmp(&(&*b.id, b.type_name)));
+        list
+    }
+}
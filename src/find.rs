@@ -0,0 +1,145 @@
+use crate::{
+    source::{OwnedDirEntry, Source},
+    utils::HashSet,
+    AnyCache, SharedString,
+};
+use std::fmt;
+
+fn child_id(dir: &str, name: &str) -> SharedString {
+    if dir.is_empty() {
+        name.into()
+    } else {
+        format!("{dir}.{name}").into()
+    }
+}
+
+/// An iterator over the entries of a source that match a pattern.
+///
+/// This `struct` is created by [`AnyCache::find`]. Directories are read
+/// lazily, when the iterator reaches them.
+pub struct Find<'a> {
+    cache: AnyCache<'a>,
+    segments: Vec<Box<str>>,
+    extensions: Option<Vec<Box<str>>>,
+
+    /// Directories to read with the index of the segment to match there.
+    pending: Vec<(SharedString, usize)>,
+    visited: HashSet<(SharedString, usize)>,
+    found: Vec<OwnedDirEntry>,
+}
+
+impl<'a> Find<'a> {
+    pub(crate) fn new(cache: AnyCache<'a>, pattern: &str) -> Self {
+        let segments: Vec<Box<str>> = pattern.split('.').map(Box::from).collect();
+
+        // Empty segments can't match any id
+        let pending = if segments.iter().any(|s| s.is_empty()) {
+            Vec::new()
+        } else {
+            vec![(SharedString::from(""), 0)]
+        };
+
+        Find {
+            cache,
+            segments,
+            extensions: None,
+            pending,
+            visited: HashSet::new(),
+            found: Vec::new(),
+        }
+    }
+
+    /// Only yields files with one of the given extensions.
+    ///
+    /// Directories are not yielded when this is set.
+    pub fn with_extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions = Some(extensions.iter().map(|&ext| ext.into()).collect());
+        self
+    }
+
+    fn accepts_ext(&self, ext: &str) -> bool {
+        match &self.extensions {
+            Some(extensions) => extensions.iter().any(|e| **e == *ext),
+            None => true,
+        }
+    }
+
+    /// Reads directory `dir` and matches its entries against segment `index`.
+    fn visit(&mut self, dir: SharedString, index: usize) {
+        if !self.visited.insert((dir.clone(), index)) {
+            return;
+        }
+
+        let segment = &*self.segments[index];
+        let is_last = index + 1 == self.segments.len();
+        let is_glob = segment == "**";
+
+        // Literal directory names do not require reading the directory
+        if !is_last && !segment.contains(['*', '?']) {
+            self.pending.push((child_id(&dir, segment), index + 1));
+            return;
+        }
+
+        // `**` can match no segment at all
+        if is_glob && !is_last {
+            self.pending.push((dir.clone(), index + 1));
+        }
+
+        let mut entries = Vec::new();
+        let source = self.cache.raw_source();
+        let _ = self.cache.no_record(|| {
+            source.read_dir(&dir, &mut |entry| {
+                let name = match entry.id().rfind('.') {
+                    Some(n) => &entry.id()[n + 1..],
+                    None => entry.id(),
+                };
+                if is_glob || crate::utils::wildcard_match(segment, name) {
+                    entries.push(entry.to_owned());
+                }
+            })
+        });
+
+        for entry in entries {
+            match entry {
+                OwnedDirEntry::File(_, ref ext) => {
+                    if is_last && self.accepts_ext(ext) {
+                        self.found.push(entry);
+                    }
+                }
+                OwnedDirEntry::Directory(ref id) => {
+                    if is_glob {
+                        self.pending.push((id.clone(), index));
+                    } else if !is_last {
+                        self.pending.push((id.clone(), index + 1));
+                    }
+                    if is_last && self.extensions.is_none() {
+                        self.found.push(entry);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Find<'_> {
+    type Item = OwnedDirEntry;
+
+    fn next(&mut self) -> Option<OwnedDirEntry> {
+        loop {
+            if let Some(entry) = self.found.pop() {
+                return Some(entry);
+            }
+            let (dir, index) = self.pending.pop()?;
+            self.visit(dir, index);
+        }
+    }
+}
+
+impl fmt::Debug for Find<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Find")
+            .field("segments", &self.segments)
+            .field("extensions", &self.extensions)
+            .finish_non_exhaustive()
+    }
+}
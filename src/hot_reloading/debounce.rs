@@ -0,0 +1,97 @@
+use super::AssetKey;
+use crate::utils::HashMap;
+use std::{
+    any::TypeId,
+    time::{Duration, Instant},
+};
+
+/// Delays reloads until changes settle.
+pub(crate) struct Debounce {
+    /// How long to wait after the last event before handling changes.
+    global: Duration,
+
+    /// How long to wait after the last event before reloading assets of a
+    /// given type.
+    by_type: HashMap<TypeId, Duration>,
+
+    last_event: Option<Instant>,
+
+    /// Assets whose reload was delayed, with the instant at which they
+    /// should be reloaded.
+    delayed: HashMap<AssetKey, Instant>,
+}
+
+impl Debounce {
+    pub fn new() -> Self {
+        Self {
+            global: Duration::ZERO,
+            by_type: HashMap::new(),
+            last_event: None,
+            delayed: HashMap::new(),
+        }
+    }
+
+    pub fn set(&mut self, type_id: Option<TypeId>, delay: Option<Duration>) {
+        match (type_id, delay) {
+            (None, delay) => self.global = delay.unwrap_or(Duration::ZERO),
+            (Some(type_id), Some(delay)) => {
+                self.by_type.insert(type_id, delay);
+            }
+            (Some(type_id), None) => {
+                self.by_type.remove(&type_id);
+            }
+        }
+    }
+
+    pub fn on_event(&mut self, now: Instant) {
+        self.last_event = Some(now);
+    }
+
+    /// The instant at which changes can be handled.
+    pub fn settled_at(&self) -> Option<Instant> {
+        Some(self.last_event? + self.global)
+    }
+
+    /// Returns `true` if events happened too recently to handle changes.
+    pub fn is_settling(&self, now: Instant) -> bool {
+        self.settled_at().is_some_and(|at| at > now)
+    }
+
+    /// Returns `true` if the reload of the asset should be delayed, and
+    /// remembers to reload it later.
+    pub fn delay(&mut self, key: &AssetKey, now: Instant) -> bool {
+        let (Some(delay), Some(last_event)) = (self.by_type.get(&key.type_id), self.last_event)
+        else {
+            return false;
+        };
+
+        let deadline = last_event + *delay;
+        if deadline <= now {
+            return false;
+        }
+
+        self.delayed.insert(key.clone(), deadline);
+        true
+    }
+
+    /// Returns the assets whose delay elapsed.
+    pub fn take_due(&mut self, now: Instant) -> Vec<AssetKey> {
+        let due: Vec<_> = self
+            .delayed
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in &due {
+            self.delayed.remove(key);
+        }
+
+        due
+    }
+
+    /// Returns the next instant at which a delayed asset should be reloaded.
+    pub fn next_reload(&self) -> Option<Instant> {
+        self.delayed.values().copied().min()
+    }
+}
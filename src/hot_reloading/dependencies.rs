@@ -1,8 +1,12 @@
-use super::{BorrowedDependency, Dependencies, Dependency};
+use super::{
+    graph::{DependencyGraph, DependencyNode},
+    staging::Staging,
+    AssetKey, BorrowedDependency, Dependencies, Dependency,
+};
 use crate::{
     key::Type,
     source::OwnedDirEntry,
-    utils::{HashMap, HashSet, OwnedKey},
+    utils::{HashMap, HashSet},
 };
 use hashbrown::hash_map::Entry;
 
@@ -45,7 +49,7 @@ impl DepsGraph {
         DepsGraph(HashMap::new())
     }
 
-    pub fn insert_asset(&mut self, asset_key: OwnedKey, deps: Dependencies, typ: Type) {
+    pub fn insert_asset(&mut self, asset_key: AssetKey, deps: Dependencies, typ: Type) {
         self.insert(Dependency::Asset(asset_key), deps, typ)
     }
 
@@ -61,7 +65,7 @@ impl DepsGraph {
             }
             Entry::Occupied(entry) => {
                 let entry = entry.into_mut();
-                let removed: Vec<_> = entry.deps.difference(&deps).cloned().collect();
+                let removed: Vec<_> = entry.deps.0.difference(&deps.0).cloned().collect();
                 entry.deps = deps;
                 entry.typ = Some(typ);
 
@@ -82,7 +86,7 @@ impl DepsGraph {
 
     pub fn topological_sort_from<'a>(
         &self,
-        iter: impl IntoIterator<Item = &'a OwnedDirEntry>,
+        iter: impl IntoIterator<Item = BorrowedDependency<'a>>,
     ) -> TopologicalSort {
         let mut sort_data = TopologicalSortData {
             visited: HashSet::new(),
@@ -90,7 +94,7 @@ impl DepsGraph {
         };
 
         for key in iter {
-            self.visit(&mut sort_data, key.as_dependency());
+            self.visit(&mut sort_data, key);
         }
 
         TopologicalSort(sort_data.list)
@@ -116,34 +120,147 @@ impl DepsGraph {
         }
     }
 
-    pub fn reload(&mut self, cache: crate::AnyCache, key: OwnedKey) {
+    /// Returns `None` if the asset was not reloaded, and whether reloading
+    /// succeeded otherwise.
+    ///
+    /// If `staging` is given, the new value is stored there instead of being
+    /// written to the cache.
+    pub fn reload(
+        &mut self,
+        cache: crate::AnyCache,
+        key: AssetKey,
+        staging: Option<&Staging>,
+    ) -> Option<bool> {
         let id = &key.id;
         let b_key = BorrowedDependency::Asset(&key);
-        if let Some(entry) = self.0.get_mut(&b_key) {
-            if let Some(typ) = entry.typ {
-                let new_deps = cache.reload_untyped(id.clone(), typ);
+        let typ = self.0.get_mut(&b_key)?.typ?;
 
-                if let Some(new_deps) = new_deps {
-                    self.insert(Dependency::Asset(key), new_deps, typ);
+        match cache.reload_untyped(id.clone(), typ)? {
+            Ok((handle, entry, new_deps)) => {
+                log::info!("Reloading \"{id}\"");
+                match staging {
+                    Some(staging) => staging.insert(key.clone(), entry),
+                    None => handle.write(entry),
                 }
+                self.insert(Dependency::Asset(key), new_deps, typ);
+                Some(true)
             }
+            Err(()) => Some(false),
         }
     }
 
     pub fn contains(&self, key: &OwnedDirEntry) -> bool {
         self.0.contains_key(&key.as_dependency())
     }
+
+    /// Removes an asset and the assets that are only used by it, and returns
+    /// the keys of the removed assets.
+    pub fn remove_cascade(&mut self, root: AssetKey) -> Vec<AssetKey> {
+        let mut removed = HashSet::new();
+        let mut list = Vec::new();
+        let mut queue = vec![root];
+
+        while let Some(key) = queue.pop() {
+            let dep = Dependency::Asset(key);
+            if !removed.insert(dep.clone()) {
+                continue;
+            }
+            let Some(node) = self.0.get(&dep) else {
+                continue;
+            };
+
+            for child in node.deps.iter() {
+                let Dependency::Asset(child_key) = child else {
+                    continue;
+                };
+                let exclusive = self.0.get(child).is_some_and(|child| {
+                    child.typ.is_some() && child.rdeps.iter().all(|r| removed.contains(r))
+                });
+                if exclusive {
+                    queue.push(child_key.clone());
+                }
+            }
+
+            if let Dependency::Asset(key) = dep {
+                list.push(key);
+            }
+        }
+
+        for key in &list {
+            self.remove_node(Dependency::Asset(key.clone()));
+        }
+
+        list
+    }
+
+    fn remove_node(&mut self, key: Dependency) {
+        let Some(node) = self.0.get_mut(&key) else {
+            return;
+        };
+        let deps = std::mem::take(&mut node.deps);
+        node.typ = None;
+        if node.rdeps.is_empty() {
+            self.0.remove(&key);
+        }
+
+        for dep in deps.iter() {
+            if let Some(node) = self.0.get_mut(dep) {
+                node.rdeps.remove(&key);
+                if node.rdeps.is_empty() && node.typ.is_none() {
+                    self.0.remove(dep);
+                }
+            }
+        }
+    }
+
+    /// Returns the assets that are reloaded when an entity with the given id
+    /// changes, in reload order.
+    pub fn dependents_of(&self, id: &str) -> Vec<AssetKey> {
+        let roots = self.0.keys().filter(|dep| dep.id() == id);
+        let sorted = self.topological_sort_from(roots.map(Dependency::as_borrowed));
+        sorted
+            .into_iter()
+            .filter(|key| {
+                let key = BorrowedDependency::Asset(key);
+                self.0.get(&key).is_some_and(|node| node.typ.is_some())
+            })
+            .collect()
+    }
+
+    /// Returns a snapshot of the graph of loaded assets.
+    pub fn export(&self) -> DependencyGraph {
+        let nodes = self
+            .0
+            .iter()
+            .filter_map(|(key, node)| match (key, node.typ) {
+                (Dependency::Asset(key), Some(typ)) => {
+                    let deps = node.deps.iter().cloned().collect();
+                    Some(DependencyNode::new(key.clone(), typ.type_name(), deps))
+                }
+                _ => None,
+            })
+            .collect();
+        DependencyGraph::new(nodes)
+    }
+
+    /// Returns the keys of all loaded assets.
+    pub fn assets(&self) -> impl Iterator<Item = &AssetKey> {
+        self.0.iter().filter_map(|(key, node)| match key {
+            Dependency::Asset(key) if node.typ.is_some() => Some(key),
+            _ => None,
+        })
+    }
 }
 
 struct TopologicalSortData {
     visited: HashSet<Dependency>,
-    list: Vec<OwnedKey>,
+    list: Vec<AssetKey>,
 }
 
-pub(crate) struct TopologicalSort(Vec<OwnedKey>);
+pub(crate) struct TopologicalSort(Vec<AssetKey>);
 
 impl TopologicalSort {
-    pub fn into_iter(self) -> impl ExactSizeIterator<Item = OwnedKey> {
+    pub fn into_iter(self) -> impl ExactSizeIterator<Item = AssetKey> {
         self.0.into_iter().rev()
     }
 }
@@ -35,10 +35,27 @@ impl FsWatcherBuilder {
         Err("hot-reloading feature is disabled".into())
     }
 
+    #[inline]
+    pub fn polling(_: std::time::Duration, _: bool) -> Result<Self, BoxedError> {
+        Err("hot-reloading feature is disabled".into())
+    }
+
     pub fn watch(&mut self, _: std::path::PathBuf) -> Result<(), BoxedError> {
         match self.0 {}
     }
 
+    pub fn ignore(&mut self, _: &str) {
+        match self.0 {}
+    }
+
+    pub fn watch_extensions(&mut self, _: &[&str]) {
+        match self.0 {}
+    }
+
+    pub fn nfc_ids(&mut self) {
+        match self.0 {}
+    }
+
     pub fn build(self, _: EventSender) {
         match self.0 {}
     }
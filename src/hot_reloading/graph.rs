@@ -0,0 +1,228 @@
+use super::{AssetKey, Dependency};
+use std::{any::TypeId, cmp::Ordering, fmt};
+
+#[cfg(doc)]
+use crate::AssetCache;
+
+/// A snapshot of the dependency graph used for hot-reloading.
+///
+/// It contains each hot-reloaded asset of the cache, with the files,
+/// directories and assets that were used to load it. When one of these
+/// changes, the asset is reloaded.
+///
+/// This is returned by [`AssetCache::dependency_graph`]. It can be printed
+/// in the DOT format with [`to_dot`](Self::to_dot), or serialized with
+/// feature `serde`.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    nodes: Vec<DependencyNode>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn new(mut nodes: Vec<DependencyNode>) -> Self {
+        nodes.sort_unstable_by(DependencyNode::cmp_key);
+        Self { nodes }
+    }
+
+    /// Returns the assets of the graph, sorted by id and type name.
+    #[inline]
+    pub fn nodes(&self) -> &[DependencyNode] {
+        &self.nodes
+    }
+
+    /// Returns the node of the given asset, if it is in the graph.
+    pub fn get(&self, key: &AssetKey) -> Option<&DependencyNode> {
+        self.nodes.iter().find(|n| n.key == *key)
+    }
+
+    /// Returns the name of a type of asset of the graph.
+    fn type_name_of(&self, type_id: TypeId) -> &'static str {
+        self.nodes
+            .iter()
+            .find(|n| n.key.type_id == type_id)
+            .map_or("?", |n| n.type_name)
+    }
+
+    fn fmt_dependency(&self, dep: &Dependency, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match dep {
+            Dependency::File(id, ext) => write_quoted(f, format_args!("{id}.{ext}")),
+            Dependency::Directory(id) => write_quoted(f, format_args!("{id}/")),
+            Dependency::Asset(key) => {
+                let type_name = self.type_name_of(key.type_id);
+                write_quoted(f, format_args!("{} ({type_name})", key.id))
+            }
+        }
+    }
+
+    /// Returns the graph in the DOT format, to be rendered with Graphviz for
+    /// example.
+    ///
+    /// Edges go from assets to what they depend on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "hot-reloading")] {
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// let dot = cache.dependency_graph().to_dot();
+    /// assert!(dot.contains(r#""example.hello (alloc::string::String)" -> "example.hello.txt""#));
+    /// # }}
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn to_dot(&self) -> String {
+        Dot(self).to_string()
+    }
+}
+
+struct Dot<'a>(&'a DependencyGraph);
+
+impl fmt::Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let graph = self.0;
+        f.write_str("digraph assets {\n")?;
+        for node in &graph.nodes {
+            for dep in &node.dependencies {
+                f.write_str("    ")?;
+                graph.fmt_dependency(&Dependency::Asset(node.key.clone()), f)?;
+                f.write_str(" -> ")?;
+                graph.fmt_dependency(dep, f)?;
+                f.write_str(";\n")?;
+            }
+        }
+        f.write_str("}\n")
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: fmt::Arguments) -> fmt::Result {
+    let s = s.to_string();
+    f.write_str("\"")?;
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+        fmt::Write::write_char(f, c)?;
+    }
+    f.write_str("\"")
+}
+
+/// An asset of a [`DependencyGraph`].
+#[derive(Debug, Clone)]
+pub struct DependencyNode {
+    key: AssetKey,
+    type_name: &'static str,
+    dependencies: Vec<Dependency>,
+}
+
+impl DependencyNode {
+    pub(crate) fn new(
+        key: AssetKey,
+        type_name: &'static str,
+        mut dependencies: Vec<Dependency>,
+    ) -> Self {
+        dependencies.sort_unstable_by(cmp_dependencies);
+        Self {
+            key,
+            type_name,
+            dependencies,
+        }
+    }
+
+    fn cmp_key(&self, other: &Self) -> Ordering {
+        (&*self.key.id, self.type_name).cmp(&(&*other.key.id, other.type_name))
+    }
+
+    /// The key of the asset.
+    #[inline]
+    pub fn key(&self) -> &AssetKey {
+        &self.key
+    }
+
+    /// The name of the type of the asset.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The dependencies of the asset, sorted by kind and id.
+    #[inline]
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+}
+
+fn cmp_dependencies(a: &Dependency, b: &Dependency) -> Ordering {
+    fn key(dep: &Dependency) -> (u8, &str, &str) {
+        match dep {
+            Dependency::File(id, ext) => (0, id, ext),
+            Dependency::Directory(id) => (1, id, ""),
+            Dependency::Asset(key) => (2, &key.id, ""),
+        }
+    }
+
+    key(a).cmp(&key(b))
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for DependencyGraph {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeSeq, SerializeStruct};
+
+        struct Deps<'a>(&'a DependencyGraph, &'a [Dependency]);
+        struct Dep<'a>(&'a DependencyGraph, &'a Dependency);
+        struct Node<'a>(&'a DependencyGraph, &'a DependencyNode);
+
+        impl serde::Serialize for Dep<'_> {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                let mut dep = s.serialize_struct("Dependency", 3)?;
+                match self.1 {
+                    Dependency::File(id, ext) => {
+                        dep.serialize_field("kind", "file")?;
+                        dep.serialize_field("id", id)?;
+                        dep.serialize_field("ext", ext)?;
+                    }
+                    Dependency::Directory(id) => {
+                        dep.serialize_field("kind", "directory")?;
+                        dep.serialize_field("id", id)?;
+                    }
+                    Dependency::Asset(key) => {
+                        dep.serialize_field("kind", "asset")?;
+                        dep.serialize_field("id", &key.id)?;
+                        dep.serialize_field("type", self.0.type_name_of(key.type_id))?;
+                    }
+                }
+                dep.end()
+            }
+        }
+
+        impl serde::Serialize for Deps<'_> {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                let mut seq = s.serialize_seq(Some(self.1.len()))?;
+                for dep in self.1 {
+                    seq.serialize_element(&Dep(self.0, dep))?;
+                }
+                seq.end()
+            }
+        }
+
+        impl serde::Serialize for Node<'_> {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                let mut node = s.serialize_struct("DependencyNode", 3)?;
+                node.serialize_field("id", &self.1.key.id)?;
+                node.serialize_field("type", self.1.type_name)?;
+                node.serialize_field("dependencies", &Deps(self.0, &self.1.dependencies))?;
+                node.end()
+            }
+        }
+
+        let mut seq = s.serialize_seq(Some(self.nodes.len()))?;
+        for node in &self.nodes {
+            seq.serialize_element(&Node(self, node))?;
+        }
+        seq.end()
+    }
+}
@@ -3,40 +3,55 @@
 //! If you don't implement hot-reloading for a custom source, you should not
 //! need this.
 
+mod debounce;
 mod dependencies;
+mod graph;
 mod paths;
+mod quarantine;
 pub(crate) mod records;
+mod refresh;
+mod staging;
 mod watcher;
 
 #[cfg(test)]
 mod tests;
 
 use paths::{AssetReloadInfos, HotReloadingData};
+use quarantine::Quarantine;
+pub(crate) use refresh::RefreshFilter;
 
 use crossbeam_channel::{self as channel, Receiver, Sender};
 use std::{
+    any::TypeId,
     fmt,
     ptr::NonNull,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
     thread,
+    time::Duration,
 };
 
 use crate::{
+    entry::Reloads,
+    failures::Failures,
     key::Type,
+    listing::Listing,
     source::{OwnedDirEntry, Source},
-    utils::{Condvar, Mutex},
-    SharedString,
+    utils::{Condvar, Mutex, RwLock, RwLockReadGuard},
+    Error, SharedString,
 };
 
 #[cfg(doc)]
 use crate::AssetCache;
 
+pub use graph::{DependencyGraph, DependencyNode};
+pub use quarantine::{QuarantinePolicy, QuarantinedAsset};
 pub use watcher::FsWatcherBuilder;
 
-pub(crate) use records::{BorrowedDependency, Dependencies, Dependency};
+pub(crate) use records::BorrowedDependency;
+pub use records::{AssetKey, Dependencies, Dependency};
 
 enum CacheMessage {
     Ptr(NonNull<crate::cache::AssetMap>, NonNull<HotReloader>, usize),
@@ -44,6 +59,15 @@ enum CacheMessage {
 
     Clear,
     AddAsset(AssetReloadInfos),
+    SkipUnchanged(bool),
+    SetRefresh(RefreshFilter, Option<Duration>),
+    SetDebounce(Option<TypeId>, Option<Duration>),
+    SetAtomic(bool),
+    Invalidate(SharedString, Option<TypeId>),
+    RemoveCascade(AssetKey, Sender<Vec<AssetKey>>),
+    DependencyGraph(Sender<DependencyGraph>),
+    DependentsOf(SharedString, Sender<Vec<AssetKey>>),
+    Unfreeze,
 }
 unsafe impl Send for CacheMessage where crate::cache::AssetMap: Sync {}
 
@@ -51,13 +75,13 @@ unsafe impl Send for CacheMessage where crate::cache::AssetMap: Sync {}
 #[derive(Debug)]
 pub struct Disconnected;
 
-enum Events {
+pub(crate) enum Events {
     Single(OwnedDirEntry),
     Multiple(Vec<OwnedDirEntry>),
 }
 
 impl Events {
-    fn for_each(self, mut f: impl FnMut(OwnedDirEntry)) {
+    pub(crate) fn for_each(self, mut f: impl FnMut(OwnedDirEntry)) {
         match self {
             Self::Single(e) => f(e),
             Self::Multiple(e) => e.into_iter().for_each(f),
@@ -67,7 +91,7 @@ impl Events {
 
 /// Sends events for hot-reloading.
 #[derive(Debug, Clone)]
-pub struct EventSender(Sender<Events>);
+pub struct EventSender(pub(crate) Sender<Events>);
 
 impl EventSender {
     /// Sends an event.
@@ -139,31 +163,65 @@ impl Answers {
     }
 }
 
+type ReloadErrorHook = Box<dyn Fn(&SharedString, &Error) + Send + Sync>;
+
 /// The hot-reloading handler.
 pub(crate) struct HotReloader {
     sender: Sender<CacheMessage>,
     answers: Arc<Answers>,
+    quarantine: Arc<Mutex<Quarantine>>,
+    freeze: Arc<RwLock<()>>,
+    on_error: RwLock<Option<ReloadErrorHook>>,
+    skip_unchanged: AtomicBool,
+    reloads: Arc<Reloads>,
 }
 
 impl HotReloader {
     /// Starts hot-reloading.
-    fn start(events: Receiver<Events>, source: Box<dyn Source + Send>) -> Self {
+    fn start(
+        events: Receiver<Events>,
+        source: Box<dyn Source + Send>,
+        listing: Arc<Listing>,
+        failures: Arc<Failures>,
+    ) -> Self {
         let (cache_msg_tx, cache_msg_rx) = channel::unbounded();
         let answers = Arc::new(Answers::default());
         let answers_clone = answers.clone();
+        let quarantine = Arc::new(Mutex::new(Quarantine::new()));
+        let quarantine_clone = quarantine.clone();
+        let freeze = Arc::new(RwLock::new(()));
+        let freeze_clone = freeze.clone();
 
         thread::Builder::new()
             .name("assets_hot_reload".to_string())
-            .spawn(|| hot_reloading_thread(source, events, cache_msg_rx, answers_clone))
+            .spawn(|| {
+                let data = HotReloadingData::new(
+                    source,
+                    quarantine_clone,
+                    freeze_clone,
+                    listing,
+                    failures,
+                );
+                hot_reloading_thread(data, events, cache_msg_rx, answers_clone)
+            })
             .unwrap();
 
         Self {
             sender: cache_msg_tx,
             answers,
+            quarantine,
+            freeze,
+            on_error: RwLock::new(None),
+            skip_unchanged: AtomicBool::new(false),
+            reloads: Arc::new(Reloads::new()),
         }
     }
 
-    pub fn make<S: Source>(source: S) -> Option<Self> {
+    pub fn make<S: Source>(
+        source: S,
+        listing: Arc<Listing>,
+        failures: Arc<Failures>,
+    ) -> Option<Self> {
         let sent_source = source.make_source()?;
         let (events_tx, events_rx) = channel::unbounded();
 
@@ -174,22 +232,116 @@ impl HotReloader {
             })
             .ok()?;
 
-        Some(Self::start(events_rx, sent_source))
+        Some(Self::start(events_rx, sent_source, listing, failures))
     }
 
     // All theses methods ignore send/recv errors: the program can continue
     // without hot-reloading if it stopped, and an error should have already
     // been logged.
 
-    pub(crate) fn add_asset(&self, id: SharedString, deps: Dependencies, typ: Type) {
-        let infos = AssetReloadInfos::from_type(id, deps, typ);
+    pub(crate) fn add_asset(
+        &self,
+        id: SharedString,
+        deps: Dependencies,
+        hashes: records::FileHashes,
+        typ: Type,
+    ) {
+        let infos = AssetReloadInfos::from_type(id, deps, hashes, typ);
         let _ = self.sender.send(CacheMessage::AddAsset(infos));
     }
 
+    /// Used to wake up threads waiting for assets of the cache to be
+    /// reloaded.
+    pub(crate) fn reloads(&self) -> &Arc<Reloads> {
+        &self.reloads
+    }
+
     pub(crate) fn clear(&self) {
         let _ = self.sender.send(CacheMessage::Clear);
     }
 
+    pub(crate) fn set_quarantine_policy(&self, policy: Option<QuarantinePolicy>) {
+        self.quarantine.lock().set_policy(policy);
+    }
+
+    pub(crate) fn quarantined_assets(&self) -> Vec<QuarantinedAsset> {
+        self.quarantine.lock().list()
+    }
+
+    pub(crate) fn set_error_hook(&self, hook: Option<ReloadErrorHook>) {
+        *self.on_error.write() = hook;
+    }
+
+    /// Calls the user hook after an asset failed to reload.
+    pub(crate) fn report_error(&self, id: &SharedString, error: &Error) {
+        if let Some(hook) = &*self.on_error.read() {
+            hook(id, error);
+        }
+    }
+
+    pub(crate) fn skip_unchanged(&self, skip: bool) {
+        self.skip_unchanged.store(skip, Ordering::Relaxed);
+        let _ = self.sender.send(CacheMessage::SkipUnchanged(skip));
+    }
+
+    /// Returns `true` if the content of files read by loaders should be
+    /// hashed, to skip reloads when it does not change.
+    pub(crate) fn hashes_content(&self) -> bool {
+        self.skip_unchanged.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_refresh(&self, filter: RefreshFilter, interval: Option<Duration>) {
+        let _ = self.sender.send(CacheMessage::SetRefresh(filter, interval));
+    }
+
+    pub(crate) fn invalidate(&self, id: SharedString, type_id: Option<TypeId>) {
+        let _ = self.sender.send(CacheMessage::Invalidate(id, type_id));
+    }
+
+    pub(crate) fn set_debounce(&self, type_id: Option<TypeId>, delay: Option<Duration>) {
+        let _ = self.sender.send(CacheMessage::SetDebounce(type_id, delay));
+    }
+
+    pub(crate) fn set_atomic(&self, atomic: bool) {
+        let _ = self.sender.send(CacheMessage::SetAtomic(atomic));
+    }
+
+    /// Removes an asset and its exclusive dependencies from the graph, and
+    /// returns their keys.
+    pub(crate) fn remove_cascade(&self, key: AssetKey) -> Vec<AssetKey> {
+        let (tx, rx) = channel::bounded(1);
+        match self.sender.send(CacheMessage::RemoveCascade(key, tx)) {
+            Ok(()) => rx.recv().unwrap_or_default(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns a snapshot of the dependency graph, including assets added
+    /// before this call.
+    pub(crate) fn dependency_graph(&self) -> DependencyGraph {
+        let (tx, rx) = channel::bounded(1);
+        match self.sender.send(CacheMessage::DependencyGraph(tx)) {
+            Ok(()) => rx.recv().unwrap_or_default(),
+            Err(_) => DependencyGraph::default(),
+        }
+    }
+
+    /// Returns the assets that are reloaded when an entity with the given id
+    /// changes.
+    pub(crate) fn dependents_of(&self, id: SharedString) -> Vec<AssetKey> {
+        let (tx, rx) = channel::bounded(1);
+        match self.sender.send(CacheMessage::DependentsOf(id, tx)) {
+            Ok(()) => rx.recv().unwrap_or_default(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    pub(crate) fn freeze(&self) -> FreezeGuard<'_> {
+        FreezeGuard {
+            inner: Some((self, self.freeze.read())),
+        }
+    }
+
     pub(crate) fn reload(&self, map: &crate::cache::AssetMap) {
         let token = self.answers.get_unique_token();
         if self
@@ -211,6 +363,36 @@ impl HotReloader {
     }
 }
 
+/// A guard that defers hot-reloading while it is alive.
+///
+/// See [`AssetCache::freeze`].
+#[must_use = "the cache is unfrozen when the guard is dropped"]
+pub struct FreezeGuard<'a> {
+    inner: Option<(&'a HotReloader, RwLockReadGuard<'a, ()>)>,
+}
+
+impl FreezeGuard<'_> {
+    #[inline]
+    pub(crate) fn none() -> Self {
+        FreezeGuard { inner: None }
+    }
+}
+
+impl Drop for FreezeGuard<'_> {
+    fn drop(&mut self) {
+        if let Some((reloader, guard)) = self.inner.take() {
+            drop(guard);
+            let _ = reloader.sender.send(CacheMessage::Unfreeze);
+        }
+    }
+}
+
+impl fmt::Debug for FreezeGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("FreezeGuard { .. }")
+    }
+}
+
 impl fmt::Debug for HotReloader {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("HotReloader { .. }")
@@ -218,23 +400,32 @@ impl fmt::Debug for HotReloader {
 }
 
 fn hot_reloading_thread(
-    source: Box<dyn Source>,
+    mut cache: HotReloadingData,
     events: Receiver<Events>,
     cache_msg: Receiver<CacheMessage>,
     answers: Arc<Answers>,
 ) {
     log::info!("Starting hot-reloading");
 
-    let mut cache = HotReloadingData::new(source);
-
     let mut select = channel::Select::new();
     select.recv(&cache_msg);
     select.recv(&events);
 
-    loop {
+    'main: loop {
         // We don't use `select` method here as we always want to check
         // `cache_msg` channel first.
-        let ready = select.ready();
+        let ready = match cache.next_wakeup() {
+            Some(deadline) => match select.ready_deadline(deadline) {
+                Ok(ready) => ready,
+                Err(_) => {
+                    // Retry reloads skipped because of a quarantine and
+                    // refresh assets
+                    cache.update_if_static();
+                    continue;
+                }
+            },
+            None => select.ready(),
+        };
 
         loop {
             match cache_msg.try_recv() {
@@ -251,7 +442,26 @@ fn hot_reloading_thread(
                 }
                 Ok(CacheMessage::Clear) => cache.clear_local_cache(),
                 Ok(CacheMessage::AddAsset(infos)) => cache.add_asset(infos),
-                Err(_) => break,
+                Ok(CacheMessage::SkipUnchanged(skip)) => cache.skip_unchanged(skip),
+                Ok(CacheMessage::SetRefresh(filter, interval)) => {
+                    cache.set_refresh(filter, interval)
+                }
+                Ok(CacheMessage::SetDebounce(type_id, delay)) => cache.set_debounce(type_id, delay),
+                Ok(CacheMessage::SetAtomic(atomic)) => cache.set_atomic(atomic),
+                Ok(CacheMessage::Invalidate(id, type_id)) => cache.invalidate(&id, type_id),
+                Ok(CacheMessage::RemoveCascade(key, answer)) => {
+                    let _ = answer.send(cache.remove_cascade(key));
+                }
+                Ok(CacheMessage::DependencyGraph(answer)) => {
+                    let _ = answer.send(cache.dependency_graph());
+                }
+                Ok(CacheMessage::DependentsOf(id, answer)) => {
+                    let _ = answer.send(cache.dependents_of(&id));
+                }
+                Ok(CacheMessage::Unfreeze) => cache.update_if_static(),
+                Err(channel::TryRecvError::Empty) => break,
+                // The cache was dropped, we can stop now
+                Err(channel::TryRecvError::Disconnected) => break 'main,
             }
         }
 
@@ -260,7 +470,7 @@ fn hot_reloading_thread(
                 Ok(msg) => cache.handle_events(msg),
                 Err(crossbeam_channel::TryRecvError::Empty) => (),
                 // We won't receive events anymore, we can stop now
-                Err(crossbeam_channel::TryRecvError::Disconnected) => break,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => break 'main,
             }
         }
     }
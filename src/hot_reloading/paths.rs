@@ -1,25 +1,66 @@
 use crate::{
     cache::AssetMap,
+    entry::{CacheEntry, UntypedHandle},
+    failures::Failures,
+    listing::Listing,
     source::{OwnedDirEntry, Source},
-    utils::{HashSet, OwnedKey},
+    utils::{HashMap, HashSet, Mutex, RwLock},
     AnyCache, SharedString,
 };
+use std::{
+    any::TypeId,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::{
+    debounce::Debounce,
+    dependencies::DepsGraph,
+    quarantine::Quarantine,
+    records::{content_hash, FileHashes},
+    refresh::{Refresh, RefreshFilter},
+    staging::Staging,
+    AssetKey, BorrowedDependency, Dependencies, Dependency,
+};
+
+/// The assets of the cache, seen through the new values of the current
+/// reload transaction if any.
+#[derive(Clone, Copy)]
+struct Assets<'a> {
+    map: &'a AssetMap,
+    staging: Option<&'a Staging>,
+}
+
+impl crate::anycache::AssetMap for Assets<'_> {
+    fn get(&self, id: &str, type_id: TypeId) -> Option<&UntypedHandle> {
+        match self.staging.and_then(|s| s.get(id, type_id)) {
+            Some(handle) => Some(handle),
+            None => self.map.get(id, type_id),
+        }
+    }
+
+    fn insert(&self, entry: CacheEntry) -> &UntypedHandle {
+        self.map.insert(entry)
+    }
 
-use super::{dependencies::DepsGraph, records::Dependencies};
+    fn contains_key(&self, id: &str, type_id: TypeId) -> bool {
+        self.map.contains_key(id, type_id)
+    }
+}
 
 #[derive(Clone, Copy)]
 struct BorrowedCache<'a> {
-    assets: &'a AssetMap,
+    assets: Assets<'a>,
     source: &'a (dyn Source + 'static),
     reloader: &'a super::HotReloader,
 }
 
 impl<'a> crate::anycache::RawCache for BorrowedCache<'a> {
-    type AssetMap = AssetMap;
+    type AssetMap = Assets<'a>;
     type Source = &'a dyn Source;
 
-    fn assets(&self) -> &AssetMap {
-        self.assets
+    fn assets(&self) -> &Assets<'a> {
+        &self.assets
     }
 
     fn get_source(&self) -> &&'a (dyn Source + 'static) {
@@ -38,24 +79,44 @@ impl<'a> BorrowedCache<'a> {
         source: &'a (dyn Source + 'static),
     ) -> Self {
         Self {
-            assets,
+            assets: Assets {
+                map: assets,
+                staging: None,
+            },
             reloader,
             source,
         }
     }
 
-    fn as_any_cache(&self) -> AnyCache {
+    /// Returns a cache in which reloaded assets are looked up in `staging`
+    /// first.
+    fn with_staging(self, staging: &'a Staging) -> Self {
+        Self {
+            assets: Assets {
+                staging: Some(staging),
+                ..self.assets
+            },
+            ..self
+        }
+    }
+
+    fn as_any_cache(&self) -> AnyCache<'_> {
         crate::anycache::CacheExt::_as_any_cache(self)
     }
 }
 
-pub(crate) struct AssetReloadInfos(OwnedKey, Dependencies, crate::key::Type);
+pub(crate) struct AssetReloadInfos(AssetKey, Dependencies, FileHashes, crate::key::Type);
 
 impl AssetReloadInfos {
     #[inline]
-    pub(crate) fn from_type(id: SharedString, deps: Dependencies, typ: crate::key::Type) -> Self {
-        let key = OwnedKey::new_with(id, typ.type_id);
-        Self(key, deps, typ)
+    pub(crate) fn from_type(
+        id: SharedString,
+        deps: Dependencies,
+        hashes: FileHashes,
+        typ: crate::key::Type,
+    ) -> Self {
+        let key = AssetKey::new_with(id, typ.type_id);
+        Self(key, deps, hashes, typ)
     }
 }
 
@@ -64,28 +125,101 @@ enum CacheKind {
     Static(&'static AssetMap, &'static super::HotReloader),
 }
 
+/// Hashes of the content of files, used to skip reloads when a file's
+/// content did not actually change.
+struct ContentHashes {
+    enabled: bool,
+    hashes: HashMap<OwnedDirEntry, u64>,
+}
+
+impl ContentHashes {
+    fn new() -> Self {
+        Self {
+            enabled: false,
+            hashes: HashMap::new(),
+        }
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hashes.clear();
+        }
+    }
+
+    /// Stores the hash of the content of the file, and returns `true` if it
+    /// changed since last call.
+    fn update(&mut self, source: &dyn Source, entry: &OwnedDirEntry) -> bool {
+        let OwnedDirEntry::File(id, ext) = entry else {
+            return true;
+        };
+
+        match source.read(id, ext) {
+            Ok(content) => {
+                let hash = content_hash(content.as_ref());
+                self.hashes.insert(entry.clone(), hash) != Some(hash)
+            }
+            Err(_) => {
+                self.hashes.remove(entry);
+                true
+            }
+        }
+    }
+}
+
 pub(super) struct HotReloadingData {
     source: Box<dyn Source>,
     to_reload: HashSet<OwnedDirEntry>,
     cache: CacheKind,
     deps: DepsGraph,
+    hashes: ContentHashes,
+    quarantine: Arc<Mutex<Quarantine>>,
+    refresh: Refresh,
+    debounce: Debounce,
+    freeze: Arc<RwLock<()>>,
+    atomic: bool,
+    listing: Arc<Listing>,
+    failures: Arc<Failures>,
 }
 
 impl HotReloadingData {
-    pub fn new(source: Box<dyn Source>) -> Self {
+    pub fn new(
+        source: Box<dyn Source>,
+        quarantine: Arc<Mutex<Quarantine>>,
+        freeze: Arc<RwLock<()>>,
+        listing: Arc<Listing>,
+        failures: Arc<Failures>,
+    ) -> Self {
         HotReloadingData {
             source,
             to_reload: HashSet::new(),
             cache: CacheKind::Local,
             deps: DepsGraph::new(),
+            hashes: ContentHashes::new(),
+            quarantine,
+            refresh: Refresh::new(),
+            debounce: Debounce::new(),
+            freeze,
+            atomic: false,
+            listing,
+            failures,
         }
     }
 
     pub fn handle_events(&mut self, events: super::Events) {
         events.for_each(|entry| {
+            self.listing.update(&self.source, &entry);
+            self.failures.remove_id(entry.as_dir_entry().id());
+
             if self.deps.contains(&entry) {
+                if self.hashes.enabled && !self.hashes.update(&self.source, &entry) {
+                    log::trace!("Skipping event with unchanged content: {entry:?}");
+                    return;
+                }
+
                 log::trace!("New event: {entry:?}");
                 self.to_reload.insert(entry);
+                self.debounce.on_event(Instant::now());
             }
         });
         self.update_if_static();
@@ -94,14 +228,57 @@ impl HotReloadingData {
     pub fn update_if_local(&mut self, cache: &AssetMap, reloader: &super::HotReloader) {
         if let CacheKind::Local = &mut self.cache {
             let cache = BorrowedCache::new(cache, reloader, &self.source);
-            run_update(&mut self.to_reload, &mut self.deps, cache);
+            run_update(
+                &mut self.to_reload,
+                &mut self.refresh,
+                &mut self.debounce,
+                &mut self.deps,
+                &self.quarantine,
+                &self.freeze,
+                self.atomic,
+                cache,
+            );
         }
     }
 
-    fn update_if_static(&mut self) {
+    /// Returns the next instant at which reloads skipped because of a
+    /// quarantine should be retried, at which assets should be refreshed or
+    /// at which debounced changes should be handled, if the cache can be
+    /// updated from this thread.
+    pub fn next_wakeup(&self) -> Option<Instant> {
+        match self.cache {
+            CacheKind::Static(..) => {
+                let now = Instant::now();
+                let retry = self.quarantine.lock().next_retry();
+                let refresh = self.refresh.next_refresh();
+                let settled = self
+                    .debounce
+                    .settled_at()
+                    .filter(|_| !self.to_reload.is_empty());
+                // Past deadlines are handled by the next update
+                let debounce = settled
+                    .into_iter()
+                    .chain(self.debounce.next_reload())
+                    .filter(|at| *at > now);
+                retry.into_iter().chain(refresh).chain(debounce).min()
+            }
+            CacheKind::Local => None,
+        }
+    }
+
+    pub fn update_if_static(&mut self) {
         if let CacheKind::Static(cache, reloader) = &mut self.cache {
             let cache = BorrowedCache::new(cache, reloader, &self.source);
-            run_update(&mut self.to_reload, &mut self.deps, cache);
+            run_update(
+                &mut self.to_reload,
+                &mut self.refresh,
+                &mut self.debounce,
+                &mut self.deps,
+                &self.quarantine,
+                &self.freeze,
+                self.atomic,
+                cache,
+            );
         }
     }
 
@@ -117,25 +294,165 @@ impl HotReloadingData {
             log::trace!("Hot-reloading now use a 'static reference");
 
             let cache = BorrowedCache::new(asset_cache, reloader, &self.source);
-            run_update(&mut self.to_reload, &mut self.deps, cache);
+            run_update(
+                &mut self.to_reload,
+                &mut self.refresh,
+                &mut self.debounce,
+                &mut self.deps,
+                &self.quarantine,
+                &self.freeze,
+                self.atomic,
+                cache,
+            );
         }
     }
 
     pub fn add_asset(&mut self, infos: AssetReloadInfos) {
-        let AssetReloadInfos(key, new_deps, typ) = infos;
+        let AssetReloadInfos(key, new_deps, hashes, typ) = infos;
+
+        if self.hashes.enabled {
+            // Use the content the asset was actually loaded from, so that
+            // changes made since then are not missed
+            for (entry, hash) in hashes {
+                self.hashes.hashes.insert(entry, hash);
+            }
+
+            // Files that were opened as streams were not hashed
+            for dep in new_deps.iter() {
+                if let Dependency::File(id, ext) = dep {
+                    let entry = OwnedDirEntry::File(id.clone(), ext.clone());
+                    if !self.hashes.hashes.contains_key(&entry) {
+                        self.hashes.update(&self.source, &entry);
+                    }
+                }
+            }
+        }
+
+        self.refresh.add_asset(&key, Instant::now());
         self.deps.insert_asset(key, new_deps, typ);
     }
 
+    pub fn set_refresh(&mut self, filter: RefreshFilter, interval: Option<Duration>) {
+        let now = Instant::now();
+        self.refresh
+            .set_rule(filter, interval, self.deps.assets(), now);
+    }
+
+    /// Marks assets with the given id to be reloaded, with all types if
+    /// `type_id` is `None`.
+    pub fn invalidate(&mut self, id: &str, type_id: Option<TypeId>) {
+        let keys = self
+            .deps
+            .assets()
+            .filter(|key| key.id == id && type_id.map_or(true, |t| key.type_id == t));
+        for key in keys {
+            self.refresh.force(key.clone());
+        }
+        self.update_if_static();
+    }
+
+    pub fn set_debounce(&mut self, type_id: Option<TypeId>, delay: Option<Duration>) {
+        self.debounce.set(type_id, delay);
+    }
+
+    pub fn set_atomic(&mut self, atomic: bool) {
+        self.atomic = atomic;
+    }
+
+    pub fn remove_cascade(&mut self, key: AssetKey) -> Vec<AssetKey> {
+        let removed = self.deps.remove_cascade(key);
+        for key in &removed {
+            self.refresh.remove_asset(key);
+        }
+        removed
+    }
+
+    pub fn dependents_of(&self, id: &str) -> Vec<AssetKey> {
+        self.deps.dependents_of(id)
+    }
+
+    pub fn dependency_graph(&self) -> super::DependencyGraph {
+        self.deps.export()
+    }
+
+    pub fn skip_unchanged(&mut self, skip: bool) {
+        self.hashes.set_enabled(skip);
+    }
+
     pub fn clear_local_cache(&mut self) {
         self.to_reload.clear();
+        self.refresh.clear_forced();
     }
 }
 
-fn run_update(changed: &mut HashSet<OwnedDirEntry>, deps: &mut DepsGraph, cache: BorrowedCache) {
-    let to_update = deps.topological_sort_from(changed.iter());
-    changed.clear();
+#[allow(clippy::too_many_arguments)]
+fn run_update(
+    changed: &mut HashSet<OwnedDirEntry>,
+    refresh: &mut Refresh,
+    debounce: &mut Debounce,
+    deps: &mut DepsGraph,
+    quarantine: &Mutex<Quarantine>,
+    freeze: &RwLock<()>,
+    atomic: bool,
+    cache: BorrowedCache,
+) {
+    // If the cache is frozen, keep changes for when it is unfrozen
+    let Some(_guard) = freeze.try_write() else {
+        log::trace!("Cache is frozen, deferring reloads");
+        return;
+    };
+
+    let now = Instant::now();
+
+    // Wait for changes to settle before handling them
+    let settling = debounce.is_settling(now);
+    let pending = if settling { None } else { Some(changed.iter()) };
+
+    let refreshed = refresh.take_due(now);
+    let debounced = debounce.take_due(now);
+    let changed_deps = pending
+        .into_iter()
+        .flatten()
+        .map(OwnedDirEntry::as_dependency);
+    let refreshed_deps = refreshed
+        .iter()
+        .chain(&debounced)
+        .map(BorrowedDependency::Asset);
+    let mut to_update: Vec<_> = deps
+        .topological_sort_from(changed_deps.chain(refreshed_deps))
+        .into_iter()
+        .collect();
+    if !settling {
+        changed.clear();
+    }
+    to_update.retain(|key| !debounce.delay(key, now));
+
+    let mut retries = quarantine.lock().take_expired(now);
+    retries.retain(|key| !to_update.contains(key));
+
+    // In a transaction, new values are only written once they are all loaded
+    let staging = atomic.then(Staging::new);
+    let reload_cache = match &staging {
+        Some(staging) => cache.with_staging(staging),
+        None => cache,
+    };
+
+    for key in to_update.into_iter().chain(retries) {
+        if quarantine.lock().skip(&key, now) {
+            log::debug!("Skipping reload of quarantined asset \"{}\"", key.id);
+            continue;
+        }
+
+        // Do not keep the lock while reloading, as it may take a while
+        let result = deps.reload(reload_cache.as_any_cache(), key.clone(), staging.as_ref());
+        match result {
+            Some(true) => quarantine.lock().on_success(&key),
+            Some(false) => quarantine.lock().on_failure(key, now),
+            None => (),
+        }
+    }
 
-    for key in to_update.into_iter() {
-        deps.reload(cache.as_any_cache(), key);
+    if let Some(staging) = staging {
+        staging.commit(cache.assets.map);
     }
 }
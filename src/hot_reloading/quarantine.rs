@@ -0,0 +1,188 @@
+use super::AssetKey;
+use crate::utils::HashMap;
+use crate::SharedString;
+use std::{
+    any::TypeId,
+    time::{Duration, Instant},
+};
+
+#[cfg(doc)]
+use crate::AssetCache;
+
+/// Configures how assets that repeatedly fail to reload are quarantined.
+///
+/// When an asset fails to reload `threshold` times in a row, it is not
+/// reloaded again until a delay has elapsed, starting at `base_delay` and
+/// doubling with each new failure, up to `max_delay`. Changes that happen
+/// during that time are not lost: the asset is reloaded once the delay
+/// elapsed.
+///
+/// The quarantine of an asset ends when it is successfully reloaded.
+///
+/// See [`AssetCache::set_quarantine_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuarantinePolicy {
+    /// The number of consecutive failures after which an asset is
+    /// quarantined.
+    pub threshold: u32,
+
+    /// The delay after the first failure in quarantine.
+    pub base_delay: Duration,
+
+    /// The maximum delay between two reloads of a quarantined asset.
+    pub max_delay: Duration,
+}
+
+impl Default for QuarantinePolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl QuarantinePolicy {
+    fn delay(&self, failures: u32) -> Duration {
+        let exp = failures.saturating_sub(self.threshold).min(31);
+        self.base_delay.saturating_mul(1 << exp).min(self.max_delay)
+    }
+}
+
+/// An asset that is quarantined after failing to reload repeatedly.
+///
+/// See [`AssetCache::quarantined_assets`].
+#[derive(Debug, Clone)]
+pub struct QuarantinedAsset {
+    id: SharedString,
+    type_id: TypeId,
+    failures: u32,
+    until: Instant,
+}
+
+impl QuarantinedAsset {
+    /// The id of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// The `TypeId` of the asset.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The number of consecutive failed reloads.
+    #[inline]
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// The instant until which the asset will not be reloaded.
+    #[inline]
+    pub fn until(&self) -> Instant {
+        self.until
+    }
+}
+
+struct Failures {
+    count: u32,
+    until: Option<Instant>,
+
+    /// `true` if a reload was skipped because of the quarantine.
+    pending: bool,
+}
+
+pub(crate) struct Quarantine {
+    policy: Option<QuarantinePolicy>,
+    failures: HashMap<AssetKey, Failures>,
+}
+
+impl Quarantine {
+    pub fn new() -> Self {
+        Self {
+            policy: None,
+            failures: HashMap::new(),
+        }
+    }
+
+    pub fn set_policy(&mut self, policy: Option<QuarantinePolicy>) {
+        self.policy = policy;
+        if policy.is_none() {
+            self.failures.clear();
+        }
+    }
+
+    /// Returns `true` if the asset should not be reloaded now, and remembers
+    /// to reload it later.
+    pub fn skip(&mut self, key: &AssetKey, now: Instant) -> bool {
+        match self.failures.get_mut(key) {
+            Some(f) if f.until.is_some_and(|until| until > now) => {
+                f.pending = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn on_success(&mut self, key: &AssetKey) {
+        if self.failures.remove(key).is_some_and(|f| f.until.is_some()) {
+            log::info!("\"{}\" is not quarantined anymore", key.id);
+        }
+    }
+
+    pub fn on_failure(&mut self, key: AssetKey, now: Instant) {
+        let Some(policy) = &self.policy else { return };
+
+        let f = self.failures.entry(key).or_insert(Failures {
+            count: 0,
+            until: None,
+            pending: false,
+        });
+        f.count = f.count.saturating_add(1);
+
+        if f.count >= policy.threshold {
+            f.until = Some(now + policy.delay(f.count));
+        }
+    }
+
+    /// Returns the keys of assets whose reload was skipped and whose
+    /// quarantine delay elapsed.
+    pub fn take_expired(&mut self, now: Instant) -> Vec<AssetKey> {
+        let mut expired = Vec::new();
+
+        for (key, f) in self.failures.iter_mut() {
+            if f.pending && f.until.is_some_and(|until| until <= now) {
+                f.pending = false;
+                expired.push(key.clone());
+            }
+        }
+
+        expired
+    }
+
+    /// Returns the next instant at which a skipped reload should be retried.
+    pub fn next_retry(&self) -> Option<Instant> {
+        self.failures
+            .values()
+            .filter(|f| f.pending)
+            .filter_map(|f| f.until)
+            .min()
+    }
+
+    pub fn list(&self) -> Vec<QuarantinedAsset> {
+        self.failures
+            .iter()
+            .filter_map(|(key, f)| {
+                Some(QuarantinedAsset {
+                    id: key.id.clone(),
+                    type_id: key.type_id,
+                    failures: f.count,
+                    until: f.until?,
+                })
+            })
+            .collect()
+    }
+}
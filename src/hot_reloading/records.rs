@@ -1,17 +1,102 @@
-use crate::utils::{HashSet, OwnedKey, SharedString};
-use std::{any::TypeId, cell::Cell, ptr::NonNull};
+use crate::{source::OwnedDirEntry, utils::HashSet, SharedString, Storable};
+use std::{
+    any::TypeId, cell::Cell, collections::hash_map::DefaultHasher, fmt, hash::Hasher, ptr::NonNull,
+};
 
 use super::HotReloader;
 
+/// The key used to identify an asset in a cache: its id and its type.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub(crate) enum Dependency {
+pub struct AssetKey {
+    pub(crate) type_id: TypeId,
+    pub(crate) id: SharedString,
+}
+
+impl AssetKey {
+    /// Creates the key of the asset of type `T` with the given id.
+    #[inline]
+    pub fn new<T: Storable>(id: SharedString) -> Self {
+        Self::new_with(id, TypeId::of::<T>())
+    }
+
+    /// Creates a `AssetKey` with the given type and id.
+    #[inline]
+    pub fn new_with(id: SharedString, type_id: TypeId) -> Self {
+        Self { id, type_id }
+    }
+
+    /// The id of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// The `TypeId` of the asset.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+}
+
+/// Something an asset depends on.
+///
+/// When an asset is loaded in a hot-reloaded cache, the files and
+/// directories read and the assets loaded are recorded as dependencies of the
+/// asset. When a dependency changes, the asset is reloaded.
+///
+/// Two dependencies are equal if they point at the same entity.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Dependency {
+    /// A file, with its id and its extension.
     File(SharedString, SharedString),
+
+    /// A directory with its id.
     Directory(SharedString),
-    Asset(OwnedKey),
+
+    /// An asset stored in the cache.
+    Asset(AssetKey),
 }
 
 impl Dependency {
-    pub fn as_borrowed(&self) -> BorrowedDependency<'_> {
+    /// Creates a dependency on a file.
+    #[inline]
+    pub fn file(id: SharedString, ext: SharedString) -> Self {
+        Self::File(id, ext)
+    }
+
+    /// Creates a dependency on a directory.
+    #[inline]
+    pub fn directory(id: SharedString) -> Self {
+        Self::Directory(id)
+    }
+
+    /// Creates a dependency on the asset of type `T` with the given id.
+    #[inline]
+    pub fn asset<T: Storable>(id: SharedString) -> Self {
+        Self::Asset(AssetKey::new::<T>(id))
+    }
+
+    /// The id of the pointed entity.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        match self {
+            Dependency::File(id, _) | Dependency::Directory(id) => id,
+            Dependency::Asset(key) => &key.id,
+        }
+    }
+
+    /// Returns the entry of the source if the dependency is a file or a
+    /// directory.
+    #[inline]
+    pub fn to_dir_entry(&self) -> Option<OwnedDirEntry> {
+        match self {
+            Dependency::File(id, ext) => Some(OwnedDirEntry::File(id.clone(), ext.clone())),
+            Dependency::Directory(id) => Some(OwnedDirEntry::Directory(id.clone())),
+            Dependency::Asset(_) => None,
+        }
+    }
+
+    pub(crate) fn as_borrowed(&self) -> BorrowedDependency<'_> {
         match self {
             Dependency::File(id, ext) => BorrowedDependency::File(id, ext),
             Dependency::Directory(id) => BorrowedDependency::Directory(id),
@@ -20,17 +105,135 @@ impl Dependency {
     }
 }
 
-pub(crate) type Dependencies = HashSet<Dependency>;
+impl From<OwnedDirEntry> for Dependency {
+    #[inline]
+    fn from(entry: OwnedDirEntry) -> Self {
+        match entry {
+            OwnedDirEntry::File(id, ext) => Self::File(id, ext),
+            OwnedDirEntry::Directory(id) => Self::Directory(id),
+        }
+    }
+}
+
+impl From<AssetKey> for Dependency {
+    #[inline]
+    fn from(key: AssetKey) -> Self {
+        Self::Asset(key)
+    }
+}
+
+/// A set of [`Dependency`].
+pub struct Dependencies(pub(crate) HashSet<Dependency>);
+
+impl Dependencies {
+    /// Creates an empty set of dependencies.
+    #[inline]
+    pub fn new() -> Self {
+        Self(HashSet::new())
+    }
+
+    /// Returns the number of dependencies in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the set contains no dependency.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the set contains the given dependency.
+    #[inline]
+    pub fn contains(&self, dep: &Dependency) -> bool {
+        self.0.contains(dep)
+    }
+
+    /// Adds a dependency to the set.
+    ///
+    /// Returns `false` if the set already contained this dependency.
+    #[inline]
+    pub fn insert(&mut self, dep: Dependency) -> bool {
+        self.0.insert(dep)
+    }
+
+    /// Removes a dependency from the set.
+    ///
+    /// Returns `true` if the set contained this dependency.
+    #[inline]
+    pub fn remove(&mut self, dep: &Dependency) -> bool {
+        self.0.remove(dep)
+    }
+
+    /// Returns an iterator over the dependencies of the set, in arbitrary
+    /// order.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Dependency> + '_ {
+        self.0.iter()
+    }
+}
+
+impl Default for Dependencies {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for Dependencies {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl PartialEq for Dependencies {
+    fn eq(&self, other: &Self) -> bool {
+        *self.0 == *other.0
+    }
+}
+
+impl Eq for Dependencies {}
+
+impl fmt::Debug for Dependencies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Dependency> for Dependencies {
+    fn from_iter<I: IntoIterator<Item = Dependency>>(iter: I) -> Self {
+        let mut deps = Self::new();
+        deps.extend(iter);
+        deps
+    }
+}
+
+impl Extend<Dependency> for Dependencies {
+    fn extend<I: IntoIterator<Item = Dependency>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<'a> IntoIterator for &'a Dependencies {
+    type Item = &'a Dependency;
+    type IntoIter = hashbrown::hash_set::Iter<'a, Dependency>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum BorrowedDependency<'a> {
     File(&'a SharedString, &'a SharedString),
     Directory(&'a SharedString),
-    Asset(&'a OwnedKey),
+    Asset(&'a AssetKey),
 }
 
 impl BorrowedDependency<'_> {
-    pub fn into_owned(self) -> Dependency {
+    pub(crate) fn into_owned(self) -> Dependency {
         match self {
             BorrowedDependency::File(id, ext) => Dependency::File(id.clone(), ext.clone()),
             BorrowedDependency::Directory(id) => Dependency::Directory(id.clone()),
@@ -45,9 +248,23 @@ impl hashbrown::Equivalent<Dependency> for BorrowedDependency<'_> {
     }
 }
 
+/// Hashes of the content of the files read by a loader.
+pub(crate) type FileHashes = Vec<(OwnedDirEntry, u64)>;
+
+/// Hashes the content of a file.
+///
+/// Hashes are compared across threads, so they do not use a random seed.
+pub(crate) fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(content);
+    hasher.finish()
+}
+
 struct Record {
     reloader: *const HotReloader,
     records: Dependencies,
+    hashes: FileHashes,
+    warned: bool,
 }
 
 impl Record {
@@ -55,25 +272,35 @@ impl Record {
         Record {
             reloader,
             records: Dependencies::new(),
+            hashes: Vec::new(),
+            warned: false,
         }
     }
 
-    fn insert_asset(&mut self, reloader: &HotReloader, key: OwnedKey) {
+    fn insert(&mut self, reloader: &HotReloader, dep: Dependency) {
         if self.reloader == reloader {
-            self.records.insert(Dependency::Asset(key));
+            self.records.insert(dep);
+        } else if !self.warned {
+            // Dependencies are tracked per cache, so changes to an asset of
+            // another cache cannot trigger a reload of the recorded asset.
+            self.warned = true;
+            let id = dep.id();
+            log::warn!(
+                "\"{id}\" was loaded from another hot-reloaded cache: changes to it will not be propagated"
+            );
         }
     }
 
+    fn insert_asset(&mut self, reloader: &HotReloader, key: AssetKey) {
+        self.insert(reloader, Dependency::Asset(key));
+    }
+
     fn insert_file(&mut self, reloader: &HotReloader, id: SharedString, ext: SharedString) {
-        if self.reloader == reloader {
-            self.records.insert(Dependency::File(id, ext));
-        }
+        self.insert(reloader, Dependency::File(id, ext));
     }
 
     fn insert_dir(&mut self, reloader: &HotReloader, id: SharedString) {
-        if self.reloader == reloader {
-            self.records.insert(Dependency::Directory(id));
-        }
+        self.insert(reloader, Dependency::Directory(id));
     }
 }
 
@@ -100,12 +327,15 @@ thread_local! {
     static RECORDING: Cell<Option<NonNull<Record>>> = const { Cell::new(None) };
 }
 
-pub(crate) fn record<F: FnOnce() -> T, T>(reloader: &HotReloader, f: F) -> (T, Dependencies) {
+pub(crate) fn record<F: FnOnce() -> T, T>(
+    reloader: &HotReloader,
+    f: F,
+) -> (T, Dependencies, FileHashes) {
     RECORDING.with(|rec| {
         let mut record = Record::new(reloader);
         let _guard = CellGuard::replace(rec, Some(NonNull::from(&mut record)));
         let result = f();
-        (result, record.records)
+        (result, record.records, record.hashes)
     })
 }
 
@@ -120,7 +350,7 @@ pub(crate) fn add_record(reloader: &HotReloader, id: SharedString, type_id: Type
     RECORDING.with(|rec| {
         if let Some(mut recorder) = rec.get() {
             let recorder = unsafe { recorder.as_mut() };
-            recorder.insert_asset(reloader, OwnedKey::new_with(id, type_id));
+            recorder.insert_asset(reloader, AssetKey::new_with(id, type_id));
         }
     });
 }
@@ -134,6 +364,19 @@ pub(crate) fn add_file_record(reloader: &HotReloader, id: &str, ext: &str) {
     });
 }
 
+/// Records the hash of the content of a file read by the loader.
+pub(crate) fn add_file_hash(reloader: &HotReloader, id: &str, ext: &str, hash: u64) {
+    RECORDING.with(|rec| {
+        if let Some(mut recorder) = rec.get() {
+            let recorder = unsafe { recorder.as_mut() };
+            if recorder.reloader == reloader {
+                let entry = OwnedDirEntry::File(id.into(), ext.into());
+                recorder.hashes.push((entry, hash));
+            }
+        }
+    });
+}
+
 pub(crate) fn add_dir_record(reloader: &HotReloader, id: &str) {
     RECORDING.with(|rec| {
         if let Some(mut recorder) = rec.get() {
@@ -0,0 +1,127 @@
+use super::AssetKey;
+use crate::{
+    utils::{HashMap, HashSet},
+    SharedString,
+};
+use std::{
+    any::TypeId,
+    time::{Duration, Instant},
+};
+
+/// Selects the assets that are periodically refreshed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RefreshFilter {
+    Type(TypeId),
+    Prefix(SharedString),
+}
+
+impl RefreshFilter {
+    fn matches(&self, key: &AssetKey) -> bool {
+        match self {
+            RefreshFilter::Type(type_id) => key.type_id == *type_id,
+            RefreshFilter::Prefix(prefix) => {
+                prefix.is_empty()
+                    || key
+                        .id
+                        .strip_prefix(&**prefix)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            }
+        }
+    }
+}
+
+pub(crate) struct Refresh {
+    rules: Vec<(RefreshFilter, Duration)>,
+    next: HashMap<AssetKey, Instant>,
+
+    /// Assets to refresh at the next update, whatever the rules.
+    forced: HashSet<AssetKey>,
+}
+
+impl Refresh {
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            next: HashMap::new(),
+            forced: HashSet::new(),
+        }
+    }
+
+    /// The interval at which an asset is refreshed, the smallest of all
+    /// matching rules.
+    fn interval(&self, key: &AssetKey) -> Option<Duration> {
+        self.rules
+            .iter()
+            .filter(|(filter, _)| filter.matches(key))
+            .map(|(_, interval)| *interval)
+            .min()
+    }
+
+    /// Changes the rule for `filter` and reschedules all given assets.
+    pub fn set_rule<'a>(
+        &mut self,
+        filter: RefreshFilter,
+        interval: Option<Duration>,
+        assets: impl Iterator<Item = &'a AssetKey>,
+        now: Instant,
+    ) {
+        self.rules.retain(|(f, _)| *f != filter);
+        if let Some(interval) = interval {
+            self.rules.push((filter, interval));
+        }
+
+        self.next.clear();
+        for key in assets {
+            self.add_asset(key, now);
+        }
+    }
+
+    /// Schedules the refresh of a new asset.
+    pub fn add_asset(&mut self, key: &AssetKey, now: Instant) {
+        if self.rules.is_empty() || self.next.contains_key(key) {
+            return;
+        }
+        if let Some(interval) = self.interval(key) {
+            self.next.insert(key.clone(), now + interval);
+        }
+    }
+
+    /// Stops refreshing an asset.
+    pub fn remove_asset(&mut self, key: &AssetKey) {
+        self.next.remove(key);
+        self.forced.remove(key);
+    }
+
+    /// Refreshes an asset at the next update.
+    pub fn force(&mut self, key: AssetKey) {
+        self.forced.insert(key);
+    }
+
+    pub fn clear_forced(&mut self) {
+        self.forced.clear();
+    }
+
+    /// Returns the assets that should be refreshed now, and schedules their
+    /// next refresh.
+    pub fn take_due(&mut self, now: Instant) -> Vec<AssetKey> {
+        let mut due: Vec<_> = self.forced.drain().collect();
+
+        for (key, next) in self.next.iter() {
+            if *next <= now && !due.contains(key) {
+                due.push(key.clone());
+            }
+        }
+        for key in &due {
+            if let Some(interval) = self.interval(key) {
+                self.next.insert(key.clone(), now + interval);
+            }
+        }
+
+        due
+    }
+
+    /// Returns the next instant at which an asset should be refreshed.
+    pub fn next_refresh(&self) -> Option<Instant> {
+        self.next.values().copied().min()
+    }
+}
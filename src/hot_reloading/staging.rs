@@ -0,0 +1,56 @@
+use super::AssetKey;
+use crate::{
+    anycache::AssetMap,
+    entry::{CacheEntry, UntypedHandle},
+    utils::{HashMap, Mutex},
+};
+use std::any::TypeId;
+
+/// New values of assets reloaded together, which are hidden from the cache
+/// until they are committed.
+pub(crate) struct Staging {
+    entries: Mutex<HashMap<AssetKey, CacheEntry>>,
+}
+
+impl Staging {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, id: &str, type_id: TypeId) -> Option<&UntypedHandle> {
+        let key = AssetKey::new_with(id.into(), type_id);
+        let entries = self.entries.lock();
+        let entry = entries.get(&key)?;
+        // Safety: entries are boxed and are never removed before the staging
+        // is consumed.
+        unsafe { Some(entry.inner().extend_lifetime()) }
+    }
+
+    pub fn insert(&self, key: AssetKey, entry: CacheEntry) {
+        let mut entries = self.entries.lock();
+        match entries.get(&key) {
+            // Do not drop an entry that may still be borrowed
+            Some(staged) => staged.inner().write(entry),
+            None => {
+                entries.insert(key, entry);
+            }
+        }
+    }
+
+    /// Writes all new values to the cache at once.
+    pub fn commit(mut self, cache: &crate::cache::AssetMap) {
+        let writes: Vec<_> = self
+            .entries
+            .get_mut()
+            .drain()
+            .filter_map(|(key, entry)| Some((cache.get(&key.id, key.type_id)?, entry)))
+            .collect();
+
+        if !writes.is_empty() {
+            log::debug!("Committing {} reloaded assets", writes.len());
+            crate::entry::write_all(writes);
+        }
+    }
+}
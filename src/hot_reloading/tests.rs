@@ -1,12 +1,34 @@
 use crate::{
-    source::DirEntry,
-    tests::{X, Y, Z},
+    source::{DirEntry, Source},
+    tests::{Scaled, X, Y, Z},
     AssetCache, BoxedError,
 };
-use std::{fs::File, io, io::Write, path::Path, sync::Arc};
+use std::{
+    fs::File,
+    io,
+    io::Write,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 fn sleep() {
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    std::thread::sleep(Duration::from_millis(20));
+}
+
+/// Calls `hot_reload` until `cond` returns `true`, failing after a timeout.
+///
+/// This is a no-op wait for caches with `enhance_hot_reloading`.
+fn reload_until<S: Source + Sync>(cache: &AssetCache<S>, mut cond: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        cache.hot_reload();
+        if cond() {
+            return;
+        }
+        assert!(Instant::now() < deadline, "timed out waiting for a reload");
+        sleep();
+    }
 }
 
 type Res = Result<(), Box<dyn std::error::Error>>;
@@ -27,6 +49,17 @@ macro_rules! test_scenario {
     (@reload $cache:ident true) => {};
     (@reload $cache:ident false) => { $cache.hot_reload(); };
 
+    // A single write may trigger several reloads, so wait for the right one
+    (@wait $cache:ident $watcher:ident $asset:ident $n:expr, true) => {
+        let mut watcher = $watcher;
+        while $asset.read().0 != $n {
+            assert!(watcher.wait_for_reload(std::time::Duration::from_secs(5)));
+        }
+    };
+    (@wait $cache:ident $watcher:ident $asset:ident $n:expr, false) => {
+        reload_until(&$cache, || $asset.read().0 == $n);
+    };
+
     (
         name: $name:ident,
         is_static: $is_static:tt,
@@ -44,9 +77,13 @@ macro_rules! test_scenario {
 
             test_scenario!(@leak cache $is_static);
 
+            // Only write the file if needed, so that no event can be received
+            // after the asset is loaded
             let path = cache.raw_source().path_of(DirEntry::File(id, "x"));
-            write_i32(&path, $n)?;
-            sleep();
+            if std::fs::read_to_string(&path)? != $n.to_string() {
+                write_i32(&path, $n)?;
+                sleep();
+            }
 
             test_scenario!(@enhance cache $is_static);
 
@@ -58,16 +95,14 @@ macro_rules! test_scenario {
 
             let n = rand::random();
             write_i32(&path, n)?;
-            sleep();
-            test_scenario!(@reload cache $is_static);
+            test_scenario!(@wait cache watcher asset n, $is_static);
             assert_eq!(asset.read().0, n);
             assert!(watcher.reloaded());
             assert!(!watcher.reloaded());
             $( assert!(!cache.contains::<$not_loaded>(id)); )?
 
             write_i32(&path, $n)?;
-            sleep();
-            test_scenario!(@reload cache $is_static);
+            test_scenario!(@wait cache watcher asset $n, $is_static);
             assert_eq!(asset.read().0, $n);
             assert!(watcher.reloaded());
             assert!(!watcher.reloaded());
@@ -140,43 +175,1130 @@ test_scenario! {
 fn directory() -> Result<(), BoxedError> {
     let _ = env_logger::try_init();
 
-    let _ = std::fs::remove_dir_all("assets/test/hot_dir/");
-    std::fs::create_dir_all("assets/test/hot_dir/")?;
-    write_i32("assets/test/hot_dir/a.x".as_ref(), 1)?;
+    let root = std::env::temp_dir().join("assets_manager_directory");
+    let dir = root.join("dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    write_i32(&dir.join("a.x"), 1)?;
 
-    let cache = AssetCache::new("assets")?;
+    let cache = AssetCache::new(&root)?;
+    sleep();
+
+    let handle = cache.load_dir::<X>("dir")?;
+    let mut watcher = handle.reload_watcher();
+    cache.hot_reload();
+    assert!(!watcher.reloaded());
 
-    let dir = cache.load_dir::<X>("test.hot_dir")?;
-    let mut watcher = dir.reload_watcher();
+    assert_eq!(handle.read().ids().collect::<Vec<_>>(), ["dir.a"]);
+
+    write_i32(&dir.join("a.x"), 1)?;
+    sleep();
+    cache.hot_reload();
     assert!(!watcher.reloaded());
 
-    assert_eq!(dir.read().ids().collect::<Vec<_>>(), ["test.hot_dir.a"]);
+    write_i32(&dir.join("b.x"), 1)?;
+    reload_until(&cache, || handle.read().ids().count() == 2);
+    assert_eq!(handle.read().ids().collect::<Vec<_>>(), ["dir.a", "dir.b"]);
+    assert!(watcher.reloaded());
+
+    std::fs::remove_file(dir.join("b.x"))?;
+    reload_until(&cache, || handle.read().ids().count() == 1);
+    assert_eq!(handle.read().ids().collect::<Vec<_>>(), ["dir.a"]);
+    assert!(watcher.reloaded());
+
+    std::fs::remove_file(dir.join("a.x"))?;
+    reload_until(&cache, || handle.read().ids().count() == 0);
+    assert!(watcher.reloaded());
+
+    Ok(())
+}
+
+#[test]
+fn filtered_dir() -> Result<(), BoxedError> {
+    use crate::{DirFilter, FilteredDir};
+
+    struct NoSkip;
+    impl DirFilter for NoSkip {
+        fn includes(id: &str) -> bool {
+            !id.ends_with(".skip")
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let root = std::env::temp_dir().join("assets_manager_filtered_dir");
+    let dir = root.join("dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    write_i32(&dir.join("a.x"), 1)?;
+
+    let cache = AssetCache::new(&root)?;
+    sleep();
+
+    let handle = cache.load::<FilteredDir<X, NoSkip>>("dir")?;
+    let mut watcher = handle.reload_watcher();
+    assert_eq!(handle.read().ids().collect::<Vec<_>>(), ["dir.a"]);
+
+    write_i32(&dir.join("skip.x"), 1)?;
+    sleep();
+    cache.hot_reload();
+    assert_eq!(handle.read().ids().collect::<Vec<_>>(), ["dir.a"]);
+
+    write_i32(&dir.join("b.x"), 1)?;
+    reload_until(&cache, || handle.read().ids().count() == 2);
+    assert_eq!(handle.read().ids().collect::<Vec<_>>(), ["dir.a", "dir.b"]);
+    assert!(watcher.reloaded());
 
-    write_i32("assets/test/hot_dir/a.x".as_ref(), 1)?;
+    Ok(())
+}
+
+#[test]
+fn skip_unchanged() -> Res {
+    let _ = env_logger::try_init();
+
+    // Write atomically, so that no event can see a partially written file
+    fn write_atomic(path: &Path, n: i32) -> io::Result<()> {
+        let tmp = path.with_extension("tmp");
+        write_i32(&tmp, n)?;
+        std::fs::rename(tmp, path)
+    }
+
+    let dir = std::env::temp_dir().join("assets_manager_skip_unchanged");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_atomic(&path, 65)?;
+
+    let cache = AssetCache::new(&dir)?;
+    cache.skip_unchanged_reloads(true);
+    sleep();
+
+    let asset = cache.load::<X>("a")?;
+    let mut watcher = asset.reload_watcher();
+    cache.hot_reload();
+
+    write_atomic(&path, 65)?;
     sleep();
     cache.hot_reload();
     assert!(!watcher.reloaded());
 
-    write_i32("assets/test/hot_dir/b.x".as_ref(), 1)?;
+    write_atomic(&path, 12)?;
+    reload_until(&cache, || asset.read().0 == 12);
+    assert!(watcher.reloaded());
+
+    write_atomic(&path, 65)?;
+    reload_until(&cache, || asset.read().0 == 65);
+
+    Ok(())
+}
+
+#[test]
+fn skip_unchanged_edit_during_load() -> Res {
+    use crate::{
+        hot_reloading::EventSender,
+        source::{FileContent, OwnedDirEntry},
+        utils::Mutex,
+    };
+    use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+    /// A source whose file is edited right after it is first read
+    #[derive(Clone)]
+    struct Racy(
+        Arc<AtomicI32>,
+        Arc<AtomicBool>,
+        Arc<Mutex<Option<EventSender>>>,
+    );
+
+    impl Source for Racy {
+        fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+            match (id, ext) {
+                ("a", "x") => {
+                    let n = self.0.load(Ordering::Relaxed);
+                    if self.1.swap(false, Ordering::Relaxed) {
+                        self.0.store(n + 1, Ordering::Relaxed);
+                    }
+                    Ok(n.to_string().into_bytes().into())
+                }
+                _ => Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+
+        fn read_dir(&self, _: &str, _: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn exists(&self, entry: DirEntry) -> bool {
+            entry == DirEntry::File("a", "x")
+        }
+
+        fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+            Some(Box::new(self.clone()))
+        }
+
+        fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+            *self.2.lock() = Some(events);
+            Ok(())
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let source = Racy(
+        Arc::new(AtomicI32::new(1)),
+        Arc::new(AtomicBool::new(true)),
+        Arc::default(),
+    );
+    let cache = AssetCache::with_source(source.clone());
+    cache.skip_unchanged_reloads(true);
+
+    let asset = cache.load::<X>("a")?;
+    assert_eq!(asset.read().0, 1);
+    cache.hot_reload();
+
+    // The edit made during the load is not mistaken for the loaded content
+    let events = source.2.lock().clone().unwrap();
+    events
+        .send(OwnedDirEntry::File("a".into(), "x".into()))
+        .unwrap();
+    reload_until(&cache, || asset.read().0 == 2);
+
+    Ok(())
+}
+
+#[test]
+fn quarantine() -> Res {
+    use crate::hot_reloading::QuarantinePolicy;
+
+    let _ = env_logger::try_init();
+
+    let dir = std::env::temp_dir().join("assets_manager_quarantine");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let cache = AssetCache::new(&dir)?;
+    cache.set_quarantine_policy(Some(QuarantinePolicy {
+        threshold: 1,
+        base_delay: Duration::from_millis(300),
+        max_delay: Duration::from_secs(1),
+    }));
+    sleep();
+
+    let asset = cache.load::<X>("a")?;
+    cache.hot_reload();
+    assert!(cache.quarantined_assets().is_empty());
+
+    std::fs::write(&path, "invalid")?;
+    reload_until(&cache, || !cache.quarantined_assets().is_empty());
+    let quarantined = cache.quarantined_assets();
+    assert_eq!(quarantined.len(), 1);
+    assert_eq!(quarantined[0].id(), "a");
+
+    // Fixing the asset during the quarantine does not reload it immediately
+    write_i32(&path, 2)?;
+    sleep();
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 1);
+
+    reload_until(&cache, || asset.read().0 == 2);
+    assert!(cache.quarantined_assets().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn dependencies() {
+    use super::{AssetKey, Dependencies, Dependency};
+    use crate::source::OwnedDirEntry;
+
+    let file = Dependency::file("a.b".into(), "x".into());
+    let entry = OwnedDirEntry::File("a.b".into(), "x".into());
+    assert_eq!(file, Dependency::from(entry.clone()));
+    assert_eq!(file.to_dir_entry(), Some(entry));
+    assert_eq!(file.id(), "a.b");
+
+    let asset = Dependency::asset::<X>("a.b".into());
+    assert_eq!(asset, AssetKey::new::<X>("a.b".into()).into());
+    assert_ne!(asset, Dependency::asset::<Y>("a.b".into()));
+    assert!(asset.to_dir_entry().is_none());
+
+    let mut deps: Dependencies = [file.clone(), Dependency::directory("a".into())]
+        .into_iter()
+        .collect();
+    assert!(!deps.insert(file.clone()));
+    assert!(deps.insert(asset.clone()));
+    assert_eq!(deps.len(), 3);
+    assert!(deps.contains(&asset));
+    assert_eq!(deps.clone(), deps);
+
+    assert!(deps.remove(&file));
+    assert!(!deps.contains(&file));
+}
+
+#[test]
+fn refresh() -> Res {
+    use crate::{
+        hot_reloading::EventSender,
+        source::{FileContent, Source},
+        utils::Mutex,
+    };
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    /// A source that does not send events
+    #[derive(Clone)]
+    struct Remote(Arc<AtomicI32>, Arc<Mutex<Option<EventSender>>>);
+
+    impl Source for Remote {
+        fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+            match (id, ext) {
+                ("a", "x") => Ok(self
+                    .0
+                    .load(Ordering::Relaxed)
+                    .to_string()
+                    .into_bytes()
+                    .into()),
+                _ => Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+
+        fn read_dir(&self, _: &str, _: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn exists(&self, entry: DirEntry) -> bool {
+            entry == DirEntry::File("a", "x")
+        }
+
+        fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+            Some(Box::new(self.clone()))
+        }
+
+        fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+            *self.1.lock() = Some(events);
+            Ok(())
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let value = Arc::new(AtomicI32::new(1));
+    let cache = AssetCache::with_source(Remote(value.clone(), Arc::default()));
+    cache.set_prefix_refresh_interval("a", Some(Duration::from_millis(50)));
+
+    let asset = cache.load::<Y>("a")?;
+    value.store(2, Ordering::Relaxed);
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 1);
+
+    reload_until(&cache, || asset.read().0 == 2);
+
+    // Removing the rule stops refreshes
+    cache.set_prefix_refresh_interval("a", None);
+    value.store(3, Ordering::Relaxed);
+    std::thread::sleep(Duration::from_millis(100));
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 2);
+
+    // Refreshes can also be set for a single type
+    cache.set_refresh_interval::<X>(Some(Duration::from_millis(50)));
+    reload_until(&cache, || asset.read().0 == 3);
+
+    Ok(())
+}
+
+#[test]
+fn default_value_created() -> Res {
+    use crate::{loader, Asset, SharedString};
+
+    struct D(i32);
+
+    impl From<i32> for D {
+        fn from(n: i32) -> D {
+            D(n)
+        }
+    }
+
+    impl Asset for D {
+        type Loader = loader::LoadFrom<i32, loader::ParseLoader>;
+        const EXTENSIONS: &'static [&'static str] = &["x", "y"];
+
+        fn default_value(_: &SharedString, _: BoxedError) -> Result<D, BoxedError> {
+            Ok(D(-1))
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let id = "test.hot_default.a";
+    let cache = AssetCache::new("assets")?;
+
+    let path = cache.raw_source().path_of(DirEntry::File(id, "y"));
+    let dir = path.parent().unwrap();
+    let _ = std::fs::remove_dir_all(dir);
+    sleep();
+
+    let asset = cache.load::<D>(id)?;
+    assert_eq!(asset.read().0, -1);
+    cache.hot_reload();
+
+    // Creating one of the missing files replaces the default value
+    std::fs::create_dir(dir)?;
+    write_i32(&path, 5)?;
+    reload_until(&cache, || asset.read().0 == 5);
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn unload_cascade() -> Res {
+    use crate::{AnyCache, Compound, SharedString};
+    use std::any::TypeId;
+
+    struct Boss;
+
+    impl Compound for Boss {
+        fn load(cache: AnyCache, _: &SharedString) -> Result<Self, BoxedError> {
+            cache.load::<X>("test.b")?;
+            cache.load::<X>("test.cache")?;
+            Ok(Boss)
+        }
+    }
+
+    struct Level;
+
+    impl Compound for Level {
+        fn load(cache: AnyCache, _: &SharedString) -> Result<Self, BoxedError> {
+            cache.load::<Boss>("boss")?;
+            cache.load::<X>("test.cache")?;
+            Ok(Level)
+        }
+    }
+
+    let mut cache = AssetCache::new("assets")?;
+    cache.load::<Boss>("boss")?;
+    cache.load::<Level>("level")?;
+
+    // `test.cache` is still used by `level`
+    assert_eq!(cache.unload_cascade("boss", TypeId::of::<Boss>()), 2);
+    assert!(!cache.contains::<Boss>("boss"));
+    assert!(!cache.contains::<X>("test.b"));
+    assert!(cache.contains::<X>("test.cache"));
+
+    // Everything is only used by `level` now
+    cache.load::<Boss>("boss")?;
+    assert_eq!(cache.unload_cascade("level", TypeId::of::<Level>()), 4);
+    assert!(!cache.contains::<Boss>("boss"));
+    assert!(!cache.contains::<X>("test.cache"));
+
+    // Unknown assets are only removed from the cache
+    assert_eq!(cache.unload_cascade("test.b", TypeId::of::<X>()), 0);
+    cache.load::<X>("test.b")?;
+    assert_eq!(cache.unload_cascade("test.b", TypeId::of::<X>()), 1);
+
+    Ok(())
+}
+
+#[test]
+fn freeze() -> Res {
+    let _ = env_logger::try_init();
+
+    let id = "a";
+    let dir = std::env::temp_dir().join("assets_manager_freeze");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let cache = AssetCache::new(&dir)?;
+    sleep();
+
+    let asset = cache.load::<X>(id)?;
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 1);
+
+    let guard = cache.freeze();
+    write_i32(&path, 2)?;
+    std::thread::sleep(Duration::from_millis(200));
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 1);
+
+    // Changes are applied when the cache is unfrozen
+    drop(guard);
+    reload_until(&cache, || asset.read().0 == 2);
+
+    Ok(())
+}
+
+#[test]
+fn load_with() -> Res {
+    let _ = env_logger::try_init();
+
+    let dir = std::env::temp_dir().join("assets_manager_load_with");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let cache = AssetCache::new(&dir)?;
+    sleep();
+
+    let double = cache.load_with::<Scaled>("a", 2)?;
+    let triple = cache.load_with::<Scaled>("a", 3)?;
+    cache.hot_reload();
+    assert_eq!(double.read().0, 2);
+    assert_eq!(triple.read().0, 3);
+
+    // All variants are reloaded
+    write_i32(&path, 5)?;
+    reload_until(&cache, || double.read().0 == 10 && triple.read().0 == 15);
+
+    Ok(())
+}
+
+#[test]
+fn debounce() -> Res {
+    let _ = env_logger::try_init();
+
+    let id = "a";
+    let dir = std::env::temp_dir().join("assets_manager_debounce");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let cache = AssetCache::new(&dir)?;
+    sleep();
+
+    let asset = cache.load::<X>(id)?;
+    cache.hot_reload();
+    cache.set_reload_debounce(Some(Duration::from_millis(300)));
+
+    write_i32(&path, 2)?;
+    std::thread::sleep(Duration::from_millis(50));
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 1);
+
+    reload_until(&cache, || asset.read().0 == 2);
+
+    // Delays can be set for a single type
+    cache.set_reload_debounce(None);
+    cache.set_type_reload_debounce::<X>(Some(Duration::from_millis(300)));
+
+    write_i32(&path, 3)?;
+    std::thread::sleep(Duration::from_millis(50));
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 2);
+
+    reload_until(&cache, || asset.read().0 == 3);
+
+    Ok(())
+}
+
+#[test]
+fn invalidate() -> Res {
+    use crate::{
+        hot_reloading::EventSender,
+        source::{FileContent, Source},
+        utils::Mutex,
+    };
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    /// A source that does not send events
+    #[derive(Clone)]
+    struct Remote(Arc<AtomicI32>, Arc<Mutex<Option<EventSender>>>);
+
+    impl Source for Remote {
+        fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+            match (id, ext) {
+                ("a", "x") => Ok(self
+                    .0
+                    .load(Ordering::Relaxed)
+                    .to_string()
+                    .into_bytes()
+                    .into()),
+                _ => Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+
+        fn read_dir(&self, _: &str, _: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn exists(&self, entry: DirEntry) -> bool {
+            entry == DirEntry::File("a", "x")
+        }
+
+        fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+            Some(Box::new(self.clone()))
+        }
+
+        fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+            *self.1.lock() = Some(events);
+            Ok(())
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let value = Arc::new(AtomicI32::new(1));
+    let cache = AssetCache::with_source(Remote(value.clone(), Arc::default()));
+
+    let compound = cache.load::<Y>("a")?;
+    let asset = cache.load::<X>("a")?;
+    value.store(2, Ordering::Relaxed);
+    cache.hot_reload();
+    assert_eq!(compound.read().0, 1);
+
+    // Dependents are reloaded too
+    cache.invalidate::<X>("a");
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 2);
+    assert_eq!(compound.read().0, 2);
+
+    value.store(3, Ordering::Relaxed);
+    cache.invalidate_id("a");
+    cache.hot_reload();
+    assert_eq!(compound.read().0, 3);
+
+    // Other types are not reloaded
+    value.store(4, Ordering::Relaxed);
+    cache.invalidate::<Y>("a");
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 3);
+
+    Ok(())
+}
+
+#[cfg(feature = "tar")]
+#[test]
+fn tar() -> Res {
+    use crate::source::Tar;
+
+    fn write_tar(path: &Path, n: i32, mtime: u64) -> io::Result<()> {
+        let content = n.to_string();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mtime(mtime);
+        header.set_mode(0o644);
+
+        // Write atomically, so that the archive is never read partially
+        let tmp = path.with_extension("tmp");
+        let mut builder = tar::Builder::new(File::create(&tmp)?);
+        builder.append_data(&mut header, "a.x", content.as_bytes())?;
+        builder.into_inner()?;
+        std::fs::rename(tmp, path)
+    }
+
+    let _ = env_logger::try_init();
+
+    let dir = std::env::temp_dir().join("assets_manager_tar");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("test.tar");
+    write_tar(&path, 1, 1)?;
+
+    let cache = AssetCache::with_source(Tar::open(&path)?);
+
+    let asset = cache.load::<X>("a")?;
+    assert_eq!(asset.read().0, 1);
+
+    write_tar(&path, 2, 2)?;
+    reload_until(&cache, || asset.read().0 == 2);
+
+    Ok(())
+}
+
+#[test]
+fn watch_ignore() -> Res {
+    use crate::source::FileSystem;
+
+    let _ = env_logger::try_init();
+
+    let dir = std::env::temp_dir().join("assets_manager_watch_ignore");
+    std::fs::create_dir_all(dir.join("skipped"))?;
+    write_i32(&dir.join("a.x"), 1)?;
+    write_i32(&dir.join("skipped/b.x"), 1)?;
+
+    let source = FileSystem::new(&dir)?.with_watch_ignore("skip*");
+    let cache = AssetCache::with_source(source);
     sleep();
+
+    let a = cache.load::<X>("a")?;
+    let b = cache.load::<X>("skipped.b")?;
     cache.hot_reload();
+
+    // `b` is written first, so its event would be handled with `a`'s one
+    write_i32(&dir.join("skipped/b.x"), 2)?;
+    write_i32(&dir.join("a.x"), 2)?;
+    reload_until(&cache, || a.read().0 == 2);
+    assert_eq!(b.read().0, 1);
+
+    Ok(())
+}
+
+#[test]
+fn polling() -> Res {
+    use crate::source::FileSystem;
+
+    let _ = env_logger::try_init();
+
+    let dir = std::env::temp_dir().join("assets_manager_polling");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let source = FileSystem::new(&dir)?.with_polling(Duration::from_millis(20), true);
+    let cache = AssetCache::with_source(source);
+
+    let asset = cache.load::<X>("a")?;
+    assert_eq!(asset.read().0, 1);
+
+    // Content hashing detects changes within the same second
+    write_i32(&path, 2)?;
+    reload_until(&cache, || asset.read().0 == 2);
+
+    Ok(())
+}
+
+#[test]
+fn dependency_ids() -> Res {
+    use crate::hot_reloading::AssetKey;
+
+    let _ = env_logger::try_init();
+
+    let dir = std::env::temp_dir().join("assets_manager_dependency_ids");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let cache = AssetCache::new(&dir)?;
+    sleep();
+
+    let compound = cache.load::<Y>("a")?;
+    let asset = cache.load::<X>("a")?;
+    let key = AssetKey::new::<X>("a".into());
     assert_eq!(
-        dir.read().ids().collect::<Vec<_>>(),
-        ["test.hot_dir.a", "test.hot_dir.b"]
+        compound.dependency_ids(),
+        [(key.clone(), asset.last_reload_id())]
     );
+    assert!(asset.dependency_ids().is_empty());
 
+    write_i32(&path, 2)?;
+    let mut watcher = compound.reload_watcher();
+    reload_until(&cache, || compound.read().0 == 2);
     assert!(watcher.reloaded());
+    assert_eq!(compound.dependency_ids(), [(key, asset.last_reload_id())]);
+    assert_ne!(asset.last_reload_id(), crate::ReloadId::NEVER);
+
+    Ok(())
+}
+
+#[test]
+fn atomic_reloads() -> Res {
+    use crate::hot_reloading::AssetKey;
+
+    let _ = env_logger::try_init();
 
-    std::fs::remove_file("assets/test/hot_dir/b.x")?;
+    let dir = std::env::temp_dir().join("assets_manager_atomic_reloads");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("a.x");
+    write_i32(&path, 1)?;
+
+    let cache = AssetCache::new(&dir)?;
     sleep();
+
+    let compound = cache.load::<Y>("a")?;
+    let asset = cache.load::<X>("a")?;
     cache.hot_reload();
-    assert_eq!(dir.read().ids().collect::<Vec<_>>(), ["test.hot_dir.a"]);
-    assert!(watcher.reloaded());
+    cache.set_atomic_reloads(true);
+
+    write_i32(&path, 2)?;
+    reload_until(&cache, || compound.read().0 == 2);
+    assert_eq!(asset.read().0, 2);
+
+    // New values are written to existing entries
+    assert!(std::ptr::eq(cache.get_cached::<X>("a").unwrap(), asset));
+    let key = AssetKey::new::<X>("a".into());
+    assert_eq!(compound.dependency_ids(), [(key, asset.last_reload_id())]);
+
+    Ok(())
+}
+
+#[test]
+fn scan() -> Res {
+    let _ = env_logger::try_init();
 
-    std::fs::remove_file("assets/test/hot_dir/a.x")?;
+    let dir = std::env::temp_dir().join("assets_manager_scan");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("b.x");
+    let _ = std::fs::remove_file(&path);
+    write_i32(&dir.join("a.x"), 1)?;
+
+    let cache = AssetCache::new(&dir)?;
+    cache.scan()?;
     sleep();
+
+    let source = cache.as_any_cache().raw_source();
+    assert!(!source.exists(DirEntry::File("b", "x")));
+
+    // The listing is updated by the watcher
+    write_i32(&path, 2)?;
+    reload_until(&cache, || source.exists(DirEntry::File("b", "x")));
+    assert_eq!(cache.load::<X>("b")?.read().0, 2);
+
+    Ok(())
+}
+
+#[test]
+fn in_memory() -> Res {
+    use crate::source::InMemory;
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+
+    let cache = AssetCache::with_source(source.clone());
+    let asset = cache.load::<X>("a")?;
+    let compound = cache.load::<Y>("a")?;
+    let dir = cache.load_dir::<X>("")?;
+    assert_eq!(dir.read().ids().count(), 1);
+
+    source.insert("a", "x", b"2".to_vec());
+    source.insert("b", "x", b"3".to_vec());
+    reload_until(&cache, || dir.read().ids().count() == 2);
+    assert_eq!(asset.read().0, 2);
+    assert_eq!(compound.read().0, 2);
+
+    source.remove("b", "x");
+    reload_until(&cache, || dir.read().ids().count() == 1);
+
+    Ok(())
+}
+
+#[test]
+fn snapshot() -> Res {
+    use crate::source::InMemory;
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+
+    let cache = AssetCache::with_source(source.clone());
+    let asset = cache.load::<X>("a")?;
+    let snapshot = asset.snapshot();
+    assert!(Arc::ptr_eq(&snapshot, &asset.snapshot()));
+
+    source.insert("a", "x", b"2".to_vec());
+    reload_until(&cache, || asset.snapshot().0 == 2);
+    assert_eq!(snapshot.0, 1);
+    assert!(!Arc::ptr_eq(&snapshot, &asset.snapshot()));
+
+    Ok(())
+}
+
+#[test]
+fn load_failures() -> Res {
+    use crate::{source::InMemory, RetryPolicy};
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    let cache = AssetCache::with_source(source.clone());
+    cache.set_retry_policy(RetryPolicy::Never);
+
+    assert!(cache.load::<X>("a").is_err());
+    assert!(cache.last_error::<X>("a").is_some());
+
+    // Changing the file forgets the failure
+    source.insert("a", "x", b"1".to_vec());
+    reload_until(&cache, || cache.last_error::<X>("a").is_none());
+    assert_eq!(cache.load::<X>("a")?.read().0, 1);
+
+    Ok(())
+}
+
+#[test]
+fn reload_errors() -> Res {
+    use crate::source::InMemory;
+    use std::sync::{Arc, Mutex};
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+    let cache = AssetCache::with_source(source.clone());
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let reported_clone = reported.clone();
+    cache.on_reload_error(move |id, _| reported_clone.lock().unwrap().push(id.clone()));
+
+    let handle = cache.load::<X>("a")?;
+    assert!(handle.last_reload_error().is_none());
+
+    // The old value is kept and the error is reported
+    source.insert("a", "x", b"a".to_vec());
+    reload_until(&cache, || handle.last_reload_error().is_some());
+    assert_eq!(handle.read().0, 1);
+    let error = handle.last_reload_error().unwrap();
+    assert_eq!(error.id(), "a");
+    assert_eq!(error.attempts(), 1);
+    assert_eq!(*reported.lock().unwrap(), ["a"]);
+
+    // A successful reload clears the error
+    source.insert("a", "x", b"2".to_vec());
+    reload_until(&cache, || handle.read().0 == 2);
+    assert!(handle.last_reload_error().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn reload_panic() -> Res {
+    use crate::{source::InMemory, AnyCache, Compound, ErrorKind, PanicError, SharedString};
+
+    struct Positive(i32);
+
+    impl Compound for Positive {
+        fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+            let n = cache.load::<X>(id)?.read().0;
+            assert!(n >= 0, "negative value");
+            Ok(Positive(n))
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+    let cache = AssetCache::with_source(source.clone());
+    let handle = cache.load::<Positive>("a")?;
+
+    let message = Arc::new(std::sync::Mutex::new(None));
+    let message_clone = message.clone();
+    cache.on_reload_error(move |_, err| {
+        let panic = err.reason().downcast_ref::<PanicError>();
+        *message_clone.lock().unwrap() = panic.and_then(|p| p.message()).map(String::from);
+    });
+
+    source.insert("a", "x", b"-1".to_vec());
+    reload_until(&cache, || handle.last_reload_error().is_some());
+    assert_eq!(handle.read().0, 1);
+
+    let error = handle.last_reload_error().unwrap();
+    assert_eq!(error.kind(), ErrorKind::Panic);
+    assert!(error.message().contains("negative value"));
+    assert_eq!(message.lock().unwrap().as_deref(), Some("negative value"));
+
+    Ok(())
+}
+
+#[test]
+fn dependency_graph() -> Res {
+    use crate::{
+        hot_reloading::{AssetKey, Dependency},
+        source::InMemory,
+    };
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+    let cache = AssetCache::with_source(source);
+    cache.load::<Y>("a")?;
+
+    let graph = cache.dependency_graph();
+    assert_eq!(graph.nodes().len(), 2);
+    let y = graph.get(&AssetKey::new::<Y>("a".into())).unwrap();
+    assert_eq!(y.dependencies(), [Dependency::asset::<X>("a".into())]);
+    let x = graph.get(&AssetKey::new::<X>("a".into())).unwrap();
+    assert_eq!(x.dependencies(), [Dependency::file("a".into(), "x".into())]);
+
+    let dot = graph.to_dot();
+    assert!(dot.contains(r#""a (assets_manager::tests::Y)" -> "a (assets_manager::tests::X)";"#));
+    assert!(dot.contains(r#""a (assets_manager::tests::X)" -> "a.x";"#));
+
+    Ok(())
+}
+
+#[test]
+fn record_dependency() -> Res {
+    use crate::{source::InMemory, AnyCache, Compound, SharedString};
+
+    // Reads its file without the cache knowing it
+    struct External(i32);
+
+    impl Compound for External {
+        fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+            let source = cache.raw_source();
+            let content = cache.no_record(|| source.read(id, "x"))?;
+            cache.record_dependency(DirEntry::File(id, "x"));
+            Ok(External(std::str::from_utf8(content.as_ref())?.parse()?))
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+    let cache = AssetCache::with_source(source.clone());
+    let handle = cache.load::<External>("a")?;
+    assert_eq!(handle.read().0, 1);
+
+    source.insert("a", "x", b"2".to_vec());
+    reload_until(&cache, || handle.read().0 == 2);
+
+    Ok(())
+}
+
+#[test]
+fn dependents_of() -> Res {
+    use crate::{hot_reloading::AssetKey, source::InMemory};
+
+    let source = InMemory::new();
+    source.insert("a", "x", b"1".to_vec());
+    source.insert("b", "x", b"2".to_vec());
+    let cache = AssetCache::with_source(source);
+    cache.load::<Y>("a")?;
+    cache.load::<X>("b")?;
+
+    let dependents = cache.dependents_of("a");
+    assert_eq!(
+        dependents,
+        [
+            AssetKey::new::<X>("a".into()),
+            AssetKey::new::<Y>("a".into())
+        ]
+    );
+    assert_eq!(cache.dependents_of("b"), [AssetKey::new::<X>("b".into())]);
+    assert!(cache.dependents_of("c").is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn keyed() -> Result<(), BoxedError> {
+    use crate::{asset::Keyed, source::InMemory};
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("items", "json", br#"{"a": "a"}"#.to_vec());
+    source.insert("a", "x", b"1".to_vec());
+    source.insert("b", "x", b"2".to_vec());
+
+    let cache = AssetCache::with_source(source.clone());
+    let items = cache.load::<Keyed<X>>("items")?;
+    let mut watcher = items.reload_watcher();
+    assert_eq!(items.read().get(&cache, "a").unwrap().read().0, 1);
+
+    // Reloading a target reloads the registry
+    source.insert("a", "x", b"3".to_vec());
+    reload_until(&cache, || {
+        items.read().get(&cache, "a").unwrap().read().0 == 3
+    });
+    assert!(watcher.reloaded());
+
+    // Changing the manifest changes the keys
+    source.insert("items", "json", br#"{"a": "a", "b": "b"}"#.to_vec());
+    reload_until(&cache, || items.read().get(&cache, "b").is_some());
+    assert!(watcher.reloaded());
+    assert_eq!(items.read().get(&cache, "b").unwrap().read().0, 2);
+
+    Ok(())
+}
+
+#[test]
+fn localized() -> Result<(), BoxedError> {
+    use crate::{source::InMemory, Locale, Localized};
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("menu", "x", b"1".to_vec());
+    source.insert("menu@fr", "x", b"2".to_vec());
+
+    let cache = AssetCache::with_source(source.clone());
+    let menu = cache.load::<Localized<X>>("menu")?;
+    let mut watcher = menu.reload_watcher();
+    assert_eq!(menu.read().id(), "menu");
+
+    // Changing the locale reloads localized assets
+    cache.set_locale(Locale::new("fr-CA"));
     cache.hot_reload();
-    assert_eq!(dir.read().ids().collect::<Vec<_>>().len(), 0);
+    assert!(watcher.reloaded());
+    assert_eq!(menu.read().id(), "menu@fr");
+
+    // So does adding a better localized version
+    source.insert("menu@fr-CA", "x", b"3".to_vec());
+    reload_until(&cache, || menu.read().id() == "menu@fr-CA");
+    assert!(watcher.reloaded());
+    assert_eq!(menu.read().get(&cache).unwrap().read().0, 3);
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn layered_config() -> Result<(), BoxedError> {
+    use crate::{asset::LayeredConfig, source::InMemory};
+    use std::collections::BTreeMap;
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("settings", "json", br#"{"a": 1, "b": 2}"#.to_vec());
+
+    let cache = AssetCache::with_source(source.clone());
+    let settings = cache.load::<LayeredConfig<BTreeMap<String, i32>>>("settings")?;
+    let mut watcher = settings.reload_watcher();
+    assert_eq!(settings.read().get()["b"], 2);
+
+    // Adding a layer reloads the config
+    source.insert("settings@user", "json", br#"{"b": 3}"#.to_vec());
+    reload_until(&cache, || settings.read().get()["b"] == 3);
+    assert!(watcher.reloaded());
+    assert_eq!(settings.read().get()["a"], 1);
+
+    // So does modifying one
+    source.insert("settings", "json", br#"{"a": 4, "b": 2}"#.to_vec());
+    reload_until(&cache, || settings.read().get()["a"] == 4);
+    assert!(watcher.reloaded());
+    assert_eq!(settings.read().get()["b"], 3);
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn atlas() -> Result<(), BoxedError> {
+    use crate::{asset::Atlas, source::InMemory};
+
+    let _ = env_logger::try_init();
+
+    let source = InMemory::new();
+    source.insert("atlas", "x", b"1".to_vec());
+    source.insert(
+        "atlas",
+        "json",
+        br#"{"frames": {"a": {"x": 0, "y": 0, "w": 1, "h": 1}}}"#.to_vec(),
+    );
+
+    let cache = AssetCache::with_source(source.clone());
+    let atlas = cache.load::<Atlas<X>>("atlas")?;
+    let mut watcher = atlas.reload_watcher();
+    assert_eq!(atlas.read().len(), 1);
+
+    // Changing the frames reloads the atlas
+    source.insert(
+        "atlas",
+        "json",
+        br#"{"frames": {"a": {"x": 0, "y": 0, "w": 1, "h": 1}, "b": {"x": 1, "y": 0, "w": 1, "h": 1}}}"#
+            .to_vec(),
+    );
+    reload_until(&cache, || atlas.read().frame("b").is_some());
+    assert!(watcher.reloaded());
+    assert_eq!(atlas.read().frame("b").unwrap().x, 1);
+
+    // So does changing the image
+    source.insert("atlas", "x", b"2".to_vec());
+    reload_until(&cache, || atlas.read().image(&cache).unwrap().read().0 == 2);
     assert!(watcher.reloaded());
 
     Ok(())
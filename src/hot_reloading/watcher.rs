@@ -1,7 +1,8 @@
 use crate::{source::OwnedDirEntry, utils::IdBuilder, BoxedError};
 use std::{
     fmt,
-    path::{self, Path, PathBuf},
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
 #[cfg(doc)]
@@ -12,7 +13,9 @@ use crate::source::Source;
 /// You can use it to quickly set up hot-reloading for a custom [`Source`].
 pub struct FsWatcherBuilder {
     roots: Vec<PathBuf>,
-    watcher: notify::RecommendedWatcher,
+    filter: Filter,
+    nfc_ids: bool,
+    watcher: Box<dyn notify::Watcher + Send>,
     payload_sender: crossbeam_channel::Sender<NotifyEventHandler>,
 }
 
@@ -24,22 +27,78 @@ impl FsWatcherBuilder {
 
         Ok(Self {
             roots: Vec::new(),
-            watcher,
+            filter: Filter::default(),
+            nfc_ids: false,
+            watcher: Box::new(watcher),
+            payload_sender,
+        })
+    }
+
+    /// Creates a new builder that polls the file system at the given interval
+    /// instead of relying on events of the operating system.
+    ///
+    /// This is useful for file systems that do not generate events, such as
+    /// network file systems (NFS, SMB) or some container mounts, but it is
+    /// more expensive. Files are detected as modified when their modification
+    /// time changes, which may only have a one second precision. If
+    /// `compare_contents` is `true`, the contents of files are also hashed to
+    /// detect changes, which requires reading all watched files at each poll.
+    pub fn polling(interval: Duration, compare_contents: bool) -> Result<Self, BoxedError> {
+        let (payload_sender, payload_receiver) = crossbeam_channel::unbounded();
+        let config = notify::Config::default()
+            .with_poll_interval(interval)
+            .with_compare_contents(compare_contents);
+        let watcher = notify::PollWatcher::new(EventHandlerPayload::new(payload_receiver), config)?;
+
+        Ok(Self {
+            roots: Vec::new(),
+            filter: Filter::default(),
+            nfc_ids: false,
+            watcher: Box::new(watcher),
             payload_sender,
         })
     }
 
     /// Adds a path to watch.
     pub fn watch(&mut self, path: PathBuf) -> Result<(), BoxedError> {
-        notify::Watcher::watch(&mut self.watcher, &path, notify::RecursiveMode::Recursive)?;
+        self.watcher
+            .watch(&path, notify::RecursiveMode::Recursive)?;
         self.roots.push(path);
         Ok(())
     }
 
+    /// Ignores events for paths that contain a file or directory whose name
+    /// matches `pattern`.
+    ///
+    /// In patterns, `*` matches any sequence of characters and `?` matches a
+    /// single character. For example, `.git` ignores everything in `.git`
+    /// directories and `*.tmp` ignores temporary files.
+    pub fn ignore(&mut self, pattern: &str) {
+        self.filter.ignore.push(pattern.to_owned());
+    }
+
+    /// Only reports events for files with one of the given extensions.
+    ///
+    /// Events for directories are still reported. By default, events for all
+    /// files are reported.
+    pub fn watch_extensions(&mut self, extensions: &[&str]) {
+        let extensions = extensions.iter().map(|&ext| ext.to_owned()).collect();
+        self.filter.extensions = Some(extensions);
+    }
+
+    /// Normalizes ids built from paths to Unicode Normalization Form C.
+    ///
+    /// See [`FileSystem::with_nfc_ids`](crate::source::FileSystem::with_nfc_ids).
+    pub fn nfc_ids(&mut self) {
+        self.nfc_ids = true;
+    }
+
     /// Starts the watcher.
     pub fn build(self, events: super::EventSender) {
         let event_handler = NotifyEventHandler {
             roots: self.roots,
+            filter: self.filter,
+            nfc_ids: self.nfc_ids,
             events,
             id_builder: IdBuilder::default(),
 
@@ -54,36 +113,13 @@ impl fmt::Debug for FsWatcherBuilder {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("FsWatcherBuilder")
             .field("roots", &self.roots)
+            .field("ignore", &self.filter.ignore)
+            .field("extensions", &self.filter.extensions)
+            .field("nfc_ids", &self.nfc_ids)
             .finish()
     }
 }
 
-fn id_of_path(id_builder: &mut IdBuilder, root: &Path, path: &Path) -> Option<OwnedDirEntry> {
-    id_builder.reset();
-
-    for comp in path.parent()?.strip_prefix(root).ok()?.components() {
-        match comp {
-            path::Component::Normal(s) => id_builder.push(s.to_str()?)?,
-            path::Component::ParentDir => id_builder.pop()?,
-            path::Component::CurDir => continue,
-            _ => return None,
-        }
-    }
-
-    // Build the id of the file.
-    id_builder.push(path.file_stem()?.to_str()?)?;
-    let id = id_builder.join();
-
-    let entry = if path.is_dir() {
-        OwnedDirEntry::Directory(id)
-    } else {
-        let ext = crate::utils::extension_of(path)?.into();
-        OwnedDirEntry::File(id, ext)
-    };
-
-    Some(entry)
-}
-
 enum EventHandlerPayload<H> {
     Waiting(crossbeam_channel::Receiver<H>),
     Handler(H),
@@ -109,12 +145,50 @@ impl<H: notify::EventHandler> notify::EventHandler for EventHandlerPayload<H> {
     }
 }
 
+/// Selects the paths for which events are reported.
+#[derive(Default)]
+struct Filter {
+    ignore: Vec<String>,
+    extensions: Option<Vec<String>>,
+}
+
+impl Filter {
+    fn is_ignored(&self, roots: &[PathBuf], path: &Path) -> bool {
+        let ignored_name = roots
+            .iter()
+            .filter_map(|root| path.strip_prefix(root).ok())
+            .flat_map(|rel| rel.components())
+            .filter_map(|comp| comp.as_os_str().to_str())
+            .any(|name| {
+                self.ignore
+                    .iter()
+                    .any(|pattern| crate::utils::wildcard_match(pattern, name))
+            });
+        if ignored_name {
+            return true;
+        }
+
+        // Paths without extension may be removed directories
+        match (
+            &self.extensions,
+            path.extension().and_then(|ext| ext.to_str()),
+        ) {
+            (Some(extensions), Some(ext)) => {
+                !path.is_dir() && !extensions.iter().any(|allowed| allowed == ext)
+            }
+            _ => false,
+        }
+    }
+}
+
 struct NotifyEventHandler {
     roots: Vec<PathBuf>,
+    filter: Filter,
+    nfc_ids: bool,
     events: super::EventSender,
     id_builder: IdBuilder,
 
-    watcher: Option<notify::RecommendedWatcher>,
+    watcher: Option<Box<dyn notify::Watcher + Send>>,
 }
 
 impl notify::EventHandler for NotifyEventHandler {
@@ -124,22 +198,41 @@ impl notify::EventHandler for NotifyEventHandler {
                 log::trace!("Received filesystem event: {event:?}");
 
                 for path in event.paths {
+                    if self.filter.is_ignored(&self.roots, &path) {
+                        continue;
+                    }
+
+                    let mut created = Vec::new();
                     let paths = match event.kind {
                         notify::EventKind::Any | notify::EventKind::Modify(_) => vec![&*path],
-                        notify::EventKind::Create(_) => match path.parent() {
-                            Some(parent) => vec![&path, parent],
-                            None => vec![&*path],
-                        },
+                        notify::EventKind::Create(_) => {
+                            // Files created with a directory may be created
+                            // before the directory is watched, so they would
+                            // never be reported otherwise.
+                            if path.is_dir() {
+                                list_dir_recursive(&path, &mut created);
+                                created.retain(|p| !self.filter.is_ignored(&self.roots, p));
+                            }
+
+                            let mut paths = vec![&*path];
+                            paths.extend(path.parent());
+                            paths.extend(created.iter().map(|p| &**p));
+                            paths
+                        }
                         notify::EventKind::Remove(_) => match path.parent() {
                             Some(parent) => vec![parent],
                             None => vec![],
                         },
                         notify::EventKind::Access(_) | notify::EventKind::Other => return,
                     };
+                    let nfc_ids = self.nfc_ids;
                     let ids = paths
                         .into_iter()
                         .flat_map(|p| self.roots.iter().map(move |r| (p, r)))
-                        .filter_map(|(path, root)| id_of_path(&mut self.id_builder, root, path));
+                        .filter_map(|(path, root)| {
+                            crate::utils::entry_of_path(&mut self.id_builder, root, path)
+                        })
+                        .map(|entry| if nfc_ids { nfc_entry(entry) } else { entry });
 
                     if self.events.send_multiple(ids).is_err() {
                         drop(self.watcher.take());
@@ -150,3 +243,24 @@ impl notify::EventHandler for NotifyEventHandler {
         }
     }
 }
+
+fn nfc_entry(entry: OwnedDirEntry) -> OwnedDirEntry {
+    match entry {
+        OwnedDirEntry::File(id, ext) => OwnedDirEntry::File(crate::utils::nfc_id(id), ext),
+        OwnedDirEntry::Directory(id) => OwnedDirEntry::Directory(crate::utils::nfc_id(id)),
+    }
+}
+
+fn list_dir_recursive(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_dir_recursive(&path, paths);
+        }
+        paths.push(path);
+    }
+}
@@ -5,24 +5,44 @@ use std::{
     cmp, fmt, hash,
 };
 
-use crate::{asset::Storable, entry::CacheEntry, AnyCache, Compound, Error, SharedString};
+use crate::{
+    asset::{ParamAsset, Storable},
+    entry::CacheEntry,
+    AnyCache, Compound, Error, SharedString,
+};
 
 impl Inner {
     fn of_asset<T: Compound>() -> &'static Self {
         fn load_entry<T: Compound>(cache: AnyCache, id: SharedString) -> Result<CacheEntry, Error> {
             match T::load(cache, &id) {
-                Ok(asset) => Ok(CacheEntry::new(asset, id, || cache.is_hot_reloaded())),
-                Err(err) => Err(Error::new(id, err)),
+                Ok(asset) => Ok(CacheEntry::new(asset, id, cache)),
+                Err(err) => Err(Error::with_type(id, std::any::type_name::<T>(), err)),
             }
         }
 
         &Self {
             hot_reloaded: T::HOT_RELOADED,
+            type_name: std::any::type_name::<T>,
+            load: load_entry::<T>,
+        }
+    }
+
+    fn of_params<T: ParamAsset>() -> &'static Self {
+        fn load_entry<T: ParamAsset>(
+            cache: AnyCache,
+            id: SharedString,
+        ) -> Result<CacheEntry, Error> {
+            let asset = crate::asset::load_with_params::<T>(cache, &id)?;
+            Ok(CacheEntry::new_any(asset, id, T::HOT_RELOADED, cache))
+        }
+
+        &Self {
+            hot_reloaded: T::HOT_RELOADED,
+            type_name: std::any::type_name::<T>,
             load: load_entry::<T>,
         }
     }
 
-    #[allow(clippy::extra_unused_type_parameters)]
     fn of_any<T: Any>() -> &'static Self {
         fn load(_: AnyCache, _: SharedString) -> Result<CacheEntry, Error> {
             panic!("Attempted to load non-`Compound` type")
@@ -30,6 +50,7 @@ impl Inner {
 
         &Self {
             hot_reloaded: false,
+            type_name: std::any::type_name::<T>,
             load,
         }
     }
@@ -37,6 +58,7 @@ impl Inner {
 
 pub(crate) struct Inner {
     hot_reloaded: bool,
+    type_name: fn() -> &'static str,
     pub load: fn(AnyCache, id: SharedString) -> Result<CacheEntry, Error>,
 }
 
@@ -58,6 +80,14 @@ impl Type {
         }
     }
 
+    #[inline]
+    pub(crate) fn of_params<T: ParamAsset>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            inner: Inner::of_params::<T>(),
+        }
+    }
+
     #[inline]
     pub(crate) fn of_any<T: Storable>() -> Self {
         Self {
@@ -70,6 +100,11 @@ impl Type {
     pub fn is_hot_reloaded(self) -> bool {
         self.inner.hot_reloaded
     }
+
+    #[inline]
+    pub fn type_name(self) -> &'static str {
+        (self.inner.type_name)()
+    }
 }
 
 impl hash::Hash for Type {
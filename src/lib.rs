@@ -9,6 +9,7 @@
 //!
 //! - `hot-reloading`: Add hot-reloading.
 //! - `macros`: Add support for deriving `Asset` trait.
+//! - `stats`: Record statistics about loads, see [`AssetCache::statistics`].
 //!
 //! ### Additional sources
 //!
@@ -21,15 +22,22 @@
 //!   - `zip-bzip2`: Enable `bzip2` decompression.
 //!   - `zip-deflate`: Enable `flate2` decompression.
 //! - `tar`: Read assets from TAR archives.
+//! - `pack`: Read assets from packs, an archive format with compression.
+//! - `gzip`, `zstd`: Read compressed files with [`source::Decompress`]. Brotli
+//!   is not supported.
+//! - `http`: Read assets from a HTTP server (HTTPS is not supported).
+//! - `test-kit`: Add conformance tests for custom sources in
+//!   [`source::test_kit`].
 //!
 //! ### Additional formats
 //!
 //! These features add support for asset formats. There is one feature per
 //! format.
 //!
-//! - Serialisation formats (with [`serde`] crate): `bincode`, `json`,
+//! - Serialisation formats (with [`serde`] crate): `bincode`, `csv`, `json`,
 //!   `msgpack`, `ron`, `toml`, `yaml`.
 //! - Image formats (with [`image`] crate): `bmp`, `jpeg`, `png` `webp`.
+//! - GPU texture formats, loaded without decoding: `dds`, `ktx2`.
 //! - 3D formats (with [`gltf`] crate): `gltf`.
 //!
 //! ## External crates support
@@ -186,31 +194,67 @@ mod anycache;
 pub use anycache::{AnyCache, AsAnyCache};
 
 pub mod asset;
-pub use asset::{Asset, Compound, Storable};
+pub use asset::{Asset, Compound, FileAsset, ParamAsset, SaveAsset, Storable};
 
 mod cache;
-pub use cache::AssetCache;
+pub use cache::{AssetCache, SealPolicy};
+
+mod deferred;
+pub use deferred::PendingHandle;
 
 mod dirs;
-pub use dirs::{Directory, RecursiveDirectory};
+pub use dirs::{DirFilter, DirValues, Directory, FilteredDir, RecursiveDirectory};
 
 mod error;
-pub use error::{BoxedError, Error};
+pub use error::{
+    AggregateError, BoxedError, Error, ErrorKind, PanicError, SealedError, TooLargeError,
+};
+
+mod failures;
+pub use failures::{LoadFailure, RetryPolicy};
+
+mod find;
+pub use find::Find;
 
 pub mod loader;
 
 mod local_cache;
 pub use local_cache::LocalAssetCache;
 
+mod locale;
+pub use locale::{Locale, LocaleLayout, Localized};
+
+mod listing;
+
+mod manifest;
+pub use manifest::{Manifest, ManifestDiff, ManifestEntry};
+
 mod map;
 
+mod mount;
+
 mod entry;
-pub use entry::{AssetReadGuard, AtomicReloadId, Handle, ReloadId, ReloadWatcher, UntypedHandle};
+pub use entry::{
+    AssetReadGuard, AtomicReloadId, Handle, ProjectedHandle, ReloadCache, ReloadId, ReloadWatcher,
+    UntypedHandle, WeakHandle,
+};
 
 mod key;
 
+mod server;
+pub use server::{AssetServer, HandleId};
+
 pub mod source;
 
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+pub use stats::{Statistics, TypeStatistics};
+
+mod validation;
+pub use validation::{DirLoadReport, ValidationReport};
+
 #[cfg_attr(not(feature = "hot-reloading"), path = "hot_reloading/disabled.rs")]
 pub mod hot_reloading;
 
@@ -220,19 +264,157 @@ mod utils;
 pub use utils::cell::OnceInitCell;
 pub use utils::{SharedBytes, SharedString};
 
-/// Implements [`Asset`] for a type.
+/// Implements [`Asset`] or [`Compound`] for a type.
 ///
 /// Note that the type must implement the right traits for it to work (eg
 /// `serde::Deserialize` or `std::str::FromStr`).
 ///
 /// # Supported formats
 ///
+/// - `"bincode"`: Use [`loader::BincodeLoader`] and extension `.bin`
 /// - `"json"`: Use [`loader::JsonLoader`] and extension `.json`
+/// - `"msgpack"`: Use [`loader::MessagePackLoader`] and extension `.msgpack`
 /// - `"ron"`: Use [`loader::RonLoader`] and extension `.ron`
 /// - `"toml"`: Use [`loader::TomlLoader`] and extension `.toml`
 /// - `"txt"`: Use [`loader::ParseLoader`] and extension `.txt`
 /// - `"yaml"` or `"yml"`: Use [`loader::YamlLoader`] and extensions `.yaml` and `.yml`
 ///
+/// # Several formats
+///
+/// With `#[asset(formats("ron", "json"))]`, the asset can be loaded from files
+/// of any of the given formats, which is chosen from the extension of the
+/// file.
+///
+/// # Custom extensions
+///
+/// The extensions of the formats can be replaced with
+/// `#[asset(extension = "...")]`, which can be repeated to set several
+/// extensions.
+/// When an extension does not belong to a format, each format is tried in
+/// order.
+///
+/// # Custom loaders
+///
+/// Other loaders can be used with `#[asset(loader = "...", extension = "...")]`.
+/// As above, the `extension` key can be repeated to set several extensions.
+///
+/// # Custom functions
+///
+/// A function with signature `fn(&[u8]) -> Result<Self, BoxedError>` can be
+/// used to load the asset with
+/// `#[asset(with = "path::to::function", extension = "...")]`.
+///
+/// ```rust
+/// # use assets_manager::{Asset, AssetCache, BoxedError, source::InMemory};
+/// #[derive(Asset)]
+/// #[asset(with = "Header::parse", extension = "hdr")]
+/// struct Header {
+///     version: u8,
+///     flags: u8,
+/// }
+///
+/// impl Header {
+///     fn parse(bytes: &[u8]) -> Result<Self, BoxedError> {
+///         match *bytes {
+///             [version, flags] => Ok(Header { version, flags }),
+///             _ => Err("invalid header".into()),
+///         }
+///     }
+/// }
+///
+/// let source = InMemory::new();
+/// source.insert("header", "hdr", vec![2, 0b101]);
+///
+/// let cache = AssetCache::with_source(source);
+/// let header = cache.load::<Header>("header")?.read();
+/// assert_eq!(header.version, 2);
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+///
+/// # Default value
+///
+/// With `#[asset(default)]`, an asset that fails to load is replaced by its
+/// `Default` value, after the error is logged. See [`Asset::default_value`].
+///
+/// ```rust
+/// # cfg_if::cfg_if! { if #[cfg(all(feature = "json", feature = "ron"))] {
+/// # use assets_manager::{Asset, AssetCache, source::InMemory};
+/// #[derive(Asset, Default, serde::Deserialize)]
+/// #[asset(formats("ron", "json"), extension = "mat", default)]
+/// struct Material {
+///     color: (u8, u8, u8),
+/// }
+///
+/// let source = InMemory::new();
+/// source.insert("red", "mat", b"(color: (255, 0, 0))".to_vec());
+/// source.insert("blue", "mat", br#"{ "color": [0, 0, 255] }"#.to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+/// assert_eq!(cache.load::<Material>("red")?.read().color, (255, 0, 0));
+/// assert_eq!(cache.load::<Material>("blue")?.read().color, (0, 0, 255));
+/// assert_eq!(cache.load::<Material>("missing")?.read().color, (0, 0, 0));
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+///
+/// # Validation
+///
+/// With `#[asset(validate)]`, loaded values are checked with their
+/// implementation of [`asset::Validate`], as described in
+/// [`Asset::validate`].
+///
+/// # Compounds
+///
+/// If no format is given, the derive implements [`Compound`] for a struct, by
+/// loading each of its fields from the cache with [`AnyCache::load_owned`].
+/// The fields must therefore implement [`Compound`] (which includes assets).
+///
+/// By default, each field is loaded with the same id as the struct. This can
+/// be changed with attributes on the field:
+/// - `#[asset(id = "...")]`: Load the field with the given id.
+/// - `#[asset(id_suffix = "...")]`: Load the field with the id of the struct
+///   followed by the given suffix.
+/// - `#[asset(skip)]`: Do not load the field, but use `Default::default()`.
+///
+/// `#[asset(validate)]` is also supported on compounds.
+///
+/// ```rust
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// # use assets_manager::{Asset, AssetCache};
+/// #[derive(Asset, serde::Deserialize)]
+/// #[asset_format = "ron"]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// #[derive(Asset)]
+/// struct Character {
+///     // Load "assets/common/name.txt"
+///     #[asset(id_suffix = ".name")]
+///     name: String,
+///     // Load "assets/common/position.ron"
+///     #[asset(id_suffix = ".position")]
+///     position: Point,
+///     #[asset(skip)]
+///     health: Option<u32>,
+/// }
+///
+/// let cache = AssetCache::new("assets")?;
+/// let character = cache.load::<Character>("common")?.read();
+/// assert_eq!(character.name.trim(), "Aragorn");
+/// assert_eq!(character.position.x, 5);
+/// assert_eq!(character.health, None);
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+///
+/// # Generic types and enums
+///
+/// The derive can be used on enums and generic types. The bounds needed by
+/// the loader (eg `serde::Deserialize`) are added to the generated `impl`, so
+/// the type only implements `Asset` for parameters that satisfy them.
+///
 /// # Example
 ///
 /// ```rust
@@ -259,6 +441,27 @@ pub use utils::{SharedBytes, SharedString};
 ///     }
 /// }
 ///
+/// // Define a generic type
+/// #[derive(Asset, serde::Deserialize)]
+/// #[asset_format = "ron"]
+/// #[serde(rename = "Point")]
+/// struct GenericPoint<T> {
+///     x: T,
+///     y: T,
+/// }
+///
+/// // Define a type with a custom loader
+/// #[derive(Asset)]
+/// #[asset(loader = "assets_manager::loader::LoadFrom<i32, assets_manager::loader::ParseLoader>")]
+/// #[asset(extension = "x")]
+/// struct Number(i32);
+///
+/// impl From<i32> for Number {
+///     fn from(n: i32) -> Self {
+///         Self(n)
+///     }
+/// }
+///
 /// let cache = AssetCache::new("assets")?;
 ///
 /// // Load "assets/common/position.ron"
@@ -266,9 +469,16 @@ pub use utils::{SharedBytes, SharedString};
 /// assert_eq!(position.read().x, 5);
 /// assert_eq!(position.read().y, -6);
 ///
+/// let position = cache.load::<GenericPoint<i64>>("common.position")?;
+/// assert_eq!(position.read().x, 5);
+///
 /// // Load "assets/common/name.txt"
 /// let name = cache.load::<Name>("common.name")?;
 /// assert_eq!(name.read().0, "Aragorn");
+///
+/// // Load "assets/test/b.x"
+/// let number = cache.load::<Number>("test.b")?;
+/// assert_eq!(number.read().0, -7);
 /// # }}
 /// # Ok::<(), assets_manager::BoxedError>(())
 /// ```
@@ -276,5 +486,28 @@ pub use utils::{SharedBytes, SharedString};
 #[cfg(feature = "macros")]
 pub use assets_manager_macros::Asset;
 
+/// Items used by the code generated by `#[derive(Asset)]`.
+#[doc(hidden)]
+#[cfg(feature = "macros")]
+pub mod __private {
+    use crate::{BoxedError, SharedString};
+
+    /// The loader of assets that use several formats.
+    ///
+    /// Its implementations of [`Loader`](crate::loader::Loader) are generated
+    /// by the derive.
+    #[allow(missing_debug_implementations)]
+    pub struct DeriveLoader(());
+
+    /// Logs the error and uses the default value, for `#[asset(default)]`.
+    pub fn default_value<T: Default>(
+        id: &SharedString,
+        error: BoxedError,
+    ) -> Result<T, BoxedError> {
+        log::warn!("Error loading \"{id}\": {error}. Using default value");
+        Ok(T::default())
+    }
+}
+
 #[cfg(test)]
 mod tests;
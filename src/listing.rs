@@ -0,0 +1,113 @@
+//! In-memory index of the directories of a source
+
+use crate::{
+    source::{DirEntry, OwnedDirEntry, Source},
+    utils::{HashMap, RwLock},
+    SharedString,
+};
+use std::io;
+
+type Dirs = HashMap<SharedString, Vec<OwnedDirEntry>>;
+
+/// Reads directory `id` and its subdirectories into `dirs`.
+fn scan_dir(source: &dyn Source, id: &str, dirs: &mut Dirs) -> io::Result<()> {
+    let mut entries = Vec::new();
+    source.read_dir(id, &mut |entry| entries.push(entry.to_owned()))?;
+
+    for entry in &entries {
+        if let OwnedDirEntry::Directory(dir) = entry {
+            if let Err(err) = scan_dir(source, dir, dirs) {
+                log::warn!("Error scanning directory \"{dir}\": {err}");
+            }
+        }
+    }
+
+    dirs.insert(id.into(), entries);
+    Ok(())
+}
+
+/// The directory structure of a source, used instead of the source to read
+/// directories once it is scanned.
+pub(crate) struct Listing {
+    dirs: RwLock<Option<Dirs>>,
+}
+
+impl Listing {
+    pub fn new() -> Self {
+        Self {
+            dirs: RwLock::new(None),
+        }
+    }
+
+    /// Walks the source and stores its directory structure.
+    pub fn scan(&self, source: &dyn Source) -> io::Result<()> {
+        let mut dirs = HashMap::new();
+        scan_dir(source, "", &mut dirs)?;
+        log::debug!("Scanned {} directories", dirs.len());
+        *self.dirs.write() = Some(dirs);
+        Ok(())
+    }
+
+    /// Returns `None` if the source was not scanned.
+    pub fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> Option<io::Result<()>> {
+        let dirs = self.dirs.read();
+        let Some(entries) = dirs.as_ref()?.get(id) else {
+            return Some(Err(io::ErrorKind::NotFound.into()));
+        };
+        entries.iter().for_each(|e| f(e.as_dir_entry()));
+        Some(Ok(()))
+    }
+
+    /// Returns `None` if the source was not scanned.
+    pub fn exists(&self, entry: DirEntry) -> Option<bool> {
+        let dirs = self.dirs.read();
+        let dirs = dirs.as_ref()?;
+        Some(match entry.parent_id() {
+            _ if entry.is_dir() => dirs.contains_key(entry.id()),
+            Some(parent) => dirs
+                .get(parent)
+                .is_some_and(|entries| entries.iter().any(|e| e.as_dir_entry() == entry)),
+            None => false,
+        })
+    }
+
+    /// Updates the listing after an entry of the source changed.
+    #[cfg(feature = "hot-reloading")]
+    pub fn update(&self, source: &dyn Source, entry: &OwnedDirEntry) {
+        let mut dirs = self.dirs.write();
+        let Some(dirs) = &mut *dirs else {
+            return;
+        };
+
+        let exists = source.exists(entry.as_dir_entry());
+
+        if let OwnedDirEntry::Directory(id) = entry {
+            dirs.retain(|dir, _| !crate::mount::is_under(dir, id));
+            if exists {
+                if let Err(err) = scan_dir(source, id, dirs) {
+                    log::warn!("Error scanning directory \"{id}\": {err}");
+                }
+            }
+        }
+
+        if !exists {
+            let parent = entry.as_dir_entry().parent_id();
+            if let Some(entries) = parent.and_then(|p| dirs.get_mut(p)) {
+                entries.retain(|e| e != entry);
+            }
+            return;
+        }
+
+        // Add the entry to its parent, and the parent to its own parent, etc
+        let mut entry = entry.clone();
+        while let Some(parent) = entry.as_dir_entry().parent_id() {
+            let parent = SharedString::from(parent);
+            let entries = dirs.entry(parent.clone()).or_default();
+            if entries.contains(&entry) {
+                break;
+            }
+            entries.push(entry);
+            entry = OwnedDirEntry::Directory(parent);
+        }
+    }
+}
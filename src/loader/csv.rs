@@ -0,0 +1,508 @@
+//! A small CSV reader that deserializes rows with `serde`.
+
+use super::Loader;
+use crate::BoxedError;
+use serde::de::{self, Deserialize, Deserializer, IntoDeserializer, Unexpected, Visitor};
+use std::{borrow::Cow, fmt};
+
+/// Options to read CSV files.
+///
+/// By default, fields are separated with commas and the first row contains
+/// the headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CsvOptions {
+    delimiter: u8,
+    has_headers: bool,
+}
+
+impl CsvOptions {
+    /// Default options for CSV files.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+        }
+    }
+
+    /// Default options for TSV files, whose fields are separated with tabs.
+    #[inline]
+    pub const fn tsv() -> Self {
+        Self {
+            delimiter: b'\t',
+            has_headers: true,
+        }
+    }
+
+    /// Sets the character that separates fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delimiter is not an ASCII character, or if it is a quote
+    /// or a line break.
+    #[inline]
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        assert!(
+            delimiter.is_ascii() && !matches!(delimiter, b'"' | b'\n' | b'\r'),
+            "invalid CSV delimiter"
+        );
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first row contains headers.
+    ///
+    /// With headers, rows are deserialized as maps from headers to fields, so
+    /// structs can be in a different order than the columns. Without headers,
+    /// rows are deserialized as sequences, so the fields of structs must be
+    /// in the same order as the columns.
+    #[inline]
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Returns the character that separates fields.
+    #[inline]
+    pub fn delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Returns `true` if the first row contains headers.
+    #[inline]
+    pub fn has_headers(&self) -> bool {
+        self.has_headers
+    }
+
+    /// Returns the default options for files with the given extension.
+    pub(crate) fn for_extension(ext: &str) -> Self {
+        match ext {
+            "tsv" => Self::tsv(),
+            _ => Self::new(),
+        }
+    }
+}
+
+impl Default for CsvOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An error while reading a CSV file.
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Splits a CSV file in records.
+struct Reader<'a> {
+    input: &'a str,
+    delimiter: char,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(input: &'a str, delimiter: u8) -> Self {
+        Self {
+            input,
+            delimiter: char::from(delimiter),
+            line: 1,
+        }
+    }
+
+    /// Reads the next non-empty record into `fields`, and returns the line
+    /// on which it starts, or `None` at the end of the file.
+    fn next_record(&mut self, fields: &mut Vec<Cow<'a, str>>) -> Result<Option<usize>, Error> {
+        fields.clear();
+
+        // Skip empty lines
+        loop {
+            if let Some(rest) = self.input.strip_prefix("\r\n") {
+                self.input = rest;
+            } else if let Some(rest) = self.input.strip_prefix('\n') {
+                self.input = rest;
+            } else {
+                break;
+            }
+            self.line += 1;
+        }
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        let start = self.line;
+        loop {
+            let field = match self.input.strip_prefix('"') {
+                Some(rest) => self.quoted_field(rest)?,
+                None => {
+                    let end = self
+                        .input
+                        .find([self.delimiter, '\n', '\r'])
+                        .unwrap_or(self.input.len());
+                    let (field, rest) = self.input.split_at(end);
+                    self.input = rest;
+                    Cow::Borrowed(field)
+                }
+            };
+            fields.push(field);
+
+            if let Some(rest) = self.input.strip_prefix(self.delimiter) {
+                self.input = rest;
+            } else if let Some(rest) = self.input.strip_prefix("\r\n") {
+                self.input = rest;
+                self.line += 1;
+                return Ok(Some(start));
+            } else if let Some(rest) = self.input.strip_prefix('\n') {
+                self.input = rest;
+                self.line += 1;
+                return Ok(Some(start));
+            } else if self.input.is_empty() {
+                return Ok(Some(start));
+            } else {
+                let msg = format!("line {}: unexpected character after a quote", self.line);
+                return Err(Error(msg));
+            }
+        }
+    }
+
+    /// Reads a quoted field, starting after the opening quote.
+    fn quoted_field(&mut self, mut input: &'a str) -> Result<Cow<'a, str>, Error> {
+        let mut field = Cow::Borrowed("");
+        loop {
+            let Some(end) = input.find('"') else {
+                let msg = format!("line {}: unterminated quoted field", self.line);
+                return Err(Error(msg));
+            };
+            let (part, rest) = input.split_at(end);
+            self.line += part.matches('\n').count();
+
+            match field {
+                Cow::Borrowed("") => field = Cow::Borrowed(part),
+                _ => field.to_mut().push_str(part),
+            }
+
+            // Two quotes are an escaped quote
+            match rest[1..].strip_prefix('"') {
+                Some(rest) => {
+                    field.to_mut().push('"');
+                    input = rest;
+                }
+                None => {
+                    self.input = &rest[1..];
+                    return Ok(field);
+                }
+            }
+        }
+    }
+}
+
+/// Deserializes a record.
+struct Row<'r, 'a> {
+    headers: Option<&'r [Cow<'a, str>]>,
+    fields: &'r [Cow<'a, str>],
+}
+
+impl Row<'_, '_> {
+    /// Returns the field of a row with a single column.
+    fn single_field(&self) -> Result<Field<'_>, Error> {
+        match self.fields {
+            [field] => Ok(Field(field)),
+            _ => Err(Error(format!(
+                "expected a single field, found {}",
+                self.fields.len()
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_to_field {
+    ( $( $method:ident )* ) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.single_field()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Row<'_, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.headers {
+            Some(headers) => {
+                if headers.len() != self.fields.len() {
+                    return Err(Error(format!(
+                        "expected {} fields, found {}",
+                        headers.len(),
+                        self.fields.len()
+                    )));
+                }
+                let entries = headers
+                    .iter()
+                    .zip(self.fields)
+                    .map(|(h, f)| (&**h, Field(f)));
+                visitor.visit_map(de::value::MapDeserializer::new(entries))
+            }
+            None => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let fields = self.fields.iter().map(|f| Field(f));
+        visitor.visit_seq(de::value::SeqDeserializer::new(fields))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.single_field()?
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_field! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16
+        deserialize_u32 deserialize_u64 deserialize_u128 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_unit deserialize_identifier
+    }
+}
+
+/// Deserializes a field.
+struct Field<'f>(&'f str);
+
+macro_rules! parse_field {
+    ( $( $method:ident => $visit:ident, )* ) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0.trim().parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(de::Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Field<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_str(self.0)
+    }
+
+    parse_field! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_bytes(self.0.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    /// Empty fields are `None`.
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            "" => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            "" => visitor.visit_unit(),
+            _ => Err(de::Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Only unit variants can be read from a field.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, Error> for Field<'_> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Deserializes each row of a CSV file.
+///
+/// The content must be UTF-8, and fields can be quoted with `"`, in which
+/// case they can contain delimiters, line breaks and escaped quotes (`""`).
+/// Empty lines are skipped, and empty fields are read as `None` for options.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::loader::{load_csv, CsvOptions};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Monster {
+///     name: String,
+///     health: u32,
+/// }
+///
+/// let content = b"name;health\nGoblin;20\n\"Giant; bat\";15\n";
+/// let options = CsvOptions::new().with_delimiter(b';');
+/// let monsters: Vec<Monster> = load_csv(content, options)?;
+///
+/// assert_eq!(monsters[1].name, "Giant; bat");
+/// assert_eq!(monsters[1].health, 15);
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub fn load_csv<T>(content: &[u8], options: CsvOptions) -> Result<Vec<T>, BoxedError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let content = std::str::from_utf8(content)?;
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut reader = Reader::new(content, options.delimiter);
+
+    let mut headers = Vec::new();
+    if options.has_headers && reader.next_record(&mut headers)?.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    while let Some(line) = reader.next_record(&mut fields)? {
+        let row = Row {
+            headers: options.has_headers.then_some(&headers[..]),
+            fields: &fields,
+        };
+        let value = T::deserialize(row).map_err(|err| Error(format!("line {line}: {err}")))?;
+        rows.push(value);
+    }
+
+    Ok(rows)
+}
+
+/// Loads CSV and TSV files, deserializing each row with `serde`.
+///
+/// Files with extension `tsv` are separated with tabs, and other files with
+/// commas. The first row contains the headers. Use [`load_csv`] directly for
+/// other options.
+///
+/// See trait [`Loader`] for more informations.
+pub struct CsvLoader(());
+
+impl<T> Loader<Vec<T>> for CsvLoader
+where
+    T: for<'de> Deserialize<'de>,
+{
+    #[inline]
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Vec<T>, BoxedError> {
+        load_csv(&content, CsvOptions::for_extension(ext))
+    }
+}
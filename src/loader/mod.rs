@@ -22,9 +22,15 @@ use std::{
     str::{self, FromStr},
 };
 
+#[cfg(feature = "csv")]
+mod csv;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "csv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+pub use self::csv::{load_csv, CsvLoader, CsvOptions};
+
 /// Specifies how an asset is loaded.
 ///
 /// With this trait, you can easily specify how you want your data to be loaded.
@@ -222,6 +228,24 @@ where
     }
 }
 
+/// Loads assets that implement [`FileAsset`].
+///
+/// This is the loader used by the implementation of [`Asset`] for all
+/// `FileAsset`s.
+///
+/// [`Asset`]: crate::Asset
+/// [`FileAsset`]: crate::FileAsset
+pub struct FileAssetLoader(());
+impl<T> Loader<T> for FileAssetLoader
+where
+    T: crate::FileAsset,
+{
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        T::from_bytes(content)
+    }
+}
+
 /// Loads assets used as sounds.
 pub struct SoundLoader(());
 
@@ -253,6 +277,9 @@ impl Loader<gltf::Gltf> for GltfLoader {
 /// Loads fonts.
 pub struct FontLoader(());
 
+/// Loads GPU textures.
+pub struct TextureLoader(());
+
 macro_rules! serde_loaders {
     (
         $(
@@ -1,7 +1,7 @@
 use super::*;
 use crate::tests::X;
 
-fn raw(s: &str) -> Cow<[u8]> {
+fn raw(s: &str) -> Cow<'_, [u8]> {
     s.as_bytes().into()
 }
 
@@ -154,3 +154,67 @@ test_loader!(
     YamlLoader,
     serde_yaml::to_string
 );
+
+#[cfg(feature = "csv")]
+mod csv {
+    use super::*;
+
+    #[test]
+    fn csv_loader_ok() {
+        let point = rand::random::<Point>();
+        let csv = format!("y,x\n{},{}\n", point.y, point.x);
+
+        let tsv = format!("x\ty\r\n\r\n{}\t{}", point.x, point.y);
+
+        let loaded: Vec<Point> = CsvLoader::load(raw(&csv), "csv").unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0], point);
+
+        let loaded: Vec<Point> = CsvLoader::load(raw(&tsv), "tsv").unwrap();
+        assert_eq!(loaded, [point]);
+    }
+
+    #[test]
+    fn csv_loader_err() {
+        let loaded: Result<Vec<Point>, _> = CsvLoader::load(raw("x,y\n1,a\n"), "csv");
+        assert!(loaded.is_err());
+
+        let loaded: Result<Vec<Point>, _> = CsvLoader::load(raw("x,y\n1,2,3\n"), "csv");
+        assert!(loaded.is_err());
+
+        let loaded: Result<Vec<Point>, _> = CsvLoader::load(raw("x,y\n\"1,2\n"), "csv");
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn quoted_fields() {
+        let content = "\u{feff}name,comment\n\"a, b\",\"say \"\"hi\"\"\nnow\"\nc,\n";
+        let loaded: Vec<(String, Option<String>)> =
+            load_csv(content.as_bytes(), CsvOptions::new().with_headers(false)).unwrap();
+
+        assert_eq!(
+            loaded,
+            [
+                ("name".into(), Some("comment".into())),
+                ("a, b".into(), Some("say \"hi\"\nnow".into())),
+                ("c".into(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_column() {
+        let options = CsvOptions::new().with_headers(false);
+        let loaded: Vec<u32> = load_csv(b"1\n 2\n3", options).unwrap();
+        assert_eq!(loaded, [1, 2, 3]);
+
+        let loaded: Vec<Point> = load_csv(b"", CsvOptions::new()).unwrap();
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn error_line() {
+        let err = load_csv::<Point>(b"x,y\n1,2\n\n3,b\n", CsvOptions::new()).unwrap_err();
+        assert!(err.to_string().starts_with("line 4:"), "{err}");
+    }
+}
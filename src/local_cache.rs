@@ -1,12 +1,13 @@
 use crate::{
     anycache::{Cache, CacheExt},
-    asset::DirLoadable,
+    asset::{DirLoadable, ParamAsset},
     entry::{CacheEntry, UntypedHandle},
     source::Source,
     utils::RandomState,
-    AnyCache, Compound, Error, Handle, Storable,
+    AggregateError, AnyCache, BoxedError, Compound, DirLoadReport, DirValues, Error, Find, Handle,
+    Locale, SharedString, Storable, ValidationReport,
 };
-use std::{any::TypeId, cell::RefCell, fmt};
+use std::{any::TypeId, borrow::Cow, cell::RefCell, fmt};
 
 #[cfg(doc)]
 use crate::AssetReadGuard;
@@ -40,6 +41,10 @@ impl AssetMap {
     fn clear(&mut self) {
         self.map.get_mut().clear();
     }
+
+    fn retain(&mut self, f: &mut dyn FnMut(&UntypedHandle) -> bool) {
+        self.map.get_mut().retain(f);
+    }
 }
 
 impl crate::anycache::AssetMap for AssetMap {
@@ -132,6 +137,22 @@ impl<S: Source> LocalAssetCache<S> {
         self._get_cached(id)
     }
 
+    /// Gets a copy of a value from the cache.
+    ///
+    /// See [`AnyCache::get_copied`] for more details.
+    #[inline]
+    pub fn get_copied<T: Storable + Copy>(&self, id: &str) -> Option<T> {
+        self._get_copied(id)
+    }
+
+    /// Gets a clone of a value from the cache.
+    ///
+    /// See [`AnyCache::get_cloned`] for more details.
+    #[inline]
+    pub fn get_cloned<T: Storable + Clone>(&self, id: &str) -> Option<T> {
+        self._get_cloned(id)
+    }
+
     /// Gets a value with the given type from the cache.
     ///
     /// This is an equivalent of `get_cached` but with a dynamic type.
@@ -164,6 +185,18 @@ impl<S: Source> LocalAssetCache<S> {
         self._load(id)
     }
 
+    /// Loads an asset with parameters.
+    ///
+    /// See [`AnyCache::load_with`] for more details.
+    #[inline]
+    pub fn load_with<T: ParamAsset>(
+        &self,
+        id: &str,
+        params: T::Params,
+    ) -> Result<&Handle<T>, Error> {
+        self._load_with(id, params)
+    }
+
     /// Loads an asset and panic if an error happens.
     ///
     /// See [`AnyCache::load_expect`] for more details.
@@ -194,6 +227,74 @@ impl<S: Source> LocalAssetCache<S> {
         self.load::<crate::RecursiveDirectory<T>>(id)
     }
 
+    /// Loads a directory and all the assets it contains.
+    ///
+    /// See [`AnyCache::load_dir_values`] for more details.
+    #[inline]
+    pub fn load_dir_values<T: DirLoadable + Compound>(
+        &self,
+        id: &str,
+    ) -> Result<DirValues<'_, T>, Error> {
+        self._load_dir_values(id)
+    }
+
+    /// Loads all files of a directory with a custom loader.
+    ///
+    /// See [`AnyCache::load_dir_with`] for more details.
+    #[inline]
+    pub fn load_dir_with<T: Storable>(
+        &self,
+        id: &str,
+        recursive: bool,
+        extensions: &[&str],
+        mut load: impl FnMut(Cow<[u8]>, &str) -> Result<T, BoxedError>,
+    ) -> Result<Vec<&UntypedHandle>, Error> {
+        self._load_dir_with(id, recursive, extensions, &mut load)
+    }
+
+    /// Returns the ids of all assets of type `T` that the source contains in
+    /// the given directory and its subdirectories.
+    ///
+    /// See [`AnyCache::available_ids`] for more details.
+    #[inline]
+    pub fn available_ids<T: DirLoadable>(&self, id: &str) -> Result<Vec<SharedString>, Error> {
+        self._available_ids::<T>(id)
+    }
+
+    /// Returns the ids of all files of the source that match a pattern.
+    ///
+    /// See [`AnyCache::find_ids`] for more details.
+    #[inline]
+    pub fn find_ids(&self, pattern: &str) -> Vec<SharedString> {
+        self._find_ids(pattern)
+    }
+
+    /// Returns an iterator over the entries of the source that match a
+    /// pattern.
+    ///
+    /// See [`AnyCache::find`] for more details.
+    #[inline]
+    pub fn find(&self, pattern: &str) -> Find<'_> {
+        self._find(pattern)
+    }
+
+    /// Returns the locale used to load [`Localized`](crate::Localized)
+    /// assets.
+    ///
+    /// See [`AnyCache::locale`] for more details.
+    #[inline]
+    pub fn locale(&self) -> Locale {
+        self._locale()
+    }
+
+    /// Sets the locale used to load [`Localized`](crate::Localized) assets.
+    ///
+    /// See [`AnyCache::set_locale`] for more details.
+    #[inline]
+    pub fn set_locale(&self, locale: Locale) {
+        self._set_locale(locale)
+    }
+
     /// Loads an owned version of an asset.
     ///
     /// See [`AnyCache::load_owned`] for more details.
@@ -202,9 +303,57 @@ impl<S: Source> LocalAssetCache<S> {
         self._load_owned(id)
     }
 
+    /// Loads an asset and returns a clone of it.
+    ///
+    /// See [`AnyCache::load_cloned`] for more details.
+    #[inline]
+    pub fn load_cloned<T: Compound + Clone>(&self, id: &str) -> Result<T, Error> {
+        self._load_cloned(id)
+    }
+
+    /// Loads all the given assets, collecting all errors.
+    ///
+    /// See [`AnyCache::load_all`] for more details.
+    #[inline]
+    pub fn load_all<'i, T: Compound>(
+        &self,
+        ids: impl IntoIterator<Item = &'i str>,
+    ) -> Result<Vec<&Handle<T>>, AggregateError> {
+        self._load_all(&mut ids.into_iter())
+    }
+
+    /// Loads all the given assets and reports errors.
+    ///
+    /// See [`AnyCache::validate`] for more details.
+    #[inline]
+    pub fn validate<'i, T: Compound>(
+        &self,
+        ids: impl IntoIterator<Item = &'i str>,
+    ) -> ValidationReport {
+        self._validate::<T>(&mut ids.into_iter())
+    }
+
+    /// Loads all assets of a directory and its subdirectories and reports
+    /// errors.
+    ///
+    /// See [`AnyCache::validate_dir`] for more details.
+    #[inline]
+    pub fn validate_dir<T: DirLoadable + Compound>(&self, id: &str) -> ValidationReport {
+        self._validate_dir::<T>(id)
+    }
+
+    /// Loads all assets of a directory and its subdirectories, and collects
+    /// both the loaded assets and the errors.
+    ///
+    /// See [`AnyCache::load_dir_report`] for more details.
+    #[inline]
+    pub fn load_dir_report<T: DirLoadable + Compound>(&self, id: &str) -> DirLoadReport<'_, T> {
+        self._load_dir_report::<T>(id)
+    }
+
     /// Converts to an `AnyCache`.
     #[inline]
-    pub fn as_any_cache(&self) -> AnyCache {
+    pub fn as_any_cache(&self) -> AnyCache<'_> {
         self._as_any_cache()
     }
 }
@@ -236,6 +385,14 @@ impl<S> LocalAssetCache<S> {
     pub fn clear(&mut self) {
         self.assets.clear();
     }
+
+    /// Removes from the cache all the assets for which `f` returns `false`.
+    ///
+    /// See [`AssetCache::retain`](crate::AssetCache::retain) for more details.
+    #[inline]
+    pub fn retain(&mut self, mut f: impl FnMut(&UntypedHandle) -> bool) {
+        self.assets.retain(&mut f);
+    }
 }
 
 impl<S> Default for LocalAssetCache<S>
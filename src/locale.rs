@@ -0,0 +1,245 @@
+use crate::{
+    entry::CacheEntry, utils::RwLock, AnyCache, AsAnyCache, BoxedError, Compound, Handle,
+    SharedString,
+};
+use std::{fmt, marker::PhantomData};
+
+/// The id under which the locale is stored in the cache.
+const LOCALE_ID: &str = "";
+
+/// Where localized versions of an asset are stored.
+///
+/// See [`Locale::with_layout`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LocaleLayout {
+    /// The locale is a suffix of the id: `"strings.menu"` is localized as
+    /// `"strings.menu@fr"`.
+    #[default]
+    Suffix,
+
+    /// Each locale has a directory next to the asset: `"strings.menu"` is
+    /// localized as `"strings.fr.menu"`.
+    Directory,
+}
+
+/// The locale used to load [`Localized`] assets.
+///
+/// A locale is a list of language tags, sorted by preference. When a tag has
+/// a region (eg `"fr-CA"`), the language without region (`"fr"`) is added
+/// after it, so the locale `Locale::new("fr-CA").with_fallback("en")` tries
+/// `"fr-CA"`, then `"fr"`, then `"en"`.
+///
+/// The default locale has no tag, so only non-localized assets are loaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Locale {
+    tags: Vec<SharedString>,
+    layout: LocaleLayout,
+}
+
+impl Locale {
+    /// Creates a locale with a language tag.
+    pub fn new(tag: &str) -> Self {
+        Self::default().with_fallback(tag)
+    }
+
+    /// Adds a language tag to try after the current ones.
+    pub fn with_fallback(mut self, tag: &str) -> Self {
+        let mut tag = tag;
+        loop {
+            if !self.tags.iter().any(|t| **t == *tag) {
+                self.tags.push(tag.into());
+            }
+            match tag.rfind('-') {
+                Some(n) => tag = &tag[..n],
+                None => break,
+            }
+        }
+        self
+    }
+
+    /// Sets where localized versions of assets are stored.
+    ///
+    /// The default is [`LocaleLayout::Suffix`].
+    #[inline]
+    pub fn with_layout(mut self, layout: LocaleLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Returns an iterator over the language tags of the locale, sorted by
+    /// preference.
+    #[inline]
+    pub fn tags(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.tags.iter().map(|t| &**t)
+    }
+
+    /// Returns where localized versions of assets are stored.
+    #[inline]
+    pub fn layout(&self) -> LocaleLayout {
+        self.layout
+    }
+
+    /// Returns the id of the version of asset `id` for language `tag`.
+    fn localized_id(&self, id: &str, tag: &str) -> String {
+        match self.layout {
+            LocaleLayout::Suffix => format!("{id}@{tag}"),
+            LocaleLayout::Directory => match id.rsplit_once('.') {
+                Some((dir, name)) => format!("{dir}.{tag}.{name}"),
+                None => format!("{tag}.{id}"),
+            },
+        }
+    }
+}
+
+/// The locale of a cache, stored in it so that it is available when
+/// hot-reloading.
+struct LocaleSetting(RwLock<Locale>);
+
+/// A marker for changes of the cache's locale.
+///
+/// `Localized` assets depend on it, and it is reloaded when the locale
+/// changes, so they are reloaded too.
+struct LocaleChange;
+
+impl Compound for LocaleChange {
+    fn load(_: AnyCache, _: &SharedString) -> Result<Self, BoxedError> {
+        Ok(LocaleChange)
+    }
+
+    const HOT_RELOADED: bool = true;
+}
+
+pub(crate) fn get(cache: AnyCache) -> Locale {
+    match cache.no_record(|| cache.get_cached::<LocaleSetting>(LOCALE_ID)) {
+        Some(setting) => setting.read().0.read().clone(),
+        None => Locale::default(),
+    }
+}
+
+pub(crate) fn set(cache: AnyCache, locale: Locale) {
+    let setting = match cache.no_record(|| cache.get_cached::<LocaleSetting>(LOCALE_ID)) {
+        Some(setting) => setting,
+        None => {
+            let setting = LocaleSetting(RwLock::new(Locale::default()));
+            let entry = CacheEntry::new_any(setting, LOCALE_ID.into(), false, cache);
+            cache.insert_entry(entry).downcast_ref_ok()
+        }
+    };
+    *setting.read().0.write() = locale;
+
+    #[cfg(feature = "hot-reloading")]
+    if let Some(reloader) = cache.reloader() {
+        let type_id = std::any::TypeId::of::<LocaleChange>();
+        reloader.invalidate(LOCALE_ID.into(), Some(type_id));
+    }
+}
+
+/// An asset in the version of the cache's locale.
+///
+/// When loading `Localized<T>` with id `"strings.menu"`, the language tags of
+/// the [`Locale`] of the cache are tried in order, and the first version that
+/// exists in the source is loaded. The non-localized `"strings.menu"` is used
+/// if none of them exists. The id of a localized version is given by the
+/// [`LocaleLayout`] of the locale.
+///
+/// As a `Localized` is stored in the cache, it only stores the id of the
+/// chosen version, and the asset is given by the cache it was loaded from.
+///
+/// ## Hot-reloading
+///
+/// `Localized` assets are reloaded when the locale of the cache changes, and
+/// when a localized version is added or removed. Without hot-reloading,
+/// changing the locale only affects assets that are loaded afterwards.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{source::InMemory, AssetCache, Locale, Localized};
+///
+/// let source = InMemory::new();
+/// source.insert("menu", "txt", b"Play".to_vec());
+/// source.insert("menu@fr", "txt", b"Jouer".to_vec());
+///
+/// let cache = AssetCache::with_source(source);
+/// cache.set_locale(Locale::new("fr-CA").with_fallback("en"));
+///
+/// let menu = cache.load::<Localized<String>>("menu")?.read();
+/// assert_eq!(menu.id(), "menu@fr");
+/// assert_eq!(menu.tag(), Some("fr"));
+/// assert_eq!(*menu.get(&cache).unwrap().read(), "Jouer");
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct Localized<T> {
+    id: SharedString,
+    tag: Option<SharedString>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Compound for Localized<T>
+where
+    T: Compound,
+{
+    fn load(cache: AnyCache, id: &SharedString) -> Result<Self, BoxedError> {
+        cache.load::<LocaleChange>(LOCALE_ID)?;
+        let locale = get(cache);
+
+        for tag in locale.tags() {
+            let localized_id = locale.localized_id(id, tag);
+            if cache.has_entry(&localized_id) {
+                let handle = cache.load::<T>(&localized_id)?;
+                return Ok(Localized {
+                    id: handle.id().clone(),
+                    tag: Some(tag.into()),
+                    _marker: PhantomData,
+                });
+            }
+        }
+
+        let handle = cache.load::<T>(id)?;
+        Ok(Localized {
+            id: handle.id().clone(),
+            tag: None,
+            _marker: PhantomData,
+        })
+    }
+
+    const HOT_RELOADED: bool = true;
+}
+
+impl<T> Localized<T> {
+    /// Returns the id of the chosen version of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// Returns the language tag of the chosen version, or `None` if the
+    /// non-localized version is used.
+    #[inline]
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+}
+
+impl<T> Localized<T>
+where
+    T: Compound,
+{
+    /// Returns the chosen version of the asset.
+    ///
+    /// This function does not do any I/O: `cache` must be the cache the
+    /// asset was loaded from.
+    #[inline]
+    pub fn get<'a>(&self, cache: impl AsAnyCache<'a>) -> Option<&'a Handle<T>> {
+        cache.as_any_cache().get_cached(&self.id)
+    }
+}
+
+impl<T> fmt::Debug for Localized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Localized")
+            .field("id", &self.id)
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
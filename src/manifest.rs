@@ -0,0 +1,253 @@
+use crate::{LoadFailure, ReloadId, SharedString};
+use std::{cmp::Ordering, fmt};
+
+#[cfg(doc)]
+use crate::AssetCache;
+
+/// A summary of the content of a cache.
+///
+/// It is returned by [`AssetCache::dump_manifest`], and is meant to be
+/// printed (with its `Display` implementation) or serialized (with feature
+/// `serde`), eg to be attached to crash reports.
+///
+/// Entries are sorted by id and type name, so that dumping the same cache
+/// twice gives the same manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>,
+    failures: Vec<LoadFailure>,
+}
+
+impl Manifest {
+    pub(crate) fn new(mut entries: Vec<ManifestEntry>, failures: Vec<LoadFailure>) -> Self {
+        entries.sort_unstable_by(ManifestEntry::cmp_key);
+        Self { entries, failures }
+    }
+
+    /// Returns the entries of the manifest, sorted by id and type name.
+    #[inline]
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Returns the assets that failed to load, sorted by id and type name.
+    ///
+    /// See [`AssetCache::load_failures`].
+    #[inline]
+    pub fn failures(&self) -> &[LoadFailure] {
+        &self.failures
+    }
+
+    /// Compares this manifest with a newer one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::AssetCache;
+    ///
+    /// let cache = AssetCache::new("assets")?;
+    /// let before = cache.dump_manifest();
+    /// cache.load::<String>("example.hello")?;
+    ///
+    /// let after = cache.dump_manifest();
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.added()[0].id(), "example.hello");
+    /// assert!(diff.removed().is_empty());
+    /// # Ok::<(), assets_manager::BoxedError>(())
+    /// ```
+    pub fn diff<'a>(&'a self, newer: &'a Manifest) -> ManifestDiff<'a> {
+        let mut diff = ManifestDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+
+        let mut old = self.entries.iter().peekable();
+        let mut new = newer.entries.iter().peekable();
+
+        loop {
+            match (old.peek(), new.peek()) {
+                (Some(a), Some(b)) => match a.cmp_key(b) {
+                    Ordering::Less => diff.removed.extend(old.next()),
+                    Ordering::Greater => diff.added.extend(new.next()),
+                    Ordering::Equal => {
+                        if a != b {
+                            diff.changed.push((a, b));
+                        }
+                        old.next();
+                        new.next();
+                    }
+                },
+                (Some(_), None) => diff.removed.extend(old.next()),
+                (None, Some(_)) => diff.added.extend(new.next()),
+                (None, None) => break,
+            }
+        }
+
+        diff
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for entry in &self.entries {
+            if !std::mem::take(&mut first) {
+                f.write_str("\n")?;
+            }
+            fmt::Display::fmt(entry, f)?;
+        }
+        for failure in &self.failures {
+            if !std::mem::take(&mut first) {
+                f.write_str("\n")?;
+            }
+            write!(f, "failed: {failure}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An entry of a [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub(crate) id: SharedString,
+    pub(crate) type_name: &'static str,
+    pub(crate) reload_id: ReloadId,
+    pub(crate) mount: Option<SharedString>,
+}
+
+impl ManifestEntry {
+    fn cmp_key(&self, other: &Self) -> Ordering {
+        (&*self.id, self.type_name).cmp(&(&*other.id, other.type_name))
+    }
+
+    /// The id of the asset.
+    #[inline]
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// The name of the type of the asset.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The last `ReloadId` of the asset.
+    #[inline]
+    pub fn reload_id(&self) -> ReloadId {
+        self.reload_id
+    }
+
+    /// The prefix of the mounted source the asset was loaded from, or `None`
+    /// if it was loaded from the source of the cache.
+    #[inline]
+    pub fn mount(&self) -> Option<&SharedString> {
+        self.mount.as_ref()
+    }
+}
+
+impl fmt::Display for ManifestEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" ({})", self.id, self.type_name)?;
+        if self.reload_id != ReloadId::NEVER {
+            write!(f, ", reload {}", self.reload_id.0)?;
+        }
+        if let Some(mount) = &self.mount {
+            write!(f, ", mounted at \"{mount}\"")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for ManifestEntry {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut entry = s.serialize_struct("ManifestEntry", 4)?;
+        entry.serialize_field("id", &self.id)?;
+        entry.serialize_field("type", self.type_name)?;
+        entry.serialize_field("reload_id", &self.reload_id.0)?;
+        entry.serialize_field("mount", &self.mount)?;
+        entry.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Manifest {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut manifest = s.serialize_struct("Manifest", 2)?;
+        manifest.serialize_field("entries", &self.entries)?;
+        manifest.serialize_field("failures", &self.failures)?;
+        manifest.end()
+    }
+}
+
+/// The differences between two [`Manifest`]s.
+///
+/// This is returned by [`Manifest::diff`].
+#[derive(Debug, Clone)]
+pub struct ManifestDiff<'a> {
+    added: Vec<&'a ManifestEntry>,
+    removed: Vec<&'a ManifestEntry>,
+    changed: Vec<(&'a ManifestEntry, &'a ManifestEntry)>,
+}
+
+impl<'a> ManifestDiff<'a> {
+    /// Returns `true` if both manifests are equal.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// The entries that are only in the newer manifest.
+    #[inline]
+    pub fn added(&self) -> &[&'a ManifestEntry] {
+        &self.added
+    }
+
+    /// The entries that are only in the older manifest.
+    #[inline]
+    pub fn removed(&self) -> &[&'a ManifestEntry] {
+        &self.removed
+    }
+
+    /// The entries that are in both manifests but differ, as pairs of old and
+    /// new entries.
+    #[inline]
+    pub fn changed(&self) -> &[(&'a ManifestEntry, &'a ManifestEntry)] {
+        &self.changed
+    }
+}
+
+impl fmt::Display for ManifestDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let mut line = |f: &mut fmt::Formatter<'_>| {
+            if std::mem::take(&mut first) {
+                Ok(())
+            } else {
+                f.write_str("\n")
+            }
+        };
+
+        for entry in &self.removed {
+            line(f)?;
+            write!(f, "- {entry}")?;
+        }
+        for entry in &self.added {
+            line(f)?;
+            write!(f, "+ {entry}")?;
+        }
+        for (old, new) in &self.changed {
+            line(f)?;
+            write!(f, "~ {old} -> {new}")?;
+        }
+        Ok(())
+    }
+}
@@ -45,6 +45,14 @@ impl AssetMap {
         self.map.clear();
     }
 
+    pub fn retain(&mut self, f: &mut dyn FnMut(&UntypedHandle) -> bool) {
+        self.map.retain(|e| f(e.inner()));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CacheEntry> + '_ {
+        self.map.iter()
+    }
+
     pub fn iter_for_debug(&self) -> impl Iterator<Item = (&str, &CacheEntry)> + '_ {
         self.map.iter().map(|e| (e.as_key().1, e))
     }
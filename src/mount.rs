@@ -0,0 +1,146 @@
+//! Sources mounted at a prefix of ids
+
+use crate::{
+    source::{DirEntry, Source},
+    utils::HashSet,
+    SharedString,
+};
+use std::{fmt, io};
+
+/// Returns `true` if `id` is `prefix` or is in directory `prefix`.
+pub(crate) fn is_under(id: &str, prefix: &str) -> bool {
+    prefix.is_empty()
+        || id
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+struct Mount {
+    prefix: SharedString,
+    source: Box<dyn Source + Send + Sync>,
+}
+
+/// A list of sources that serve ids under a prefix.
+#[derive(Default)]
+pub(crate) struct Mounts {
+    /// Sorted by decreasing prefix length, so nested mounts are tried first.
+    mounts: Vec<Mount>,
+}
+
+impl Mounts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.mounts.is_empty()
+    }
+
+    /// Adds a mount, replacing the one with the same prefix if any.
+    pub fn insert(&mut self, prefix: SharedString, source: Box<dyn Source + Send + Sync>) {
+        self.remove(&prefix);
+        let pos = self
+            .mounts
+            .partition_point(|m| m.prefix.len() >= prefix.len());
+        self.mounts.insert(pos, Mount { prefix, source });
+    }
+
+    pub fn remove(&mut self, prefix: &str) -> bool {
+        let len = self.mounts.len();
+        self.mounts.retain(|m| m.prefix != prefix);
+        self.mounts.len() != len
+    }
+
+    /// Returns the source that serves `id` and the id relative to it.
+    pub fn route<'a>(&self, id: &'a str) -> Option<(&dyn Source, &'a str)> {
+        self.mounts.iter().find_map(|m| {
+            if !is_under(id, &m.prefix) {
+                return None;
+            }
+            let rel = match id.get(m.prefix.len()..)? {
+                "" => "",
+                rest if m.prefix.is_empty() => rest,
+                rest => &rest[1..],
+            };
+            Some((&*m.source as &dyn Source, rel))
+        })
+    }
+
+    /// Returns the prefix of the mount that serves `id`, if any.
+    pub fn prefix_of(&self, id: &str) -> Option<&SharedString> {
+        let m = self.mounts.iter().find(|m| is_under(id, &m.prefix))?;
+        Some(&m.prefix)
+    }
+
+    /// Reads a directory of a mounted source, giving ids relative to the
+    /// cache to `f`.
+    pub fn read_dir(
+        source: &dyn Source,
+        id: &str,
+        rel: &str,
+        f: &mut dyn FnMut(DirEntry),
+    ) -> io::Result<()> {
+        let prefix = &id[..id.len() - rel.len()];
+        let mut buf = String::new();
+
+        source.read_dir(rel, &mut |entry| {
+            buf.clear();
+            buf.push_str(prefix);
+            if rel.is_empty() && !prefix.is_empty() {
+                buf.push('.');
+            }
+            buf.push_str(entry.id());
+
+            match entry {
+                DirEntry::File(_, ext) => f(DirEntry::File(&buf, ext)),
+                DirEntry::Directory(_) => f(DirEntry::Directory(&buf)),
+            }
+        })
+    }
+
+    /// Calls `f` with the directories of directory `id` that lead to a mount,
+    /// except those in `seen`. Returns `true` if there was any.
+    pub fn mount_points(
+        &self,
+        id: &str,
+        seen: &HashSet<String>,
+        f: &mut dyn FnMut(DirEntry),
+    ) -> bool {
+        let mut found = HashSet::new();
+
+        for m in &self.mounts {
+            let rest = match id {
+                "" => &*m.prefix,
+                _ => match m.prefix.strip_prefix(id).and_then(|r| r.strip_prefix('.')) {
+                    Some(rest) => rest,
+                    None => continue,
+                },
+            };
+            if rest.is_empty() {
+                continue;
+            }
+
+            let len = rest.find('.').unwrap_or(rest.len());
+            let child = &m.prefix[..m.prefix.len() - rest.len() + len];
+            if !seen.contains(child) && found.insert(child) {
+                f(DirEntry::Directory(child));
+            }
+        }
+
+        !found.is_empty()
+    }
+
+    /// Returns `true` if `id` is a directory that leads to a mount.
+    pub fn is_mount_point(&self, id: &str) -> bool {
+        self.mounts.iter().any(|m| is_under(&m.prefix, id))
+    }
+}
+
+impl fmt::Debug for Mounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.mounts.iter().map(|m| &m.prefix))
+            .finish()
+    }
+}
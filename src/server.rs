@@ -0,0 +1,204 @@
+use crate::{
+    source::{FileSystem, Source},
+    utils::{HashMap, RwLock},
+    AnyCache, AssetCache, Compound, Error, SharedString, UntypedHandle,
+};
+use std::{any::TypeId, fmt};
+
+/// The id of an asset loaded by an [`AssetServer`].
+///
+/// This is a small `Copy` value that can be stored anywhere, eg in the
+/// components of an ECS. Each asset loaded by a server is given a unique id,
+/// which is never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandleId(u64);
+
+impl HandleId {
+    /// Returns the raw value of the id.
+    #[inline]
+    pub fn to_bits(self) -> u64 {
+        self.0
+    }
+
+    /// Creates an id from its raw value.
+    ///
+    /// The id is meaningful only for the server that created it.
+    #[inline]
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+}
+
+type LoadFn = for<'a> fn(AnyCache<'a>, &str) -> Result<&'a UntypedHandle, Error>;
+
+fn load_untyped<'a, T: Compound>(
+    cache: AnyCache<'a>,
+    id: &str,
+) -> Result<&'a UntypedHandle, Error> {
+    Ok(cache.load::<T>(id)?.as_untyped())
+}
+
+struct Kind {
+    name: SharedString,
+    type_id: TypeId,
+    load: LoadFn,
+}
+
+struct Handles {
+    ids: HashMap<(usize, SharedString), HandleId>,
+    keys: Vec<(usize, SharedString)>,
+}
+
+/// A dynamic front end over an [`AssetCache`].
+///
+/// Typed loads of the cache require to know types at compile time. This
+/// type is meant for engines and tools that need to load assets from data,
+/// such as scripts or scene files: types are registered with names ("kinds"),
+/// and assets are loaded with a kind given as a string.
+///
+/// Loaded assets are identified by a [`HandleId`], which can be used to get
+/// the asset back, and to enumerate loaded assets.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, AssetServer};
+///
+/// let mut server = AssetServer::new(AssetCache::new("assets")?);
+/// server.register::<String>("text");
+///
+/// let handle_id = server.load_untyped("example.hello", "text")?;
+///
+/// let handle = server.get(handle_id).unwrap();
+/// assert_eq!(*handle.downcast_ref::<String>().unwrap().read(), "Hello !\n");
+///
+/// let (id, kind) = server.handle_info(handle_id).unwrap();
+/// assert_eq!((&*id, kind), ("example.hello", "text"));
+///
+/// // Unknown kinds are reported as errors
+/// assert!(server.load_untyped("example.hello", "sound").is_err());
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct AssetServer<S = FileSystem> {
+    cache: AssetCache<S>,
+    kinds: Vec<Kind>,
+    handles: RwLock<Handles>,
+}
+
+impl<S: Source> AssetServer<S> {
+    /// Creates a new server over a cache.
+    pub fn new(cache: AssetCache<S>) -> Self {
+        Self {
+            cache,
+            kinds: Vec::new(),
+            handles: RwLock::new(Handles {
+                ids: HashMap::new(),
+                keys: Vec::new(),
+            }),
+        }
+    }
+
+    /// Registers type `T` with the given kind name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the kind is already registered.
+    pub fn register<T: Compound>(&mut self, kind: &str) {
+        assert!(
+            self.kind_index(kind).is_none(),
+            "asset kind \"{kind}\" is already registered"
+        );
+        self.kinds.push(Kind {
+            name: kind.into(),
+            type_id: TypeId::of::<T>(),
+            load: load_untyped::<T>,
+        });
+    }
+
+    /// Returns an iterator over the names of registered kinds.
+    pub fn kinds(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.kinds.iter().map(|kind| &*kind.name)
+    }
+
+    fn kind_index(&self, kind: &str) -> Option<usize> {
+        self.kinds.iter().position(|k| k.name == kind)
+    }
+
+    /// Loads an asset with the given kind, and returns its id.
+    ///
+    /// Loading the same asset twice returns the same id.
+    pub fn load_untyped(&self, id: &str, kind: &str) -> Result<HandleId, Error> {
+        let Some(index) = self.kind_index(kind) else {
+            let err = format!("unknown asset kind \"{kind}\"");
+            return Err(Error::new(id.into(), err.into()));
+        };
+
+        let handle = (self.kinds[index].load)(self.cache.as_any_cache(), id)?;
+        let key = (index, handle.id().clone());
+
+        if let Some(&handle_id) = self.handles.read().ids.get(&key) {
+            return Ok(handle_id);
+        }
+
+        let mut handles = self.handles.write();
+        let next = HandleId(handles.keys.len() as u64);
+        let handle_id = *handles.ids.entry(key.clone()).or_insert(next);
+        if handle_id == next {
+            handles.keys.push(key);
+        }
+        Ok(handle_id)
+    }
+
+    /// Returns the id of an asset loaded by this server, if any.
+    ///
+    /// This function does not load the asset.
+    pub fn find(&self, id: &str, kind: &str) -> Option<HandleId> {
+        let index = self.kind_index(kind)?;
+        let key = (index, SharedString::from(id));
+        self.handles.read().ids.get(&key).copied()
+    }
+
+    /// Returns the asset with the given id.
+    ///
+    /// Returns `None` if the id was not returned by this server.
+    pub fn get(&self, handle_id: HandleId) -> Option<&UntypedHandle> {
+        let (index, id) = self.handles.read().keys.get(handle_id.0 as usize)?.clone();
+        self.cache
+            .get_cached_untyped(&id, self.kinds[index].type_id)
+    }
+
+    /// Returns the id and the kind of the asset with the given id.
+    ///
+    /// Returns `None` if the id was not returned by this server.
+    pub fn handle_info(&self, handle_id: HandleId) -> Option<(SharedString, &str)> {
+        let (index, id) = self.handles.read().keys.get(handle_id.0 as usize)?.clone();
+        Some((id, &self.kinds[index].name))
+    }
+
+    /// Returns the ids of all assets loaded by this server, in loading order.
+    pub fn handles(&self) -> Vec<HandleId> {
+        let count = self.handles.read().keys.len() as u64;
+        (0..count).map(HandleId).collect()
+    }
+
+    /// Returns the underlying cache.
+    #[inline]
+    pub fn cache(&self) -> &AssetCache<S> {
+        &self.cache
+    }
+
+    /// Returns the underlying cache, dropping the server.
+    #[inline]
+    pub fn into_cache(self) -> AssetCache<S> {
+        self.cache
+    }
+}
+
+impl<S> fmt::Debug for AssetServer<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds: Vec<_> = self.kinds.iter().map(|kind| &*kind.name).collect();
+        f.debug_struct("AssetServer")
+            .field("kinds", &kinds)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,62 @@
+//! Errors shared by sources that read archives.
+//!
+//! `format` is the name of the archive format, used when the archive has no
+//! label.
+
+use std::{error::Error, fmt, io};
+
+/// An error with a message that gives its context.
+#[derive(Debug)]
+struct ContextError<E> {
+    err: E,
+    msg: String,
+}
+
+impl<E> fmt::Display for ContextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl<E: Error + 'static> Error for ContextError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.err)
+    }
+}
+
+fn location<'a>(label: &'a Option<String>, format: &'a str) -> &'a str {
+    label.as_deref().unwrap_or(format)
+}
+
+#[cold]
+pub fn find_file(id: &str, label: &Option<String>, format: &str) -> io::Error {
+    let msg = format!(
+        "Could not find asset \"{id}\" in {}",
+        location(label, format)
+    );
+    io::Error::new(io::ErrorKind::NotFound, msg)
+}
+
+#[cold]
+pub fn read_file(err: io::Error, id: &str, label: &Option<String>, format: &str) -> io::Error {
+    let msg = format!("Could not read \"{id}\" in {}", location(label, format));
+    with_context(err.kind(), err, msg)
+}
+
+#[cold]
+pub fn find_dir(id: &str, label: &Option<String>, format: &str) -> io::Error {
+    let msg = format!(
+        "Could not find directory \"{id}\" in {}",
+        location(label, format)
+    );
+    io::Error::new(io::ErrorKind::NotFound, msg)
+}
+
+/// Wraps an error with a message.
+#[cold]
+pub fn with_context<E>(kind: io::ErrorKind, err: E, msg: String) -> io::Error
+where
+    E: Error + Send + Sync + 'static,
+{
+    io::Error::new(kind, ContextError { err, msg })
+}
@@ -0,0 +1,61 @@
+//! Case-insensitive resolution of ids, used by sources that support it.
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+use crate::{utils::HashMap, SharedString};
+
+/// Returns `true` if two names are equal when ignoring case.
+pub(crate) fn eq(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || (!a.is_ascii() && a.to_lowercase() == b.to_lowercase())
+}
+
+/// Reports that a fallback match was used to find an entry.
+#[cold]
+pub(crate) fn warn_mismatch(requested: &str, found: &dyn std::fmt::Display) {
+    log::warn!("CaseMismatch: \"{requested}\" was resolved to {found}");
+}
+
+/// Maps folded ids to the ones actually present in an archive.
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub(crate) struct CaseMap {
+    files: HashMap<(String, String), (SharedString, SharedString)>,
+    dirs: HashMap<String, SharedString>,
+}
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+impl CaseMap {
+    pub fn new<'a>(
+        files: impl Iterator<Item = (&'a SharedString, &'a SharedString)>,
+        dirs: impl Iterator<Item = &'a SharedString>,
+    ) -> Self {
+        let mut map = CaseMap {
+            files: HashMap::new(),
+            dirs: HashMap::new(),
+        };
+        for (id, ext) in files {
+            let key = (id.to_lowercase(), ext.to_lowercase());
+            map.files
+                .entry(key)
+                .or_insert_with(|| (id.clone(), ext.clone()));
+        }
+        for id in dirs {
+            map.dirs
+                .entry(id.to_lowercase())
+                .or_insert_with(|| id.clone());
+        }
+        map
+    }
+
+    /// Finds the actual id and extension of a file.
+    pub fn file(&self, id: &str, ext: &str) -> Option<(&str, &str)> {
+        let (found_id, found_ext) = self.files.get(&(id.to_lowercase(), ext.to_lowercase()))?;
+        warn_mismatch(id, &format_args!("\"{found_id}\" ({found_ext})"));
+        Some((found_id, found_ext))
+    }
+
+    /// Finds the actual id of a directory.
+    pub fn dir(&self, id: &str) -> Option<&str> {
+        let found = self.dirs.get(&*id.to_lowercase())?;
+        warn_mismatch(id, &format_args!("\"{found}\""));
+        Some(found)
+    }
+}
@@ -0,0 +1,278 @@
+use super::{DirEntry, FileContent, Source};
+use crate::{hot_reloading::EventSender, BoxedError, TooLargeError};
+use std::io::{self, Read};
+
+/// Default maximum size of a decompressed file (1 GiB).
+const DEFAULT_MAX_SIZE: u64 = 1 << 30;
+
+/// A compression format supported by [`Decompress`].
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+/// Enabled formats, in the order they are tried.
+const FORMATS: &[Format] = &[
+    #[cfg(feature = "gzip")]
+    Format::Gzip,
+    #[cfg(feature = "zstd")]
+    Format::Zstd,
+];
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            #[cfg(feature = "gzip")]
+            Format::Gzip => "gz",
+            #[cfg(feature = "zstd")]
+            Format::Zstd => "zst",
+        }
+    }
+
+    fn is_extension(ext: &str) -> bool {
+        FORMATS.iter().any(|f| f.extension() == ext)
+    }
+
+    /// The extension of the compressed version of a file with extension `ext`.
+    fn compressed_ext(self, ext: &str) -> String {
+        if ext.is_empty() {
+            self.extension().to_owned()
+        } else {
+            format!("{ext}.{}", self.extension())
+        }
+    }
+
+    /// Decompresses `data`, failing if the result is bigger than `max_size`.
+    fn decompress(self, data: &[u8], max_size: u64) -> io::Result<Vec<u8>> {
+        let decoder: Box<dyn Read + '_> = match self {
+            #[cfg(feature = "gzip")]
+            Format::Gzip => Box::new(flate2::read::MultiGzDecoder::new(data)),
+            #[cfg(feature = "zstd")]
+            Format::Zstd => Box::new(zstd::stream::read::Decoder::with_buffer(data)?),
+        };
+
+        // Read one more byte than allowed to detect files that are too large
+        let mut content = Vec::new();
+        decoder
+            .take(max_size.saturating_add(1))
+            .read_to_end(&mut content)?;
+        TooLargeError::check(content.len() as u64, max_size)?;
+        Ok(content)
+    }
+}
+
+/// A [`Source`] that transparently decompresses files.
+///
+/// When a file cannot be found in the inner source, a compressed version of
+/// it is looked for: `big_table.json` can be stored as `big_table.json.gz`
+/// (with feature `gzip`) or as `big_table.json.zst` (with feature `zstd`),
+/// and it is still loaded with id `big_table` and extension `json`.
+///
+/// Brotli (`.br`) is not supported.
+///
+/// Compressed files are listed without their compression extension when
+/// reading directories.
+///
+/// Decompressing a file that would be bigger than a maximum size (1 GiB by
+/// default, see [`with_max_size`](Self::with_max_size)) stops early and fails
+/// with a [`TooLargeError`]. This limit applies before the ones of
+/// [`SizeLimit`](super::SizeLimit) and [`Asset::MAX_SIZE`], which are only
+/// checked once the file is decompressed.
+///
+/// [`Asset::MAX_SIZE`]: crate::Asset::MAX_SIZE
+///
+/// Note that this requires the inner source to support extensions that
+/// contain a `.`, such as [`FileSystem`](super::FileSystem). Archives do not.
+///
+/// ## Hot-reloading
+///
+/// Hot-reloading events of the inner source are forwarded, and changes of
+/// compressed files are reported for the decompressed file: a change of
+/// `big_table.json.zst` reloads `big_table` with extension `json`.
+///
+/// # Example
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "json")] {
+/// use assets_manager::{AssetCache, asset::Json, source::{Decompress, FileSystem}};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Table {
+///     rows: Vec<Vec<f32>>,
+/// }
+///
+/// let cache = AssetCache::with_source(Decompress::new(FileSystem::new("assets")?));
+///
+/// // Reads "assets/data/big_table.json.zst"
+/// let table = cache.load::<Json<Table>>("data.big_table")?;
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decompress<S> {
+    source: S,
+    max_size: u64,
+}
+
+impl<S> Decompress<S> {
+    /// Creates a new `Decompress` source.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+
+    /// Sets the maximum size of a decompressed file, in bytes.
+    #[inline]
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// The maximum size of a decompressed file, in bytes.
+    #[inline]
+    pub fn max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    /// Gets a reference to the inner source.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Consumes the `Decompress`, returning the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source> Source for Decompress<S> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let err = match self.source.read(id, ext) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => err,
+            res => return res,
+        };
+
+        for &format in FORMATS {
+            match self.source.read(id, &format.compressed_ext(ext)) {
+                Ok(content) => {
+                    let content = format.decompress(content.as_ref(), self.max_size)?;
+                    return Ok(FileContent::Buffer(content));
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(err)
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        self.source.read_dir(id, &mut |entry| {
+            if let DirEntry::File(file_id, ext) = entry {
+                let name = match id {
+                    "" => Some(file_id),
+                    _ => file_id.strip_prefix(id).and_then(|n| n.strip_prefix('.')),
+                };
+
+                // `big_table.json` with extension `zst` is reported as
+                // `big_table` with extension `json`
+                if let Some((stem, inner_ext)) = name.and_then(|n| n.rsplit_once('.')) {
+                    if Format::is_extension(ext) && !stem.is_empty() {
+                        let file_id = match id {
+                            "" => stem.to_owned(),
+                            _ => format!("{id}.{stem}"),
+                        };
+                        return f(DirEntry::File(&file_id, inner_ext));
+                    }
+                }
+            }
+
+            f(entry)
+        })
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        match entry {
+            DirEntry::File(id, ext) => {
+                self.source.exists(entry)
+                    || FORMATS.iter().any(|format| {
+                        let ext = format.compressed_ext(ext);
+                        self.source.exists(DirEntry::File(id, &ext))
+                    })
+            }
+            DirEntry::Directory(_) => self.source.exists(entry),
+        }
+    }
+
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        // Only uncompressed files are found here: the size of a compressed
+        // file is not the size of its content
+        self.source.file_size(id, ext)
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        let source = self.source.make_source()?;
+        Some(Box::new(
+            Decompress::new(source).with_max_size(self.max_size),
+        ))
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        self.source.configure_hot_reloading(EventSender(tx))?;
+
+        std::thread::Builder::new()
+            .name("assets_decompress".to_owned())
+            .spawn(move || {
+                for inner in rx {
+                    let mut mapped = Vec::new();
+                    inner.for_each(|event| map_event(event, &mut mapped));
+                    if events.send_multiple(mapped).is_err() {
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "hot-reloading"))]
+    #[inline]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        self.source.configure_hot_reloading(events)
+    }
+}
+
+/// Maps an event of the inner source to the files it may affect.
+///
+/// `big_table.json` with extension `zst` is reported as `big_table` with
+/// extension `json`. As we cannot know if `big_table` is a directory here, it
+/// is also reported as `big_table.json` without extension.
+#[cfg(feature = "hot-reloading")]
+fn map_event(event: super::OwnedDirEntry, events: &mut Vec<super::OwnedDirEntry>) {
+    use super::OwnedDirEntry;
+
+    if let OwnedDirEntry::File(id, ext) = &event {
+        if Format::is_extension(ext) {
+            if let Some((stem, inner_ext)) = id.rsplit_once('.') {
+                if !stem.is_empty() && !inner_ext.is_empty() {
+                    events.push(OwnedDirEntry::File(stem.into(), inner_ext.into()));
+                }
+            }
+            events.push(OwnedDirEntry::File(id.clone(), "".into()));
+            return;
+        }
+    }
+
+    events.push(event);
+}
@@ -1,5 +1,5 @@
 use super::{DirEntry, Source};
-use std::{collections::HashMap, io};
+use std::{collections::HashMap, io, path::Path};
 
 /// The raw representation of embedded files.
 ///
@@ -66,7 +66,7 @@ impl<'a> From<RawEmbedded<'a>> for Embedded<'a> {
 
 #[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
 impl Source for Embedded<'_> {
-    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent<'_>> {
         match self.files.get(&(id, ext)) {
             Some(content) => Ok(super::FileContent::Slice(content)),
             None => Err(io::ErrorKind::NotFound.into()),
@@ -85,4 +85,35 @@ impl Source for Embedded<'_> {
             DirEntry::Directory(id) => self.dirs.contains_key(id),
         }
     }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        Some(self.files.get(&(id, ext))?.len() as u64)
+    }
+}
+
+/// Tells Cargo to rebuild the crate when an embedded directory changes.
+///
+/// The [`embed!`](super::embed) macro reads the content of directories when
+/// the crate is compiled. Changes to embedded files are detected by Cargo,
+/// but adding, removing or renaming files is not, so the embedded content may
+/// silently be outdated.
+///
+/// This function is meant to be called from a build script, with the path of
+/// the directory relative to the root of the crate. It emits
+/// `cargo:rerun-if-changed` for the directory, so the build script is run
+/// again when anything in it changes, which in turn recompiles the crate.
+///
+/// Note that when a build script emits `rerun-if-changed`, Cargo stops
+/// running it again for other changes in the crate.
+///
+/// # Example
+///
+/// In `build.rs`, with `assets_manager` as a build dependency:
+///
+/// ```no_run
+/// assets_manager::source::track_embedded("assets");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
+pub fn track_embedded<P: AsRef<Path>>(path: P) {
+    println!("cargo:rerun-if-changed={}", path.as_ref().display());
 }
@@ -0,0 +1,157 @@
+use super::{DirEntry, FileContent, Source};
+use crate::{hot_reloading::EventSender, BoxedError};
+use std::{io, sync::Arc};
+
+/// A cipher used by an [`Encrypted`] source to decrypt files.
+///
+/// The id and the extension of the file are given, so that they can be used
+/// to derive a key or a nonce.
+pub trait Cipher: Send + Sync + 'static {
+    /// Decrypts the content of a file.
+    fn decrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>>;
+
+    /// Encrypts the content of a file.
+    ///
+    /// This is only needed to write encrypted packs with a `PackWriter`. The
+    /// default implementation returns an error.
+    #[allow(unused_variables)]
+    fn encrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "this cipher does not support encryption",
+        ))
+    }
+}
+
+impl<C: Cipher + ?Sized> Cipher for Box<C> {
+    #[inline]
+    fn decrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+        (**self).decrypt(id, ext, data)
+    }
+
+    #[inline]
+    fn encrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+        (**self).encrypt(id, ext, data)
+    }
+}
+
+impl<C: Cipher + ?Sized> Cipher for Arc<C> {
+    #[inline]
+    fn decrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+        (**self).decrypt(id, ext, data)
+    }
+
+    #[inline]
+    fn encrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+        (**self).encrypt(id, ext, data)
+    }
+}
+
+/// A [`Source`] that decrypts the files of another source.
+///
+/// Files are decrypted with a [`Cipher`] when they are read, so encrypted
+/// assets are loaded with the same types as plain ones. The crate does not
+/// provide a cipher: implement [`Cipher`] with a vetted cryptography crate,
+/// such as `chacha20poly1305`.
+///
+/// Note that a key shipped with a program can always be retrieved, so this
+/// only makes it harder to extract assets.
+///
+/// ## Hot-reloading
+///
+/// Hot-reloading events of the inner source are forwarded.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::{Cipher, Encrypted, FileSystem}};
+///
+/// /// A (very) weak cipher.
+/// struct Xor(u8);
+///
+/// impl Cipher for Xor {
+///     fn decrypt(&self, _id: &str, _ext: &str, data: &[u8]) -> std::io::Result<Vec<u8>> {
+///         Ok(data.iter().map(|b| b ^ self.0).collect())
+///     }
+/// }
+///
+/// let source = Encrypted::new(FileSystem::new("assets")?, Xor(0));
+/// let cache = AssetCache::with_source(source);
+///
+/// let hello = cache.load::<String>("example.hello")?;
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Debug)]
+pub struct Encrypted<S, C> {
+    source: S,
+    cipher: Arc<C>,
+}
+
+impl<S: Clone, C> Clone for Encrypted<S, C> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            cipher: self.cipher.clone(),
+        }
+    }
+}
+
+impl<S, C> Encrypted<S, C> {
+    /// Creates a new `Encrypted` source that decrypts files with `cipher`.
+    #[inline]
+    pub fn new(source: S, cipher: C) -> Self {
+        Self {
+            source,
+            cipher: Arc::new(cipher),
+        }
+    }
+
+    /// Gets a reference to the cipher.
+    #[inline]
+    pub fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Gets a reference to the inner source.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Consumes the `Encrypted`, returning the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source, C: Cipher> Source for Encrypted<S, C> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let content = self.source.read(id, ext)?;
+        let content = self.cipher.decrypt(id, ext, content.as_ref())?;
+        Ok(FileContent::Buffer(content))
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        self.source.read_dir(id, f)
+    }
+
+    #[inline]
+    fn exists(&self, entry: DirEntry) -> bool {
+        self.source.exists(entry)
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        let source = self.source.make_source()?;
+        Some(Box::new(Encrypted {
+            source,
+            cipher: self.cipher.clone(),
+        }))
+    }
+
+    #[inline]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        self.source.configure_hot_reloading(events)
+    }
+}
@@ -0,0 +1,199 @@
+use super::{DirEntry, FileContent, ReadSeek, Source};
+use crate::{hot_reloading::EventSender, utils::HashSet, BoxedError};
+use std::{fmt, io};
+
+/// The source that satisfied a read of a [`FallbackSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// The primary source.
+    Primary,
+
+    /// The fallback source.
+    Fallback,
+}
+
+fn is_not_found(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::NotFound
+}
+
+fn entry_key(entry: DirEntry) -> (String, Option<String>) {
+    match entry {
+        DirEntry::File(id, ext) => (id.to_owned(), Some(ext.to_owned())),
+        DirEntry::Directory(id) => (id.to_owned(), None),
+    }
+}
+
+/// A [`Source`] that tries a primary source, and falls back to another one.
+///
+/// By default, the fallback source is only used when the primary one returns
+/// a [`NotFound`](io::ErrorKind::NotFound) error, so that other errors (eg a
+/// file that cannot be read) are reported instead of being hidden. This can
+/// be changed with [`fallback_on`](Self::fallback_on).
+///
+/// Reading a directory merges the entries of both sources.
+///
+/// ## Hot-reloading
+///
+/// Hot-reloading events of both sources are forwarded, if they support it.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::{FallbackSource, FileSystem, Origin}};
+///
+/// // Let files in "assets/example" override the defaults
+/// let source = FallbackSource::new(
+///     FileSystem::new("assets/example")?,
+///     FileSystem::new("assets")?,
+/// );
+///
+/// let (_, origin) = source.read_with_origin("hello", "txt")?;
+/// assert_eq!(origin, Origin::Primary);
+///
+/// let (_, origin) = source.read_with_origin("common.name", "txt")?;
+/// assert_eq!(origin, Origin::Fallback);
+///
+/// let cache = AssetCache::with_source(source);
+/// let hello = cache.load::<String>("hello")?;
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Clone)]
+pub struct FallbackSource<A, B> {
+    primary: A,
+    fallback: B,
+    fallback_on: fn(&io::Error) -> bool,
+}
+
+impl<A: Source, B: Source> FallbackSource<A, B> {
+    /// Creates a new `FallbackSource`.
+    #[inline]
+    pub fn new(primary: A, fallback: B) -> Self {
+        Self {
+            primary,
+            fallback,
+            fallback_on: is_not_found,
+        }
+    }
+
+    /// Sets the errors of the primary source for which the fallback source is
+    /// used.
+    ///
+    /// For example, `|_| true` uses the fallback source for all errors.
+    #[inline]
+    pub fn fallback_on(mut self, f: fn(&io::Error) -> bool) -> Self {
+        self.fallback_on = f;
+        self
+    }
+
+    /// Returns a reference to the primary source.
+    #[inline]
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns a reference to the fallback source.
+    #[inline]
+    pub fn fallback(&self) -> &B {
+        &self.fallback
+    }
+
+    /// Reads the content of a file, and returns which source it comes from.
+    pub fn read_with_origin(&self, id: &str, ext: &str) -> io::Result<(FileContent<'_>, Origin)> {
+        match self.primary.read(id, ext) {
+            Ok(content) => Ok((content, Origin::Primary)),
+            Err(err) if (self.fallback_on)(&err) => {
+                let content = self.fallback.read(id, ext)?;
+                Ok((content, Origin::Fallback))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the source that contains the given entry, if any.
+    pub fn origin_of(&self, entry: DirEntry) -> Option<Origin> {
+        if self.primary.exists(entry) {
+            Some(Origin::Primary)
+        } else if self.fallback.exists(entry) {
+            Some(Origin::Fallback)
+        } else {
+            None
+        }
+    }
+}
+
+impl<A: Source, B: Source> Source for FallbackSource<A, B> {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let (content, _) = self.read_with_origin(id, ext)?;
+        Ok(content)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        match self.primary.open(id, ext) {
+            Err(err) if (self.fallback_on)(&err) => self.fallback.open(id, ext),
+            result => result,
+        }
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let mut seen = HashSet::new();
+
+        let primary = self.primary.read_dir(id, &mut |entry| {
+            seen.insert(entry_key(entry));
+            f(entry);
+        });
+
+        if let Err(err) = &primary {
+            if !(self.fallback_on)(err) {
+                return primary;
+            }
+        }
+
+        let fallback = self.fallback.read_dir(id, &mut |entry| {
+            if !seen.contains(&entry_key(entry)) {
+                f(entry);
+            }
+        });
+
+        // Succeed if any source contains the directory
+        primary.or(fallback)
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        self.origin_of(entry).is_some()
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        match self.origin_of(DirEntry::File(id, ext))? {
+            Origin::Primary => self.primary.file_size(id, ext),
+            Origin::Fallback => self.fallback.file_size(id, ext),
+        }
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        Some(Box::new(FallbackSource {
+            primary: self.primary.make_source()?,
+            fallback: self.fallback.make_source()?,
+            fallback_on: self.fallback_on,
+        }))
+    }
+
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        let primary = self.primary.configure_hot_reloading(events.clone());
+        let fallback = self.fallback.configure_hot_reloading(events);
+
+        match (primary, fallback) {
+            (Err(err), Err(_)) => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Debug for FallbackSource<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FallbackSource")
+            .field("primary", &self.primary)
+            .field("fallback", &self.fallback)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,18 +1,20 @@
 use crate::{
     hot_reloading::{EventSender, FsWatcherBuilder},
-    utils::extension_of,
-    BoxedError,
+    utils::{extension_of, IdBuilder},
+    BoxedError, SharedString,
 };
 
 #[cfg(doc)]
 use crate::AssetCache;
 
 use std::{
+    borrow::Cow,
     fmt, fs, io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
-use super::{DirEntry, Source};
+use super::{DirEntry, OwnedDirEntry, Source, WritableSource};
 
 /// A [`Source`] to load assets from a directory in the file system.
 ///
@@ -23,6 +25,25 @@ use super::{DirEntry, Source};
 /// This source supports hot-reloading: when a file is edited, the corresponding
 /// assets are reloaded when [`AssetCache::hot_reload`] is called.
 ///
+/// In large directories, events for irrelevant files can be skipped with
+/// [`with_watch_ignore`](Self::with_watch_ignore) and
+/// [`with_watch_extensions`](Self::with_watch_extensions).
+///
+/// Some file systems, such as network file systems, do not report changes.
+/// Use [`with_polling`](Self::with_polling) for them.
+///
+/// ## Case sensitivity
+///
+/// Ids are case-sensitive on most platforms, which can break assets authored
+/// on case-insensitive file systems. Use
+/// [`with_case_insensitive`](Self::with_case_insensitive) to resolve them
+/// regardless of case.
+///
+/// File names created on macOS are usually in Unicode Normalization Form D,
+/// whereas strings written in code are usually in Form C, so ids that look
+/// the same may not match. Use [`with_nfc_ids`](Self::with_nfc_ids) to
+/// normalize them.
+///
 /// ## WebAssembly
 ///
 /// This source does not work in WebAssembly, because there is no file system.
@@ -30,6 +51,13 @@ use super::{DirEntry, Source};
 #[derive(Clone)]
 pub struct FileSystem {
     path: PathBuf,
+    watch_ignore: Vec<String>,
+    watch_extensions: Option<Vec<String>>,
+    polling: Option<(Duration, bool)>,
+    case_insensitive: bool,
+    nfc_ids: bool,
+    #[cfg(feature = "mmap")]
+    mmap_threshold: Option<u64>,
 }
 
 impl FileSystem {
@@ -46,7 +74,128 @@ impl FileSystem {
         let path = path.as_ref().canonicalize()?;
         let _ = path.read_dir()?;
 
-        Ok(FileSystem { path })
+        Ok(FileSystem {
+            path,
+            watch_ignore: Vec::new(),
+            watch_extensions: None,
+            polling: None,
+            case_insensitive: false,
+            nfc_ids: false,
+            #[cfg(feature = "mmap")]
+            mmap_threshold: None,
+        })
+    }
+
+    /// Ignores file system events for paths that contain a file or directory
+    /// whose name matches `pattern`.
+    ///
+    /// In patterns, `*` matches any sequence of characters and `?` matches a
+    /// single character. This only affects hot-reloading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::FileSystem;
+    ///
+    /// let fs = FileSystem::new("assets")?
+    ///     .with_watch_ignore(".git")
+    ///     .with_watch_ignore("*.tmp")
+    ///     .with_watch_ignore("*.swp");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_watch_ignore(mut self, pattern: &str) -> Self {
+        self.watch_ignore.push(pattern.to_owned());
+        self
+    }
+
+    /// Polls the file system at the given interval to detect changes, instead
+    /// of relying on events of the operating system.
+    ///
+    /// See [`FsWatcherBuilder::polling`] for details. This only affects
+    /// hot-reloading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::FileSystem;
+    /// use std::time::Duration;
+    ///
+    /// let fs = FileSystem::new("assets")?.with_polling(Duration::from_secs(1), false);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_polling(mut self, interval: Duration, compare_contents: bool) -> Self {
+        self.polling = Some((interval, compare_contents));
+        self
+    }
+
+    /// Only reports file system events for files with one of the given
+    /// extensions.
+    ///
+    /// Events for directories are still reported. This only affects
+    /// hot-reloading.
+    pub fn with_watch_extensions(mut self, extensions: &[&str]) -> Self {
+        let extensions = extensions.iter().map(|&ext| ext.to_owned()).collect();
+        self.watch_extensions = Some(extensions);
+        self
+    }
+
+    /// Resolves ids case-insensitively when no entry matches exactly.
+    ///
+    /// When an id is not found, the names of the directories on its path are
+    /// compared to its components ignoring case. A warning mentioning
+    /// `CaseMismatch` is logged each time such a fallback match is used, so
+    /// that the ids can be fixed.
+    ///
+    /// This is `false` by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{FileSystem, Source};
+    ///
+    /// let fs = FileSystem::new("assets")?.with_case_insensitive(true);
+    /// assert!(fs.read("Example.HELLO", "txt").is_ok());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Normalizes ids built from file names to Unicode Normalization Form C
+    /// (NFC).
+    ///
+    /// This affects ids given by [`read_dir`](Source::read_dir),
+    /// [`id_of`](Self::id_of) and hot-reloading. When an id is not found,
+    /// the names of the directories on its path are normalized and compared
+    /// to its components, so files with names in another form can still be
+    /// read.
+    ///
+    /// This is `false` by default.
+    pub fn with_nfc_ids(mut self, enabled: bool) -> Self {
+        self.nfc_ids = enabled;
+        self
+    }
+
+    /// Memory maps files of at least `threshold` bytes instead of reading
+    /// them.
+    ///
+    /// This avoids copying large files (eg archives or textures) in memory.
+    /// Smaller files are still read, as mapping them is more expensive.
+    ///
+    /// # Safety
+    ///
+    /// See [`ArcMap::map`] for why this function is unsafe. In particular,
+    /// files must not be modified while they are mapped, which happens when
+    /// they are edited with hot-reloading or written with
+    /// [`AssetCache::save`].
+    ///
+    /// [`ArcMap::map`]: super::ArcMap::map
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub unsafe fn with_mmap_threshold(mut self, threshold: u64) -> Self {
+        self.mmap_threshold = Some(threshold);
+        self
     }
 
     /// Gets the path of the source's root.
@@ -58,23 +207,183 @@ impl FileSystem {
     }
 
     /// Returns the path that the directory entry would have if it exists.
+    ///
+    /// The returned path is inside [`root`](Self::root). This is the path
+    /// that is read when loading the entry, so it can be given to external
+    /// programs, for example to open the file backing an asset in an editor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{DirEntry, FileSystem};
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    /// let path = fs.path_of(DirEntry::File("example.hello", "txt"));
+    /// assert!(path.ends_with("example/hello.txt"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
     #[inline]
     pub fn path_of(&self, entry: DirEntry) -> PathBuf {
         crate::utils::path_of_entry(&self.path, entry)
     }
+
+    /// Returns the directory entry that a path represents, if any.
+    ///
+    /// This is the inverse of [`path_of`](Self::path_of). Relative paths are
+    /// resolved from the current directory. `None` is returned if the path is
+    /// not inside [`root`](Self::root) or if it cannot be represented with an
+    /// id, for example if a component contains a `.`.
+    ///
+    /// Whether the entry is a file or a directory is determined by looking at
+    /// the file system: a path that does not exist is considered a file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{FileSystem, OwnedDirEntry};
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    ///
+    /// let entry = fs.id_of("assets/example/hello.txt".as_ref());
+    /// assert_eq!(entry, Some(OwnedDirEntry::File("example.hello".into(), "txt".into())));
+    ///
+    /// let entry = fs.id_of("assets/example".as_ref());
+    /// assert_eq!(entry, Some(OwnedDirEntry::Directory("example".into())));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn id_of(&self, path: &Path) -> Option<OwnedDirEntry> {
+        let path = match path.canonicalize() {
+            Ok(path) => path,
+            // The path may not exist (yet), so try to resolve its parent
+            Err(_) => path.parent()?.canonicalize().ok()?.join(path.file_name()?),
+        };
+        if path == self.path {
+            return Some(OwnedDirEntry::Directory("".into()));
+        }
+
+        let entry = crate::utils::entry_of_path(&mut IdBuilder::default(), &self.path, &path)?;
+        Some(match entry {
+            OwnedDirEntry::File(id, ext) => OwnedDirEntry::File(self.normalize_id(id), ext),
+            OwnedDirEntry::Directory(id) => OwnedDirEntry::Directory(self.normalize_id(id)),
+        })
+    }
+
+    fn normalize_id(&self, id: SharedString) -> SharedString {
+        if self.nfc_ids {
+            crate::utils::nfc_id(id)
+        } else {
+            id
+        }
+    }
+
+    fn normalize<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if self.nfc_ids {
+            crate::utils::nfc(name)
+        } else {
+            Cow::Borrowed(name)
+        }
+    }
+
+    /// Returns the path of an entry, falling back to a case-insensitive or
+    /// normalized match if enabled.
+    fn resolve(&self, entry: DirEntry) -> PathBuf {
+        let path = self.path_of(entry);
+        if (self.case_insensitive || self.nfc_ids) && !path.exists() {
+            if let Some((found, case_mismatch)) = self.find_path(entry) {
+                if case_mismatch {
+                    super::case::warn_mismatch(entry.id(), &found.display());
+                }
+                return found;
+            }
+        }
+        path
+    }
+
+    /// Finds the path of an entry by comparing the names of files with its
+    /// components, returning whether the case differs.
+    fn find_path(&self, entry: DirEntry) -> Option<(PathBuf, bool)> {
+        let (id, ext) = match entry {
+            DirEntry::File(id, ext) => (id, Some(ext)),
+            DirEntry::Directory(id) => (id, None),
+        };
+        if id.is_empty() {
+            return None;
+        }
+
+        let mut path = self.path.clone();
+        let mut case_mismatch = false;
+        let mut components = id.split('.').peekable();
+
+        while let Some(comp) = components.next() {
+            let name = match ext {
+                Some(ext) if components.peek().is_none() && !ext.is_empty() => {
+                    Cow::Owned(format!("{comp}.{ext}"))
+                }
+                _ => Cow::Borrowed(comp),
+            };
+
+            let exact = path.join(&*name);
+            if exact.exists() {
+                path = exact;
+                continue;
+            }
+
+            let name = self.normalize(&name);
+            let mut found = None;
+            for entry in fs::read_dir(&path).ok()?.flatten() {
+                let file_name = entry.file_name();
+                let Some(file_name) = file_name.to_str() else {
+                    continue;
+                };
+                let file_name = self.normalize(file_name);
+
+                if file_name == name {
+                    found = Some((entry.path(), false));
+                    break;
+                } else if self.case_insensitive
+                    && found.is_none()
+                    && super::case::eq(&file_name, &name)
+                {
+                    found = Some((entry.path(), true));
+                }
+            }
+
+            let (found, mismatch) = found?;
+            path = found;
+            case_mismatch |= mismatch;
+        }
+
+        Some((path, case_mismatch))
+    }
 }
 
 impl Source for FileSystem {
-    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent> {
-        let path = self.path_of(DirEntry::File(id, ext));
+    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent<'_>> {
+        let path = self.resolve(DirEntry::File(id, ext));
+
+        #[cfg(feature = "mmap")]
+        if let Some(threshold) = self.mmap_threshold {
+            // Safety: The caller of `with_mmap_threshold` guaranteed that
+            // mapping files is sound.
+            return unsafe { read_mapped(&path, threshold) }.map_err(|err| read_error(err, path));
+        }
+
         match fs::read(&path) {
             Ok(buf) => Ok(super::FileContent::Buffer(buf)),
             Err(err) => Err(read_error(err, path)),
         }
     }
 
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn super::ReadSeek + '_>> {
+        let path = self.resolve(DirEntry::File(id, ext));
+        match fs::File::open(&path) {
+            Ok(file) => Ok(Box::new(file)),
+            Err(err) => Err(read_error(err, path)),
+        }
+    }
+
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
-        let dir_path = self.path_of(DirEntry::Directory(id));
+        let dir_path = self.resolve(DirEntry::Directory(id));
         let entries = fs::read_dir(&dir_path).map_err(|err| read_error(err, dir_path))?;
 
         let mut entry_id = id.to_owned();
@@ -84,16 +393,16 @@ impl Source for FileSystem {
             let path = entry.path();
 
             let name = match path.file_stem().and_then(|n| n.to_str()) {
-                Some(name) => name,
+                Some(name) => self.normalize(name),
                 None => continue,
             };
 
             let this_id: &str = if !id.is_empty() {
                 entry_id.truncate(id.len());
-                entry_id.extend([".", name].iter().copied());
+                entry_id.extend([".", &name].iter().copied());
                 &entry_id
             } else {
-                name
+                &name
             };
 
             if path.is_file() {
@@ -109,7 +418,12 @@ impl Source for FileSystem {
     }
 
     fn exists(&self, entry: DirEntry) -> bool {
-        self.path_of(entry).exists()
+        self.resolve(entry).exists()
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        let metadata = fs::metadata(self.resolve(DirEntry::File(id, ext))).ok()?;
+        metadata.is_file().then_some(metadata.len())
     }
 
     fn make_source(&self) -> Option<Box<dyn Source + Send>> {
@@ -117,13 +431,56 @@ impl Source for FileSystem {
     }
 
     fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
-        let mut watcher = FsWatcherBuilder::new()?;
+        let mut watcher = match self.polling {
+            Some((interval, compare_contents)) => {
+                FsWatcherBuilder::polling(interval, compare_contents)?
+            }
+            None => FsWatcherBuilder::new()?,
+        };
         watcher.watch(self.path.clone())?;
+        for pattern in &self.watch_ignore {
+            watcher.ignore(pattern);
+        }
+        if let Some(extensions) = &self.watch_extensions {
+            let extensions: Vec<_> = extensions.iter().map(String::as_str).collect();
+            watcher.watch_extensions(&extensions);
+        }
+        if self.nfc_ids {
+            watcher.nfc_ids();
+        }
         watcher.build(events);
         Ok(())
     }
 }
 
+impl WritableSource for FileSystem {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> io::Result<()> {
+        check_writable(id, ext)?;
+        let path = self.path_of(DirEntry::File(id, ext));
+
+        let result = match path.parent() {
+            Some(parent) => fs::create_dir_all(parent).and_then(|()| fs::write(&path, content)),
+            None => fs::write(&path, content),
+        };
+        result.map_err(|err| write_error(err, path))
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> io::Result<()> {
+        check_writable(id, ext)?;
+        let path = self.path_of(DirEntry::File(id, ext));
+        fs::remove_file(&path).map_err(|err| write_error(err, path))
+    }
+}
+
+/// Rejects ids and extensions that would give a path outside of the root
+/// directory.
+fn check_writable(id: &str, ext: &str) -> io::Result<()> {
+    if crate::asset::is_invalid_id(id) || ext.contains(['/', '\\']) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid id"));
+    }
+    Ok(())
+}
+
 impl fmt::Debug for FileSystem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FileSystem")
@@ -132,17 +489,45 @@ impl fmt::Debug for FileSystem {
     }
 }
 
+/// Reads a file, memory mapping it if it is large enough.
+///
+/// See [`ArcMap::map`](super::ArcMap::map) for safety.
+#[cfg(feature = "mmap")]
+unsafe fn read_mapped(path: &Path, threshold: u64) -> io::Result<super::FileContent<'static>> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+
+    if len != 0 && len >= threshold {
+        let map = super::ArcMap::map(&file)?;
+        return Ok(super::FileContent::from_owned(map));
+    }
+
+    let mut buf = Vec::with_capacity(len as usize);
+    io::Read::read_to_end(&mut file, &mut buf)?;
+    Ok(super::FileContent::Buffer(buf))
+}
+
 #[cold]
 pub fn read_error(err: io::Error, path: PathBuf) -> io::Error {
+    path_error(err, path, "read")
+}
+
+#[cold]
+fn write_error(err: io::Error, path: PathBuf) -> io::Error {
+    path_error(err, path, "write")
+}
+
+fn path_error(err: io::Error, path: PathBuf, action: &'static str) -> io::Error {
     #[derive(Debug)]
     struct Error {
         err: io::Error,
         path: PathBuf,
+        action: &'static str,
     }
 
     impl fmt::Display for Error {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            write!(f, "failed to read {}", self.path.display())
+            write!(f, "failed to {} {}", self.action, self.path.display())
         }
     }
 
@@ -152,5 +537,5 @@ pub fn read_error(err: io::Error, path: PathBuf) -> io::Error {
         }
     }
 
-    io::Error::new(err.kind(), Error { err, path })
+    io::Error::new(err.kind(), Error { err, path, action })
 }
@@ -0,0 +1,615 @@
+use super::{DirEntry, FileContent, OwnedDirEntry, Source};
+use crate::{
+    hot_reloading::EventSender,
+    utils::{HashMap, Mutex},
+    BoxedError,
+};
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+#[cfg(doc)]
+use crate::AssetCache;
+
+/// A [`Source`] to load assets from a HTTP server.
+///
+/// The id and the extension of a file are mapped to an URL relative to the
+/// base URL of the source: with base URL `http://example.com/assets`, file
+/// `"common.name"` with extension `"ron"` is fetched from
+/// `http://example.com/assets/common/name.ron`. The extension used in URLs
+/// can be changed with [`map_extension`](Self::map_extension).
+///
+/// Fetched files are kept in memory with their `ETag` and `Last-Modified`
+/// headers, so that they are not downloaded again if the server answers that
+/// they did not change. [`Source::exists`] and [`Source::file_size`] send a
+/// `HEAD` request the first time they are called for a file that was not
+/// downloaded, and files that were not found are remembered.
+///
+/// Responses larger than [`max_response_size`](Self::max_response_size) are
+/// rejected.
+///
+/// HTTP has no standard way to list directories, so reading a directory
+/// always fails and directories never exist.
+///
+/// **Only plain HTTP/1.1 is supported**: this source does not implement TLS,
+/// so it cannot connect to HTTPS servers, and URLs with the `https` scheme
+/// are rejected. It is meant for development servers on a trusted network,
+/// or HTTPS servers reached through a local proxy.
+///
+/// ## Hot-reloading
+///
+/// This source supports hot-reloading if a polling interval is set with
+/// [`poll_interval`](Self::poll_interval): the files that were requested
+/// are then requested again at each interval, and assets are reloaded when
+/// the server returns a new content, or when a file is created or removed.
+///
+/// # Example
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::Http};
+/// use std::time::Duration;
+///
+/// let source = Http::new("http://localhost:8080/assets")?
+///     .poll_interval(Duration::from_secs(5));
+/// let cache = AssetCache::with_source(source);
+///
+/// let name = cache.load::<String>("common.name")?;
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Clone)]
+pub struct Http {
+    config: Arc<Config>,
+    files: Arc<Files>,
+}
+
+type Files = Mutex<HashMap<(String, String), FileState>>;
+
+/// What is known about a requested file.
+#[derive(Clone)]
+enum FileState {
+    /// The server answered that the file does not exist.
+    Missing,
+
+    /// The file exists, but only its headers were requested.
+    Exists { size: Option<u64> },
+
+    /// The file was downloaded.
+    Fetched(CachedFile),
+}
+
+#[derive(Clone)]
+struct Config {
+    /// The value of the `Host` header.
+    host: String,
+    addr: (String, u16),
+
+    /// The path of the base URL, ending with a `/`.
+    base_path: String,
+
+    extensions: Vec<(String, String)>,
+    poll_interval: Option<Duration>,
+    timeout: Duration,
+    max_response_size: u64,
+}
+
+#[derive(Clone)]
+struct CachedFile {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content: Arc<[u8]>,
+}
+
+struct Response {
+    status: u16,
+    content_length: Option<u64>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Http {
+    /// Creates a new `Http` source from a base URL.
+    ///
+    /// The URL must have the form `http://host[:port][/path]`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the URL is invalid or if it does not use the
+    /// `http` scheme. No request is made by this function.
+    pub fn new(base_url: &str) -> io::Result<Http> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "invalid HTTP URL");
+
+        let rest = match base_url.strip_prefix("http://") {
+            Some(rest) => rest,
+            None if base_url.starts_with("https://") => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "HTTPS is not supported",
+                ))
+            }
+            None => return Err(invalid()),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(n) => rest.split_at(n),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rfind(':') {
+            Some(n) if !authority[n..].contains(']') => {
+                let port = authority[n + 1..].parse().map_err(|_| invalid())?;
+                (&authority[..n], port)
+            }
+            _ => (authority, 80),
+        };
+        let host_name = host.trim_start_matches('[').trim_end_matches(']');
+        if host_name.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut base_path = path.to_owned();
+        if !base_path.ends_with('/') {
+            base_path.push('/');
+        }
+
+        Ok(Http {
+            config: Arc::new(Config {
+                host: authority.to_owned(),
+                addr: (host_name.to_owned(), port),
+                base_path,
+                extensions: Vec::new(),
+                poll_interval: None,
+                timeout: Duration::from_secs(30),
+                max_response_size: 1 << 30,
+            }),
+            files: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Uses `url_ext` instead of `ext` as the extension of files in URLs.
+    ///
+    /// For example, mapping `"ron"` to `"ron.txt"` fetches `"common.name"`
+    /// with extension `"ron"` from `common/name.ron.txt`. An empty `url_ext`
+    /// means no extension.
+    pub fn map_extension(mut self, ext: &str, url_ext: &str) -> Self {
+        let config = Arc::make_mut(&mut self.config);
+        config.extensions.retain(|(e, _)| e != ext);
+        config.extensions.push((ext.to_owned(), url_ext.to_owned()));
+        self
+    }
+
+    /// Sets the interval at which fetched files are requested again to
+    /// hot-reload them.
+    ///
+    /// Polling is disabled by default, and the source does not support
+    /// hot-reloading in that case.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        Arc::make_mut(&mut self.config).poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets the timeout of network operations (30 seconds by default).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        Arc::make_mut(&mut self.config).timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum size of the body of a response (1 GiB by default).
+    ///
+    /// Larger files fail to load with an error of kind
+    /// [`InvalidData`](io::ErrorKind::InvalidData).
+    pub fn max_response_size(mut self, size: u64) -> Self {
+        Arc::make_mut(&mut self.config).max_response_size = size;
+        self
+    }
+
+    /// Returns the URL at which the file would be fetched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::Http;
+    ///
+    /// let source = Http::new("http://localhost:8080/assets")?;
+    /// let url = source.url_of("common.name", "ron");
+    /// assert_eq!(url, "http://localhost:8080/assets/common/name.ron");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn url_of(&self, id: &str, ext: &str) -> String {
+        format!(
+            "http://{}{}",
+            self.config.host,
+            self.config.path_of(id, ext)
+        )
+    }
+
+    /// Returns the cached state of a file, or `None` if it was never
+    /// requested.
+    fn cached(&self, id: &str, ext: &str) -> Option<FileState> {
+        self.files
+            .lock()
+            .get(&(id.to_owned(), ext.to_owned()))
+            .cloned()
+    }
+}
+
+impl Config {
+    fn path_of(&self, id: &str, ext: &str) -> String {
+        let mut path = self.base_path.clone();
+
+        for (i, segment) in id.split('.').enumerate() {
+            if i != 0 {
+                path.push('/');
+            }
+            percent_encode(segment, &mut path);
+        }
+
+        let ext = match self.extensions.iter().find(|(e, _)| e == ext) {
+            Some((_, url_ext)) => url_ext,
+            None => ext,
+        };
+        if !ext.is_empty() {
+            path.push('.');
+            percent_encode(ext, &mut path);
+        }
+
+        path
+    }
+
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut last_err = None;
+        for addr in (&*self.addr.0, self.addr.1).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, self.timeout) {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host has no address")))
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        cached: Option<&CachedFile>,
+    ) -> io::Result<Response> {
+        let stream = self.connect()?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        let mut request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+            self.host
+        );
+        if let Some(etag) = cached.and_then(|c| c.etag.as_ref()) {
+            request.push_str(&format!("If-None-Match: {etag}\r\n"));
+        }
+        if let Some(date) = cached.and_then(|c| c.last_modified.as_ref()) {
+            request.push_str(&format!("If-Modified-Since: {date}\r\n"));
+        }
+        request.push_str("\r\n");
+        (&stream).write_all(request.as_bytes())?;
+
+        let is_head = method == "HEAD";
+        read_response(BufReader::new(stream), is_head, self.max_response_size)
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Reads `len` bytes at the end of `body`, without allocating more than
+/// what is received.
+fn read_body(reader: &mut impl BufRead, body: &mut Vec<u8>, len: u64) -> io::Result<()> {
+    let read = reader.take(len).read_to_end(body)?;
+    if (read as u64) < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+fn too_large() -> io::Error {
+    invalid_data("HTTP response too large")
+}
+
+fn read_response(mut reader: impl BufRead, is_head: bool, max_size: u64) -> io::Result<Response> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let status = line
+        .strip_prefix("HTTP/1.")
+        .and_then(|l| l.get(2..5))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid_data("invalid HTTP status line"))?;
+
+    let mut response = Response {
+        status,
+        content_length: None,
+        etag: None,
+        last_modified: None,
+        body: Vec::new(),
+    };
+    let mut chunked = false;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid_data("invalid HTTP header"))?;
+        let value = value.trim();
+
+        if name.eq_ignore_ascii_case("content-length") {
+            let len = value
+                .parse()
+                .map_err(|_| invalid_data("invalid Content-Length"))?;
+            response.content_length = Some(len);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("etag") {
+            response.etag = Some(value.to_owned());
+        } else if name.eq_ignore_ascii_case("last-modified") {
+            response.last_modified = Some(value.to_owned());
+        }
+    }
+
+    if is_head || status == 204 || status == 304 || (100..200).contains(&status) {
+        return Ok(response);
+    }
+
+    if chunked {
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            let size = line.split(';').next().unwrap_or("").trim();
+            let size = u64::from_str_radix(size, 16)
+                .map_err(|_| invalid_data("invalid HTTP chunk size"))?;
+
+            if size == 0 {
+                // Skip trailers
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                        break;
+                    }
+                }
+                break;
+            }
+
+            let total = (response.body.len() as u64).checked_add(size);
+            if total.map_or(true, |total| total > max_size) {
+                return Err(too_large());
+            }
+            read_body(&mut reader, &mut response.body, size)?;
+            reader.read_exact(&mut [0; 2])?;
+        }
+    } else if let Some(len) = response.content_length {
+        if len > max_size {
+            return Err(too_large());
+        }
+        read_body(&mut reader, &mut response.body, len)?;
+    } else {
+        let limit = max_size.saturating_add(1);
+        reader.take(limit).read_to_end(&mut response.body)?;
+        if response.body.len() as u64 > max_size {
+            return Err(too_large());
+        }
+    }
+
+    Ok(response)
+}
+
+fn percent_encode(s: &str, out: &mut String) {
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+}
+
+fn http_error(status: u16, path: &str) -> io::Error {
+    let kind = match status {
+        404 | 410 => io::ErrorKind::NotFound,
+        401 | 403 => io::ErrorKind::PermissionDenied,
+        _ => io::ErrorKind::Other,
+    };
+    io::Error::new(kind, format!("HTTP error {status} for \"{path}\""))
+}
+
+/// Fetches a file, using and updating the cached version.
+///
+/// Returns the content of the file and `true` if it changed since the cached
+/// version.
+fn fetch(
+    config: &Config,
+    files: &Files,
+    id: &str,
+    ext: &str,
+    cached: Option<CachedFile>,
+) -> io::Result<(Arc<[u8]>, bool)> {
+    let path = config.path_of(id, ext);
+    let response = config.request("GET", &path, cached.as_ref())?;
+
+    match (response.status, cached) {
+        (200, cached) => {
+            let content: Arc<[u8]> = response.body.into();
+            let changed = cached.map_or(true, |c| c.content != content);
+
+            let file = CachedFile {
+                etag: response.etag,
+                last_modified: response.last_modified,
+                content: content.clone(),
+            };
+            files
+                .lock()
+                .insert((id.to_owned(), ext.to_owned()), FileState::Fetched(file));
+
+            Ok((content, changed))
+        }
+        (304, Some(cached)) => Ok((cached.content, false)),
+        (status, _) => {
+            if status == 404 || status == 410 {
+                let key = (id.to_owned(), ext.to_owned());
+                files.lock().insert(key, FileState::Missing);
+            }
+            Err(http_error(status, &path))
+        }
+    }
+}
+
+/// Checks that a file exists without downloading it.
+///
+/// Returns the size of the file, if the server gives it.
+fn head(config: &Config, files: &Files, id: &str, ext: &str) -> io::Result<Option<u64>> {
+    let path = config.path_of(id, ext);
+    let response = config.request("HEAD", &path, None)?;
+
+    let key = (id.to_owned(), ext.to_owned());
+    match response.status {
+        200 => {
+            let size = response.content_length;
+            let mut files = files.lock();
+            // Keep the downloaded version of the file if there is one
+            if !matches!(files.get(&key), Some(FileState::Fetched(_))) {
+                files.insert(key, FileState::Exists { size });
+            }
+            Ok(size)
+        }
+        status => {
+            if status == 404 || status == 410 {
+                files.lock().insert(key, FileState::Missing);
+            }
+            Err(http_error(status, &path))
+        }
+    }
+}
+
+fn poll(config: Arc<Config>, files: Weak<Files>, events: EventSender, interval: Duration) {
+    loop {
+        std::thread::sleep(interval);
+
+        // Stop when the source is dropped
+        let Some(files) = files.upgrade() else { return };
+        let entries: Vec<_> = files
+            .lock()
+            .iter()
+            .map(|(key, file)| (key.clone(), file.clone()))
+            .collect();
+
+        for ((id, ext), state) in entries {
+            let (found, result) = match state {
+                FileState::Fetched(file) => {
+                    let result = fetch(&config, &files, &id, &ext, Some(file));
+                    (true, result.map(|(_, changed)| changed))
+                }
+                FileState::Exists { .. } => (true, head(&config, &files, &id, &ext).map(|_| false)),
+                FileState::Missing => {
+                    let result = fetch(&config, &files, &id, &ext, None);
+                    (false, result.map(|_| true))
+                }
+            };
+
+            let changed = match result {
+                Ok(changed) => changed,
+                // Only report the removal of a file once
+                Err(err) if err.kind() == io::ErrorKind::NotFound => found,
+                Err(err) => {
+                    log::warn!("Error polling \"{id}\": {err}");
+                    false
+                }
+            };
+
+            if changed
+                && events
+                    .send(OwnedDirEntry::File(id.into(), ext.into()))
+                    .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+impl Source for Http {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let cached = match self.cached(id, ext) {
+            Some(FileState::Fetched(file)) => Some(file),
+            _ => None,
+        };
+        let (content, _) = fetch(&self.config, &self.files, id, ext, cached)?;
+        Ok(FileContent::from_owned(content))
+    }
+
+    fn read_dir(&self, _id: &str, _f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "HTTP sources cannot read directories",
+        ))
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        match entry {
+            DirEntry::File(id, ext) => match self.cached(id, ext) {
+                Some(FileState::Missing) => false,
+                Some(_) => true,
+                None => head(&self.config, &self.files, id, ext).is_ok(),
+            },
+            DirEntry::Directory(_) => false,
+        }
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        match self.cached(id, ext) {
+            Some(FileState::Fetched(file)) => Some(file.content.len() as u64),
+            Some(FileState::Exists { size }) => size,
+            Some(FileState::Missing) => None,
+            None => head(&self.config, &self.files, id, ext).ok()?,
+        }
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        self.config.poll_interval?;
+        Some(Box::new(self.clone()))
+    }
+
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        let interval = self
+            .config
+            .poll_interval
+            .ok_or("polling is disabled for this HTTP source")?;
+
+        let config = self.config.clone();
+        let files = Arc::downgrade(&self.files);
+        std::thread::Builder::new()
+            .name("assets_http_poll".to_owned())
+            .spawn(move || poll(config, files, events, interval))?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Http {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http")
+            .field(
+                "url",
+                &format_args!("http://{}{}", self.config.host, self.config.base_path),
+            )
+            .field("poll_interval", &self.config.poll_interval)
+            .finish_non_exhaustive()
+    }
+}
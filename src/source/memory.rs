@@ -0,0 +1,223 @@
+use super::{DirEntry, FileContent, OwnedDirEntry, Source};
+use crate::{
+    utils::{HashMap, HashSet, RwLock},
+    SharedString,
+};
+use std::{fmt, io, sync::Arc};
+
+#[cfg(feature = "hot-reloading")]
+use crate::{hot_reloading::EventSender, utils::Mutex, BoxedError};
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct FileDesc(SharedString, SharedString);
+
+impl hashbrown::Equivalent<FileDesc> for (&str, &str) {
+    fn equivalent(&self, key: &FileDesc) -> bool {
+        key.0 == self.0 && key.1 == self.1
+    }
+}
+
+struct State {
+    files: HashMap<FileDesc, Arc<[u8]>>,
+    dirs: HashMap<SharedString, HashSet<OwnedDirEntry>>,
+}
+
+impl State {
+    /// Adds an entry to its parent, creating ancestors as needed, and pushes
+    /// the directories that changed to `changed`.
+    fn add(&mut self, mut entry: OwnedDirEntry, changed: &mut Vec<OwnedDirEntry>) {
+        while let Some(parent) = entry.as_dir_entry().parent_id() {
+            let parent = SharedString::from(parent);
+            let is_new = !self.dirs.contains_key(&parent);
+            let entries = self.dirs.entry(parent.clone()).or_insert_with(HashSet::new);
+            if !entries.insert(entry) {
+                break;
+            }
+            changed.push(OwnedDirEntry::Directory(parent.clone()));
+            if !is_new {
+                break;
+            }
+            entry = OwnedDirEntry::Directory(parent);
+        }
+    }
+
+    /// Removes an entry from its parent, removing ancestors that become
+    /// empty, and pushes the directories that changed to `changed`.
+    fn remove(&mut self, mut entry: OwnedDirEntry, changed: &mut Vec<OwnedDirEntry>) {
+        while let Some(parent) = entry.as_dir_entry().parent_id() {
+            let Some(entries) = self.dirs.get_mut(parent) else {
+                break;
+            };
+            entries.remove(&entry);
+            changed.push(OwnedDirEntry::Directory(parent.into()));
+
+            if !entries.is_empty() || parent.is_empty() {
+                break;
+            }
+            self.dirs.remove(parent);
+            entry = OwnedDirEntry::Directory(parent.into());
+        }
+    }
+}
+
+/// A [`Source`] that stores files in memory, which can be added and removed
+/// at runtime.
+///
+/// This is useful for tests, or to give generated content to a cache while
+/// still going through the usual loading process. Clones of an `InMemory`
+/// share the same files.
+///
+/// ## Hot-reloading
+///
+/// Adding, replacing or removing a file sends hot-reloading events, so
+/// assets loaded from it are reloaded.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::InMemory};
+///
+/// let source = InMemory::new();
+/// source.insert("greetings.hello", "txt", b"Hello !".to_vec());
+///
+/// let cache = AssetCache::with_source(source.clone());
+/// let hello = cache.load::<String>("greetings.hello")?;
+/// assert_eq!(*hello.read(), "Hello !");
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Clone)]
+pub struct InMemory {
+    state: Arc<RwLock<State>>,
+
+    #[cfg(feature = "hot-reloading")]
+    events: Arc<Mutex<Vec<EventSender>>>,
+}
+
+impl InMemory {
+    /// Creates a new `InMemory` source without files.
+    pub fn new() -> Self {
+        let mut dirs = HashMap::new();
+        dirs.insert(SharedString::from(""), HashSet::new());
+
+        Self {
+            state: Arc::new(RwLock::new(State {
+                files: HashMap::new(),
+                dirs,
+            })),
+
+            #[cfg(feature = "hot-reloading")]
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Adds a file, replacing the previous one with the same id and
+    /// extension.
+    ///
+    /// Directories that contain the file are created as needed.
+    pub fn insert(&self, id: &str, ext: &str, content: impl Into<Arc<[u8]>>) {
+        let desc = FileDesc(id.into(), ext.into());
+        let entry = OwnedDirEntry::File(desc.0.clone(), desc.1.clone());
+        let mut events = vec![entry.clone()];
+
+        let mut state = self.state.write();
+        if state.files.insert(desc, content.into()).is_none() {
+            state.add(entry, &mut events);
+        }
+        drop(state);
+
+        self.send(events);
+    }
+
+    /// Removes a file, returning `true` if it existed.
+    ///
+    /// Directories that become empty are removed too.
+    pub fn remove(&self, id: &str, ext: &str) -> bool {
+        let mut state = self.state.write();
+        let Some((FileDesc(id, ext), _)) = state.files.remove_entry(&(id, ext)) else {
+            return false;
+        };
+
+        let entry = OwnedDirEntry::File(id, ext);
+        let mut events = vec![entry.clone()];
+        state.remove(entry, &mut events);
+        drop(state);
+
+        self.send(events);
+        true
+    }
+
+    /// The number of files.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.state.read().files.len()
+    }
+
+    /// Returns `true` if there is no file.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn send(&self, events: Vec<OwnedDirEntry>) {
+        let mut senders = self.events.lock();
+        senders.retain(|sender| sender.send_multiple(events.iter().cloned()).is_ok());
+    }
+
+    #[cfg(not(feature = "hot-reloading"))]
+    #[inline]
+    fn send(&self, _events: Vec<OwnedDirEntry>) {}
+}
+
+impl Default for InMemory {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Source for InMemory {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        match self.state.read().files.get(&(id, ext)) {
+            Some(content) => Ok(FileContent::from_owned(content.clone())),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let state = self.state.read();
+        let dir = state.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
+        dir.iter().map(OwnedDirEntry::as_dir_entry).for_each(f);
+        Ok(())
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        let state = self.state.read();
+        match entry {
+            DirEntry::File(id, ext) => state.files.contains_key(&(id, ext)),
+            DirEntry::Directory(id) => state.dirs.contains_key(id),
+        }
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        Some(self.state.read().files.get(&(id, ext))?.len() as u64)
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        Some(Box::new(self.clone()))
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        self.events.lock().push(events);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for InMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemory")
+            .field("files", &self.len())
+            .finish_non_exhaustive()
+    }
+}
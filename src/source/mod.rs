@@ -37,6 +37,20 @@ use std::{borrow::Cow, fmt, io};
 use crate::{asset::DirLoadable, AssetCache};
 use crate::{hot_reloading::EventSender, BoxedError, SharedString};
 
+mod case;
+
+#[cfg(any(feature = "pack", feature = "tar", feature = "zip"))]
+mod archive_error;
+
+mod fallback;
+pub use fallback::{FallbackSource, Origin};
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod decompress;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "gzip", feature = "zstd"))))]
+pub use decompress::Decompress;
+
 mod filesystem;
 pub use filesystem::FileSystem;
 
@@ -44,7 +58,13 @@ pub use filesystem::FileSystem;
 mod embedded;
 #[cfg(feature = "embedded")]
 #[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
-pub use embedded::{Embedded, RawEmbedded};
+pub use embedded::{track_embedded, Embedded, RawEmbedded};
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub use self::http::Http;
 
 #[cfg(feature = "tar")]
 mod tar;
@@ -52,6 +72,12 @@ mod tar;
 #[cfg_attr(docsrs, doc(cfg(feature = "tar")))]
 pub use self::tar::Tar;
 
+#[cfg(feature = "pack")]
+mod pack;
+#[cfg(feature = "pack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pack")))]
+pub use self::pack::{Pack, PackWriter};
+
 #[cfg(feature = "zip")]
 mod zip;
 #[cfg(feature = "zip")]
@@ -64,12 +90,46 @@ pub use self::zip::Zip;
 /// returns a [`RawEmbedded`], which can be used to create an [`Embedded`]
 /// source.
 ///
+/// Relative paths are resolved from the current directory of the compiler,
+/// which may be the root of the workspace rather than the one of the crate.
+/// The path can be resolved from the directory of the crate instead with the
+/// `manifest_dir:` prefix. With the `expand_vars` option, environment
+/// variables are expanded in the path when written `$VAR` or `${VAR}` (a
+/// literal `$` is then written `$$`). The `env = "..."` option sets a variable
+/// that replaces the path when it is defined at compile time. The crate is
+/// rebuilt when one of these variables changes.
+///
+/// Ids can be normalized to Unicode Normalization Form C with the `nfc_ids`
+/// option, like [`FileSystem::with_nfc_ids`] does.
+///
+/// Embedded files are tracked by the compiler, so the crate is rebuilt when
+/// they are modified. Files added to or removed from the directory are not,
+/// which can be solved with [`track_embedded`] in a build script.
+///
+/// The embedded files can be selected with the following options:
+/// - `include = ["..."]`: Only embed files that match one of these patterns.
+/// - `exclude = ["..."]`: Do not embed files and directories that match one
+///   of these patterns.
+/// - `extensions = ["..."]`: Only embed files with one of these extensions.
+///
+/// Patterns are `/`-separated paths relative to the embedded directory, in
+/// which `**` matches any number of directories, `*` matches any sequence of
+/// characters and `?` matches any single character.
+///
 /// ## Example
 ///
 /// ```no_run
 /// use assets_manager::{AssetCache, source::{embed, Embedded, RawEmbedded}};
 ///
 /// static EMBEDDED: RawEmbedded<'static> = embed!("assets");
+/// static NORMALIZED: RawEmbedded<'static> = embed!("assets", nfc_ids);
+/// static FILTERED: RawEmbedded<'static> = embed!(
+///     "assets",
+///     include = ["**/*.png", "**/*.ron"],
+///     exclude = ["**/*.psd", "raw/**"],
+/// );
+/// static IN_CRATE: RawEmbedded<'static> = embed!(manifest_dir: "assets", env = "ASSETS_DIR");
+/// static EXPANDED: RawEmbedded<'static> = embed!("$CARGO_MANIFEST_DIR/assets", expand_vars);
 ///
 /// let embedded = Embedded::from(EMBEDDED);
 /// let cache = AssetCache::with_source(embedded);
@@ -78,6 +138,25 @@ pub use self::zip::Zip;
 #[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
 pub use assets_manager_macros::embed;
 
+mod memory;
+pub use memory::InMemory;
+
+mod overlay;
+pub use overlay::Overlay;
+
+mod record;
+pub use record::{Call, Recorder, Replay, Session};
+
+mod size_limit;
+pub use size_limit::SizeLimit;
+
+mod encrypted;
+pub use encrypted::{Cipher, Encrypted};
+
+#[cfg(feature = "test-kit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-kit")))]
+pub mod test_kit;
+
 #[cfg(test)]
 mod tests;
 
@@ -138,6 +217,15 @@ impl<'a> DirEntry<'a> {
             }
         }
     }
+
+    /// Converts to an owned `OwnedDirEntry`.
+    #[inline]
+    pub fn to_owned(self) -> OwnedDirEntry {
+        match self {
+            DirEntry::File(id, ext) => OwnedDirEntry::File(id.into(), ext.into()),
+            DirEntry::Directory(id) => OwnedDirEntry::Directory(id.into()),
+        }
+    }
 }
 
 /// An owned version of a `DirEntry`
@@ -172,7 +260,10 @@ impl OwnedDirEntry {
 #[cfg(feature = "mmap")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
 #[derive(Debug, Clone)]
-pub struct ArcMap(std::sync::Arc<memmap2::Mmap>);
+pub struct ArcMap {
+    map: std::sync::Arc<memmap2::Mmap>,
+    hash: Option<u64>,
+}
 
 #[cfg(feature = "mmap")]
 impl ArcMap {
@@ -185,17 +276,108 @@ impl ArcMap {
     /// out of process. Applications must consider the risk and take appropriate precautions when using
     /// file-backed maps. Solutions such as file permissions, locks or process-private (e.g. unlinked)
     /// files exist but are platform specific and limited.
+    ///
+    /// [`map_private`](Self::map_private) and
+    /// [`with_verification`](Self::with_verification) reduce the risks, but
+    /// do not remove them.
+    ///
+    /// If this is not acceptable, safe alternatives are to read the whole
+    /// file in memory (eg with [`Zip::from_bytes`] and [`std::fs::read`]) or
+    /// to read it on demand (eg with [`Zip::open`]).
+    ///
+    /// [`Zip::from_bytes`]: crate::source::Zip::from_bytes
+    /// [`Zip::open`]: crate::source::Zip::open
     pub unsafe fn map(file: &std::fs::File) -> io::Result<Self> {
         let map = memmap2::Mmap::map(file)?;
-        Ok(Self(std::sync::Arc::new(map)))
+        Ok(Self::new(map))
     }
+
+    /// Creates a private, copy-on-write memory map backed by a file.
+    ///
+    /// The length of the map is pinned to the length of the file when it is
+    /// mapped, so data appended to the file later is never seen, and writes
+    /// made through other maps of the file in this process are not shared.
+    ///
+    /// ## Safety
+    ///
+    /// See [`map`](Self::map). Pages that were not written to may still
+    /// reflect changes made to the file, and truncating the file still
+    /// invalidates the map.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # cfg_if::cfg_if! { if #[cfg(feature = "zip")] {
+    /// use assets_manager::source::{ArcMap, Zip};
+    ///
+    /// let file = std::fs::File::open("assets.zip")?;
+    /// let map = unsafe { ArcMap::map_private(&file)? }.with_verification();
+    /// let zip = Zip::from_bytes(map.clone())?;
+    ///
+    /// // Load assets from `zip`...
+    ///
+    /// map.verify()?;
+    /// # }}
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub unsafe fn map_private(file: &std::fs::File) -> io::Result<Self> {
+        let len = usize::try_from(file.metadata()?.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "file too large to be mapped")
+        })?;
+        let map = memmap2::MmapOptions::new()
+            .len(len)
+            .map_copy_read_only(file)?;
+        Ok(Self::new(map))
+    }
+
+    fn new(map: memmap2::Mmap) -> Self {
+        Self {
+            map: std::sync::Arc::new(map),
+            hash: None,
+        }
+    }
+
+    /// Remembers a hash of the current content of the map, so that changes to
+    /// the underlying file can be detected with [`verify`](Self::verify).
+    ///
+    /// This reads the whole map.
+    pub fn with_verification(mut self) -> Self {
+        self.hash = Some(hash_bytes(&self.map));
+        self
+    }
+
+    /// Checks that the content of the map did not change since
+    /// [`with_verification`](Self::with_verification) was called.
+    ///
+    /// This should be called after data from the map was used (for example
+    /// after assets were loaded from it), so that values built from a file
+    /// modified in the meantime can be discarded. It always succeeds if
+    /// verification was not enabled.
+    pub fn verify(&self) -> io::Result<()> {
+        match self.hash {
+            Some(hash) if hash != hash_bytes(&self.map) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "memory mapped file was modified",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    use std::hash::Hasher;
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
 }
 
 #[cfg(feature = "mmap")]
 impl AsRef<[u8]> for ArcMap {
     #[inline]
     fn as_ref(&self) -> &[u8] {
-        self.0.as_ref()
+        self.map.as_ref()
     }
 }
 
@@ -262,6 +444,14 @@ impl From<Vec<u8>> for FileContent<'_> {
     }
 }
 
+/// A reader that can seek, returned by [`Source::open`].
+///
+/// This trait is implemented for all types that implement both [`io::Read`]
+/// and [`io::Seek`].
+pub trait ReadSeek: io::Read + io::Seek {}
+
+impl<T: io::Read + io::Seek + ?Sized> ReadSeek for T {}
+
 /// Bytes sources to load assets from.
 ///
 /// This trait provides an abstraction over a basic filesystem, which is used to
@@ -281,7 +471,31 @@ pub trait Source {
     /// done for you by an [`AssetCache`] when you load [`Asset`]s.
     ///
     /// [`Asset`]: crate::Asset
-    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent>;
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>>;
+
+    /// Opens a file given an id and an extension, to read it progressively.
+    ///
+    /// This is useful for large files (eg videos or music) that do not need
+    /// to be fully read in memory. The default implementation reads the whole
+    /// file with [`read`](Self::read).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{FileSystem, Source};
+    /// use std::io::Read;
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    ///
+    /// let mut content = String::new();
+    /// fs.open("example.hello", "txt")?.read_to_string(&mut content)?;
+    /// assert!(content.starts_with("Hello"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        let content = self.read(id, ext)?;
+        Ok(Box::new(io::Cursor::new(content)))
+    }
 
     /// Reads the content of a directory.
     ///
@@ -330,6 +544,16 @@ pub trait Source {
     /// ```
     fn exists(&self, entry: DirEntry) -> bool;
 
+    /// Returns the size of a file in bytes, if it can be known without
+    /// reading the file.
+    ///
+    /// This is used to reject files that are too large before reading them
+    /// (see [`SizeLimit`]). The default implementation returns `None`.
+    #[inline]
+    fn file_size(&self, _id: &str, _ext: &str) -> Option<u64> {
+        None
+    }
+
     /// Returns a source to use with hot-reloading.
     ///
     /// This method returns `None` when the source does not support
@@ -356,10 +580,15 @@ where
     S: Source + ?Sized,
 {
     #[inline]
-    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
         self.as_ref().read(id, ext)
     }
 
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        self.as_ref().open(id, ext)
+    }
+
     #[inline]
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
         self.as_ref().read_dir(id, f)
@@ -370,6 +599,11 @@ where
         self.as_ref().exists(entry)
     }
 
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.as_ref().file_size(id, ext)
+    }
+
     #[inline]
     fn make_source(&self) -> Option<Box<dyn Source + Send>> {
         self.as_ref().make_source()
@@ -386,10 +620,15 @@ where
     S: Source + ?Sized,
 {
     #[inline]
-    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
         (**self).read(id, ext)
     }
 
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        (**self).open(id, ext)
+    }
+
     #[inline]
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
         (**self).read_dir(id, f)
@@ -400,6 +639,11 @@ where
         (**self).exists(entry)
     }
 
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        (**self).file_size(id, ext)
+    }
+
     #[inline]
     fn make_source(&self) -> Option<Box<dyn Source + Send>> {
         (**self).make_source()
@@ -416,10 +660,15 @@ where
     S: Source + ?Sized,
 {
     #[inline]
-    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
         self.as_ref().read(id, ext)
     }
 
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        self.as_ref().open(id, ext)
+    }
+
     #[inline]
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
         self.as_ref().read_dir(id, f)
@@ -430,6 +679,11 @@ where
         self.as_ref().exists(entry)
     }
 
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.as_ref().file_size(id, ext)
+    }
+
     #[inline]
     fn make_source(&self) -> Option<Box<dyn Source + Send>> {
         (**self).make_source()
@@ -441,6 +695,67 @@ where
     }
 }
 
+/// A [`Source`] that can be written to.
+///
+/// This enables to save assets back to the storage they were loaded from,
+/// using the same ids and extensions. See [`AssetCache::save`].
+///
+/// Note that when hot-reloading is enabled, writing a file triggers the
+/// reload of the assets that depend on it.
+pub trait WritableSource: Source {
+    /// Writes the content of a file, creating it if it does not exist and
+    /// replacing its content otherwise.
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> io::Result<()>;
+
+    /// Removes a file.
+    fn remove(&self, id: &str, ext: &str) -> io::Result<()>;
+}
+
+impl<S> WritableSource for Box<S>
+where
+    S: WritableSource + ?Sized,
+{
+    #[inline]
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> io::Result<()> {
+        self.as_ref().write(id, ext, content)
+    }
+
+    #[inline]
+    fn remove(&self, id: &str, ext: &str) -> io::Result<()> {
+        self.as_ref().remove(id, ext)
+    }
+}
+
+impl<S> WritableSource for &S
+where
+    S: WritableSource + ?Sized,
+{
+    #[inline]
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> io::Result<()> {
+        (**self).write(id, ext, content)
+    }
+
+    #[inline]
+    fn remove(&self, id: &str, ext: &str) -> io::Result<()> {
+        (**self).remove(id, ext)
+    }
+}
+
+impl<S> WritableSource for std::sync::Arc<S>
+where
+    S: WritableSource + ?Sized,
+{
+    #[inline]
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> io::Result<()> {
+        self.as_ref().write(id, ext, content)
+    }
+
+    #[inline]
+    fn remove(&self, id: &str, ext: &str) -> io::Result<()> {
+        self.as_ref().remove(id, ext)
+    }
+}
+
 /// A [`Source`] that contains nothing.
 ///
 /// Calling `read` or `read_dir` from this source will always return an error.
@@ -449,7 +764,7 @@ pub struct Empty;
 
 impl Source for Empty {
     #[inline]
-    fn read(&self, _id: &str, _ext: &str) -> io::Result<FileContent> {
+    fn read(&self, _id: &str, _ext: &str) -> io::Result<FileContent<'_>> {
         Err(io::Error::from(io::ErrorKind::NotFound))
     }
 
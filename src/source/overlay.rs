@@ -0,0 +1,189 @@
+use super::{DirEntry, FileContent, ReadSeek, Source};
+use crate::{hot_reloading::EventSender, utils::HashSet, BoxedError};
+use std::{fmt, io, sync::Arc};
+
+/// A [`Source`] that combines several sources in layers.
+///
+/// Each layer has a priority: when reading a file, layers are tried by
+/// decreasing priority until one of them contains the file. Layers with the
+/// same priority are tried in the order they were added. This is useful to
+/// let files in a directory override files shipped in an archive, for
+/// example to support mods.
+///
+/// Reading a directory merges the entries of all layers that contain it.
+///
+/// ## Hot-reloading
+///
+/// Hot-reloading events of all layers that support it are forwarded.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::{FileSystem, Overlay}};
+///
+/// let source = Overlay::new()
+///     .with_layer(FileSystem::new("assets")?, 0)
+///     .with_layer(FileSystem::new("assets/example")?, 1);
+/// let cache = AssetCache::with_source(source);
+///
+/// // Found in the second layer
+/// let hello = cache.load::<String>("hello")?;
+/// // Found in the first layer
+/// let hello = cache.load::<String>("example.hello")?;
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct Overlay {
+    /// Sorted by decreasing priority
+    layers: Vec<(i32, Arc<dyn Source + Send + Sync>)>,
+}
+
+impl Overlay {
+    /// Creates a new `Overlay` without layers.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer with the given priority.
+    pub fn add_layer<S: Source + Send + Sync + 'static>(&mut self, source: S, priority: i32) {
+        let pos = self.layers.partition_point(|(p, _)| *p >= priority);
+        self.layers.insert(pos, (priority, Arc::new(source)));
+    }
+
+    /// Adds a layer with the given priority.
+    ///
+    /// This is the same as [`add_layer`](Self::add_layer), but takes `self`
+    /// by value to be chained.
+    #[inline]
+    pub fn with_layer<S: Source + Send + Sync + 'static>(
+        mut self,
+        source: S,
+        priority: i32,
+    ) -> Self {
+        self.add_layer(source, priority);
+        self
+    }
+
+    /// The number of layers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if there is no layer.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    fn sources(&self) -> impl Iterator<Item = &(dyn Source + Send + Sync)> {
+        self.layers.iter().map(|(_, source)| &**source)
+    }
+}
+
+impl Source for Overlay {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let mut error = None;
+
+        for source in self.sources() {
+            match source.read(id, ext) {
+                Ok(content) => return Ok(content),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+
+        Err(error.unwrap_or_else(|| io::ErrorKind::NotFound.into()))
+    }
+
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        let mut error = None;
+
+        for source in self.sources() {
+            match source.open(id, ext) {
+                Ok(reader) => return Ok(reader),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+
+        Err(error.unwrap_or_else(|| io::ErrorKind::NotFound.into()))
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let mut seen = HashSet::new();
+        let mut error = None;
+        let mut found = false;
+
+        for source in self.sources() {
+            let result = source.read_dir(id, &mut |entry| {
+                let key = match entry {
+                    DirEntry::File(id, ext) => (id.to_owned(), Some(ext.to_owned())),
+                    DirEntry::Directory(id) => (id.to_owned(), None),
+                };
+                if seen.insert(key) {
+                    f(entry);
+                }
+            });
+
+            match result {
+                Ok(()) => found = true,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+
+        match (found, error) {
+            (true, _) => Ok(()),
+            (false, Some(err)) => Err(err),
+            (false, None) => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        self.sources().any(|source| source.exists(entry))
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.sources()
+            .find(|source| source.exists(DirEntry::File(id, ext)))?
+            .file_size(id, ext)
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        let mut error = None;
+        let mut configured = false;
+
+        for source in self.sources() {
+            match source.configure_hot_reloading(events.clone()) {
+                Ok(()) => configured = true,
+                Err(err) => error = Some(err),
+            }
+        }
+
+        match (configured, error) {
+            (true, _) => Ok(()),
+            (false, Some(err)) => Err(err),
+            (false, None) => Err("no layer supports hot-reloading".into()),
+        }
+    }
+}
+
+impl fmt::Debug for Overlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Overlay")
+            .field("layers", &self.layers.len())
+            .finish_non_exhaustive()
+    }
+}
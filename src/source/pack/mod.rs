@@ -0,0 +1,347 @@
+//! A file format to ship assets.
+//!
+//! A pack is laid out as follows (integers are little-endian):
+//!
+//! - A header: the magic number (8 bytes), the version (`u32`) and flags
+//!   (`u32`), which are reserved and must be zero.
+//! - The content of files, one after the other.
+//! - The index.
+//! - A trailer: the offset (`u64`) and the length (`u64`) of the index.
+//!
+//! The index starts with [`INDEX_MAGIC`] and the number of files (`u32`),
+//! followed by one record for each file: its id and its extension (both a
+//! `u16` length then UTF-8 bytes), its offset, its length in the pack and its
+//! size once decompressed (`u64`s), and flags (`u8`).
+//!
+//! Encrypted files are compressed first, then encrypted.
+
+mod writer;
+
+pub use writer::PackWriter;
+
+use super::{archive_error as error, Cipher, DirEntry, FileContent, OwnedDirEntry, Source};
+use crate::{utils::HashMap, SharedString};
+use std::{
+    fmt,
+    io::{self, Read},
+    path,
+};
+use sync_file::SyncFile;
+
+const MAGIC: [u8; 8] = *b"AMPACK\r\n";
+const VERSION: u32 = 1;
+const HEADER_LEN: u64 = 16;
+const TRAILER_LEN: u64 = 16;
+const INDEX_MAGIC: [u8; 4] = *b"INDX";
+
+/// File flag: the file is compressed with zstd.
+const COMPRESSED: u8 = 1;
+/// File flag: the file is encrypted with the cipher of the pack.
+const ENCRYPTED: u8 = 2;
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct FileDesc(SharedString, SharedString);
+
+impl hashbrown::Equivalent<FileDesc> for (&str, &str) {
+    fn equivalent(&self, key: &FileDesc) -> bool {
+        key.0 == self.0 && key.1 == self.1
+    }
+}
+
+/// The position of a file in a pack.
+#[derive(Debug, Clone, Copy)]
+struct FileInfo {
+    offset: u64,
+    len: u64,
+    size: u64,
+    flags: u8,
+}
+
+#[cold]
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid pack: {msg}"))
+}
+
+/// Reads integers and strings from a buffer.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(invalid("unexpected end of index"));
+        }
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn str(&mut self) -> io::Result<&'a str> {
+        let len = u16::from_le_bytes(self.array()?);
+        let bytes = self.bytes(len as usize)?;
+        std::str::from_utf8(bytes).map_err(|_| invalid("non UTF-8 string"))
+    }
+}
+
+fn read_at<R: io::Read + io::Seek>(reader: &mut R, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0; len.try_into().map_err(|_| invalid("file too large"))?];
+    reader.seek(io::SeekFrom::Start(offset))?;
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Checks that an id read from an index is valid.
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.split('.').all(|s| !s.is_empty())
+}
+
+/// Registers a file and its ancestors in the listing of directories.
+fn register_dirs(dirs: &mut HashMap<SharedString, Vec<OwnedDirEntry>>, desc: &FileDesc) {
+    let mut entry = OwnedDirEntry::File(desc.0.clone(), desc.1.clone());
+    while let Some(parent) = entry.as_dir_entry().parent_id() {
+        let parent = SharedString::from(parent);
+        let is_new = !dirs.contains_key(&parent);
+        dirs.entry(parent.clone()).or_default().push(entry);
+        if !is_new {
+            break;
+        }
+        entry = OwnedDirEntry::Directory(parent);
+    }
+}
+
+/// A [`Source`] to load assets from a pack.
+///
+/// Packs are an archive format designed to ship assets: they have a compact
+/// index and files can be compressed with zstd. Packs are built with a
+/// [`PackWriter`].
+///
+/// Like [`Tar`](super::Tar), the pack can be backed by any reader that also
+/// implements [`io::Seek`] and [`Clone`], which should be cheap to clone.
+///
+/// Packs do not support hot-reloading.
+///
+/// ## Encryption
+///
+/// Files can be encrypted with a [`Cipher`] given to
+/// [`PackWriter::set_cipher`], and the same cipher must then be given to
+/// [`with_cipher`](Self::with_cipher) to read them. The crate does not
+/// provide a cipher: see [`Encrypted`](super::Encrypted) for more details.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::{Pack, PackWriter}};
+///
+/// let mut writer = PackWriter::new(Vec::new())?;
+/// writer.add_dir("assets/example")?;
+/// let pack = writer.finish()?;
+///
+/// let source = Pack::from_reader(std::io::Cursor::new(pack))?;
+/// let cache = AssetCache::with_source(source);
+///
+/// let hello = cache.load::<String>("hello")?;
+/// assert!(hello.read().starts_with("Hello"));
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+pub struct Pack<R = SyncFile> {
+    reader: R,
+    files: HashMap<FileDesc, FileInfo>,
+    dirs: HashMap<SharedString, Vec<OwnedDirEntry>>,
+    label: Option<String>,
+    cipher: Option<Box<dyn Cipher>>,
+}
+
+impl Pack<SyncFile> {
+    /// Opens the pack at the given path.
+    #[inline]
+    pub fn open<P: AsRef<path::Path>>(path: P) -> io::Result<Self> {
+        Self::_open(path.as_ref())
+    }
+
+    fn _open(path: &path::Path) -> io::Result<Self> {
+        let file = SyncFile::open(path)?;
+        Self::create(file, Some(path.display().to_string()))
+    }
+}
+
+impl<R> Pack<R>
+where
+    R: io::Read + io::Seek,
+{
+    /// Creates a `Pack` backed by a reader that supports seeking.
+    pub fn from_reader(reader: R) -> io::Result<Self> {
+        Self::create(reader, None)
+    }
+
+    fn create(mut reader: R, label: Option<String>) -> io::Result<Self> {
+        let header = read_at(&mut reader, 0, HEADER_LEN)?;
+        let mut r = Reader(&header);
+        if r.array()? != MAGIC {
+            return Err(invalid("invalid magic number"));
+        }
+        let version = r.u32()?;
+        if version != VERSION {
+            return Err(invalid(&format!("unsupported version {version}")));
+        }
+        let flags = r.u32()?;
+        if flags != 0 {
+            return Err(invalid(&format!("unsupported flags {flags:#x}")));
+        }
+
+        let end = reader.seek(io::SeekFrom::End(0))?;
+        let trailer_start = end
+            .checked_sub(TRAILER_LEN)
+            .filter(|&s| s >= HEADER_LEN)
+            .ok_or_else(|| invalid("file too short"))?;
+        let trailer = read_at(&mut reader, trailer_start, TRAILER_LEN)?;
+        let mut r = Reader(&trailer);
+        let (index_offset, index_len) = (r.u64()?, r.u64()?);
+        if index_offset.checked_add(index_len) != Some(trailer_start) {
+            return Err(invalid("invalid index position"));
+        }
+
+        let index = read_at(&mut reader, index_offset, index_len)?;
+
+        let mut r = Reader(&index);
+        if r.array()? != INDEX_MAGIC {
+            return Err(invalid("invalid index"));
+        }
+
+        let count = r.u32()?;
+        let mut files = HashMap::new();
+        let mut dirs = HashMap::new();
+        dirs.insert(SharedString::from(""), Vec::new());
+
+        for _ in 0..count {
+            let (id, ext) = (r.str()?, r.str()?);
+            let info = FileInfo {
+                offset: r.u64()?,
+                len: r.u64()?,
+                size: r.u64()?,
+                flags: r.u8()?,
+            };
+
+            if info
+                .offset
+                .checked_add(info.len)
+                .map_or(true, |e| e > index_offset)
+            {
+                return Err(invalid("file out of bounds"));
+            }
+            if !is_valid_id(id) {
+                log::warn!("Invalid id in pack: \"{id}\"");
+                continue;
+            }
+
+            let desc = FileDesc(id.into(), ext.into());
+            if files.insert(desc.clone(), info).is_none() {
+                register_dirs(&mut dirs, &desc);
+            }
+        }
+
+        Ok(Pack {
+            reader,
+            files,
+            dirs,
+            label,
+            cipher: None,
+        })
+    }
+}
+
+impl<R> Pack<R> {
+    /// Sets the cipher used to decrypt encrypted files.
+    ///
+    /// Reading an encrypted file fails if no cipher is set.
+    pub fn with_cipher<C: Cipher>(mut self, cipher: C) -> Self {
+        self.cipher = Some(Box::new(cipher));
+        self
+    }
+}
+
+impl<R> Source for Pack<R>
+where
+    R: io::Read + io::Seek + Clone,
+{
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let info = *self
+            .files
+            .get(&(id, ext))
+            .ok_or_else(|| error::find_file(id, &self.label, "pack"))?;
+
+        let read = || {
+            let mut content = read_at(&mut self.reader.clone(), info.offset, info.len)?;
+            if info.flags & ENCRYPTED != 0 {
+                let cipher = self.cipher.as_ref().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "encrypted file without cipher",
+                    )
+                })?;
+                content = cipher.decrypt(id, ext, &content)?;
+            }
+            if info.flags & COMPRESSED != 0 {
+                // Do not trust the size from the index to allocate memory
+                let mut decompressed = Vec::new();
+                zstd::stream::read::Decoder::new(&content[..])?
+                    .take(info.size.saturating_add(1))
+                    .read_to_end(&mut decompressed)?;
+                content = decompressed;
+            }
+            if content.len() as u64 != info.size {
+                return Err(invalid("wrong file size"));
+            }
+            Ok(content)
+        };
+
+        match read() {
+            Ok(content) => Ok(FileContent::Buffer(content)),
+            Err(err) => Err(error::read_file(err, id, &self.label, "pack")),
+        }
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let dir = self
+            .dirs
+            .get(id)
+            .ok_or_else(|| error::find_dir(id, &self.label, "pack"))?;
+        dir.iter().map(OwnedDirEntry::as_dir_entry).for_each(f);
+        Ok(())
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        match entry {
+            DirEntry::File(id, ext) => self.files.contains_key(&(id, ext)),
+            DirEntry::Directory(id) => self.dirs.contains_key(id),
+        }
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        Some(self.files.get(&(id, ext))?.size)
+    }
+}
+
+impl<R> fmt::Debug for Pack<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pack")
+            .field("label", &self.label)
+            .field("dirs", &self.dirs)
+            .field("encrypted", &self.cipher.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,165 @@
+use super::{is_valid_id, COMPRESSED, ENCRYPTED, INDEX_MAGIC, MAGIC, VERSION};
+use crate::source::{Cipher, FileSystem, OwnedDirEntry, Source};
+use std::{fmt, io, path};
+
+/// A file written in a pack.
+struct Record {
+    id: String,
+    ext: String,
+    offset: u64,
+    len: u64,
+    size: u64,
+    flags: u8,
+}
+
+fn too_long(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("{what} is too long"))
+}
+
+/// Builds a [`Pack`](super::Pack).
+///
+/// Files are compressed with zstd when it makes them smaller.
+///
+/// See [`Pack`](super::Pack) for an example.
+pub struct PackWriter<W: io::Write> {
+    writer: W,
+    offset: u64,
+    records: Vec<Record>,
+    level: i32,
+    cipher: Option<Box<dyn Cipher>>,
+}
+
+impl<W: io::Write> PackWriter<W> {
+    /// Creates a `PackWriter`.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+
+        Ok(Self {
+            writer,
+            offset: super::HEADER_LEN,
+            records: Vec::new(),
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+            cipher: None,
+        })
+    }
+
+    /// Sets the zstd compression level of the next files.
+    ///
+    /// Level `0` means zstd's default level.
+    #[inline]
+    pub fn set_compression_level(&mut self, level: i32) {
+        self.level = level;
+    }
+
+    /// Sets the cipher used to encrypt the next files, or `None` not to
+    /// encrypt them.
+    ///
+    /// The cipher must implement [`Cipher::encrypt`].
+    #[inline]
+    pub fn set_cipher(&mut self, cipher: Option<Box<dyn Cipher>>) {
+        self.cipher = cipher;
+    }
+
+    /// Adds a file to the pack.
+    pub fn add_file(&mut self, id: &str, ext: &str, content: &[u8]) -> io::Result<()> {
+        if !is_valid_id(id) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid id: \"{id}\""),
+            ));
+        }
+        if id.len() > u16::MAX as usize || ext.len() > u16::MAX as usize {
+            return Err(too_long("id or extension"));
+        }
+        if self.records.len() >= u32::MAX as usize {
+            return Err(too_long("pack"));
+        }
+
+        let compressed = zstd::bulk::compress(content, self.level)?;
+        let (mut data, mut flags) = if compressed.len() < content.len() {
+            (compressed, COMPRESSED)
+        } else {
+            (content.to_vec(), 0)
+        };
+        if let Some(cipher) = &self.cipher {
+            data = cipher.encrypt(id, ext, &data)?;
+            flags |= ENCRYPTED;
+        }
+        self.writer.write_all(&data)?;
+
+        self.records.push(Record {
+            id: id.to_owned(),
+            ext: ext.to_owned(),
+            offset: self.offset,
+            len: data.len() as u64,
+            size: content.len() as u64,
+            flags,
+        });
+        self.offset += data.len() as u64;
+
+        Ok(())
+    }
+
+    /// Adds all files of a directory and its subdirectories to the pack.
+    ///
+    /// Files are given the same ids as with a [`FileSystem`] source at the
+    /// same path.
+    pub fn add_dir<P: AsRef<path::Path>>(&mut self, path: P) -> io::Result<()> {
+        let source = FileSystem::new(path)?;
+        self.add_source_dir(&source, "")
+    }
+
+    fn add_source_dir(&mut self, source: &dyn Source, id: &str) -> io::Result<()> {
+        let mut entries = Vec::new();
+        source.read_dir(id, &mut |entry| entries.push(entry.to_owned()))?;
+        entries.sort_unstable_by(|a, b| a.as_dir_entry().id().cmp(b.as_dir_entry().id()));
+
+        for entry in entries {
+            match entry {
+                OwnedDirEntry::File(id, ext) => {
+                    let content = source.read(&id, &ext)?;
+                    self.add_file(&id, &ext, content.as_ref())?;
+                }
+                OwnedDirEntry::Directory(id) => self.add_source_dir(source, &id)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the index of the pack and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut index = Vec::new();
+        index.extend_from_slice(&INDEX_MAGIC);
+        index.extend_from_slice(&(self.records.len() as u32).to_le_bytes());
+        for record in &self.records {
+            for s in [&record.id, &record.ext] {
+                index.extend_from_slice(&(s.len() as u16).to_le_bytes());
+                index.extend_from_slice(s.as_bytes());
+            }
+            index.extend_from_slice(&record.offset.to_le_bytes());
+            index.extend_from_slice(&record.len.to_le_bytes());
+            index.extend_from_slice(&record.size.to_le_bytes());
+            index.push(record.flags);
+        }
+
+        self.writer.write_all(&index)?;
+        self.writer.write_all(&self.offset.to_le_bytes())?;
+        self.writer.write_all(&(index.len() as u64).to_le_bytes())?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+impl<W: io::Write> fmt::Debug for PackWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PackWriter")
+            .field("files", &self.records.len())
+            .field("level", &self.level)
+            .field("encrypted", &self.cipher.is_some())
+            .finish()
+    }
+}
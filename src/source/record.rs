@@ -0,0 +1,588 @@
+use super::{DirEntry, FileContent, OwnedDirEntry, Source};
+use crate::{
+    hot_reloading::EventSender,
+    utils::{HashMap, Mutex},
+    BoxedError, SharedString,
+};
+use std::{fmt, io, sync::Arc};
+
+/// A call made to a source, recorded by a [`Recorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Call {
+    /// A call to [`Source::read`], with the index of the content of the file
+    /// in the session.
+    Read {
+        /// The id of the file.
+        id: SharedString,
+        /// The extension of the file.
+        ext: SharedString,
+        /// The index of the content, or the kind of the error.
+        result: Result<usize, io::ErrorKind>,
+    },
+
+    /// A call to [`Source::read_dir`].
+    ReadDir {
+        /// The id of the directory.
+        id: SharedString,
+        /// The entries of the directory, or the kind of the error.
+        result: Result<Vec<OwnedDirEntry>, io::ErrorKind>,
+    },
+
+    /// A call to [`Source::exists`].
+    Exists {
+        /// The entry.
+        entry: OwnedDirEntry,
+        /// Whether the entry exists.
+        result: bool,
+    },
+}
+
+/// The calls recorded by a [`Recorder`], which can be served by a [`Replay`].
+///
+/// A session can be written to bytes and read back, so it can be stored
+/// instead of a tree of assets.
+///
+/// Contents of files are stored once, even if several files have the same
+/// content.
+pub struct Session {
+    calls: Vec<Call>,
+    contents: Vec<Arc<[u8]>>,
+    indices: HashMap<Arc<[u8]>, usize>,
+}
+
+const MAGIC: &[u8; 8] = b"AMREPLAY";
+const VERSION: u32 = 1;
+
+fn error_kind_to_u8(kind: io::ErrorKind) -> u8 {
+    match kind {
+        io::ErrorKind::NotFound => 0,
+        io::ErrorKind::PermissionDenied => 1,
+        io::ErrorKind::InvalidData => 2,
+        io::ErrorKind::InvalidInput => 3,
+        io::ErrorKind::UnexpectedEof => 4,
+        _ => u8::MAX,
+    }
+}
+
+fn error_kind_from_u8(n: u8) -> io::ErrorKind {
+    match n {
+        0 => io::ErrorKind::NotFound,
+        1 => io::ErrorKind::PermissionDenied,
+        2 => io::ErrorKind::InvalidData,
+        3 => io::ErrorKind::InvalidInput,
+        4 => io::ErrorKind::UnexpectedEof,
+        _ => io::ErrorKind::Other,
+    }
+}
+
+#[cold]
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid session: {msg}"),
+    )
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(invalid("unexpected end of data"));
+        }
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn usize(&mut self) -> io::Result<usize> {
+        self.u64()?
+            .try_into()
+            .map_err(|_| invalid("integer too large"))
+    }
+
+    fn str(&mut self) -> io::Result<SharedString> {
+        let len = self.u32()? as usize;
+        let s = std::str::from_utf8(self.bytes(len)?).map_err(|_| invalid("invalid string"))?;
+        Ok(s.into())
+    }
+
+    fn entry(&mut self) -> io::Result<OwnedDirEntry> {
+        match self.u8()? {
+            0 => Ok(OwnedDirEntry::File(self.str()?, self.str()?)),
+            1 => Ok(OwnedDirEntry::Directory(self.str()?)),
+            _ => Err(invalid("invalid entry kind")),
+        }
+    }
+
+    fn result<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> io::Result<T>,
+    ) -> io::Result<Result<T, io::ErrorKind>> {
+        match self.u8()? {
+            0 => Ok(Ok(f(self)?)),
+            1 => Ok(Err(error_kind_from_u8(self.u8()?))),
+            _ => Err(invalid("invalid result")),
+        }
+    }
+}
+
+fn write_str(w: &mut Vec<u8>, s: &str) {
+    w.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    w.extend_from_slice(s.as_bytes());
+}
+
+fn write_entry(w: &mut Vec<u8>, entry: &OwnedDirEntry) {
+    match entry {
+        OwnedDirEntry::File(id, ext) => {
+            w.push(0);
+            write_str(w, id);
+            write_str(w, ext);
+        }
+        OwnedDirEntry::Directory(id) => {
+            w.push(1);
+            write_str(w, id);
+        }
+    }
+}
+
+fn write_result<T>(
+    w: &mut Vec<u8>,
+    result: &Result<T, io::ErrorKind>,
+    f: impl FnOnce(&mut Vec<u8>, &T),
+) {
+    match result {
+        Ok(x) => {
+            w.push(0);
+            f(w, x);
+        }
+        Err(kind) => {
+            w.push(1);
+            w.push(error_kind_to_u8(*kind));
+        }
+    }
+}
+
+impl Session {
+    /// Creates an empty session.
+    pub fn new() -> Self {
+        Self {
+            calls: Vec::new(),
+            contents: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// The recorded calls, in order.
+    #[inline]
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Returns the content with the given index, as found in
+    /// [`Call::Read`].
+    #[inline]
+    pub fn content(&self, index: usize) -> Option<&[u8]> {
+        self.contents.get(index).map(|c| &**c)
+    }
+
+    /// Writes the session to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Vec::new();
+        w.extend_from_slice(MAGIC);
+        w.extend_from_slice(&VERSION.to_le_bytes());
+
+        w.extend_from_slice(&(self.calls.len() as u64).to_le_bytes());
+        for call in &self.calls {
+            match call {
+                Call::Read { id, ext, result } => {
+                    w.push(0);
+                    write_str(&mut w, id);
+                    write_str(&mut w, ext);
+                    write_result(&mut w, result, |w, &index| {
+                        w.extend_from_slice(&(index as u64).to_le_bytes())
+                    });
+                }
+                Call::ReadDir { id, result } => {
+                    w.push(1);
+                    write_str(&mut w, id);
+                    write_result(&mut w, result, |w, entries| {
+                        w.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+                        entries.iter().for_each(|e| write_entry(w, e));
+                    });
+                }
+                Call::Exists { entry, result } => {
+                    w.push(2);
+                    write_entry(&mut w, entry);
+                    w.push(*result as u8);
+                }
+            }
+        }
+
+        w.extend_from_slice(&(self.contents.len() as u64).to_le_bytes());
+        for content in &self.contents {
+            w.extend_from_slice(&(content.len() as u64).to_le_bytes());
+            w.extend_from_slice(content);
+        }
+
+        w
+    }
+
+    /// Reads a session written with [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut r = Reader(bytes);
+        if r.bytes(MAGIC.len())? != MAGIC {
+            return Err(invalid("invalid magic number"));
+        }
+        if r.u32()? != VERSION {
+            return Err(invalid("unsupported version"));
+        }
+
+        let mut calls = Vec::new();
+        for _ in 0..r.u64()? {
+            calls.push(match r.u8()? {
+                0 => Call::Read {
+                    id: r.str()?,
+                    ext: r.str()?,
+                    result: r.result(Reader::usize)?,
+                },
+                1 => Call::ReadDir {
+                    id: r.str()?,
+                    result: r.result(|r| (0..r.u64()?).map(|_| r.entry()).collect())?,
+                },
+                2 => Call::Exists {
+                    entry: r.entry()?,
+                    result: r.u8()? != 0,
+                },
+                _ => return Err(invalid("invalid call kind")),
+            });
+        }
+
+        let mut session = Self {
+            calls,
+            ..Self::new()
+        };
+        for _ in 0..r.u64()? {
+            let len = r.usize()?;
+            session.add_content(r.bytes(len)?);
+        }
+
+        let out_of_bounds = session.calls.iter().any(|call| match call {
+            Call::Read {
+                result: Ok(index), ..
+            } => *index >= session.contents.len(),
+            _ => false,
+        });
+        if out_of_bounds {
+            return Err(invalid("content index out of bounds"));
+        }
+
+        Ok(session)
+    }
+
+    /// Adds a content if it is not already in the session, and returns its
+    /// index.
+    fn add_content(&mut self, content: &[u8]) -> usize {
+        if let Some(&index) = self.indices.get(content) {
+            return index;
+        }
+        let content = Arc::<[u8]>::from(content);
+        let index = self.contents.len();
+        self.contents.push(content.clone());
+        self.indices.insert(content, index);
+        index
+    }
+
+    fn record_read(&mut self, id: &str, ext: &str, result: &io::Result<FileContent>) {
+        let result = match result {
+            Ok(content) => Ok(self.add_content(content.as_ref())),
+            Err(err) => Err(err.kind()),
+        };
+
+        self.calls.push(Call::Read {
+            id: id.into(),
+            ext: ext.into(),
+            result,
+        });
+    }
+}
+
+impl Clone for Session {
+    fn clone(&self) -> Self {
+        let mut indices = HashMap::new();
+        for (content, &index) in self.indices.iter() {
+            indices.insert(content.clone(), index);
+        }
+
+        Self {
+            calls: self.calls.clone(),
+            contents: self.contents.clone(),
+            indices,
+        }
+    }
+}
+
+impl Default for Session {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("calls", &self.calls)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Source`] that records calls made to another source in a
+/// [`Session`].
+///
+/// The session can then be served by a [`Replay`], to write deterministic
+/// tests without the original source.
+///
+/// Only calls to [`read`](Source::read), [`read_dir`](Source::read_dir)
+/// and [`exists`](Source::exists) are recorded.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::{FileSystem, Recorder, Replay, Session}};
+///
+/// let recorder = Recorder::new(FileSystem::new("assets")?);
+/// let cache = AssetCache::with_source(recorder.clone());
+/// cache.load::<String>("example.hello")?;
+///
+/// // Store the session, eg in a test fixture
+/// let bytes = recorder.session().to_bytes();
+///
+/// // Later, without the assets directory
+/// let session = Session::from_bytes(&bytes)?;
+/// let cache = AssetCache::with_source(Replay::new(session));
+/// let hello = cache.load::<String>("example.hello")?;
+/// assert!(hello.read().starts_with("Hello"));
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[derive(Clone)]
+pub struct Recorder<S> {
+    source: S,
+    session: Arc<Mutex<Session>>,
+}
+
+impl<S> Recorder<S> {
+    /// Creates a new `Recorder` with an empty session.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            session: Arc::new(Mutex::new(Session::new())),
+        }
+    }
+
+    /// Returns a copy of the recorded session.
+    ///
+    /// Clones of a `Recorder` share the same session.
+    #[inline]
+    pub fn session(&self) -> Session {
+        self.session.lock().clone()
+    }
+
+    /// Returns the recorded session, leaving an empty one in its place.
+    #[inline]
+    pub fn take_session(&self) -> Session {
+        std::mem::take(&mut *self.session.lock())
+    }
+
+    /// Gets a reference to the inner source.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Consumes the `Recorder`, returning the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source> Source for Recorder<S> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let result = self.source.read(id, ext);
+        self.session.lock().record_read(id, ext, &result);
+        result
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let mut entries = Vec::new();
+        let result = self.source.read_dir(id, &mut |entry| {
+            entries.push(entry.to_owned());
+            f(entry);
+        });
+
+        let call = Call::ReadDir {
+            id: id.into(),
+            result: match &result {
+                Ok(()) => Ok(entries),
+                Err(err) => Err(err.kind()),
+            },
+        };
+        self.session.lock().calls.push(call);
+        result
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        let result = self.source.exists(entry);
+        let call = Call::Exists {
+            entry: entry.to_owned(),
+            result,
+        };
+        self.session.lock().calls.push(call);
+        result
+    }
+
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.source.file_size(id, ext)
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        let source = self.source.make_source()?;
+        Some(Box::new(Recorder {
+            source,
+            session: self.session.clone(),
+        }))
+    }
+
+    #[inline]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        self.source.configure_hot_reloading(events)
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for Recorder<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("source", &self.source)
+            .field("session", &*self.session.lock())
+            .finish()
+    }
+}
+
+/// A [`Source`] that serves the calls recorded in a [`Session`].
+///
+/// When a call was recorded several times, the last result is used. Calls
+/// that were not recorded fail with [`io::ErrorKind::NotFound`].
+///
+/// See [`Recorder`] for an example.
+pub struct Replay {
+    session: Session,
+    files: HashMap<(SharedString, SharedString), Result<usize, io::ErrorKind>>,
+    dirs: HashMap<SharedString, Result<Vec<OwnedDirEntry>, io::ErrorKind>>,
+    exists: HashMap<OwnedDirEntry, bool>,
+}
+
+impl Replay {
+    /// Creates a new `Replay` that serves the given session.
+    pub fn new(session: Session) -> Self {
+        let mut files = HashMap::new();
+        let mut dirs = HashMap::new();
+        let mut exists = HashMap::new();
+
+        for call in &session.calls {
+            match call {
+                Call::Read { id, ext, result } => {
+                    files.insert((id.clone(), ext.clone()), *result);
+                }
+                Call::ReadDir { id, result } => {
+                    dirs.insert(id.clone(), result.clone());
+                }
+                Call::Exists { entry, result } => {
+                    exists.insert(entry.clone(), *result);
+                }
+            }
+        }
+
+        Self {
+            session,
+            files,
+            dirs,
+            exists,
+        }
+    }
+
+    /// Gets a reference to the replayed session.
+    #[inline]
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    fn file(&self, id: &str, ext: &str) -> io::Result<&[u8]> {
+        let not_recorded = || {
+            let msg = format!("\"{id}\" ({ext}) was not read during the session");
+            io::Error::new(io::ErrorKind::NotFound, msg)
+        };
+
+        let key = (SharedString::from(id), SharedString::from(ext));
+        match self.files.get(&key).ok_or_else(not_recorded)? {
+            Ok(index) => self.session.content(*index).ok_or_else(not_recorded),
+            Err(kind) => Err((*kind).into()),
+        }
+    }
+}
+
+impl Source for Replay {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        self.file(id, ext).map(FileContent::Slice)
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let entries = match self.dirs.get(id) {
+            Some(Ok(entries)) => entries,
+            Some(Err(kind)) => return Err((*kind).into()),
+            None => {
+                let msg = format!("directory \"{id}\" was not read during the session");
+                return Err(io::Error::new(io::ErrorKind::NotFound, msg));
+            }
+        };
+        entries.iter().map(OwnedDirEntry::as_dir_entry).for_each(f);
+        Ok(())
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        if let Some(&exists) = self.exists.get(&entry.to_owned()) {
+            return exists;
+        }
+        match entry {
+            DirEntry::File(id, ext) => self.file(id, ext).is_ok(),
+            DirEntry::Directory(id) => matches!(self.dirs.get(id), Some(Ok(_))),
+        }
+    }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        Some(self.file(id, ext).ok()?.len() as u64)
+    }
+}
+
+impl fmt::Debug for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Replay")
+            .field("session", &self.session)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,111 @@
+use super::{DirEntry, FileContent, ReadSeek, Source};
+use crate::{hot_reloading::EventSender, BoxedError, TooLargeError};
+use std::io;
+
+/// A [`Source`] that refuses to read files bigger than a given size.
+///
+/// When the inner source knows the size of a file (see
+/// [`Source::file_size`]), a file that is too large is not read at all.
+/// Otherwise, the file is read and its size is checked afterwards.
+///
+/// Reading a file that is too large fails with a [`TooLargeError`], which can
+/// be retrieved from the returned error.
+///
+/// Types can also set their own limit with [`Asset::MAX_SIZE`].
+///
+/// [`Asset::MAX_SIZE`]: crate::Asset::MAX_SIZE
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{AssetCache, TooLargeError, source::{FileSystem, SizeLimit}};
+///
+/// let source = SizeLimit::new(FileSystem::new("assets")?, 4);
+/// let cache = AssetCache::with_source(source);
+///
+/// let err = cache.load::<String>("example.hello").unwrap_err();
+/// assert!(err.reason().is::<TooLargeError>());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SizeLimit<S> {
+    source: S,
+    limit: u64,
+}
+
+impl<S> SizeLimit<S> {
+    /// Creates a new `SizeLimit` that refuses to read files bigger than
+    /// `limit` bytes.
+    #[inline]
+    pub fn new(source: S, limit: u64) -> Self {
+        Self { source, limit }
+    }
+
+    /// The maximum size of a file, in bytes.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Gets a reference to the inner source.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Consumes the `SizeLimit`, returning the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source> Source for SizeLimit<S> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        if let Some(size) = self.source.file_size(id, ext) {
+            TooLargeError::check(size, self.limit)?;
+        }
+
+        let content = self.source.read(id, ext)?;
+        TooLargeError::check(content.as_ref().len() as u64, self.limit)?;
+        Ok(content)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> io::Result<Box<dyn ReadSeek + '_>> {
+        if let Some(size) = self.source.file_size(id, ext) {
+            TooLargeError::check(size, self.limit)?;
+            return self.source.open(id, ext);
+        }
+
+        let mut reader = self.source.open(id, ext)?;
+        let size = reader.seek(io::SeekFrom::End(0))?;
+        TooLargeError::check(size, self.limit)?;
+        reader.rewind()?;
+        Ok(reader)
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        self.source.read_dir(id, f)
+    }
+
+    #[inline]
+    fn exists(&self, entry: DirEntry) -> bool {
+        self.source.exists(entry)
+    }
+
+    #[inline]
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        self.source.file_size(id, ext)
+    }
+
+    fn make_source(&self) -> Option<Box<dyn Source + Send>> {
+        let source = self.source.make_source()?;
+        Some(Box::new(SizeLimit::new(source, self.limit)))
+    }
+
+    #[inline]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        self.source.configure_hot_reloading(events)
+    }
+}
@@ -1,13 +1,16 @@
 #[cfg(feature = "mmap")]
 use super::ArcMap;
-use super::DirEntry;
+use super::{archive_error as error, case::CaseMap, DirEntry};
 use crate::{
-    utils::{HashMap, IdBuilder},
+    utils::{HashMap, IdBuilder, RwLock},
     SharedString,
 };
-use std::{fmt, io, path};
+use std::{fmt, io, path, sync::Arc};
 use sync_file::SyncFile;
 
+#[cfg(feature = "hot-reloading")]
+use crate::{hot_reloading::EventSender, source::OwnedDirEntry, utils::Mutex, BoxedError};
+
 #[cfg(doc)]
 use super::Source;
 
@@ -37,7 +40,7 @@ enum OwnedEntry {
 }
 
 impl OwnedEntry {
-    fn as_dir_entry(&self) -> DirEntry {
+    fn as_dir_entry(&self) -> DirEntry<'_> {
         match self {
             OwnedEntry::File(FileDesc(id, ext)) => DirEntry::File(id, ext),
             OwnedEntry::Dir(id) => DirEntry::Directory(id),
@@ -45,10 +48,18 @@ impl OwnedEntry {
     }
 }
 
+/// The position of a file in an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileInfo {
+    start: u64,
+    size: u64,
+    mtime: u64,
+}
+
 /// Register a file of an archive in maps.
 fn register_file(
     file: tar::Entry<'_, impl io::Read>,
-    files: &mut HashMap<FileDesc, (u64, u64)>,
+    files: &mut HashMap<FileDesc, FileInfo>,
     dirs: &mut HashMap<SharedString, Vec<OwnedEntry>>,
     id_builder: &mut IdBuilder,
 ) {
@@ -97,10 +108,13 @@ fn register_file(
             let ext = crate::utils::extension_of(&path)?.into();
             let desc = FileDesc(id, ext);
 
-            let start = file.raw_file_position();
-            let size = file.size();
+            let info = FileInfo {
+                start: file.raw_file_position(),
+                size: file.size(),
+                mtime: file.header().mtime().unwrap_or(0),
+            };
 
-            files.insert(desc.clone(), (start, size));
+            files.insert(desc.clone(), info);
             OwnedEntry::File(desc)
         } else {
             if !dirs.contains_key(&id) {
@@ -119,6 +133,172 @@ fn register_file(
     }
 }
 
+/// The content of an archive.
+struct Index<R> {
+    reader: R,
+    files: HashMap<FileDesc, FileInfo>,
+    dirs: HashMap<SharedString, Vec<OwnedEntry>>,
+    case_map: Option<CaseMap>,
+}
+
+impl<R: io::Read + io::Seek> Index<R> {
+    fn read(reader: R) -> io::Result<Self> {
+        let mut archive = tar::Archive::new(reader);
+        let mut id_builder = IdBuilder::default();
+
+        let mut files = HashMap::new();
+        let mut dirs = HashMap::new();
+
+        for file in archive.entries_with_seek()? {
+            register_file(file?, &mut files, &mut dirs, &mut id_builder)
+        }
+
+        Ok(Index {
+            reader: archive.into_inner(),
+            files,
+            dirs,
+            case_map: None,
+        })
+    }
+}
+
+impl<R> Index<R> {
+    fn set_case_insensitive(&mut self, enabled: bool) {
+        self.case_map = enabled.then(|| {
+            CaseMap::new(
+                self.files.keys().map(|FileDesc(id, ext)| (id, ext)),
+                self.dirs.keys(),
+            )
+        });
+    }
+
+    fn file(&self, id: &str, ext: &str) -> Option<FileInfo> {
+        match self.files.get(&(id, ext)) {
+            Some(&info) => Some(info),
+            None => self
+                .files
+                .get(&self.case_map.as_ref()?.file(id, ext)?)
+                .copied(),
+        }
+    }
+
+    fn dir(&self, id: &str) -> Option<&[OwnedEntry]> {
+        match self.dirs.get(id) {
+            Some(dir) => Some(dir),
+            None => self
+                .dirs
+                .get(self.case_map.as_ref()?.dir(id)?)
+                .map(Vec::as_slice),
+        }
+    }
+}
+
+/// Returns the entries that differ between two versions of an archive.
+#[cfg(feature = "hot-reloading")]
+fn changes<R>(old: &Index<R>, new: &Index<R>) -> Vec<OwnedDirEntry> {
+    fn listing(entries: &[OwnedEntry]) -> Vec<(&str, Option<&str>)> {
+        let mut listing: Vec<_> = entries
+            .iter()
+            .map(|entry| match entry {
+                OwnedEntry::File(FileDesc(id, ext)) => (&**id, Some(&**ext)),
+                OwnedEntry::Dir(id) => (&**id, None),
+            })
+            .collect();
+        listing.sort_unstable();
+        listing
+    }
+
+    let mut changes = Vec::new();
+
+    // Files are compared with their size and modification time, as their
+    // position changes when a file before them is modified.
+    let changed_file = |a: &FileInfo, b: &FileInfo| a.size != b.size || a.mtime != b.mtime;
+    for (desc, info) in new.files.iter() {
+        if old
+            .files
+            .get(desc)
+            .map_or(true, |old| changed_file(old, info))
+        {
+            changes.push(OwnedDirEntry::File(desc.0.clone(), desc.1.clone()));
+        }
+    }
+    for desc in old.files.keys() {
+        if !new.files.contains_key(desc) {
+            changes.push(OwnedDirEntry::File(desc.0.clone(), desc.1.clone()));
+        }
+    }
+
+    for (id, entries) in new.dirs.iter() {
+        if old
+            .dirs
+            .get(id)
+            .map_or(true, |old| listing(old) != listing(entries))
+        {
+            changes.push(OwnedDirEntry::Directory(id.clone()));
+        }
+    }
+    for id in old.dirs.keys() {
+        if !new.dirs.contains_key(id) {
+            changes.push(OwnedDirEntry::Directory(id.clone()));
+        }
+    }
+
+    changes
+}
+
+/// What is needed to hot-reload an archive opened from a file.
+///
+/// This hides the type of the reader, which is known to be `SyncFile` here.
+#[cfg(feature = "hot-reloading")]
+#[derive(Clone)]
+struct Watch {
+    path: path::PathBuf,
+
+    /// Reads the archive again and returns what changed.
+    reload: Arc<dyn Fn() -> Vec<OwnedDirEntry> + Send + Sync>,
+
+    /// Creates a source that shares the index of the archive.
+    make_source: Arc<dyn Fn() -> Box<dyn super::Source + Send> + Send + Sync>,
+}
+
+#[cfg(feature = "hot-reloading")]
+impl Watch {
+    fn new(tar: &Tar<SyncFile>, path: &path::Path) -> Self {
+        let index = tar.index.clone();
+        let file_path = path.to_owned();
+        let reload = move || match SyncFile::open(&file_path).and_then(Index::read) {
+            Ok(mut new) => {
+                let mut index = index.write();
+                new.set_case_insensitive(index.case_map.is_some());
+                let changes = changes(&index, &new);
+                *index = new;
+                changes
+            }
+            Err(err) => {
+                log::warn!("Error reloading {}: {err}", file_path.display());
+                Vec::new()
+            }
+        };
+
+        let index = tar.index.clone();
+        let label = tar.label.clone();
+        let make_source = move || -> Box<dyn super::Source + Send> {
+            Box::new(Tar {
+                index: index.clone(),
+                label: label.clone(),
+                watch: None,
+                watcher: Mutex::new(None),
+            })
+        };
+
+        Watch {
+            path: path.to_owned(),
+            reload: Arc::new(reload),
+            make_source: Arc::new(make_source),
+        }
+    }
+}
+
 /// A [`Source`] to load assets from a tar archive.
 ///
 /// The archive can be backed by any reader that also implements [`io::Seek`]
@@ -126,11 +306,21 @@ fn register_file(
 ///
 /// **Warning**: This will clone the reader each time it is read, so you should
 /// ensure that is cheap to clone (eg *not* `Vec<u8>`).
+///
+/// ## Hot-reloading
+///
+/// Archives opened with [`Tar::open`] support hot-reloading: the file is
+/// watched, and its index is read again when it is modified. Assets loaded
+/// from modified entries are then reloaded. Entries are detected as modified
+/// by their size and modification time.
 pub struct Tar<R = SyncFile> {
-    reader: R,
-    files: HashMap<FileDesc, (u64, u64)>,
-    dirs: HashMap<SharedString, Vec<OwnedEntry>>,
+    index: Arc<RwLock<Index<R>>>,
     label: Option<String>,
+
+    #[cfg(feature = "hot-reloading")]
+    watch: Option<Watch>,
+    #[cfg(feature = "hot-reloading")]
+    watcher: Mutex<Option<notify::RecommendedWatcher>>,
 }
 
 impl Tar<SyncFile> {
@@ -143,7 +333,16 @@ impl Tar<SyncFile> {
     fn _open(path: &path::Path) -> io::Result<Self> {
         let file = SyncFile::open(path)?;
         let label = path.display().to_string();
-        Self::from_reader_with_label(file, label)
+
+        #[allow(unused_mut)]
+        let mut tar = Self::from_reader_with_label(file, label)?;
+
+        #[cfg(feature = "hot-reloading")]
+        {
+            tar.watch = Some(Watch::new(&tar, path));
+        }
+
+        Ok(tar)
     }
 }
 
@@ -197,22 +396,27 @@ where
         Self::create(reader, Some(label))
     }
 
-    fn create(reader: R, label: Option<String>) -> io::Result<Self> {
-        let mut archive = tar::Archive::new(reader);
-        let mut id_builder = IdBuilder::default();
-
-        let mut files = HashMap::new();
-        let mut dirs = HashMap::new();
-
-        for file in archive.entries_with_seek()? {
-            register_file(file?, &mut files, &mut dirs, &mut id_builder)
-        }
+    /// Resolves ids case-insensitively when no entry matches exactly.
+    ///
+    /// A warning mentioning `CaseMismatch` is logged each time such a
+    /// fallback match is used, so that the ids can be fixed. This is kept
+    /// when the archive is hot-reloaded.
+    ///
+    /// This is `false` by default.
+    pub fn with_case_insensitive(self, enabled: bool) -> Self {
+        self.index.write().set_case_insensitive(enabled);
+        self
+    }
 
+    fn create(reader: R, label: Option<String>) -> io::Result<Self> {
         Ok(Tar {
-            reader: archive.into_inner(),
-            files,
-            dirs,
+            index: Arc::new(RwLock::new(Index::read(reader)?)),
             label,
+
+            #[cfg(feature = "hot-reloading")]
+            watch: None,
+            #[cfg(feature = "hot-reloading")]
+            watcher: Mutex::new(None),
         })
     }
 }
@@ -222,95 +426,89 @@ impl<R> super::Source for Tar<R>
 where
     R: io::Read + io::Seek + Clone,
 {
-    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent> {
-        let &(start, size) = self
-            .files
-            .get(&(id, ext))
-            .ok_or_else(|| error::find_file(id, &self.label))?;
-
-        let mut reader = self.reader.clone();
+    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent<'_>> {
+        let (info, mut reader) = {
+            let index = self.index.read();
+            let info = index
+                .file(id, ext)
+                .ok_or_else(|| error::find_file(id, &self.label, "TAR"))?;
+            (info, index.reader.clone())
+        };
 
-        let mut buf = vec![0; size as usize];
+        let mut buf = vec![0; info.size as usize];
         reader
-            .seek(io::SeekFrom::Start(start))
+            .seek(io::SeekFrom::Start(info.start))
             .and_then(|_| reader.read_exact(&mut buf))
-            .map_err(|err| error::read_file(err, id, &self.label))?;
+            .map_err(|err| error::read_file(err, id, &self.label, "TAR"))?;
 
         Ok(super::FileContent::Buffer(buf))
     }
 
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
-        let dir = self
-            .dirs
-            .get(id)
-            .ok_or_else(|| error::find_dir(id, &self.label))?;
+        let index = self.index.read();
+        let dir = index
+            .dir(id)
+            .ok_or_else(|| error::find_dir(id, &self.label, "TAR"))?;
         dir.iter().map(OwnedEntry::as_dir_entry).for_each(f);
         Ok(())
     }
 
     fn exists(&self, entry: DirEntry) -> bool {
+        let index = self.index.read();
         match entry {
-            DirEntry::File(id, ext) => self.files.contains_key(&(id, ext)),
-            DirEntry::Directory(id) => self.dirs.contains_key(id),
+            DirEntry::File(id, ext) => index.file(id, ext).is_some(),
+            DirEntry::Directory(id) => index.dir(id).is_some(),
         }
     }
-}
 
-impl<R> fmt::Debug for Tar<R> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Tar")
-            .field("label", &self.label)
-            .field("dirs", &self.dirs)
-            .finish()
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        Some(self.index.read().file(id, ext)?.size)
     }
-}
-
-mod error {
-    use std::{fmt, io};
 
-    #[cold]
-    pub fn find_file(id: &str, label: &Option<String>) -> io::Error {
-        let msg = match label {
-            Some(lbl) => format!("Could not find asset \"{id}\" in {lbl}"),
-            None => format!("Could not find asset \"{id}\" in TAR"),
-        };
-
-        io::Error::new(io::ErrorKind::NotFound, msg)
+    #[cfg(feature = "hot-reloading")]
+    fn make_source(&self) -> Option<Box<dyn super::Source + Send>> {
+        Some((self.watch.as_ref()?.make_source)())
     }
 
-    #[cold]
-    pub fn read_file(err: io::Error, id: &str, label: &Option<String>) -> io::Error {
-        #[derive(Debug)]
-        struct Error {
-            err: io::Error,
-            msg: String,
-        }
-        impl fmt::Display for Error {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str(&self.msg)
-            }
-        }
-        impl std::error::Error for Error {
-            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-                Some(&self.err)
-            }
-        }
-
-        let msg = match label {
-            Some(lbl) => format!("Could not read \"{id}\" in {lbl}"),
-            None => format!("Could not read \"{id}\" in TAR"),
-        };
-
-        io::Error::new(err.kind(), Error { err, msg })
+    #[cfg(feature = "hot-reloading")]
+    fn configure_hot_reloading(&self, events: EventSender) -> Result<(), BoxedError> {
+        let Watch { path, reload, .. } = self
+            .watch
+            .clone()
+            .ok_or("only archives opened from a file support hot-reloading")?;
+
+        // Watch the parent directory, so that the archive can be replaced
+        let path = path.canonicalize()?;
+        let dir = path.parent().ok_or("invalid archive path")?.to_owned();
+
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event) => {
+                    if matches!(
+                        event.kind,
+                        notify::EventKind::Access(_) | notify::EventKind::Other
+                    ) || !event.paths.contains(&path)
+                    {
+                        return;
+                    }
+
+                    log::trace!("Received filesystem event: {event:?}");
+                    let _ = events.send_multiple(reload());
+                }
+                Err(err) => log::warn!("Error from notify: {err}"),
+            })?;
+        notify::Watcher::watch(&mut watcher, &dir, notify::RecursiveMode::NonRecursive)?;
+
+        *self.watcher.lock() = Some(watcher);
+        Ok(())
     }
+}
 
-    #[cold]
-    pub fn find_dir(id: &str, label: &Option<String>) -> io::Error {
-        let msg = match label {
-            Some(lbl) => format!("Could not find directory \"{id}\" in {lbl}"),
-            None => format!("Could not find directory \"{id}\" in TAR"),
-        };
-
-        io::Error::new(io::ErrorKind::NotFound, msg)
+impl<R> fmt::Debug for Tar<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tar")
+            .field("label", &self.label)
+            .field("dirs", &self.index.read().dirs)
+            .finish()
     }
 }
@@ -0,0 +1,261 @@
+//! Conformance tests for [`Source`] implementations.
+//!
+//! The functions of this module check that a `Source` follows the contract
+//! expected by caches. They are meant to be used in tests of custom sources,
+//! and panic with a list of all the problems found.
+//!
+//! # Example
+//!
+//! ```
+//! use assets_manager::source::{test_kit, FileSystem};
+//!
+//! let source = FileSystem::new("assets/example")?;
+//! test_kit::assert_source_conforms(&source);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use super::{DirEntry, Source};
+use std::{
+    fmt::Write,
+    io::{self, Read},
+};
+
+#[cfg(feature = "hot-reloading")]
+use super::OwnedDirEntry;
+
+/// Maximum depth of directories, to avoid looping forever on broken sources.
+const MAX_DEPTH: usize = 64;
+
+const MISSING: &str = "__assets_manager_missing__";
+
+/// Checks that the whole source follows the `Source` contract.
+///
+/// This reads every directory and every file of the source, so it should be
+/// used on sources with a reasonable size.
+///
+/// The following properties are checked:
+/// - The root directory (with id `""`) can be read.
+/// - Ids yielded by [`Source::read_dir`] are valid, distinct and are direct
+///   children of the directory that was read.
+/// - Every yielded file can be read, and reading it twice gives the same
+///   bytes. [`Source::open`] gives the same bytes too.
+/// - Every yielded entry [`exists`](Source::exists).
+/// - Missing files and directories do not exist and fail to be read with
+///   [`io::ErrorKind::NotFound`].
+///
+/// # Panics
+///
+/// Panics if any of these properties is not respected.
+#[track_caller]
+pub fn assert_source_conforms<S: Source + ?Sized>(source: &S) {
+    assert_dir_conforms(source, "");
+}
+
+/// Checks that a directory of a source and its subdirectories follow the
+/// `Source` contract.
+///
+/// See [`assert_source_conforms`] for the list of checked properties.
+///
+/// # Panics
+///
+/// Panics if any of the checked properties is not respected.
+#[track_caller]
+pub fn assert_dir_conforms<S: Source + ?Sized>(source: &S, id: &str) {
+    let mut checker = Checker {
+        source,
+        errors: Vec::new(),
+    };
+    checker.check_dir(id, 0);
+
+    if !checker.errors.is_empty() {
+        let mut msg = format!("source does not conform ({} errors):", checker.errors.len());
+        for err in &checker.errors {
+            let _ = write!(msg, "\n- {err}");
+        }
+        panic!("{msg}");
+    }
+}
+
+struct Checker<'a, S: ?Sized> {
+    source: &'a S,
+    errors: Vec<String>,
+}
+
+impl<S: Source + ?Sized> Checker<'_, S> {
+    fn error(&mut self, msg: String) {
+        self.errors.push(msg);
+    }
+
+    fn check_dir(&mut self, id: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            self.error(format!("directory \"{id}\" is nested too deeply"));
+            return;
+        }
+
+        let mut entries = Vec::new();
+        let result = self.source.read_dir(id, &mut |entry| {
+            entries.push(match entry {
+                DirEntry::File(id, ext) => (id.to_owned(), Some(ext.to_owned())),
+                DirEntry::Directory(id) => (id.to_owned(), None),
+            })
+        });
+        if let Err(err) = result {
+            self.error(format!("cannot read directory \"{id}\": {err}"));
+            return;
+        }
+
+        if !self.source.exists(DirEntry::Directory(id)) {
+            self.error(format!("directory \"{id}\" can be read but does not exist"));
+        }
+
+        self.check_missing(id);
+
+        entries.sort();
+        for pair in entries.windows(2) {
+            if pair[0] == pair[1] {
+                self.error(format!("entry {:?} is yielded twice in \"{id}\"", pair[0]));
+            }
+        }
+        entries.dedup();
+
+        for (child, ext) in entries {
+            self.check_child_id(id, &child);
+
+            match ext {
+                Some(ext) => self.check_file(&child, &ext),
+                None => self.check_dir(&child, depth + 1),
+            }
+        }
+    }
+
+    fn check_child_id(&mut self, parent: &str, id: &str) {
+        let name = if parent.is_empty() {
+            Some(id)
+        } else {
+            id.strip_prefix(parent).and_then(|s| s.strip_prefix('.'))
+        };
+
+        match name {
+            Some(name) if is_valid_name(name) => (),
+            _ => self.error(format!(
+                "\"{id}\" is not a valid id for a direct child of \"{parent}\""
+            )),
+        }
+    }
+
+    fn check_file(&mut self, id: &str, ext: &str) {
+        if ext.contains('.') {
+            self.error(format!("file \"{id}\" has an invalid extension \"{ext}\""));
+        }
+
+        if !self.source.exists(DirEntry::File(id, ext)) {
+            self.error(format!("file \"{id}\" (\"{ext}\") does not exist"));
+        }
+
+        let first = match self.source.read(id, ext) {
+            Ok(content) => content.as_ref().to_owned(),
+            Err(err) => {
+                self.error(format!("cannot read file \"{id}\" (\"{ext}\"): {err}"));
+                return;
+            }
+        };
+        match self.source.read(id, ext) {
+            Ok(content) if content.as_ref() == first => (),
+            Ok(_) => self.error(format!(
+                "reading file \"{id}\" (\"{ext}\") twice gives different content"
+            )),
+            Err(err) => self.error(format!(
+                "cannot read file \"{id}\" (\"{ext}\") a second time: {err}"
+            )),
+        }
+
+        let mut streamed = Vec::new();
+        match self.source.open(id, ext) {
+            Ok(mut reader) => match reader.read_to_end(&mut streamed) {
+                Ok(_) if streamed == first => (),
+                Ok(_) => self.error(format!(
+                    "opening file \"{id}\" (\"{ext}\") gives different content than reading it"
+                )),
+                Err(err) => self.error(format!(
+                    "cannot read opened file \"{id}\" (\"{ext}\"): {err}"
+                )),
+            },
+            Err(err) => self.error(format!("cannot open file \"{id}\" (\"{ext}\"): {err}")),
+        }
+    }
+
+    fn check_missing(&mut self, dir: &str) {
+        let id = if dir.is_empty() {
+            MISSING.to_owned()
+        } else {
+            format!("{dir}.{MISSING}")
+        };
+
+        if self.source.exists(DirEntry::File(&id, "")) {
+            self.error(format!("missing file \"{id}\" exists"));
+        }
+        if self.source.exists(DirEntry::Directory(&id)) {
+            self.error(format!("missing directory \"{id}\" exists"));
+        }
+
+        match self.source.read(&id, "") {
+            Ok(_) => self.error(format!("missing file \"{id}\" can be read")),
+            Err(err) if err.kind() != io::ErrorKind::NotFound => self.error(format!(
+                "reading missing file \"{id}\" does not fail with `NotFound`: {err}"
+            )),
+            Err(_) => (),
+        }
+
+        if self.source.read_dir(&id, &mut |_| ()).is_ok() {
+            self.error(format!("missing directory \"{id}\" can be read"));
+        }
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['.', '/', '\\'])
+}
+
+/// Checks that a source delivers hot-reloading events.
+///
+/// This function configures hot-reloading on the source, calls `modify`,
+/// which should change the underlying storage so that `expected` is
+/// modified, and waits up to `timeout` for an event for `expected`.
+///
+/// Other events are ignored.
+///
+/// # Panics
+///
+/// Panics if hot-reloading cannot be configured or if no matching event is
+/// received in time.
+#[cfg(feature = "hot-reloading")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+#[track_caller]
+pub fn assert_hot_reloading_conforms<S: Source + ?Sized>(
+    source: &S,
+    expected: OwnedDirEntry,
+    timeout: std::time::Duration,
+    modify: impl FnOnce(),
+) {
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    if let Err(err) = source.configure_hot_reloading(crate::hot_reloading::EventSender(tx)) {
+        panic!("failed to configure hot-reloading: {err}");
+    }
+
+    modify();
+
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        let events = match rx.recv_deadline(deadline) {
+            Ok(events) => events,
+            Err(_) => panic!("no hot-reloading event received for {expected:?}"),
+        };
+
+        let mut found = false;
+        events.for_each(|event| found |= event == expected);
+        if found {
+            return;
+        }
+    }
+}
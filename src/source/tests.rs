@@ -1,4 +1,5 @@
 use super::*;
+use crate::TooLargeError;
 
 macro_rules! test_source {
     ($source:expr) => {
@@ -15,6 +16,24 @@ macro_rules! test_source {
             assert!(source.read("test.not_found", "x").is_err());
         }
 
+        #[test]
+        fn open() {
+            use std::io::{Read, Seek};
+
+            let source = $source;
+            let mut reader = source.open("test.b", "x").unwrap();
+            let mut content = Vec::new();
+            reader.read_to_end(&mut content).unwrap();
+            assert_eq!(content, b"-7");
+
+            reader.seek(io::SeekFrom::Start(1)).unwrap();
+            content.clear();
+            reader.read_to_end(&mut content).unwrap();
+            assert_eq!(content, b"7");
+
+            assert!(source.open("test.not_found", "x").is_err());
+        }
+
         #[test]
         fn read_dir() {
             let source = $source;
@@ -56,15 +75,135 @@ macro_rules! test_source {
             dir.sort();
             assert_eq!(dir, ["common", "example", "test"]);
         }
+
+        #[test]
+        fn file_size() {
+            let source = $source;
+            if let Some(size) = source.file_size("test.b", "x") {
+                assert_eq!(size, 2);
+            }
+            assert!(source.file_size("test.not_found", "x").is_none());
+        }
+
+        #[test]
+        fn size_limit() {
+            let source = SizeLimit::new($source, 1);
+            let err = source.read("test.b", "x").unwrap_err();
+            let err = err.get_ref().unwrap().downcast_ref::<TooLargeError>();
+            assert_eq!(err, Some(&TooLargeError::new(2, 1)));
+
+            let err = source.open("test.b", "x").err().unwrap();
+            let err = err.get_ref().unwrap().downcast_ref::<TooLargeError>();
+            assert_eq!(err, Some(&TooLargeError::new(2, 1)));
+
+            let source = SizeLimit::new(source.into_inner(), 2);
+            assert_eq!(source.read("test.b", "x").unwrap().as_ref(), b"-7");
+            assert!(source.open("test.b", "x").is_ok());
+        }
+
+        #[cfg(feature = "test-kit")]
+        #[test]
+        fn conforms() {
+            let source = $source;
+            test_kit::assert_dir_conforms(&source, "example");
+            test_kit::assert_dir_conforms(&source, "test.read_dir");
+        }
     };
 }
 
+/// Checks that a case-insensitive source finds entries regardless of case.
+fn check_case_insensitive(source: &dyn Source) {
+    let content = source.read("Test.B", "X").unwrap();
+    assert_eq!(content.as_ref(), b"-7");
+    assert_eq!(source.file_size("TEST.b", "x"), Some(2));
+
+    assert!(source.exists(DirEntry::File("test.READ_DIR.c", "TXT")));
+    assert!(source.exists(DirEntry::Directory("TEST.read_dir")));
+    assert!(!source.exists(DirEntry::File("test.b", "json")));
+
+    let mut dir = Vec::new();
+    source
+        .read_dir("Test.Read_Dir", &mut |entry| {
+            dir.push(entry.id().to_owned())
+        })
+        .unwrap();
+    assert!(dir
+        .iter()
+        .any(|id| id.eq_ignore_ascii_case("test.read_dir.c")));
+}
+
 mod filesystem {
     use super::*;
     use std::error::Error;
 
+    #[cfg(all(feature = "hot-reloading", feature = "test-kit"))]
+    #[test]
+    fn hot_reloading_conforms() {
+        let dir = std::env::temp_dir().join("assets_manager_test_kit");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.x"), "1").unwrap();
+
+        let fs = FileSystem::new(&dir).unwrap();
+        test_kit::assert_hot_reloading_conforms(
+            &fs,
+            OwnedDirEntry::File("a".into(), "x".into()),
+            std::time::Duration::from_secs(5),
+            || std::fs::write(dir.join("a.x"), "2").unwrap(),
+        );
+    }
+
     test_source!(FileSystem::new("assets").unwrap());
 
+    #[cfg(feature = "mmap")]
+    mod mmap {
+        use super::*;
+
+        test_source!(unsafe { FileSystem::new("assets").unwrap().with_mmap_threshold(2) });
+
+        #[test]
+        fn threshold() {
+            let fs = unsafe { FileSystem::new("assets").unwrap().with_mmap_threshold(3) };
+
+            let content = fs.read("test.a", "x").unwrap();
+            assert!(matches!(content, FileContent::Owned(_)));
+            assert_eq!(content.as_ref(), std::fs::read("assets/test/a.x").unwrap());
+
+            let content = fs.read("test.b", "x").unwrap();
+            assert!(matches!(content, FileContent::Buffer(_)));
+        }
+
+        #[test]
+        fn map_private() {
+            use std::io::Write;
+
+            let path = std::env::temp_dir().join(format!("am_map_private_{}", std::process::id()));
+            std::fs::write(&path, "abc").unwrap();
+
+            let file = std::fs::File::open(&path).unwrap();
+            let map = unsafe { ArcMap::map_private(&file).unwrap() }.with_verification();
+            assert_eq!(map.as_ref(), b"abc");
+
+            // Appended data is not part of the map
+            let mut append = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap();
+            append.write_all(b"def").unwrap();
+            assert_eq!(map.as_ref(), b"abc");
+            map.verify().unwrap();
+
+            let mut modified = map.clone();
+            modified.hash = modified.hash.map(|hash| !hash);
+            assert_eq!(
+                modified.verify().unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+
+            drop((map, modified));
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
     #[test]
     fn path_of() {
         let fs = FileSystem::new("assets").unwrap();
@@ -80,6 +219,70 @@ mod filesystem {
         assert_eq!(path, fs.path_of(DirEntry::File("test.a", "x")));
     }
 
+    #[test]
+    fn id_of() {
+        let fs = FileSystem::new("assets").unwrap();
+
+        for entry in [
+            OwnedDirEntry::File("test.a".into(), "x".into()),
+            OwnedDirEntry::File("test.not_found".into(), "x".into()),
+            OwnedDirEntry::File("test.read_dir.d".into(), "".into()),
+            OwnedDirEntry::Directory("test.read_dir".into()),
+            OwnedDirEntry::Directory("".into()),
+        ] {
+            let path = fs.path_of(entry.as_dir_entry());
+            assert_eq!(fs.id_of(&path), Some(entry));
+        }
+
+        assert_eq!(
+            fs.id_of("assets/test/a.x".as_ref()),
+            Some(OwnedDirEntry::File("test.a".into(), "x".into()))
+        );
+        assert_eq!(fs.id_of("src/lib.rs".as_ref()), None);
+        assert_eq!(
+            fs.id_of("assets/test/a.b.c".as_ref()),
+            Some(OwnedDirEntry::File("test.a.b".into(), "c".into()))
+        );
+        assert_eq!(fs.id_of("assets/test/.c".as_ref()), None);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let fs = FileSystem::new("assets").unwrap();
+        assert!(fs.read("Test.B", "X").is_err());
+
+        check_case_insensitive(&fs.with_case_insensitive(true));
+    }
+
+    #[test]
+    fn nfc_ids() {
+        let dir = std::env::temp_dir().join(format!("am_nfc_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("re\u{301}sume\u{301}")).unwrap();
+        std::fs::write(dir.join("re\u{301}sume\u{301}/cafe\u{301}.txt"), "hi").unwrap();
+
+        let fs = FileSystem::new(&dir).unwrap();
+        assert!(fs.read("r\u{e9}sum\u{e9}.caf\u{e9}", "txt").is_err());
+
+        let fs = fs.with_nfc_ids(true);
+        let content = fs.read("r\u{e9}sum\u{e9}.caf\u{e9}", "txt").unwrap();
+        assert_eq!(content.as_ref(), b"hi");
+        assert!(fs.exists(DirEntry::Directory("r\u{e9}sum\u{e9}")));
+
+        let mut ids = Vec::new();
+        fs.read_dir("r\u{e9}sum\u{e9}", &mut |entry| {
+            ids.push(entry.id().to_owned())
+        })
+        .unwrap();
+        assert_eq!(ids, ["r\u{e9}sum\u{e9}.caf\u{e9}"]);
+
+        let entry = fs.id_of(&dir.join("re\u{301}sume\u{301}/cafe\u{301}.txt"));
+        let expected = OwnedDirEntry::File("r\u{e9}sum\u{e9}.caf\u{e9}".into(), "txt".into());
+        assert_eq!(entry, Some(expected));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn errors() {
         let fs = FileSystem::new("assets").unwrap();
@@ -94,6 +297,260 @@ mod filesystem {
     }
 }
 
+mod memory {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let source = InMemory::new();
+        source.insert("a.b.c", "x", b"1".to_vec());
+        source.insert("a.d", "x", &b"2"[..]);
+        assert_eq!(source.read("a.b.c", "x").unwrap().as_ref(), b"1");
+        assert_eq!(source.file_size("a.d", "x"), Some(1));
+        assert!(source.exists(DirEntry::Directory("a.b")));
+
+        let mut entries = Vec::new();
+        source
+            .read_dir("a", &mut |entry| entries.push(entry.id().to_owned()))
+            .unwrap();
+        entries.sort();
+        assert_eq!(entries, ["a.b", "a.d"]);
+
+        source.insert("a.b.c", "x", b"3".to_vec());
+        assert_eq!(source.read("a.b.c", "x").unwrap().as_ref(), b"3");
+        assert_eq!(source.len(), 2);
+
+        assert!(source.remove("a.b.c", "x"));
+        assert!(!source.remove("a.b.c", "x"));
+        let err = source.read("a.b.c", "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        // Empty directories are removed
+        assert!(!source.exists(DirEntry::Directory("a.b")));
+        assert!(source.exists(DirEntry::Directory("a")));
+        assert!(source.remove("a.d", "x"));
+        assert!(!source.exists(DirEntry::Directory("a")));
+        assert!(source.exists(DirEntry::Directory("")));
+        assert!(source.is_empty());
+    }
+}
+
+mod record {
+    use super::*;
+
+    test_source!(Recorder::new(FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn replay() {
+        let recorder = Recorder::new(FileSystem::new("assets").unwrap());
+        assert_eq!(recorder.read("test.b", "x").unwrap().as_ref(), b"-7");
+        assert!(recorder.read("test.missing", "x").is_err());
+        recorder.read_dir("test.read_dir", &mut |_| ()).unwrap();
+        assert!(recorder.exists(DirEntry::Directory("test")));
+
+        let session = recorder.take_session();
+        assert_eq!(session.calls().len(), 4);
+        assert!(recorder.session().calls().is_empty());
+        match &session.calls()[0] {
+            Call::Read { id, result, .. } => {
+                assert_eq!(id, "test.b");
+                assert_eq!(session.content(*result.as_ref().unwrap()), Some(&b"-7"[..]));
+            }
+            call => panic!("unexpected call: {call:?}"),
+        }
+
+        let bytes = session.to_bytes();
+        let session = Session::from_bytes(&bytes).unwrap();
+        assert_eq!(session.to_bytes(), bytes);
+
+        let replay = Replay::new(session);
+        assert_eq!(replay.read("test.b", "x").unwrap().as_ref(), b"-7");
+        let err = replay.read("test.missing", "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(replay.read("test.a", "x").is_err());
+        assert!(replay.exists(DirEntry::Directory("test")));
+        assert!(replay.exists(DirEntry::File("test.b", "x")));
+
+        let mut entries = Vec::new();
+        replay
+            .read_dir("test.read_dir", &mut |entry| {
+                entries.push(entry.id().to_owned())
+            })
+            .unwrap();
+        let mut expected = Vec::new();
+        FileSystem::new("assets")
+            .unwrap()
+            .read_dir("test.read_dir", &mut |entry| {
+                expected.push(entry.id().to_owned())
+            })
+            .unwrap();
+        assert_eq!(entries, expected);
+    }
+}
+
+mod overlay {
+    use super::*;
+
+    test_source!(Overlay::new().with_layer(FileSystem::new("assets").unwrap(), 0));
+
+    #[test]
+    fn layers() {
+        let dir = std::env::temp_dir().join(format!("am_overlay_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("test")).unwrap();
+        std::fs::write(dir.join("test/b.x"), "5").unwrap();
+        std::fs::write(dir.join("test/new.x"), "6").unwrap();
+
+        let source = Overlay::new()
+            .with_layer(FileSystem::new("assets").unwrap(), 0)
+            .with_layer(FileSystem::new(&dir).unwrap(), 1);
+
+        // Higher priority first
+        assert_eq!(source.read("test.b", "x").unwrap().as_ref(), b"5");
+        assert_eq!(source.read("test.new", "x").unwrap().as_ref(), b"6");
+        assert_eq!(source.read("test.cache", "x").unwrap().as_ref(), b"42");
+        let err = source.read("test.missing", "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        // Directories are merged
+        let mut files = Vec::new();
+        source
+            .read_dir("test", &mut |entry| {
+                if let DirEntry::File(id, "x") = entry {
+                    files.push(id.to_owned());
+                }
+            })
+            .unwrap();
+        files.sort();
+        assert_eq!(files, ["test.a", "test.b", "test.cache", "test.new"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+mod fallback {
+    use super::*;
+
+    test_source!(FallbackSource::new(
+        FileSystem::new("assets").unwrap(),
+        FileSystem::new("assets").unwrap()
+    ));
+
+    #[test]
+    fn origin() {
+        let dir = std::env::temp_dir().join(format!("am_fallback_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("test")).unwrap();
+        std::fs::write(dir.join("test/b.x"), "5").unwrap();
+
+        let source = FallbackSource::new(
+            FileSystem::new(&dir).unwrap(),
+            FileSystem::new("assets").unwrap(),
+        );
+
+        let (content, origin) = source.read_with_origin("test.b", "x").unwrap();
+        assert_eq!((content.as_ref(), origin), (&b"5"[..], Origin::Primary));
+        drop(content);
+        let (content, origin) = source.read_with_origin("test.cache", "x").unwrap();
+        assert_eq!((content.as_ref(), origin), (&b"42"[..], Origin::Fallback));
+        drop(content);
+        assert_eq!(source.origin_of(DirEntry::File("test.missing", "x")), None);
+
+        let source = source.fallback_on(|_| false);
+        let err = source.read("test.cache", "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod decompress {
+    use super::*;
+
+    test_source!(Decompress::new(FileSystem::new("assets").unwrap()));
+
+    fn check(ext: &str, compressed: &[u8]) {
+        let dir = std::env::temp_dir().join(format!("am_decompress_{ext}_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("data")).unwrap();
+        std::fs::write(dir.join(format!("data/table.json.{ext}")), compressed).unwrap();
+        std::fs::write(dir.join("data/plain.json"), "[]").unwrap();
+
+        let source = Decompress::new(FileSystem::new(&dir).unwrap());
+
+        assert_eq!(
+            source.read("data.table", "json").unwrap().as_ref(),
+            b"[1, 2]"
+        );
+        assert_eq!(source.read("data.plain", "json").unwrap().as_ref(), b"[]");
+        let err = source.read("data.missing", "json").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        assert!(source.exists(DirEntry::File("data.table", "json")));
+        assert!(!source.exists(DirEntry::File("data.table", "ron")));
+
+        let limited = Decompress::new(FileSystem::new(&dir).unwrap()).with_max_size(6);
+        assert_eq!(
+            limited.read("data.table", "json").unwrap().as_ref(),
+            b"[1, 2]"
+        );
+        let limited = Decompress::new(FileSystem::new(&dir).unwrap()).with_max_size(5);
+        let err = limited.read("data.table", "json").unwrap_err();
+        let err = err.get_ref().unwrap().downcast_ref::<TooLargeError>();
+        assert_eq!(err, Some(&TooLargeError::new(6, 5)));
+
+        let mut files = Vec::new();
+        source
+            .read_dir("data", &mut |entry| {
+                if let DirEntry::File(id, ext) = entry {
+                    files.push((id.to_owned(), ext.to_owned()));
+                }
+            })
+            .unwrap();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                ("data.plain".to_owned(), "json".to_owned()),
+                ("data.table".to_owned(), "json".to_owned()),
+            ]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        encoder.write_all(b"[1, 2]").unwrap();
+        check("gz", &encoder.finish().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn zstd() {
+        check("zst", &zstd::encode_all(&b"[1, 2]"[..], 0).unwrap());
+    }
+
+    #[cfg(all(feature = "hot-reloading", feature = "test-kit", feature = "zstd"))]
+    #[test]
+    fn hot_reloading_conforms() {
+        let dir = std::env::temp_dir().join("assets_manager_decompress_hot");
+        std::fs::create_dir_all(dir.join("data")).unwrap();
+        let path = dir.join("data/table.json.zst");
+        std::fs::write(&path, zstd::encode_all(&b"[1]"[..], 0).unwrap()).unwrap();
+
+        let source = Decompress::new(FileSystem::new(&dir).unwrap());
+        test_kit::assert_hot_reloading_conforms(
+            &source,
+            OwnedDirEntry::File("data.table".into(), "json".into()),
+            std::time::Duration::from_secs(5),
+            || std::fs::write(&path, zstd::encode_all(&b"[2]"[..], 0).unwrap()).unwrap(),
+        );
+    }
+}
+
 #[cfg(feature = "embedded")]
 mod embedded {
     use super::*;
@@ -101,6 +558,49 @@ mod embedded {
     static RAW: RawEmbedded<'static> = embed!("assets");
 
     test_source!(Embedded::from(RAW));
+
+    #[test]
+    fn filters() {
+        static FILTERED: RawEmbedded<'static> = embed!(
+            "assets/test",
+            include = ["*.x", "read_dir/**"],
+            exclude = ["cache.x", "read_dir/b/**"],
+        );
+        static EXTENSIONS: RawEmbedded<'static> = embed!("assets/test", extensions = ["zip"]);
+
+        let source = Embedded::from(FILTERED);
+        assert!(source.exists(DirEntry::File("a", "x")));
+        assert!(!source.exists(DirEntry::File("cache", "x")));
+        assert!(!source.exists(DirEntry::File("test", "zip")));
+        assert!(source.exists(DirEntry::File("read_dir.a.dummy", "")));
+        assert!(!source.exists(DirEntry::Directory("read_dir.b")));
+
+        let source = Embedded::from(EXTENSIONS);
+        assert!(source.exists(DirEntry::File("test", "zip")));
+        assert!(!source.exists(DirEntry::File("a", "x")));
+    }
+
+    #[test]
+    fn paths() {
+        static MANIFEST_DIR: RawEmbedded<'static> =
+            embed!(manifest_dir: "assets/test", extensions = ["x"]);
+        static EXPANDED: RawEmbedded<'static> = embed!(
+            "${CARGO_MANIFEST_DIR}/assets/test",
+            expand_vars,
+            extensions = ["x"]
+        );
+        static UNSET_ENV: RawEmbedded<'static> = embed!(
+            "$CARGO_MANIFEST_DIR/assets/test",
+            expand_vars,
+            env = "ASSETS_MANAGER_UNSET_VARIABLE",
+            extensions = ["x"],
+        );
+
+        for raw in [MANIFEST_DIR, EXPANDED, UNSET_ENV] {
+            let source = Embedded::from(raw);
+            assert_eq!(source.read("b", "x").unwrap().as_ref(), b"-7");
+        }
+    }
 }
 
 #[cfg(feature = "tar")]
@@ -109,6 +609,14 @@ mod tar {
 
     test_source!(Tar::open("assets/test/test.tar").unwrap());
 
+    #[test]
+    fn case_insensitive() {
+        let tar = Tar::open("assets/test/test.tar").unwrap();
+        assert!(tar.read("Test.B", "X").is_err());
+
+        check_case_insensitive(&tar.with_case_insensitive(true));
+    }
+
     #[test]
     fn errors() {
         let tar = Tar::open("assets/test/test.tar").unwrap();
@@ -120,12 +628,119 @@ mod tar {
     }
 }
 
+mod encrypted {
+    use super::*;
+
+    pub(super) struct Xor(pub u8);
+
+    impl Cipher for Xor {
+        fn decrypt(&self, _id: &str, _ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+            Ok(data.iter().map(|b| b ^ self.0).collect())
+        }
+
+        fn encrypt(&self, id: &str, ext: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+            self.decrypt(id, ext, data)
+        }
+    }
+
+    test_source!(Encrypted::new(FileSystem::new("assets").unwrap(), Xor(0)));
+
+    #[test]
+    fn decrypt() {
+        let dir = std::env::temp_dir().join(format!("am_encrypted_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.x"), [b'4' ^ 1, b'2' ^ 1]).unwrap();
+
+        let source = Encrypted::new(FileSystem::new(&dir).unwrap(), Xor(1));
+        assert_eq!(source.read("a", "x").unwrap().as_ref(), b"42");
+        assert!(source.exists(DirEntry::File("a", "x")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[cfg(feature = "pack")]
+mod pack {
+    use super::*;
+
+    fn make_pack() -> Vec<u8> {
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add_dir("assets").unwrap();
+        writer.finish().unwrap()
+    }
+
+    test_source!(Pack::from_reader(io::Cursor::new(make_pack())).unwrap());
+
+    #[test]
+    fn compression() {
+        let content = vec![b'a'; 1000];
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add_file("big", "txt", &content).unwrap();
+        writer.add_file("dir.small", "txt", b"a").unwrap();
+        assert!(writer.add_file("bad..id", "txt", b"a").is_err());
+        let pack = writer.finish().unwrap();
+        assert!(pack.len() < 500);
+
+        let source = Pack::from_reader(io::Cursor::new(pack)).unwrap();
+        assert_eq!(source.read("big", "txt").unwrap().as_ref(), &content[..]);
+        assert_eq!(source.read("dir.small", "txt").unwrap().as_ref(), b"a");
+        assert_eq!(source.file_size("big", "txt"), Some(1000));
+        assert!(source.exists(DirEntry::Directory("dir")));
+    }
+
+    #[test]
+    fn wrong_size() {
+        let content = vec![b'a'; 1000];
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add_file("big", "txt", &content).unwrap();
+        let mut pack = writer.finish().unwrap();
+
+        // Claim a huge size in the index
+        let size = 1000u64.to_le_bytes();
+        let pos = pack.windows(8).rposition(|w| w == size).unwrap();
+        pack[pos..pos + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let source = Pack::from_reader(io::Cursor::new(pack)).unwrap();
+        let err = source.read("big", "txt").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn encryption() {
+        let content = vec![b'a'; 1000];
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add_file("plain", "txt", b"42").unwrap();
+        writer.set_cipher(Some(Box::new(super::encrypted::Xor(0x5a))));
+        writer.add_file("big", "txt", &content).unwrap();
+        writer.add_file("small", "txt", b"42").unwrap();
+        let pack = writer.finish().unwrap();
+
+        let source = Pack::from_reader(io::Cursor::new(pack)).unwrap();
+        assert_eq!(source.read("plain", "txt").unwrap().as_ref(), b"42");
+        let err = source.read("small", "txt").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        let source = source.with_cipher(super::encrypted::Xor(0x5a));
+        assert_eq!(source.read("plain", "txt").unwrap().as_ref(), b"42");
+        assert_eq!(source.read("big", "txt").unwrap().as_ref(), &content[..]);
+        assert_eq!(source.read("small", "txt").unwrap().as_ref(), b"42");
+    }
+}
+
 #[cfg(feature = "zip-deflate")]
 mod zip {
     use super::*;
 
     test_source!(Zip::open("assets/test/test.zip").unwrap());
 
+    #[test]
+    fn case_insensitive() {
+        let zip = Zip::open("assets/test/test.zip").unwrap();
+        assert!(zip.read("Test.B", "X").is_err());
+
+        check_case_insensitive(&zip.with_case_insensitive(true));
+    }
+
     #[test]
     fn errors() {
         let zip = Zip::open("assets/test/test.zip").unwrap();
@@ -135,4 +750,245 @@ mod zip {
         assert!(err.to_string().contains("assets/test/test.zip"));
         assert!(err.kind() == io::ErrorKind::NotFound);
     }
+
+    #[test]
+    fn index_cache() {
+        fn entries(zip: &Zip, id: &str) -> Vec<String> {
+            let mut entries = Vec::new();
+            zip.read_dir(id, &mut |e| entries.push(format!("{e:?}")))
+                .unwrap();
+            entries.sort();
+            entries
+        }
+
+        let dir = std::env::temp_dir().join(format!("am_zip_index_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let index = dir.join("test.zip.index");
+        let _ = std::fs::remove_file(&index);
+
+        let reference = Zip::open("assets/test/test.zip").unwrap();
+
+        // The index is created on first use, then reused
+        for _ in 0..2 {
+            let zip = Zip::open_with_index_cache("assets/test/test.zip", &index).unwrap();
+            assert!(index.exists());
+            for id in ["", "test", "test.read_dir", "test.empty"] {
+                assert_eq!(entries(&zip, id), entries(&reference, id));
+            }
+            assert_eq!(
+                zip.read("test.a", "x").unwrap().as_ref(),
+                reference.read("test.a", "x").unwrap().as_ref(),
+            );
+        }
+
+        // An invalid index is ignored and replaced
+        std::fs::write(&index, b"invalid").unwrap();
+        let zip = Zip::open_with_index_cache("assets/test/test.zip", &index).unwrap();
+        assert_eq!(entries(&zip, ""), entries(&reference, ""));
+        assert_ne!(std::fs::read(&index).unwrap(), b"invalid");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[cfg(feature = "http")]
+mod http {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    /// Serves files of the "assets" directory, and counts full responses.
+    fn serve() -> (String, Arc<AtomicUsize>) {
+        serve_dir(std::path::PathBuf::from("."))
+    }
+
+    fn serve_dir(root: std::path::PathBuf) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/assets", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split(' ');
+                let method = parts.next().unwrap().to_owned();
+                let path = parts.next().unwrap().trim_start_matches('/').to_owned();
+
+                let mut if_none_match = None;
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    if let Some(etag) = line.strip_prefix("If-None-Match: ") {
+                        if_none_match = Some(etag.trim_end().to_owned());
+                    }
+                }
+
+                let response = match std::fs::read(root.join(&path)) {
+                    Ok(content) => {
+                        let etag = format!("\"{}\"", content.len());
+                        if if_none_match.as_ref() == Some(&etag) {
+                            b"HTTP/1.1 304 Not Modified\r\n\r\n".to_vec()
+                        } else {
+                            if method == "GET" {
+                                counter.fetch_add(1, Ordering::Relaxed);
+                            }
+                            let mut response = format!(
+                                "HTTP/1.1 200 OK\r\nETag: {etag}\r\nContent-Length: {}\r\n\r\n",
+                                content.len()
+                            )
+                            .into_bytes();
+                            if method == "GET" {
+                                response.extend(content);
+                            }
+                            response
+                        }
+                    }
+                    Err(_) => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec(),
+                };
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        (url, count)
+    }
+
+    #[test]
+    fn read() {
+        let (url, count) = serve();
+        let source = Http::new(&url).unwrap();
+
+        let content = source.read("test.b", "x").unwrap();
+        assert_eq!(content.as_ref(), b"-7");
+
+        // The second read uses the cached version
+        let content = source.read("test.b", "x").unwrap();
+        assert_eq!(content.as_ref(), b"-7");
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        let err = source.read("test.not_found", "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        assert!(source.exists(DirEntry::File("test.cache", "x")));
+        assert!(!source.exists(DirEntry::File("test.not_found", "x")));
+        assert!(source.read_dir("test", &mut |_| ()).is_err());
+
+        // Checking existence does not download the file
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(source.exists(DirEntry::File("test.cache", "x")));
+        assert_eq!(source.file_size("test.cache", "x"), Some(2));
+        assert_eq!(source.file_size("test.not_found", "x"), None);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(source.read("test.cache", "x").unwrap().as_ref(), b"42");
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn max_response_size() {
+        let (url, _) = serve();
+        let source = Http::new(&url).unwrap().max_response_size(1);
+
+        let err = source.read("test.b", "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let source = Http::new(&url).unwrap().max_response_size(2);
+        assert_eq!(source.read("test.b", "x").unwrap().as_ref(), b"-7");
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    #[test]
+    fn poll() {
+        use crate::hot_reloading::EventSender;
+
+        let dir = std::env::temp_dir().join(format!("am_http_poll_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        let path = dir.join("assets/a.x");
+        std::fs::write(&path, "1").unwrap();
+
+        let (url, _) = serve_dir(dir.clone());
+        let source = Http::new(&url)
+            .unwrap()
+            .poll_interval(std::time::Duration::from_millis(10));
+        assert_eq!(source.read("a", "x").unwrap().as_ref(), b"1");
+
+        let (sender, events) = crossbeam_channel::unbounded();
+        source.configure_hot_reloading(EventSender(sender)).unwrap();
+
+        // A removed file is only reported once
+        std::fs::remove_file(&path).unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+        assert!(events.recv_timeout(timeout).is_ok());
+        assert!(events
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .is_err());
+
+        // Its creation is reported too
+        std::fs::write(&path, "2").unwrap();
+        assert!(events.recv_timeout(timeout).is_ok());
+        assert_eq!(source.read("a", "x").unwrap().as_ref(), b"2");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn map_extension() {
+        let (url, _) = serve();
+        let source = Http::new(&url).unwrap().map_extension("bin", "x");
+
+        let content = source.read("test.b", "bin").unwrap();
+        assert_eq!(content.as_ref(), b"-7");
+        assert!(source.url_of("test.b", "bin").ends_with("/assets/test/b.x"));
+    }
+
+    #[test]
+    fn invalid_urls() {
+        assert!(Http::new("ftp://localhost").is_err());
+        assert!(Http::new("http://").is_err());
+        assert!(Http::new("http://localhost:port").is_err());
+        assert_eq!(
+            Http::new("https://localhost").unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+}
+
+#[cfg(feature = "test-kit")]
+#[test]
+#[should_panic = "is not a valid id"]
+fn test_kit_invalid_id() {
+    struct Broken;
+
+    impl Source for Broken {
+        fn read(&self, _: &str, _: &str) -> io::Result<FileContent<'_>> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+            if id.is_empty() {
+                f(DirEntry::Directory("a/b"));
+                Ok(())
+            } else {
+                Err(io::ErrorKind::NotFound.into())
+            }
+        }
+
+        fn exists(&self, entry: DirEntry) -> bool {
+            entry == DirEntry::Directory("")
+        }
+    }
+
+    test_kit::assert_source_conforms(&Broken);
 }
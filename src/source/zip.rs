@@ -1,6 +1,6 @@
 #[cfg(feature = "mmap")]
 use super::ArcMap;
-use super::{DirEntry, Source};
+use super::{case::CaseMap, DirEntry, Source};
 use crate::{
     utils::{extension_of, HashMap, IdBuilder},
     SharedString,
@@ -34,7 +34,7 @@ impl fmt::Debug for OwnedEntry {
 }
 
 impl OwnedEntry {
-    fn as_dir_entry(&self) -> DirEntry {
+    fn as_dir_entry(&self) -> DirEntry<'_> {
         match self {
             OwnedEntry::File(FileDesc(id, ext)) => DirEntry::File(id, ext),
             OwnedEntry::Dir(id) => DirEntry::Directory(id),
@@ -115,6 +115,7 @@ pub struct Zip<R = SyncFile> {
     dirs: HashMap<SharedString, Vec<OwnedEntry>>,
     archive: ZipArchive<R>,
     label: Option<String>,
+    case_map: Option<CaseMap>,
 }
 
 impl Zip<SyncFile> {
@@ -129,6 +130,52 @@ impl Zip<SyncFile> {
         let file = SyncFile::open(path)?;
         Self::from_reader_with_label(file, path.display().to_string())
     }
+
+    /// Creates a `Zip` archive backed by the file at the given path, using an
+    /// index cache stored at `index_path`.
+    ///
+    /// The index maps ids to entries of the archive. If the index cache was
+    /// created for the same archive (same size and modification time), it is
+    /// used instead of parsing the paths of the archive again. Otherwise, the
+    /// index is built normally and written to `index_path`. Failing to write
+    /// the index cache is not an error.
+    ///
+    /// Note that the central directory of the archive is still read when it
+    /// is opened.
+    #[inline]
+    pub fn open_with_index_cache<P, Q>(path: P, index_path: Q) -> io::Result<Self>
+    where
+        P: AsRef<path::Path>,
+        Q: AsRef<path::Path>,
+    {
+        Self::_open_with_index_cache(path.as_ref(), index_path.as_ref())
+    }
+
+    fn _open_with_index_cache(path: &path::Path, index_path: &path::Path) -> io::Result<Self> {
+        let file = SyncFile::open(path)?;
+        let stamp = index_cache::Stamp::of(&file.metadata()?);
+        let label = path.display().to_string();
+        let archive = ZipArchive::new(file)?;
+
+        match index_cache::read(index_path, stamp, archive.len()) {
+            Ok((files, dirs)) => {
+                return Ok(Zip {
+                    files,
+                    dirs,
+                    archive,
+                    label: Some(label),
+                    case_map: None,
+                })
+            }
+            Err(err) => log::debug!("Cannot use index cache {index_path:?}: {err}"),
+        }
+
+        let zip = Self::from_archive(archive, Some(label))?;
+        if let Err(err) = index_cache::write(index_path, stamp, &zip.files, &zip.dirs) {
+            log::warn!("Cannot write index cache {index_path:?}: {err}");
+        }
+        Ok(zip)
+    }
 }
 
 #[cfg(feature = "mmap")]
@@ -184,8 +231,11 @@ where
     }
 
     fn create(reader: R, label: Option<String>) -> io::Result<Zip<R>> {
-        let mut archive = ZipArchive::new(reader)?;
+        let archive = ZipArchive::new(reader)?;
+        Self::from_archive(archive, label)
+    }
 
+    fn from_archive(mut archive: ZipArchive<R>, label: Option<String>) -> io::Result<Zip<R>> {
         let len = archive.len();
         let mut files = HashMap::with_capacity(len);
         let mut dirs = HashMap::new();
@@ -201,23 +251,61 @@ where
             dirs,
             archive,
             label,
+            case_map: None,
         })
     }
 }
 
+impl<R> Zip<R> {
+    /// Resolves ids case-insensitively when no entry matches exactly.
+    ///
+    /// A warning mentioning `CaseMismatch` is logged each time such a
+    /// fallback match is used, so that the ids can be fixed.
+    ///
+    /// This is `false` by default.
+    pub fn with_case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_map = enabled.then(|| {
+            CaseMap::new(
+                self.files.keys().map(|FileDesc(id, ext)| (id, ext)),
+                self.dirs.keys(),
+            )
+        });
+        self
+    }
+
+    fn file_index(&self, id: &str, ext: &str) -> Option<usize> {
+        match self.files.get(&(id, ext)) {
+            Some(&index) => Some(index),
+            None => self
+                .files
+                .get(&self.case_map.as_ref()?.file(id, ext)?)
+                .copied(),
+        }
+    }
+
+    fn dir(&self, id: &str) -> Option<&[OwnedEntry]> {
+        match self.dirs.get(id) {
+            Some(dir) => Some(dir),
+            None => self
+                .dirs
+                .get(self.case_map.as_ref()?.dir(id)?)
+                .map(Vec::as_slice),
+        }
+    }
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "zip")))]
 impl<R> Source for Zip<R>
 where
     R: io::Read + io::Seek + Clone,
 {
-    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent> {
+    fn read(&self, id: &str, ext: &str) -> io::Result<super::FileContent<'_>> {
         use io::Read;
 
         // Get the file within the archive
-        let index = *self
-            .files
-            .get(&(id, ext))
-            .ok_or_else(|| error::find_file(id, &self.label))?;
+        let index = self
+            .file_index(id, ext)
+            .ok_or_else(|| error::find_file(id, &self.label, "ZIP"))?;
         let mut archive = self.archive.clone();
         let mut file = archive
             .by_index(index)
@@ -226,26 +314,32 @@ where
         // Read it in a buffer
         let mut content = Vec::with_capacity(file.size() as usize);
         file.read_to_end(&mut content)
-            .map_err(|err| error::read_file(err, id, &self.label))?;
+            .map_err(|err| error::read_file(err, id, &self.label, "ZIP"))?;
 
         Ok(super::FileContent::Buffer(content))
     }
 
     fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
         let dir = self
-            .dirs
-            .get(id)
-            .ok_or_else(|| error::find_dir(id, &self.label))?;
+            .dir(id)
+            .ok_or_else(|| error::find_dir(id, &self.label, "ZIP"))?;
         dir.iter().map(OwnedEntry::as_dir_entry).for_each(f);
         Ok(())
     }
 
     fn exists(&self, entry: DirEntry) -> bool {
         match entry {
-            DirEntry::File(id, ext) => self.files.contains_key(&(id, ext)),
-            DirEntry::Directory(id) => self.dirs.contains_key(id),
+            DirEntry::File(id, ext) => self.file_index(id, ext).is_some(),
+            DirEntry::Directory(id) => self.dir(id).is_some(),
         }
     }
+
+    fn file_size(&self, id: &str, ext: &str) -> Option<u64> {
+        let index = self.file_index(id, ext)?;
+        let mut archive = self.archive.clone();
+        let file = archive.by_index_raw(index).ok()?;
+        Some(file.size())
+    }
 }
 
 impl<R> fmt::Debug for Zip<R> {
@@ -257,64 +351,177 @@ impl<R> fmt::Debug for Zip<R> {
     }
 }
 
-mod error {
-    use std::{fmt, io};
-    use zip::result::ZipError;
+/// On-disk cache of the index of an archive.
+///
+/// The format is a header with a magic number and the size and modification
+/// time of the archive, followed by the content of each directory.
+mod index_cache {
+    use super::{FileDesc, OwnedEntry};
+    use crate::{utils::HashMap, SharedString};
+    use std::{
+        fs,
+        io::{self, BufWriter, Read, Write},
+        path::Path,
+        time::UNIX_EPOCH,
+    };
 
-    #[cold]
-    pub fn find_file(id: &str, label: &Option<String>) -> io::Error {
-        let msg = match label {
-            Some(lbl) => format!("Could not find asset \"{id}\" in {lbl}"),
-            None => format!("Could not find asset \"{id}\" in ZIP"),
-        };
+    const MAGIC: &[u8; 8] = b"AMZIDX01";
 
-        io::Error::new(io::ErrorKind::NotFound, msg)
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Stamp {
+        len: u64,
+        mtime: (u64, u32),
     }
 
-    #[cold]
-    pub fn read_file(err: io::Error, id: &str, label: &Option<String>) -> io::Error {
-        #[derive(Debug)]
-        struct Error {
-            err: io::Error,
-            msg: String,
-        }
-        impl fmt::Display for Error {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str(&self.msg)
+    impl Stamp {
+        pub fn of(metadata: &fs::Metadata) -> Self {
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or((0, 0), |d| (d.as_secs(), d.subsec_nanos()));
+            Stamp {
+                len: metadata.len(),
+                mtime,
             }
         }
-        impl std::error::Error for Error {
-            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-                Some(&self.err)
+    }
+
+    type Maps = (
+        HashMap<FileDesc, usize>,
+        HashMap<SharedString, Vec<OwnedEntry>>,
+    );
+
+    fn invalid(msg: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, msg)
+    }
+
+    struct Reader<'a>(&'a [u8]);
+
+    impl Reader<'_> {
+        fn bytes(&mut self, n: usize) -> io::Result<&[u8]> {
+            if self.0.len() < n {
+                return Err(invalid("unexpected end of index"));
             }
+            let (bytes, rest) = self.0.split_at(n);
+            self.0 = rest;
+            Ok(bytes)
         }
 
-        let msg = match label {
-            Some(lbl) => format!("Could not read \"{id}\" in {lbl}"),
-            None => format!("Could not read \"{id}\" in ZIP"),
-        };
+        fn u8(&mut self) -> io::Result<u8> {
+            Ok(self.bytes(1)?[0])
+        }
+
+        fn u32(&mut self) -> io::Result<u32> {
+            Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+        }
 
-        io::Error::new(err.kind(), Error { err, msg })
+        fn u64(&mut self) -> io::Result<u64> {
+            Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+        }
+
+        fn str(&mut self) -> io::Result<SharedString> {
+            let len = self.u32()? as usize;
+            let s = std::str::from_utf8(self.bytes(len)?).map_err(|_| invalid("invalid string"))?;
+            Ok(s.into())
+        }
     }
 
-    #[cold]
-    pub fn open_file(err: ZipError, id: &str, label: &Option<String>) -> io::Error {
-        #[derive(Debug)]
-        struct Error {
-            err: ZipError,
-            msg: String,
+    pub fn read(path: &Path, stamp: Stamp, archive_len: usize) -> io::Result<Maps> {
+        let mut content = Vec::new();
+        fs::File::open(path)?.read_to_end(&mut content)?;
+        let mut r = Reader(&content);
+
+        if r.bytes(MAGIC.len())? != MAGIC {
+            return Err(invalid("invalid magic number"));
+        }
+        let found = Stamp {
+            len: r.u64()?,
+            mtime: (r.u64()?, r.u32()?),
+        };
+        if found != stamp {
+            return Err(invalid("the archive changed"));
         }
-        impl fmt::Display for Error {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str(&self.msg)
+
+        let mut files = HashMap::new();
+        let mut dirs: HashMap<SharedString, Vec<OwnedEntry>> = HashMap::new();
+
+        for _ in 0..r.u64()? {
+            let parent = r.str()?;
+            let mut entries = Vec::new();
+            for _ in 0..r.u64()? {
+                entries.push(match r.u8()? {
+                    0 => {
+                        let index = r.u64()? as usize;
+                        if index >= archive_len {
+                            return Err(invalid("invalid file index"));
+                        }
+                        let desc = FileDesc(r.str()?, r.str()?);
+                        files.insert(desc.clone(), index);
+                        OwnedEntry::File(desc)
+                    }
+                    1 => {
+                        let id = r.str()?;
+                        dirs.entry(id.clone()).or_default();
+                        OwnedEntry::Dir(id)
+                    }
+                    _ => return Err(invalid("invalid entry kind")),
+                });
             }
+            dirs.entry(parent).or_default().extend(entries);
         }
-        impl std::error::Error for Error {
-            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-                Some(&self.err)
+
+        Ok((files, dirs))
+    }
+
+    pub fn write(
+        path: &Path,
+        stamp: Stamp,
+        files: &HashMap<FileDesc, usize>,
+        dirs: &HashMap<SharedString, Vec<OwnedEntry>>,
+    ) -> io::Result<()> {
+        fn write_str(w: &mut impl Write, s: &str) -> io::Result<()> {
+            w.write_all(&(s.len() as u32).to_le_bytes())?;
+            w.write_all(s.as_bytes())
+        }
+
+        let mut w = BufWriter::new(fs::File::create(path)?);
+        w.write_all(MAGIC)?;
+        w.write_all(&stamp.len.to_le_bytes())?;
+        w.write_all(&stamp.mtime.0.to_le_bytes())?;
+        w.write_all(&stamp.mtime.1.to_le_bytes())?;
+
+        w.write_all(&(dirs.len() as u64).to_le_bytes())?;
+        for (parent, entries) in dirs.iter() {
+            write_str(&mut w, parent)?;
+            w.write_all(&(entries.len() as u64).to_le_bytes())?;
+            for entry in entries {
+                match entry {
+                    OwnedEntry::File(desc) => {
+                        w.write_all(&[0])?;
+                        w.write_all(&(files[desc] as u64).to_le_bytes())?;
+                        write_str(&mut w, &desc.0)?;
+                        write_str(&mut w, &desc.1)?;
+                    }
+                    OwnedEntry::Dir(id) => {
+                        w.write_all(&[1])?;
+                        write_str(&mut w, id)?;
+                    }
+                }
             }
         }
 
+        w.flush()
+    }
+}
+
+mod error {
+    pub use crate::source::archive_error::{find_dir, find_file, read_file};
+    use std::io;
+    use zip::result::ZipError;
+
+    #[cold]
+    pub fn open_file(err: ZipError, id: &str, label: &Option<String>) -> io::Error {
         let msg = match label {
             Some(lbl) => format!("Could not open \"{id}\" in {lbl}"),
             None => format!("Could not open \"{id}\" in ZIP"),
@@ -327,16 +534,6 @@ mod error {
             ZipError::FileNotFound => io::ErrorKind::NotFound,
         };
 
-        io::Error::new(kind, Error { err, msg })
-    }
-
-    #[cold]
-    pub fn find_dir(id: &str, label: &Option<String>) -> io::Error {
-        let msg = match label {
-            Some(lbl) => format!("Could not find directory \"{id}\" in {lbl}"),
-            None => format!("Could not find directory \"{id}\" in ZIP"),
-        };
-
-        io::Error::new(io::ErrorKind::NotFound, msg)
+        crate::source::archive_error::with_context(kind, err, msg)
     }
 }
@@ -0,0 +1,242 @@
+//! Statistics about the use of a cache.
+
+use crate::utils::{HashMap, RwLock};
+use std::{
+    any::TypeId,
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+#[cfg(doc)]
+use crate::AssetCache;
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    loads: AtomicU64,
+    reloads: AtomicU64,
+    failures: AtomicU64,
+    bytes_read: AtomicU64,
+    load_nanos: AtomicU64,
+}
+
+/// Records statistics about the assets of a cache.
+pub(crate) struct Recorder {
+    types: RwLock<HashMap<TypeId, (&'static str, Counters)>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            types: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn with<R>(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        f: impl FnOnce(&Counters) -> R,
+    ) -> R {
+        if let Some((_, counters)) = self.types.read().get(&type_id) {
+            return f(counters);
+        }
+
+        let mut types = self.types.write();
+        let (_, counters) = types
+            .entry(type_id)
+            .or_insert_with(|| (type_name, Counters::default()));
+        f(counters)
+    }
+
+    /// Records a lookup of an asset in the cache.
+    pub fn record_lookup(&self, type_id: TypeId, type_name: &'static str, hit: bool) {
+        self.with(type_id, type_name, |c| {
+            let counter = if hit { &c.hits } else { &c.misses };
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    /// Records a load of an asset from the source.
+    pub fn record_load(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        reload: bool,
+        result: Result<usize, ()>,
+        duration: Duration,
+    ) {
+        self.with(type_id, type_name, |c| {
+            match result {
+                Ok(bytes) => {
+                    c.loads.fetch_add(1, Ordering::Relaxed);
+                    c.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+                    if reload {
+                        c.reloads.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                Err(()) => {
+                    c.failures.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            let nanos = duration.as_nanos().try_into().unwrap_or(u64::MAX);
+            c.load_nanos.fetch_add(nanos, Ordering::Relaxed);
+        });
+    }
+
+    pub fn snapshot(&self) -> Statistics {
+        let mut types: Vec<_> = self
+            .types
+            .read()
+            .iter()
+            .map(|(&type_id, (type_name, c))| TypeStatistics {
+                type_id,
+                type_name,
+                hits: c.hits.load(Ordering::Relaxed),
+                misses: c.misses.load(Ordering::Relaxed),
+                loads: c.loads.load(Ordering::Relaxed),
+                reloads: c.reloads.load(Ordering::Relaxed),
+                failures: c.failures.load(Ordering::Relaxed),
+                bytes_read: c.bytes_read.load(Ordering::Relaxed),
+                load_time: Duration::from_nanos(c.load_nanos.load(Ordering::Relaxed)),
+            })
+            .collect();
+        types.sort_unstable_by_key(|t| t.type_name);
+        Statistics { types }
+    }
+
+    pub fn clear(&self) {
+        self.types.write().clear();
+    }
+}
+
+/// Statistics about the assets of a type in a cache.
+///
+/// See [`Statistics`].
+#[derive(Clone)]
+pub struct TypeStatistics {
+    type_id: TypeId,
+    type_name: &'static str,
+    hits: u64,
+    misses: u64,
+    loads: u64,
+    reloads: u64,
+    failures: u64,
+    bytes_read: u64,
+    load_time: Duration,
+}
+
+impl TypeStatistics {
+    /// The `TypeId` of the assets.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type of the assets.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The number of times an asset was found in the cache when loaded.
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of times an asset was not found in the cache when loaded.
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// The proportion of hits among lookups, or `None` if there was no
+    /// lookup.
+    #[inline]
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total != 0).then(|| self.hits as f64 / total as f64)
+    }
+
+    /// The number of successful loads from the source, including reloads and
+    /// calls to `load_owned`.
+    #[inline]
+    pub fn loads(&self) -> u64 {
+        self.loads
+    }
+
+    /// The number of successful reloads.
+    #[inline]
+    pub fn reloads(&self) -> u64 {
+        self.reloads
+    }
+
+    /// The number of failed loads and reloads.
+    #[inline]
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    /// The number of bytes read from the source by successful loads.
+    ///
+    /// Bytes read to load other assets are not counted.
+    #[inline]
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// The total time spent loading assets, including failed loads.
+    ///
+    /// This includes the time spent loading the assets they depend on.
+    #[inline]
+    pub fn load_time(&self) -> Duration {
+        self.load_time
+    }
+}
+
+impl fmt::Debug for TypeStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeStatistics")
+            .field("type_name", &self.type_name)
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .field("loads", &self.loads)
+            .field("reloads", &self.reloads)
+            .field("failures", &self.failures)
+            .field("bytes_read", &self.bytes_read)
+            .field("load_time", &self.load_time)
+            .finish()
+    }
+}
+
+/// Statistics about the use of a cache.
+///
+/// This is returned by [`AssetCache::statistics`].
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    types: Vec<TypeStatistics>,
+}
+
+impl Statistics {
+    /// Returns the statistics of each type used in the cache, sorted by type
+    /// name.
+    #[inline]
+    pub fn types(&self) -> &[TypeStatistics] {
+        &self.types
+    }
+
+    /// Returns the statistics of the given type, if it was used in the cache.
+    #[inline]
+    pub fn get(&self, type_id: TypeId) -> Option<&TypeStatistics> {
+        self.types.iter().find(|t| t.type_id == type_id)
+    }
+
+    /// Returns the statistics of type `T`, if it was used in the cache.
+    #[inline]
+    pub fn of<T: 'static>(&self) -> Option<&TypeStatistics> {
+        self.get(TypeId::of::<T>())
+    }
+}
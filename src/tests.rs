@@ -1,4 +1,8 @@
+#![allow(dead_code)]
+
 use crate::*;
+use loader::Loader;
+use std::borrow::Cow;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct X(pub i32);
@@ -14,6 +18,12 @@ impl Asset for X {
     const EXTENSION: &'static str = "x";
 }
 
+impl SaveAsset for X {
+    fn to_bytes(&self, _ext: &str) -> Result<Vec<u8>, BoxedError> {
+        Ok(self.0.to_string().into_bytes())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct XS(pub i32);
 
@@ -38,6 +48,35 @@ impl Compound for Y {
     }
 }
 
+#[derive(Debug)]
+pub struct Small(pub i32);
+
+impl Asset for Small {
+    type Loader = loader::LoadFrom<i32, loader::ParseLoader>;
+    const EXTENSION: &'static str = "x";
+    const MAX_SIZE: Option<u64> = Some(1);
+}
+
+impl From<i32> for Small {
+    fn from(n: i32) -> Small {
+        Small(n)
+    }
+}
+
+/// An `X` multiplied by a parameter.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Scaled(pub i32);
+
+impl ParamAsset for Scaled {
+    const EXTENSION: &'static str = "x";
+    type Params = i32;
+
+    fn load(content: Cow<[u8]>, ext: &str, factor: &i32) -> Result<Self, BoxedError> {
+        let n: i32 = loader::ParseLoader::load(content, ext)?;
+        Ok(Scaled(n * factor))
+    }
+}
+
 pub struct Z(pub i32);
 
 impl Compound for Z {
@@ -47,8 +86,11 @@ impl Compound for Z {
 }
 
 mod asset_cache {
-    use super::{X, Y};
-    use crate::AssetCache;
+    use super::{Scaled, Small, X, XS, Y, Z};
+    use crate::{
+        source::{self, Source, WritableSource},
+        AssetCache, ErrorKind, RetryPolicy, TooLargeError,
+    };
 
     #[test]
     fn new_with_valid_path() {
@@ -76,6 +118,50 @@ mod asset_cache {
         assert!(cache.contains::<X>("test.cache"));
     }
 
+    #[test]
+    fn load_with() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let double = cache.load_with::<Scaled>("test.cache", 2).unwrap();
+        let triple = cache.load_with::<Scaled>("test.cache", 3).unwrap();
+        assert_eq!(*double.read(), Scaled(84));
+        assert_eq!(*triple.read(), Scaled(126));
+        assert_ne!(double.id(), triple.id());
+
+        let again = cache.load_with::<Scaled>("test.cache", 2).unwrap();
+        assert!(std::ptr::eq(double, again));
+
+        let err = cache.load_with::<Scaled>("test.a", 2).unwrap_err();
+        assert_eq!(err.id(), "test.a");
+    }
+
+    #[test]
+    fn save() {
+        let dir = std::env::temp_dir().join(format!("am_save_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.x"), "1").unwrap();
+        let cache = AssetCache::new(&dir).unwrap();
+
+        assert!(cache.save::<X>("a").is_err());
+        cache.load::<X>("a").unwrap();
+        cache.save_value("a", &X(2)).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("a.x")).unwrap(), "2");
+
+        // Directories are created
+        cache.save_value("sub.b", &X(3)).unwrap();
+        assert_eq!(cache.load_owned::<X>("sub.b").unwrap(), X(3));
+        cache.raw_source().remove("sub.b", "x").unwrap();
+        assert!(cache.load_owned::<X>("sub.b").is_err());
+
+        // Files outside of the directory cannot be written
+        assert!(cache.save_value("..a", &X(4)).is_err());
+        assert!(cache.raw_source().write("a", "/../../b", b"5").is_err());
+        assert!(cache.raw_source().remove("sub/../a", "x").is_err());
+        assert_eq!(std::fs::read_to_string(dir.join("a.x")).unwrap(), "2");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn load_owned() {
         let cache = AssetCache::new("assets").unwrap();
@@ -93,6 +179,17 @@ mod asset_cache {
         assert_eq!(*cache.get_cached::<X>("test.cache").unwrap().read(), X(42));
     }
 
+    #[test]
+    fn get_copied() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        assert_eq!(cache.get_copied::<X>("test.cache"), None);
+        assert_eq!(cache.load_cloned::<X>("test.cache").unwrap(), X(42));
+        assert!(cache.contains::<X>("test.cache"));
+        assert_eq!(cache.get_copied::<X>("test.cache"), Some(X(42)));
+        assert_eq!(cache.get_cloned::<X>("test.cache"), Some(X(42)));
+    }
+
     #[test]
     fn get_or_insert() {
         let cache = AssetCache::new("assets").unwrap();
@@ -126,6 +223,157 @@ mod asset_cache {
             .is_some());
     }
 
+    #[test]
+    fn last_error() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        assert!(cache.last_error::<X>("test.a").is_none());
+        cache.load::<X>("test.a").unwrap_err();
+        cache.load::<X>("test.a").unwrap_err();
+        let failure = cache.last_error::<X>("test.a").unwrap();
+        assert_eq!(failure.attempts(), 2);
+        assert_eq!(failure.kind(), ErrorKind::Parse);
+        assert!(cache.last_error::<XS>("test.a").is_none());
+
+        cache.set_retry_policy(RetryPolicy::Never);
+        let err = cache.load::<X>("test.a").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Parse);
+        assert_eq!(cache.last_error::<X>("test.a").unwrap().attempts(), 2);
+        assert_eq!(cache.dump_manifest().failures().len(), 1);
+
+        cache.clear_load_failures();
+        assert!(cache.load_failures().is_empty());
+    }
+
+    #[test]
+    fn max_size() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let err = cache.load::<Small>("test.b").unwrap_err();
+        let err = err.reason().downcast_ref::<TooLargeError>().unwrap();
+        assert_eq!((err.size(), err.limit()), (2, 1));
+
+        let source = source::FileSystem::new("assets").unwrap();
+        let cache = AssetCache::with_source(source::SizeLimit::new(source, 1));
+        let err = cache.load_owned::<X>("test.b").unwrap_err();
+        assert_eq!(err.downcast::<TooLargeError>().unwrap().size(), 2);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn statistics() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        cache.load::<Y>("test.b").unwrap();
+        cache.load::<X>("test.b").unwrap();
+        cache.load::<X>("test.a").unwrap_err();
+
+        let stats = cache.statistics();
+        let x = stats.of::<X>().unwrap();
+        assert_eq!((x.hits(), x.misses()), (1, 2));
+        assert_eq!((x.loads(), x.failures()), (1, 1));
+        assert_eq!(x.bytes_read(), 2);
+        let y = stats.of::<Y>().unwrap();
+        assert_eq!((y.loads(), y.bytes_read()), (1, 0));
+
+        cache.reset_statistics();
+        assert!(cache.statistics().types().is_empty());
+    }
+
+    #[test]
+    fn load_all() {
+        use crate::{AggregateError, AnyCache, BoxedError, Compound, SharedString};
+
+        struct Many;
+
+        impl Compound for Many {
+            fn load(cache: AnyCache, _: &SharedString) -> Result<Self, BoxedError> {
+                cache.load_all::<X>(["test.b", "test.missing_a", "test.missing_b"])?;
+                Ok(Many)
+            }
+        }
+
+        #[derive(Debug)]
+        struct Outer;
+
+        impl Compound for Outer {
+            fn load(cache: AnyCache, _: &SharedString) -> Result<Self, BoxedError> {
+                AggregateError::collect([
+                    cache.load::<Many>("many").map(drop),
+                    cache.load::<X>("test.b").map(drop),
+                    cache.load::<X>("test.missing_c").map(drop),
+                ])?;
+                Ok(Outer)
+            }
+        }
+
+        let cache = AssetCache::new("assets").unwrap();
+
+        let handles = cache.load_all::<X>(["test.b", "test.b"]).unwrap();
+        assert_eq!(handles.len(), 2);
+        assert_eq!(handles[0].read().0, -7);
+
+        let err = cache.load::<Outer>("outer").unwrap_err();
+        let err = err.downcast::<AggregateError>().unwrap();
+        let ids: Vec<_> = err.errors().iter().map(|e| &**e.id()).collect();
+        assert_eq!(ids, ["many", "test.missing_c"]);
+
+        let display = err.to_string();
+        let mut lines = display.lines();
+        assert_eq!(lines.next(), Some("failed to load 2 assets"));
+        assert_eq!(lines.next(), Some("- \"many\": failed to load 2 assets"));
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("  - \"test.missing_a\": "));
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("  - \"test.missing_b\": "));
+        assert!(lines.next().unwrap().starts_with("- \"test.missing_c\": "));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn error_type_name() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let err = cache.load::<X>("test.missing").unwrap_err();
+        assert_eq!(err.type_name(), Some(std::any::type_name::<X>()));
+        assert!(err.to_string().contains("tests::X"));
+    }
+
+    #[test]
+    fn seal() {
+        use crate::{SealPolicy, SealedError};
+
+        let cache = AssetCache::new("assets").unwrap();
+        cache.load::<X>("test.b").unwrap();
+
+        cache.seal(SealPolicy::Error);
+        assert_eq!(cache.seal_policy(), Some(SealPolicy::Error));
+        assert_eq!(cache.load::<X>("test.b").unwrap().read().0, -7);
+
+        let err = cache.load::<Y>("test.b").unwrap_err();
+        assert!(err.reason().is::<SealedError>());
+        assert!(!cache.contains::<Y>("test.b"));
+        assert!(cache
+            .load_dir_with("test", false, &["x"], |_, _| Ok(0))
+            .is_err());
+
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            cache.get_or_insert("new", 0);
+        }));
+        assert!(res.is_err());
+
+        cache.seal(SealPolicy::LogAndAllow);
+        assert_eq!(cache.load::<Y>("test.b").unwrap().read().0, -7);
+
+        cache.unseal();
+        assert_eq!(cache.seal_policy(), None);
+        assert_eq!(*cache.get_or_insert("new", 1).read(), 1);
+    }
+
     #[test]
     fn load_dir_ok() {
         let cache = AssetCache::new("assets").unwrap();
@@ -168,6 +416,577 @@ mod asset_cache {
         assert!(loaded.next().is_none());
     }
 
+    #[test]
+    fn available_ids() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let ids = cache.available_ids::<X>("test.hot_asset").unwrap();
+        let expected: Vec<_> = "abcdefgh"
+            .chars()
+            .map(|c| format!("test.hot_asset.{c}"))
+            .collect();
+        assert_eq!(ids, expected);
+        assert!(!cache.contains::<crate::Directory<X>>("test.hot_asset"));
+
+        let ids = cache.available_ids::<X>("test").unwrap();
+        assert!(ids.iter().any(|id| id == "test.cache"));
+        assert!(ids.iter().any(|id| id == "test.hot_asset.a"));
+
+        assert!(cache.available_ids::<X>("test.missing").is_err());
+    }
+
+    #[test]
+    fn load_dir_with() {
+        let cache = AssetCache::new("assets").unwrap();
+        let parse = |bytes: std::borrow::Cow<[u8]>, _: &str| -> Result<_, crate::BoxedError> {
+            Ok(std::str::from_utf8(&bytes)?.trim().parse::<i64>()?)
+        };
+
+        let handles = cache
+            .load_dir_with("test.hot_asset", false, &["x"], parse)
+            .unwrap();
+        assert_eq!(handles.len(), 8);
+        assert_eq!(handles[0].id(), "test.hot_asset.a");
+        assert!(cache.contains::<i64>("test.hot_asset.h"));
+
+        let handles = cache.load_dir_with("test.read_dir", true, &["", "txt"], |_, ext| {
+            Ok(String::from(ext))
+        });
+        let ids: Vec<_> = handles.unwrap().iter().map(|h| h.id().clone()).collect();
+        assert_eq!(
+            ids,
+            [
+                "test.read_dir.a.dummy",
+                "test.read_dir.b.dummy",
+                "test.read_dir.c",
+                "test.read_dir.d",
+            ]
+        );
+
+        let err = cache
+            .load_dir_with("test", false, &["x"], parse)
+            .unwrap_err();
+        assert_eq!(err.id(), "test.a");
+        assert!(cache
+            .load_dir_with("test.missing", false, &["x"], parse)
+            .is_err());
+    }
+
+    #[test]
+    fn load_dir_values() {
+        let dir = std::env::temp_dir().join("assets_manager_load_dir_values");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/a.x"), "1").unwrap();
+        std::fs::write(dir.join("sub/b.x"), "2").unwrap();
+        std::fs::write(dir.join("sub/c.y"), "3").unwrap();
+        std::fs::write(dir.join("invalid.x"), "invalid").unwrap();
+
+        let cache = AssetCache::new(&dir).unwrap();
+        let values = cache.load_dir_values::<X>("sub").unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get("a").unwrap().read().0, 1);
+        assert_eq!(values.get("b").unwrap().read().0, 2);
+        assert!(!values.contains("c"));
+
+        let names: Vec<_> = values.iter_named().map(|(name, _)| name).collect();
+        assert_eq!(names, ["a", "b"]);
+        let ids: Vec<_> = values.iter().map(|h| h.id().clone()).collect();
+        assert_eq!(ids, ["sub.a", "sub.b"]);
+
+        let err = cache.load_dir_values::<X>("").unwrap_err();
+        assert_eq!(err.id(), "invalid");
+        assert!(cache.load_dir_values::<X>("missing").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rec_dir_order() {
+        let dir = std::env::temp_dir().join("assets_manager_rec_dir_order");
+        let _ = std::fs::remove_dir_all(&dir);
+        for sub in ["b", "a/c", "a/b"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+            std::fs::write(dir.join(sub).join("z.x"), "1").unwrap();
+        }
+        std::fs::write(dir.join("c.x"), "1").unwrap();
+        std::fs::write(dir.join("a.x"), "1").unwrap();
+
+        let cache = AssetCache::new(&dir).unwrap();
+        let ids = cache.load_rec_dir::<X>("").unwrap().read();
+        assert_eq!(
+            ids.ids().collect::<Vec<_>>(),
+            ["a", "a.b.z", "a.c.z", "b.z", "c"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn filtered_dir() {
+        use crate::{DirFilter, FilteredDir};
+
+        struct NotB;
+        impl DirFilter for NotB {
+            fn includes(id: &str) -> bool {
+                id.rsplit('.').next() != Some("b")
+            }
+        }
+
+        struct AnyExt;
+        impl DirFilter for AnyExt {
+            const EXTENSIONS: Option<&'static [&'static str]> = Some(&["x", "y"]);
+
+            fn includes(_: &str) -> bool {
+                true
+            }
+        }
+
+        let dir = std::env::temp_dir().join("assets_manager_filtered_dir");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.x"), "1").unwrap();
+        std::fs::write(dir.join("b.x"), "2").unwrap();
+        std::fs::write(dir.join("c.y"), "3").unwrap();
+        std::fs::write(dir.join("d.z"), "4").unwrap();
+
+        let cache = AssetCache::new(&dir).unwrap();
+
+        let filtered = cache.load::<FilteredDir<X, NotB>>("").unwrap().read();
+        assert_eq!(filtered.ids().collect::<Vec<_>>(), ["a"]);
+        let values: Vec<_> = filtered.iter(&cache).map(|h| h.unwrap().read().0).collect();
+        assert_eq!(values, [1]);
+
+        let filtered = cache.load::<FilteredDir<X, AnyExt>>("").unwrap().read();
+        assert_eq!(filtered.ids().collect::<Vec<_>>(), ["a", "b", "c"]);
+
+        struct Reversed;
+        impl DirFilter for Reversed {
+            fn includes(_: &str) -> bool {
+                true
+            }
+
+            fn compare(a: &str, b: &str) -> std::cmp::Ordering {
+                b.cmp(a)
+            }
+        }
+
+        let filtered = cache.load::<FilteredDir<X, Reversed>>("").unwrap().read();
+        assert_eq!(filtered.ids().collect::<Vec<_>>(), ["b", "a"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_dir_report() {
+        let dir = std::env::temp_dir().join("assets_manager_load_dir_report");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.x"), "1").unwrap();
+        std::fs::write(dir.join("b.x"), "invalid").unwrap();
+        std::fs::write(dir.join("sub/c.x"), "3").unwrap();
+
+        let cache = AssetCache::new(&dir).unwrap();
+        let report = cache.load_dir_report::<X>("");
+        assert!(!report.is_ok());
+
+        let ids: Vec<_> = report.handles().iter().map(|h| h.id().clone()).collect();
+        assert_eq!(ids, ["a", "sub.c"]);
+        assert_eq!(report.handles()[1].read().0, 3);
+
+        assert_eq!(report.errors().len(), 1);
+        assert_eq!(report.errors()[0].id(), "b");
+        assert!(report.to_string().starts_with("loaded 2 assets, 1 error"));
+
+        let report = cache.load_dir_report::<X>("missing");
+        assert!(report.handles().is_empty());
+        assert_eq!(report.errors()[0].id(), "missing");
+    }
+
+    #[test]
+    fn find_ids() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        assert_eq!(cache.find_ids("test.read_dir.c"), ["test.read_dir.c"]);
+        assert_eq!(
+            cache.find_ids("test.read_dir.*"),
+            ["test.read_dir.c", "test.read_dir.d"]
+        );
+        assert_eq!(
+            cache.find_ids("test.read_dir.?.dummy"),
+            ["test.read_dir.a.dummy", "test.read_dir.b.dummy"]
+        );
+        assert_eq!(
+            cache.find_ids("test.**.dummy"),
+            ["test.read_dir.a.dummy", "test.read_dir.b.dummy"]
+        );
+        assert_eq!(
+            cache.find_ids("test.read_dir.**"),
+            [
+                "test.read_dir.a.dummy",
+                "test.read_dir.b.dummy",
+                "test.read_dir.c",
+                "test.read_dir.d",
+            ]
+        );
+        assert!(cache.find_ids("test..a").is_empty());
+        assert!(cache.find_ids("test.missing.*").is_empty());
+    }
+
+    #[test]
+    fn find() {
+        use crate::source::OwnedDirEntry;
+
+        let cache = AssetCache::new("assets").unwrap();
+        let find = |pattern: &str, extensions: Option<&[&str]>| {
+            let find = cache.find(pattern);
+            let find = match extensions {
+                Some(extensions) => find.with_extensions(extensions),
+                None => find,
+            };
+            let mut entries: Vec<_> = find
+                .map(|entry| match entry {
+                    OwnedDirEntry::File(id, ext) => format!("{id} ({ext})"),
+                    OwnedDirEntry::Directory(id) => format!("{id}/"),
+                })
+                .collect();
+            entries.sort();
+            entries
+        };
+
+        assert_eq!(
+            find("test.read_dir.*", None),
+            [
+                "test.read_dir.a/",
+                "test.read_dir.b/",
+                "test.read_dir.c (txt)",
+                "test.read_dir.d ()",
+            ]
+        );
+        assert_eq!(
+            find("test.read_dir.**", None),
+            [
+                "test.read_dir.a.dummy ()",
+                "test.read_dir.a/",
+                "test.read_dir.b.dummy ()",
+                "test.read_dir.b/",
+                "test.read_dir.c (txt)",
+                "test.read_dir.d ()",
+            ]
+        );
+        assert_eq!(
+            find("test.read_dir.**", Some(&["txt"])),
+            ["test.read_dir.c (txt)"]
+        );
+        assert_eq!(
+            find("**.**.dummy", None),
+            ["test.read_dir.a.dummy ()", "test.read_dir.b.dummy ()"]
+        );
+        assert!(find("test..a", None).is_empty());
+    }
+
+    #[test]
+    fn validate() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let report = cache.validate::<X>(["test.a", "test.b", "test.cache"]);
+        assert!(!report.is_ok());
+        assert_eq!(report.checked(), 3);
+        assert_eq!(report.errors().len(), 1);
+        assert_eq!(report.errors()[0].id(), "test.a");
+        assert!(cache.contains::<X>("test.b"));
+
+        let report = cache.validate_dir::<X>("test.missing");
+        assert_eq!(report.checked(), 1);
+        assert_eq!(report.errors()[0].id(), "test.missing");
+    }
+
+    #[test]
+    fn retain() {
+        let mut cache = AssetCache::new("assets").unwrap();
+
+        cache.load::<X>("test.b").unwrap();
+        cache.load::<X>("test.cache").unwrap();
+        cache.load::<Y>("test.cache").unwrap();
+
+        cache.retain(|handle| handle.id() != "test.cache" || handle.is::<Y>());
+        assert!(cache.contains::<X>("test.b"));
+        assert!(!cache.contains::<X>("test.cache"));
+        assert!(cache.contains::<Y>("test.cache"));
+    }
+
+    #[test]
+    fn iter_handles() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        cache.load::<X>("test.b").unwrap();
+        cache.load::<Y>("test.cache").unwrap();
+
+        let mut handles: Vec<_> = cache
+            .iter_handles()
+            .map(|handle| (handle.id().as_str(), handle.type_name()))
+            .collect();
+        handles.sort_unstable();
+        assert_eq!(
+            handles,
+            [
+                ("test.b", std::any::type_name::<X>()),
+                ("test.cache", std::any::type_name::<X>()),
+                ("test.cache", std::any::type_name::<Y>()),
+            ]
+        );
+
+        let ys: Vec<_> = cache.iter_handles_of(std::any::TypeId::of::<Y>()).collect();
+        assert_eq!(ys.len(), 1);
+        assert!(ys[0].is::<Y>());
+    }
+
+    #[test]
+    fn dump_manifest() {
+        let mut cache = AssetCache::new("assets").unwrap();
+        cache.mount("mods.m", source::FileSystem::new("assets/test").unwrap());
+
+        cache.load::<X>("test.b").unwrap();
+        cache.load::<X>("mods.m.b").unwrap();
+
+        let manifest = cache.dump_manifest();
+        let entries: Vec<_> = manifest
+            .entries()
+            .iter()
+            .map(|e| (e.id().as_str(), e.mount().map(|m| m.as_str())))
+            .collect();
+        assert_eq!(entries, [("mods.m.b", Some("mods.m")), ("test.b", None)]);
+        assert!(manifest.diff(&cache.dump_manifest()).is_empty());
+    }
+
+    #[test]
+    fn evict() {
+        let mut cache = AssetCache::new("assets").unwrap();
+        assert_eq!(cache.evict(), 0);
+
+        cache.set_memory_budget(Some(0));
+        cache.get_or_insert::<X>("test.inserted", X(1));
+        cache.load::<X>("test.b").unwrap();
+        cache.evict();
+        cache.load::<X>("test.cache").unwrap();
+        cache.evict();
+        assert!(!cache.contains::<X>("test.b"));
+        assert!(!cache.contains::<X>("test.cache"));
+        assert!(cache.contains::<X>("test.inserted"));
+        assert_eq!(cache.memory_usage(), 0);
+
+        cache.set_memory_budget(Some(4));
+        cache.load::<X>("test.b").unwrap();
+        cache.evict();
+        cache.load::<X>("test.cache").unwrap();
+        assert_eq!(cache.evict(), 0);
+        cache.get_cached::<X>("test.b").unwrap();
+        assert_eq!(cache.memory_usage(), 4);
+
+        cache.set_memory_budget(Some(2));
+
+        assert_eq!(cache.evict(), 1);
+        assert!(cache.contains::<X>("test.b"));
+        assert!(!cache.contains::<X>("test.cache"));
+    }
+
+    #[test]
+    fn weak_handle() {
+        let mut cache = AssetCache::new("assets").unwrap();
+
+        let weak = cache.load::<X>("test.cache").unwrap().downgrade();
+        assert_eq!(weak.id(), "test.cache");
+        assert_eq!(weak.upgrade(&cache).unwrap().copied(), X(42));
+
+        cache.remove::<X>("test.cache");
+        assert!(weak.upgrade(&cache).is_none());
+
+        cache.load::<X>("test.cache").unwrap();
+        assert!(weak.upgrade(&cache).is_none());
+    }
+
+    #[test]
+    fn load_deferred() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let pending = cache.load_deferred::<X>("test.cache");
+        assert_eq!(pending.id(), "test.cache");
+        let handle = pending.wait().unwrap();
+        assert_eq!(handle.copied(), X(42));
+        assert_eq!(cache.get_cached::<X>("test.cache").unwrap().copied(), X(42));
+
+        let pending = cache.load_deferred::<X>("test.cache");
+        assert!(pending.is_ready());
+        assert!(std::ptr::eq(pending.wait().unwrap(), handle));
+
+        assert!(cache.load_deferred::<X>("test.missing").wait().is_err());
+        assert!(cache.load_deferred::<X>("test..cache").wait().is_err());
+    }
+
+    #[test]
+    fn locale() {
+        use crate::{source::InMemory, Locale, LocaleLayout, Localized};
+
+        let locale = Locale::new("fr-CA")
+            .with_fallback("en-US")
+            .with_fallback("en");
+        assert_eq!(
+            locale.tags().collect::<Vec<_>>(),
+            ["fr-CA", "fr", "en-US", "en"]
+        );
+
+        let source = InMemory::new();
+        source.insert("strings.menu", "x", b"1".to_vec());
+        source.insert("strings.menu@en", "x", b"2".to_vec());
+        source.insert("strings.fr.menu", "x", b"3".to_vec());
+        source.insert("strings.other", "x", b"4".to_vec());
+
+        let cache = AssetCache::with_source(source);
+        assert_eq!(cache.locale(), Locale::default());
+        let menu = cache.load_owned::<Localized<X>>("strings.menu").unwrap();
+        assert_eq!(menu.id(), "strings.menu");
+        assert_eq!(menu.tag(), None);
+
+        cache.set_locale(Locale::new("fr-CA").with_fallback("en"));
+        let menu = cache.load_owned::<Localized<X>>("strings.menu").unwrap();
+        assert_eq!(menu.id(), "strings.menu@en");
+        assert_eq!(menu.tag(), Some("en"));
+        assert_eq!(menu.get(&cache).unwrap().read().0, 2);
+
+        cache.set_locale(Locale::new("fr-CA").with_layout(LocaleLayout::Directory));
+        let menu = cache.load_owned::<Localized<X>>("strings.menu").unwrap();
+        assert_eq!(menu.id(), "strings.fr.menu");
+        assert_eq!(menu.tag(), Some("fr"));
+
+        let other = cache.load_owned::<Localized<X>>("strings.other").unwrap();
+        assert_eq!(other.id(), "strings.other");
+        assert!(cache.load::<Localized<X>>("strings.missing").is_err());
+    }
+
+    #[test]
+    fn aliases() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        assert!(cache.add_alias("test.old", "test.older"));
+        assert!(cache.add_alias("test.older", "test.cache"));
+        assert!(!cache.add_alias("test.cache", "test.old"));
+        assert!(!cache.add_alias("test.cache", "test.cache"));
+        assert_eq!(cache.resolve_alias("test.old").unwrap(), "test.cache");
+        assert_eq!(cache.resolve_alias("test.cache"), None);
+
+        let handle = cache.load::<X>("test.old").unwrap();
+        assert_eq!(handle.id(), "test.cache");
+        assert_eq!(handle.copied(), X(42));
+        assert!(cache.contains::<X>("test.older"));
+        assert!(std::ptr::eq(
+            cache.get_cached::<X>("test.older").unwrap(),
+            handle
+        ));
+        assert_eq!(cache.load_owned::<X>("test.old").unwrap(), X(42));
+
+        assert_eq!(cache.remove_alias("test.older").unwrap(), "test.cache");
+        assert!(!cache.contains::<X>("test.old"));
+        assert!(cache.load::<X>("test.old").is_err());
+    }
+
+    #[test]
+    fn mount() {
+        let mut cache = AssetCache::new("assets").unwrap();
+        cache.load::<X>("mods.cool.cache").unwrap_err();
+
+        cache.mount("mods.cool", source::FileSystem::new("assets/test").unwrap());
+        assert_eq!(cache.load::<X>("mods.cool.cache").unwrap().copied(), X(42));
+        assert_eq!(cache.load::<X>("mods.cool.b").unwrap().copied(), X(-7));
+        assert!(cache.load::<X>("test.cache").is_ok());
+
+        let ids = cache.available_ids::<X>("mods.cool").unwrap();
+        assert!(ids.iter().any(|id| id == "mods.cool.cache"));
+
+        {
+            let mut dirs = Vec::new();
+            let source = cache.as_any_cache().raw_source();
+            source
+                .read_dir("mods", &mut |entry| dirs.push(entry.id().to_owned()))
+                .unwrap();
+            assert_eq!(dirs, ["mods.cool"]);
+            assert!(source.exists(source::DirEntry::Directory("mods")));
+        }
+
+        assert!(cache.unmount("mods.cool"));
+        assert!(!cache.unmount("mods.cool"));
+        assert!(!cache.contains::<X>("mods.cool.cache"));
+        assert!(cache.contains::<X>("test.cache"));
+        cache.load::<X>("mods.cool.cache").unwrap_err();
+    }
+
+    #[test]
+    fn error_kind() {
+        let dir = std::env::temp_dir().join(format!("am_error_kind_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("nan.x"), "abc").unwrap();
+        std::fs::write(dir.join("invalid.txt"), b"\xff").unwrap();
+
+        let cache = AssetCache::new(&dir).unwrap();
+        let kind = |err: crate::Error| err.kind();
+
+        assert_eq!(
+            kind(cache.load::<X>("missing").unwrap_err()),
+            ErrorKind::NotFound
+        );
+        assert_eq!(kind(cache.load::<X>("nan").unwrap_err()), ErrorKind::Parse);
+        assert_eq!(
+            kind(cache.load::<X>("a..b").unwrap_err()),
+            ErrorKind::InvalidId
+        );
+        let err = cache.load::<String>("invalid").unwrap_err();
+        assert_eq!(kind(err), ErrorKind::InvalidUtf8);
+
+        // The root cause is found through `Compound`s
+        let err = cache.load::<Z>("nan").err().unwrap();
+        assert_eq!(kind(err), ErrorKind::Parse);
+
+        #[cfg(feature = "json")]
+        {
+            std::fs::write(dir.join("invalid.json"), "{").unwrap();
+            let err = cache
+                .load::<crate::asset::Json<i32>>("invalid")
+                .unwrap_err();
+            assert_eq!(kind(err), ErrorKind::Deserialize { format: "json" });
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn scan() {
+        let dir = std::env::temp_dir().join(format!("am_scan_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.x"), "1").unwrap();
+        std::fs::write(dir.join("sub/b.x"), "2").unwrap();
+
+        let source = source::FileSystem::new(&dir).unwrap();
+        let cache = AssetCache::without_hot_reloading(source);
+        cache.scan().unwrap();
+
+        // Files added after the scan are not seen
+        std::fs::write(dir.join("sub/c.x"), "3").unwrap();
+
+        let ids = cache.available_ids::<X>("sub").unwrap();
+        assert_eq!(ids, ["sub.b"]);
+        let source = cache.as_any_cache().raw_source();
+        assert!(source.exists(source::DirEntry::File("a", "x")));
+        assert!(source.exists(source::DirEntry::Directory("sub")));
+        assert!(!source.exists(source::DirEntry::File("sub.c", "x")));
+        assert!(source.read_dir("missing", &mut |_| ()).is_err());
+
+        cache.scan().unwrap();
+        let mut ids = cache.available_ids::<X>("sub").unwrap();
+        ids.sort();
+        assert_eq!(ids, ["sub.b", "sub.c"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn take() {
         let mut cache = AssetCache::new("assets").unwrap();
@@ -189,6 +1008,49 @@ mod asset_cache {
     }
 }
 
+mod asset_server {
+    use super::X;
+    use crate::{AssetCache, AssetServer, HandleId};
+
+    #[test]
+    fn load_untyped() {
+        let mut server = AssetServer::new(AssetCache::new("assets").unwrap());
+        server.register::<X>("x");
+        server.register::<String>("text");
+        assert_eq!(server.kinds().collect::<Vec<_>>(), ["x", "text"]);
+
+        let a = server.load_untyped("test.cache", "x").unwrap();
+        let b = server.load_untyped("test.b", "x").unwrap();
+        let hello = server.load_untyped("example.hello", "text").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(server.load_untyped("test.cache", "x").unwrap(), a);
+        assert_eq!(server.find("test.b", "x"), Some(b));
+        assert_eq!(server.find("test.b", "text"), None);
+        assert_eq!(server.handles(), [a, b, hello]);
+
+        let handle = server.get(b).unwrap();
+        assert_eq!(handle.downcast_ref::<X>().unwrap().read().0, -7);
+        assert!(server.cache().contains::<String>("example.hello"));
+
+        let (id, kind) = server.handle_info(hello).unwrap();
+        assert_eq!((&*id, kind), ("example.hello", "text"));
+        assert!(server.get(HandleId::from_bits(42)).is_none());
+
+        assert!(server.load_untyped("test.cache", "unknown").is_err());
+        assert!(server.load_untyped("test.a", "x").is_err());
+        assert!(server.load_untyped("test.missing", "x").is_err());
+        assert_eq!(server.handles().len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn register_twice() {
+        let mut server = AssetServer::new(AssetCache::new("assets").unwrap());
+        server.register::<X>("x");
+        server.register::<String>("x");
+    }
+}
+
 mod handle {
     use super::*;
 
@@ -207,6 +1069,42 @@ mod handle {
         assert!(std::ptr::eq(handle1, handle2));
     }
 
+    #[test]
+    fn reload_cache() {
+        let cache = AssetCache::new("assets").unwrap();
+        let handle = cache.load_expect::<X>("test.cache");
+        let mut derived = ReloadCache::new();
+        assert!(derived.get().is_none());
+
+        assert_eq!(*derived.get_or_recompute(handle, |x| x.0 + 1), 43);
+        assert_eq!(*derived.get_or_recompute(handle, |_| unreachable!()), 43);
+
+        derived.clear();
+        assert_eq!(*derived.get_or_recompute(handle, |x| x.0 * 2), 84);
+    }
+
+    #[test]
+    fn map_ref() {
+        let cache = AssetCache::new("assets").unwrap();
+        let handle = cache.load_expect::<X>("test.cache");
+
+        let projected = handle.map_ref(|x| &x.0);
+        assert_eq!(projected.id(), "test.cache");
+        assert_eq!(*projected.read(), 42);
+        assert!(std::ptr::eq(projected.handle(), handle));
+        assert_eq!(projected.last_reload_id(), handle.last_reload_id());
+    }
+
+    #[test]
+    fn snapshot() {
+        let cache = AssetCache::new("assets").unwrap();
+        let handle = cache.load_expect::<X>("test.cache");
+
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.0, 42);
+        assert!(std::sync::Arc::ptr_eq(&snapshot, &handle.snapshot()));
+    }
+
     #[test]
     fn untyped() {
         let cache = AssetCache::new("assets").unwrap();
@@ -9,6 +9,9 @@ pub use string::SharedString;
 mod private;
 pub(crate) use private::*;
 
+mod nfc;
+pub(crate) use nfc::nfc;
+
 #[cfg(test)]
 mod tests;
 
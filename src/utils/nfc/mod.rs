@@ -0,0 +1,140 @@
+//! Unicode Normalization Form C (NFC).
+//!
+//! This file is also included by the macros crate with a `#[path]`
+//! attribute, so that `embed!` builds the same ids.
+
+use std::borrow::Cow;
+
+mod tables;
+use tables::{COMBINING_CLASSES, COMPOSITIONS, DECOMPOSITIONS};
+
+// Constants for the algorithmic (de)composition of Hangul syllables
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+fn combining_class(c: char) -> u8 {
+    let c = c as u32;
+    match COMBINING_CLASSES.binary_search_by(|&(start, end, _)| {
+        if end < c {
+            std::cmp::Ordering::Less
+        } else if start > c {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }) {
+        Ok(i) => COMBINING_CLASSES[i].2,
+        Err(_) => 0,
+    }
+}
+
+fn decompose(c: char, out: &mut Vec<char>) {
+    let code = c as u32;
+
+    if (S_BASE..S_BASE + S_COUNT).contains(&code) {
+        let s = code - S_BASE;
+        out.extend(char::from_u32(L_BASE + s / N_COUNT));
+        out.extend(char::from_u32(V_BASE + (s % N_COUNT) / T_COUNT));
+        let t = s % T_COUNT;
+        if t > 0 {
+            out.extend(char::from_u32(T_BASE + t));
+        }
+        return;
+    }
+
+    match DECOMPOSITIONS.binary_search_by_key(&code, |&(c, _, _)| c) {
+        Ok(i) => {
+            let (_, a, b) = DECOMPOSITIONS[i];
+            decompose(char::from_u32(a).unwrap(), out);
+            if b != 0 {
+                decompose(char::from_u32(b).unwrap(), out);
+            }
+        }
+        Err(_) => out.push(c),
+    }
+}
+
+fn compose(a: char, b: char) -> Option<char> {
+    let (a, b) = (a as u32, b as u32);
+
+    if (L_BASE..L_BASE + L_COUNT).contains(&a) && (V_BASE..V_BASE + V_COUNT).contains(&b) {
+        let lv = S_BASE + ((a - L_BASE) * V_COUNT + (b - V_BASE)) * T_COUNT;
+        return char::from_u32(lv);
+    }
+    // Only LV syllables, which have no trailing consonant, can be composed
+    if (S_BASE..S_BASE + S_COUNT).contains(&a) && (T_BASE + 1..T_BASE + T_COUNT).contains(&b) {
+        return match (a - S_BASE) % T_COUNT {
+            0 => char::from_u32(a + (b - T_BASE)),
+            _ => None,
+        };
+    }
+
+    let i = COMPOSITIONS
+        .binary_search_by_key(&(a, b), |&(a, b, _)| (a, b))
+        .ok()?;
+    char::from_u32(COMPOSITIONS[i].2)
+}
+
+/// Returns the NFC normalization of a string.
+///
+/// The string is borrowed if it is already normalized.
+pub fn nfc(s: &str) -> Cow<'_, str> {
+    // Characters below U+0300 are never changed by normalization
+    if s.chars().all(|c| c < '\u{300}') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut chars = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        decompose(c, &mut chars);
+    }
+
+    // Put combining marks in canonical order
+    let mut start = 0;
+    while start < chars.len() {
+        let len = chars[start..]
+            .iter()
+            .take_while(|&&c| combining_class(c) != 0)
+            .count();
+        chars[start..start + len].sort_by_key(|&c| combining_class(c));
+        start += len.max(1);
+    }
+
+    // Compose characters with the last starter when they are not blocked
+    let mut composed: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter = None;
+    for c in chars {
+        let class = combining_class(c);
+
+        if let Some(i) = starter {
+            let blocked = match composed[i + 1..].last() {
+                Some(&prev) => combining_class(prev) >= class,
+                None => false,
+            };
+            if !blocked {
+                if let Some(c) = compose(composed[i], c) {
+                    composed[i] = c;
+                    continue;
+                }
+            }
+        }
+
+        if class == 0 {
+            starter = Some(composed.len());
+        }
+        composed.push(c);
+    }
+
+    if composed.iter().copied().eq(s.chars()) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(composed.into_iter().collect())
+    }
+}
@@ -6,9 +6,8 @@
 //! - An unified API for `HashMap`s between `std` and `ahash` hashers
 //! - A marker for private APIs
 
-use crate::{source::DirEntry, SharedString};
+use crate::source::{DirEntry, OwnedDirEntry};
 
-use std::hash::Hash;
 #[allow(unused_imports)]
 use std::{
     any::TypeId,
@@ -35,6 +34,52 @@ pub fn path_of_entry(root: &Path, entry: DirEntry) -> PathBuf {
     path
 }
 
+/// Normalizes an id to Unicode Normalization Form C, reusing it if it is
+/// already normalized.
+pub fn nfc_id(id: crate::SharedString) -> crate::SharedString {
+    if let std::borrow::Cow::Owned(normalized) = super::nfc(&id) {
+        return normalized.into();
+    }
+    id
+}
+
+/// Returns the entry that `path` represents in a source rooted at `root`.
+pub fn entry_of_path(
+    id_builder: &mut IdBuilder,
+    root: &Path,
+    path: &Path,
+) -> Option<OwnedDirEntry> {
+    id_builder.reset();
+
+    for comp in path.parent()?.strip_prefix(root).ok()?.components() {
+        match comp {
+            std::path::Component::Normal(s) => id_builder.push(s.to_str()?)?,
+            std::path::Component::ParentDir => id_builder.pop()?,
+            std::path::Component::CurDir => continue,
+            _ => return None,
+        }
+    }
+
+    // Build the id of the file. As in `FileSystem::read_dir`, the stem of a
+    // file may contain dots, such as `table.json` in `table.json.zst`.
+    let stem = path.file_stem()?.to_str()?;
+    if path.is_dir() || stem.starts_with('.') {
+        id_builder.push(stem)?;
+    } else {
+        stem.split('.').try_for_each(|s| id_builder.push(s))?;
+    }
+    let id = id_builder.join();
+
+    let entry = if path.is_dir() {
+        OwnedDirEntry::Directory(id)
+    } else {
+        let ext = extension_of(path)?.into();
+        OwnedDirEntry::File(id, ext)
+    };
+
+    Some(entry)
+}
+
 #[inline]
 pub(crate) fn extension_of(path: &Path) -> Option<&str> {
     match path.extension() {
@@ -43,17 +88,51 @@ pub(crate) fn extension_of(path: &Path) -> Option<&str> {
     }
 }
 
+/// Returns `true` if `name` matches the wildcard pattern `pattern`.
+///
+/// `*` matches any sequence of characters and `?` matches a single character.
+pub(crate) fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and of the matching name
+    // character, to backtrack to.
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Build ids from components.
 ///
 /// Using this allows to easily reuse buffers when building several ids in a
 /// row, and thus to avoid repeated allocations.
-#[cfg(any(feature = "tar", feature = "zip", feature = "hot-reloading"))]
 #[derive(Default)]
 pub struct IdBuilder {
     buf: String,
 }
 
-#[cfg(any(feature = "tar", feature = "zip", feature = "hot-reloading"))]
 impl IdBuilder {
     /// Pushs a segment in the builder.
     pub fn push(&mut self, s: &str) -> Option<()> {
@@ -82,7 +161,7 @@ impl IdBuilder {
 
     /// Joins segments to build a id.
     #[inline]
-    pub fn join(&self) -> SharedString {
+    pub fn join(&self) -> crate::SharedString {
         self.buf.as_str().into()
     }
 
@@ -93,22 +172,6 @@ impl IdBuilder {
     }
 }
 
-/// The key used to identify assets
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct OwnedKey {
-    pub type_id: TypeId,
-    pub id: SharedString,
-}
-
-impl OwnedKey {
-    /// Creates a `OwnedKey` with the given type and id.
-    #[allow(dead_code)]
-    #[inline]
-    pub fn new_with(id: SharedString, type_id: TypeId) -> Self {
-        Self { id, type_id }
-    }
-}
-
 #[cfg(feature = "parking_lot")]
 use parking_lot as sync;
 #[cfg(not(feature = "parking_lot"))]
@@ -148,15 +211,30 @@ impl<T> RwLock<T> {
 
 impl<T: ?Sized> RwLock<T> {
     #[inline]
-    pub fn read(&self) -> RwLockReadGuard<T> {
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
         wrap(self.0.read())
     }
 
     #[inline]
-    pub fn write(&self) -> RwLockWriteGuard<T> {
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
         wrap(self.0.write())
     }
 
+    /// Returns `None` if the lock is held by someone else.
+    #[cfg(feature = "hot-reloading")]
+    #[inline]
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        #[cfg(feature = "parking_lot")]
+        return self.0.try_write();
+
+        #[cfg(not(feature = "parking_lot"))]
+        match self.0.try_write() {
+            Ok(guard) => Some(guard),
+            Err(sync::TryLockError::Poisoned(err)) => Some(err.into_inner()),
+            Err(sync::TryLockError::WouldBlock) => None,
+        }
+    }
+
     #[inline]
     pub fn get_mut(&mut self) -> &mut T {
         wrap(self.0.get_mut())
@@ -170,7 +248,7 @@ pub(crate) struct Mutex<T: ?Sized>(sync::Mutex<T>);
 #[allow(unused)]
 impl<T> Mutex<T> {
     #[inline]
-    pub fn new(inner: T) -> Self {
+    pub const fn new(inner: T) -> Self {
         Self(sync::Mutex::new(inner))
     }
 }
@@ -178,9 +256,14 @@ impl<T> Mutex<T> {
 #[allow(unused)]
 impl<T: ?Sized> Mutex<T> {
     #[inline]
-    pub fn lock(&self) -> sync::MutexGuard<T> {
+    pub fn lock(&self) -> sync::MutexGuard<'_, T> {
         wrap(self.0.lock())
     }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        wrap(self.0.get_mut())
+    }
 }
 
 #[allow(unused)]
@@ -190,7 +273,7 @@ pub(crate) struct Condvar(sync::Condvar);
 #[allow(unused)]
 impl Condvar {
     #[inline]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self(sync::Condvar::new())
     }
 
@@ -224,6 +307,39 @@ impl Condvar {
             guard
         }
     }
+
+    /// Returns `true` if the timeout elapsed before the condition became
+    /// `false`.
+    pub fn wait_timeout_while<'a, T, F>(
+        &self,
+        mut guard: sync::MutexGuard<'a, T>,
+        timeout: std::time::Duration,
+        mut condition: F,
+    ) -> (sync::MutexGuard<'a, T>, bool)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let deadline = std::time::Instant::now().checked_add(timeout);
+
+        while condition(&mut guard) {
+            let now = std::time::Instant::now();
+            let remaining = match deadline {
+                Some(deadline) if deadline > now => deadline - now,
+                Some(_) => return (guard, true),
+                None => timeout,
+            };
+
+            #[cfg(feature = "parking_lot")]
+            self.0.wait_for(&mut guard, remaining);
+
+            #[cfg(not(feature = "parking_lot"))]
+            {
+                guard = wrap(self.0.wait_timeout(guard, remaining)).0;
+            }
+        }
+
+        (guard, false)
+    }
 }
 
 /// Fake public structure for internal APIs
@@ -281,10 +397,8 @@ where
     }
 }
 
-#[cfg(feature = "hot-reloading")]
 pub(crate) struct HashSet<T>(hashbrown::HashSet<T, RandomState>);
 
-#[cfg(feature = "hot-reloading")]
 impl<T> HashSet<T> {
     #[inline]
     pub fn new() -> Self {
@@ -292,7 +406,6 @@ impl<T> HashSet<T> {
     }
 }
 
-#[cfg(feature = "hot-reloading")]
 impl<T> Deref for HashSet<T> {
     type Target = hashbrown::HashSet<T, RandomState>;
 
@@ -302,7 +415,6 @@ impl<T> Deref for HashSet<T> {
     }
 }
 
-#[cfg(feature = "hot-reloading")]
 impl<T> DerefMut for HashSet<T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
@@ -310,7 +422,6 @@ impl<T> DerefMut for HashSet<T> {
     }
 }
 
-#[cfg(feature = "hot-reloading")]
 impl<T> fmt::Debug for HashSet<T>
 where
     hashbrown::HashSet<T, RandomState>: fmt::Debug,
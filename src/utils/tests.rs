@@ -1,5 +1,41 @@
 use super::*;
 
+#[test]
+fn wildcard_match() {
+    use super::wildcard_match;
+
+    assert!(wildcard_match("abc", "abc"));
+    assert!(!wildcard_match("abc", "abd"));
+    assert!(wildcard_match("*", ""));
+    assert!(wildcard_match("*", "abc"));
+    assert!(wildcard_match("a*", "abc"));
+    assert!(wildcard_match("*c", "abc"));
+    assert!(wildcard_match("a*b*c", "aXbYbZc"));
+    assert!(!wildcard_match("a*b*c", "aXbYbZ"));
+    assert!(wildcard_match("a?c", "abc"));
+    assert!(!wildcard_match("a?c", "ac"));
+    assert!(wildcard_match("lv?_*", "lvl_1"));
+}
+
+#[test]
+fn nfc() {
+    use std::borrow::Cow;
+
+    assert!(matches!(super::nfc("ascii.id"), Cow::Borrowed(_)));
+    assert!(matches!(super::nfc("caf\u{e9}"), Cow::Borrowed(_)));
+
+    assert_eq!(super::nfc("cafe\u{301}"), "caf\u{e9}");
+    assert_eq!(super::nfc("\u{212b}"), "\u{c5}");
+    // Combining marks are reordered before being composed
+    assert_eq!(super::nfc("a\u{302}\u{323}"), "\u{1ead}");
+    assert_eq!(super::nfc("a\u{323}\u{302}"), "\u{1ead}");
+    // Blocked marks are not composed
+    assert_eq!(super::nfc("a\u{301}\u{301}"), "\u{e1}\u{301}");
+    // Hangul syllables
+    assert_eq!(super::nfc("\u{1100}\u{1161}\u{11a8}"), "\u{ac01}");
+    assert_eq!(super::nfc("\u{ac01}"), "\u{ac01}");
+}
+
 mod shared_bytes {
     use super::SharedBytes;
 
@@ -0,0 +1,215 @@
+use crate::{Error, Handle};
+use std::fmt;
+
+#[cfg(doc)]
+use crate::AnyCache;
+
+/// A report of the errors encountered while loading many assets.
+///
+/// It is returned by [`AnyCache::validate`] and its variants, and is meant to
+/// be printed (with its `Display` implementation) or serialized (with feature
+/// `serde`) by tools that check assets, eg in CI.
+pub struct ValidationReport {
+    checked: usize,
+    errors: Vec<Error>,
+}
+
+impl ValidationReport {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            checked: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push<T>(&mut self, result: Result<T, Error>) {
+        self.checked += 1;
+        if let Err(err) = result {
+            self.errors.push(err);
+        }
+    }
+
+    /// Returns `true` if no error was encountered.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the number of assets that were checked.
+    #[inline]
+    pub fn checked(&self) -> usize {
+        self.checked
+    }
+
+    /// Returns the errors encountered, in the order they happened.
+    #[inline]
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Consumes the report, returning the errors encountered.
+    #[inline]
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+}
+
+/// The result of loading all assets of a directory and its subdirectories.
+///
+/// It is returned by [`AnyCache::load_dir_report`], and contains both the
+/// assets that were successfully loaded and the errors encountered, including
+/// directories that could not be read.
+pub struct DirLoadReport<'a, T> {
+    handles: Vec<&'a Handle<T>>,
+    errors: Vec<Error>,
+}
+
+impl<'a, T> DirLoadReport<'a, T> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            handles: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, result: Result<&'a Handle<T>, Error>) {
+        match result {
+            Ok(handle) => self.handles.push(handle),
+            Err(err) => self.errors.push(err),
+        }
+    }
+
+    pub(crate) fn sort(&mut self) {
+        self.handles.sort_by(|a, b| a.id().cmp(b.id()));
+        self.errors.sort_by(|a, b| a.id().cmp(b.id()));
+    }
+
+    /// Returns `true` if no error was encountered.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the assets that were successfully loaded, sorted by id.
+    #[inline]
+    pub fn handles(&self) -> &[&'a Handle<T>] {
+        &self.handles
+    }
+
+    /// Returns the errors encountered, sorted by id.
+    #[inline]
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Consumes the report, returning the loaded assets and the errors.
+    #[inline]
+    pub fn into_parts(self) -> (Vec<&'a Handle<T>>, Vec<Error>) {
+        (self.handles, self.errors)
+    }
+}
+
+impl<T> fmt::Display for DirLoadReport<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_report(f, "loaded", self.handles.len(), &self.errors)
+    }
+}
+
+impl<T> fmt::Debug for DirLoadReport<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids: Vec<_> = self.handles.iter().map(|h| h.id()).collect();
+        f.debug_struct("DirLoadReport")
+            .field("loaded", &ids)
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+/// Writes a summary line, then each error with its id on its own line.
+fn write_report(
+    f: &mut fmt::Formatter<'_>,
+    verb: &str,
+    count: usize,
+    errors: &[Error],
+) -> fmt::Result {
+    let plural = |n| if n == 1 { "" } else { "s" };
+    write!(
+        f,
+        "{verb} {count} asset{}, {} error{}",
+        plural(count),
+        errors.len(),
+        plural(errors.len()),
+    )?;
+
+    for err in errors {
+        write!(f, "\n\"{}\": {}", err.id(), ErrorChain(err.reason()))?;
+    }
+
+    Ok(())
+}
+
+/// Displays an error and all its sources on a single line.
+pub(crate) struct ErrorChain<'a>(pub &'a (dyn std::error::Error + 'static));
+
+impl fmt::Display for ErrorChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)?;
+
+        let mut source = self.0.source();
+        while let Some(err) = source {
+            write!(f, ": {err}")?;
+            source = err.source();
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_report(f, "checked", self.checked, &self.errors)
+    }
+}
+
+impl fmt::Debug for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationReport")
+            .field("checked", &self.checked)
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for ValidationReport {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeStruct};
+
+        struct Errors<'a>(&'a [Error]);
+
+        impl serde::Serialize for Errors<'_> {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                s.collect_seq(self.0.iter().map(SerializedError))
+            }
+        }
+
+        struct SerializedError<'a>(&'a Error);
+
+        impl serde::Serialize for SerializedError<'_> {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                let mut map = s.serialize_map(Some(2))?;
+                map.serialize_entry("id", &**self.0.id())?;
+                map.serialize_entry("error", &ErrorChain(self.0.reason()).to_string())?;
+                map.end()
+            }
+        }
+
+        let mut report = s.serialize_struct("ValidationReport", 2)?;
+        report.serialize_field("checked", &self.checked)?;
+        report.serialize_field("errors", &Errors(&self.errors))?;
+        report.end()
+    }
+}